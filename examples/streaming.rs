@@ -25,7 +25,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Process stream events
     while let Some(event) = stream.next().await {
         match event? {
-            StreamEvent::Start { id } => {
+            StreamEvent::Start { id, .. } => {
                 println!("Stream started (id: {})\n", id);
             }
             StreamEvent::TextDelta { delta, .. } => {
@@ -4,13 +4,12 @@
 //! - Defining tools with JSON schema
 //! - Making a streaming request with tools
 //! - Handling tool call events in the stream
-//! - Accumulating tool call arguments from deltas
+//! - Collecting the streamed events into a complete response
 //! - Executing tools and continuing the conversation
 
 use futures::StreamExt;
 use serde_json::json;
 use stakai::{ContentPart, GenerateRequest, Inference, Message, StreamEvent, Tool};
-use std::collections::HashMap;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -60,61 +59,38 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .add_tool(weather_tool.clone())
         .add_tool(time_tool.clone());
 
-    // 3. Make streaming call
+    // 3. Make streaming call, keeping the aggregated response for the follow-up
     println!("--- Streaming tool calls from model\n");
-    let mut stream = client.stream(&request).await?;
-
-    // Track tool calls being built
-    let mut tool_calls: HashMap<String, ToolCallBuilder> = HashMap::new();
-    let mut text_content = String::new();
+    let (mut stream, response) = client.stream(&request).await?.tee_response();
 
     // 4. Process stream events
     while let Some(event) = stream.next().await {
         match event? {
-            StreamEvent::Start { id } => {
+            StreamEvent::Start { id, .. } => {
                 println!("Stream started: {}", id);
             }
             StreamEvent::TextDelta { delta, .. } => {
                 print!("{}", delta);
-                text_content.push_str(&delta);
             }
             StreamEvent::ToolCallStart { id, name } => {
                 println!("\n\n🔧 Tool call started:");
                 println!("  ID: {}", id);
                 println!("  Function: {}", name);
-                tool_calls.insert(
-                    id.clone(),
-                    ToolCallBuilder {
-                        id: id.clone(),
-                        name: name.clone(),
-                        arguments: String::new(),
-                    },
-                );
-            }
-            StreamEvent::ToolCallDelta { id, delta } => {
-                if let Some(builder) = tool_calls.get_mut(&id) {
-                    builder.arguments.push_str(&delta);
-                }
             }
+            StreamEvent::ReasoningDelta { .. }
+            | StreamEvent::ToolCallDelta { .. }
+            | StreamEvent::Citation { .. }
+            | StreamEvent::ContentFilter { .. } => {}
             StreamEvent::ToolCallEnd {
                 id,
                 name,
                 arguments,
+                ..
             } => {
                 println!("\n✅ Tool call completed:");
                 println!("  ID: {}", id);
                 println!("  Function: {}", name);
                 println!("  Arguments: {}", arguments);
-
-                // Store complete tool call
-                tool_calls.insert(
-                    id.clone(),
-                    ToolCallBuilder {
-                        id: id.clone(),
-                        name: name.clone(),
-                        arguments: arguments.to_string(),
-                    },
-                );
             }
             StreamEvent::Finish { usage, reason } => {
                 println!("\n\n--- Stream finished");
@@ -130,40 +106,40 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
     }
 
+    // The same response the non-streaming API would have returned
+    let response = response.await?;
+    let tool_calls = response.tool_calls();
+
     // 5. Execute tools if we got any
     if !tool_calls.is_empty() {
         println!("\n\n--- Executing tools");
 
-        let mut tool_results = Vec::new();
-
-        for (_, builder) in tool_calls.iter() {
-            let result = execute_tool(&builder.name, &builder.arguments)?;
-            println!("\n🔨 Executed: {}", builder.name);
-            println!("   Result: {}", result);
-
-            tool_results.push((builder.id.clone(), result));
-        }
-
-        // 6. Create follow-up request with tool results
         let mut messages = request.messages.clone();
 
         // Add assistant message with tool calls
-        for (_, builder) in tool_calls.iter() {
-            messages.push(Message::new(
-                stakai::Role::Assistant,
-                vec![ContentPart::tool_call(
-                    builder.id.clone(),
-                    builder.name.clone(),
-                    serde_json::from_str(&builder.arguments).unwrap_or(serde_json::json!({})),
-                )],
-            ));
-        }
+        messages.push(Message::new(
+            stakai::Role::Assistant,
+            tool_calls
+                .iter()
+                .map(|call| {
+                    ContentPart::tool_call(
+                        call.id.clone(),
+                        call.name.clone(),
+                        call.arguments.clone(),
+                    )
+                })
+                .collect::<Vec<_>>(),
+        ));
+
+        // 6. Add tool results
+        for call in &tool_calls {
+            let result = execute_tool(&call.name, &call.arguments)?;
+            println!("\n🔨 Executed: {}", call.name);
+            println!("   Result: {}", result);
 
-        // Add tool results
-        for (call_id, result) in tool_results {
             messages.push(Message::new(
                 stakai::Role::Tool,
-                vec![ContentPart::tool_result(call_id, result)],
+                vec![ContentPart::tool_result(call.id.clone(), result)],
             ));
         }
 
@@ -185,20 +161,11 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
-// Helper struct to build tool calls from streaming deltas
-struct ToolCallBuilder {
-    id: String,
-    name: String,
-    arguments: String,
-}
-
 // Simulate tool execution
 fn execute_tool(
     name: &str,
-    arguments: &str,
+    args: &serde_json::Value,
 ) -> Result<serde_json::Value, Box<dyn std::error::Error>> {
-    let args: serde_json::Value = serde_json::from_str(arguments)?;
-
     match name {
         "get_weather" => {
             let city = args["city"].as_str().unwrap_or("Unknown");
@@ -0,0 +1,437 @@
+//! Model catalog: provider, limits, pricing and capabilities of known models
+//!
+//! The SDK ships with a built-in catalog (`models.json`) that is used to detect
+//! the provider of unprefixed model names and to pick default token limits.
+//! Entries can be added or overridden at runtime:
+//!
+//! ```rust
+//! use stakai::catalog::{self, ModelEntry, ModelPricing};
+//!
+//! catalog::register(
+//!     ModelEntry::new("acme-support-v2", "openai")
+//!         .context_window(128_000)
+//!         .max_output_tokens(16_384)
+//!         .pricing(ModelPricing::new(3.75, 15.0))
+//!         .tools(true),
+//! );
+//!
+//! assert_eq!(catalog::lookup("acme-support-v2").unwrap().provider, "openai");
+//! ```
+
+use crate::error::Result;
+use crate::types::{Cost, Usage};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{OnceLock, RwLock};
+
+/// Built-in catalog data
+const BUILTIN_CATALOG: &str = include_str!("models.json");
+
+/// Pricing of a model in USD per million tokens
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ModelPricing {
+    /// Uncached input tokens
+    pub input: f64,
+    /// Output tokens (including reasoning tokens)
+    pub output: f64,
+    /// Input tokens read from the prompt cache (defaults to `input`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cached_input: Option<f64>,
+    /// Input tokens written to the prompt cache (defaults to `input`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_write: Option<f64>,
+}
+
+impl ModelPricing {
+    /// Create pricing from input and output prices per million tokens
+    pub fn new(input: f64, output: f64) -> Self {
+        Self {
+            input,
+            output,
+            cached_input: None,
+            cache_write: None,
+        }
+    }
+
+    /// Set the price of cache reads per million tokens
+    pub fn cached_input(mut self, price: f64) -> Self {
+        self.cached_input = Some(price);
+        self
+    }
+
+    /// Set the price of cache writes per million tokens
+    pub fn cache_write(mut self, price: f64) -> Self {
+        self.cache_write = Some(price);
+        self
+    }
+
+    /// Cost of token usage at these prices
+    pub fn cost(&self, usage: &Usage) -> Cost {
+        let per_token = |price: f64, tokens: u32| price * f64::from(tokens) / 1_000_000.0;
+        let uncached = usage
+            .prompt_tokens
+            .saturating_sub(usage.cached_tokens)
+            .saturating_sub(usage.cache_creation_tokens);
+
+        Cost {
+            input: per_token(self.input, uncached),
+            cached_input: per_token(self.cached_input.unwrap_or(self.input), usage.cached_tokens),
+            cache_creation: per_token(
+                self.cache_write.unwrap_or(self.input),
+                usage.cache_creation_tokens,
+            ),
+            output: per_token(
+                self.output,
+                usage
+                    .completion_tokens
+                    .saturating_sub(usage.reasoning_tokens),
+            ),
+            reasoning: per_token(self.output, usage.reasoning_tokens),
+        }
+    }
+}
+
+/// A model known to the catalog
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModelEntry {
+    /// Model ID (also matches dated snapshots like "gpt-4o-2024-08-06")
+    pub id: String,
+    /// ID of the provider serving the model (e.g., "openai")
+    pub provider: String,
+    /// Alternative names that resolve to this model
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
+    /// Maximum input tokens
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context_window: Option<u32>,
+    /// Maximum output tokens
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_output_tokens: Option<u32>,
+    /// Token pricing
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pricing: Option<ModelPricing>,
+    /// Accepts image input
+    #[serde(default)]
+    pub vision: bool,
+    /// Supports tool calling
+    #[serde(default)]
+    pub tools: bool,
+    /// Supports reasoning (extended thinking)
+    #[serde(default)]
+    pub reasoning: bool,
+    /// Supports JSON output mode
+    #[serde(default)]
+    pub json_mode: bool,
+}
+
+impl ModelEntry {
+    /// Create an entry with only ID and provider set
+    pub fn new(id: impl Into<String>, provider: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            provider: provider.into(),
+            aliases: Vec::new(),
+            context_window: None,
+            max_output_tokens: None,
+            pricing: None,
+            vision: false,
+            tools: false,
+            reasoning: false,
+            json_mode: false,
+        }
+    }
+
+    /// Add an alias
+    pub fn alias(mut self, alias: impl Into<String>) -> Self {
+        self.aliases.push(alias.into());
+        self
+    }
+
+    /// Set the context window
+    pub fn context_window(mut self, tokens: u32) -> Self {
+        self.context_window = Some(tokens);
+        self
+    }
+
+    /// Set the maximum output tokens
+    pub fn max_output_tokens(mut self, tokens: u32) -> Self {
+        self.max_output_tokens = Some(tokens);
+        self
+    }
+
+    /// Set pricing
+    pub fn pricing(mut self, pricing: ModelPricing) -> Self {
+        self.pricing = Some(pricing);
+        self
+    }
+
+    /// Set whether the model accepts image input
+    pub fn vision(mut self, vision: bool) -> Self {
+        self.vision = vision;
+        self
+    }
+
+    /// Set whether the model supports tool calling
+    pub fn tools(mut self, tools: bool) -> Self {
+        self.tools = tools;
+        self
+    }
+
+    /// Set whether the model supports reasoning
+    pub fn reasoning(mut self, reasoning: bool) -> Self {
+        self.reasoning = reasoning;
+        self
+    }
+
+    /// Set whether the model supports JSON output mode
+    pub fn json_mode(mut self, json_mode: bool) -> Self {
+        self.json_mode = json_mode;
+        self
+    }
+}
+
+/// Model name prefix served by a single provider
+///
+/// Used to detect the provider of models that have no catalog entry yet.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModelFamily {
+    /// Model name prefix (e.g., "claude-")
+    pub prefix: String,
+    /// ID of the provider serving the family
+    pub provider: String,
+}
+
+/// Collection of model entries with name resolution
+///
+/// Names resolve by exact ID or alias first, then to the longest ID or alias
+/// that is followed by `-` or `@` in the name, so dated snapshots match their
+/// base model. Matching is case-insensitive.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ModelCatalog {
+    #[serde(default)]
+    families: Vec<ModelFamily>,
+    #[serde(default)]
+    models: Vec<ModelEntry>,
+    #[serde(skip)]
+    index: HashMap<String, usize>,
+}
+
+impl ModelCatalog {
+    /// Create an empty catalog
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load the catalog shipped with the SDK
+    pub fn builtin() -> Self {
+        Self::from_json(BUILTIN_CATALOG).expect("built-in model catalog is valid")
+    }
+
+    /// Parse a catalog from JSON (`{"families": [...], "models": [...]}`)
+    pub fn from_json(json: &str) -> Result<Self> {
+        let mut catalog: Self = serde_json::from_str(json)?;
+        catalog.reindex();
+        Ok(catalog)
+    }
+
+    /// Add a model, replacing any entry with the same ID
+    pub fn register(&mut self, model: ModelEntry) {
+        self.models.retain(|m| m.id != model.id);
+        self.models.push(model);
+        self.reindex();
+    }
+
+    /// Add a model family, taking precedence over existing ones
+    pub fn register_family(&mut self, prefix: impl Into<String>, provider: impl Into<String>) {
+        self.families.insert(
+            0,
+            ModelFamily {
+                prefix: prefix.into(),
+                provider: provider.into(),
+            },
+        );
+    }
+
+    /// Add all families and models of another catalog, overriding on conflict
+    pub fn merge(&mut self, other: ModelCatalog) {
+        for family in other.families.into_iter().rev() {
+            self.families.insert(0, family);
+        }
+        for model in other.models {
+            self.models.retain(|m| m.id != model.id);
+            self.models.push(model);
+        }
+        self.reindex();
+    }
+
+    /// All model entries
+    pub fn models(&self) -> &[ModelEntry] {
+        &self.models
+    }
+
+    /// Resolve a model name to its entry
+    pub fn get(&self, model: &str) -> Option<&ModelEntry> {
+        let name = model.to_lowercase();
+        if let Some(&i) = self.index.get(&name) {
+            return Some(&self.models[i]);
+        }
+
+        self.index
+            .iter()
+            .filter(|(key, _)| {
+                name.strip_prefix(key.as_str())
+                    .is_some_and(|rest| rest.starts_with(['-', '@']))
+            })
+            .max_by_key(|(key, _)| key.len())
+            .map(|(_, &i)| &self.models[i])
+    }
+
+    /// Detect the provider of a model name from its entry or family
+    pub fn provider_for(&self, model: &str) -> Option<&str> {
+        if let Some(entry) = self.get(model) {
+            return Some(&entry.provider);
+        }
+
+        let name = model.to_lowercase();
+        self.families
+            .iter()
+            .find(|family| name.starts_with(&family.prefix.to_lowercase()))
+            .map(|family| family.provider.as_str())
+    }
+
+    /// Rebuild the name index; later entries win on conflicting names
+    fn reindex(&mut self) {
+        self.index.clear();
+        for (i, model) in self.models.iter().enumerate() {
+            for name in std::iter::once(&model.id).chain(&model.aliases) {
+                self.index.insert(name.to_lowercase(), i);
+            }
+        }
+    }
+}
+
+/// Process-wide catalog, initialized from the built-in data
+fn global() -> &'static RwLock<ModelCatalog> {
+    static CATALOG: OnceLock<RwLock<ModelCatalog>> = OnceLock::new();
+    CATALOG.get_or_init(|| RwLock::new(ModelCatalog::builtin()))
+}
+
+/// Look up a model in the global catalog
+pub fn lookup(model: &str) -> Option<ModelEntry> {
+    let catalog = global().read().unwrap_or_else(|e| e.into_inner());
+    catalog.get(model).cloned()
+}
+
+/// Detect the provider of a model name using the global catalog
+pub fn provider_for(model: &str) -> Option<String> {
+    let catalog = global().read().unwrap_or_else(|e| e.into_inner());
+    catalog.provider_for(model).map(str::to_string)
+}
+
+/// Register a model in the global catalog, replacing any entry with the same ID
+pub fn register(model: ModelEntry) {
+    let mut catalog = global().write().unwrap_or_else(|e| e.into_inner());
+    catalog.register(model);
+}
+
+/// Register a model family in the global catalog
+pub fn register_family(prefix: impl Into<String>, provider: impl Into<String>) {
+    let mut catalog = global().write().unwrap_or_else(|e| e.into_inner());
+    catalog.register_family(prefix, provider);
+}
+
+/// Merge a JSON catalog into the global catalog, overriding built-in entries
+pub fn load_json(json: &str) -> Result<()> {
+    let overrides = ModelCatalog::from_json(json)?;
+    let mut catalog = global().write().unwrap_or_else(|e| e.into_inner());
+    catalog.merge(overrides);
+    Ok(())
+}
+
+/// Snapshot of the global catalog
+pub fn snapshot() -> ModelCatalog {
+    global().read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_resolves_snapshots_and_aliases() {
+        let catalog = ModelCatalog::builtin();
+
+        assert_eq!(catalog.get("gpt-4o").unwrap().id, "gpt-4o");
+        assert_eq!(catalog.get("gpt-4o-2024-08-06").unwrap().id, "gpt-4o");
+        assert_eq!(
+            catalog.get("gpt-4o-mini-2024-07-18").unwrap().id,
+            "gpt-4o-mini"
+        );
+        assert_eq!(catalog.get("GPT-4.1").unwrap().id, "gpt-4.1");
+        assert_eq!(
+            catalog.get("claude-opus-4-5-20251101").unwrap().id,
+            "claude-opus-4-5"
+        );
+        assert_eq!(
+            catalog.get("claude-3-5-sonnet@20240620").unwrap().id,
+            "claude-3-5-sonnet"
+        );
+        assert_eq!(
+            catalog.get("gemini-flash-latest").unwrap().id,
+            "gemini-2.5-flash"
+        );
+        assert!(catalog.get("gpt-4o3").is_none());
+    }
+
+    #[test]
+    fn test_provider_for() {
+        let catalog = ModelCatalog::builtin();
+
+        assert_eq!(catalog.provider_for("o3-mini"), Some("openai"));
+        assert_eq!(catalog.provider_for("chatgpt-4o-latest"), Some("openai"));
+        assert_eq!(
+            catalog.provider_for("text-embedding-3-small"),
+            Some("openai")
+        );
+        assert_eq!(catalog.provider_for("text-embedding-004"), Some("google"));
+        assert_eq!(catalog.provider_for("claude-future-9"), Some("anthropic"));
+        assert_eq!(catalog.provider_for("llama-3.3-70b"), None);
+    }
+
+    #[test]
+    fn test_register_overrides_builtin() {
+        let mut catalog = ModelCatalog::builtin();
+        catalog.register(
+            ModelEntry::new("gpt-4o", "azure")
+                .alias("house-model")
+                .max_output_tokens(1000),
+        );
+
+        let entry = catalog.get("gpt-4o-2024-08-06").unwrap();
+        assert_eq!(entry.provider, "azure");
+        assert_eq!(entry.max_output_tokens, Some(1000));
+        assert_eq!(catalog.get("house-model").unwrap().id, "gpt-4o");
+        assert_eq!(
+            catalog.models().iter().filter(|m| m.id == "gpt-4o").count(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_merge_json() {
+        let mut catalog = ModelCatalog::builtin();
+        catalog.merge(
+            ModelCatalog::from_json(
+                r#"{
+                    "families": [{"prefix": "gpt-oss-", "provider": "groq"}],
+                    "models": [{"id": "acme-ft-1", "provider": "openai", "tools": true}]
+                }"#,
+            )
+            .unwrap(),
+        );
+
+        assert_eq!(catalog.provider_for("gpt-oss-120b"), Some("groq"));
+        assert_eq!(catalog.provider_for("gpt-4o"), Some("openai"));
+        assert!(catalog.get("acme-ft-1").unwrap().tools);
+    }
+}
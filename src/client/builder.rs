@@ -1,18 +1,23 @@
 //! Inference client builder
 
+use super::models::ModelCache;
 use super::{ClientConfig, Inference, InferenceConfig};
 use crate::error::Result;
-use crate::provider::Provider;
+use crate::provider::{FallbackRoute, Provider, ProviderLayer};
 use crate::providers::{
     anthropic::AnthropicProvider, gemini::GeminiProvider, openai::OpenAIProvider,
 };
 use crate::registry::ProviderRegistry;
+use crate::retry::RetryPolicy;
+use std::sync::Arc;
 
 /// Builder for creating an Inference client
 #[derive(Default)]
 pub struct ClientBuilder {
     registry: Option<ProviderRegistry>,
     config: ClientConfig,
+    layers: Vec<Arc<dyn ProviderLayer>>,
+    fallbacks: Vec<(String, FallbackRoute)>,
 }
 
 impl ClientBuilder {
@@ -38,24 +43,31 @@ impl ClientBuilder {
     /// # Ok::<(), stakai::Error>(())
     /// ```
     pub fn with_inference_config(mut self, inference_config: InferenceConfig) -> Self {
-        let mut registry = self.registry.take().unwrap_or_default();
+        let http = inference_config.client_config.http_config();
+        let mut registry = self
+            .registry
+            .take()
+            .unwrap_or_else(|| ProviderRegistry::from_env(&http));
 
         // Register OpenAI if configured
-        if let Some(config) = inference_config.openai_config {
+        if let Some(mut config) = inference_config.openai_config {
+            config.http = config.http.or(&http);
             if let Ok(provider) = OpenAIProvider::new(config) {
                 registry = registry.register("openai", provider);
             }
         }
 
         // Register Anthropic if configured
-        if let Some(config) = inference_config.anthropic_config {
+        if let Some(mut config) = inference_config.anthropic_config {
+            config.http = config.http.or(&http);
             if let Ok(provider) = AnthropicProvider::new(config) {
                 registry = registry.register("anthropic", provider);
             }
         }
 
         // Register Gemini if configured
-        if let Some(config) = inference_config.gemini_config {
+        if let Some(mut config) = inference_config.gemini_config {
+            config.http = config.http.or(&http);
             if let Ok(provider) = GeminiProvider::new(config) {
                 registry = registry.register("google", provider);
             }
@@ -101,10 +113,95 @@ impl ClientBuilder {
         self
     }
 
+    /// Set request timeout in seconds
+    ///
+    /// Providers configured through [`ClientBuilder::with_inference_config`] take
+    /// their connect timeout from the [`InferenceConfig`] instead.
+    pub fn with_timeout(mut self, seconds: u64) -> Self {
+        self.config.timeout_seconds = Some(seconds);
+        self
+    }
+
+    /// Set retry policy
+    pub fn with_retry(mut self, retry: RetryPolicy) -> Self {
+        self.config.retry = retry;
+        self
+    }
+
+    /// Set how long model lists are cached, in seconds (`0` disables caching)
+    pub fn with_model_cache_ttl(mut self, seconds: u64) -> Self {
+        self.config.model_cache_ttl_seconds = Some(seconds);
+        self
+    }
+
+    /// Add a middleware layer around every registered provider
+    ///
+    /// Layers stack: the first one added sees requests first and responses last.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use stakai::provider::{LoggingLayer, RequestIdLayer};
+    /// use stakai::Inference;
+    ///
+    /// let client = Inference::builder()
+    ///     .layer(LoggingLayer::new())
+    ///     .layer(RequestIdLayer::new())
+    ///     .build()?;
+    /// # Ok::<(), stakai::Error>(())
+    /// ```
+    pub fn layer<L: ProviderLayer + 'static>(mut self, layer: L) -> Self {
+        self.layers.push(Arc::new(layer));
+        self
+    }
+
+    /// Register a fallback chain under `id`
+    ///
+    /// Targets are resolved against the registered providers when the client
+    /// is built, after layers are applied. Requests for `id` (or `id:model`)
+    /// try each target in order.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use stakai::provider::FallbackRoute;
+    /// use stakai::{GenerateRequest, Inference, Message, Role};
+    /// use std::time::Duration;
+    ///
+    /// # async fn example() -> Result<(), stakai::Error> {
+    /// let client = Inference::builder()
+    ///     .fallback(
+    ///         "smart",
+    ///         FallbackRoute::new(["openai:gpt-4o", "anthropic:claude-sonnet-4-20250514"])
+    ///             .with_circuit_breaker(3, Duration::from_secs(30)),
+    ///     )
+    ///     .build()?;
+    ///
+    /// let request = GenerateRequest::new("smart", vec![Message::new(Role::User, "Hi")]);
+    /// let response = client.generate(&request).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn fallback(mut self, id: impl Into<String>, route: FallbackRoute) -> Self {
+        self.fallbacks.push((id.into(), route));
+        self
+    }
+
     /// Build the inference client
     pub fn build(self) -> Result<Inference> {
+        let mut registry = self.layers.into_iter().rev().fold(
+            self.registry
+                .unwrap_or_else(|| ProviderRegistry::from_env(&self.config.http_config())),
+            ProviderRegistry::layer,
+        );
+        for (id, route) in &self.fallbacks {
+            let fallback = route.resolve(&registry)?;
+            registry = registry.register(id.clone(), fallback);
+        }
+
         Ok(Inference {
-            registry: self.registry.unwrap_or_default(),
+            registry,
+            models: ModelCache::new(self.config.model_cache_ttl()),
             config: self.config,
         })
     }
@@ -1,16 +1,51 @@
 //! Client configuration
 
+use crate::error::Result;
+use crate::http::{with_timeout, HttpConfig};
+use crate::provider::Provider;
 use crate::providers::{anthropic::AnthropicConfig, gemini::GeminiConfig, openai::OpenAIConfig};
+use crate::retry::RetryPolicy;
+use crate::types::{
+    EmbedRequest, EmbedResponse, GenerateOptions, GenerateRequest, GenerateResponse,
+    GenerateStream, ModelInfo,
+};
+use std::time::Duration;
+
+/// Default time to live of cached model lists (one hour)
+const DEFAULT_MODEL_CACHE_TTL_SECONDS: u64 = 3600;
 
 /// Configuration for the AI client
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone)]
 pub struct ClientConfig {
     /// Default temperature for requests
     pub default_temperature: Option<f32>,
     /// Default max tokens for requests
     pub default_max_tokens: Option<u32>,
     /// Request timeout in seconds
+    ///
+    /// Bounds connecting, each complete (non-streaming) request, and the idle
+    /// time between stream events.
     pub timeout_seconds: Option<u64>,
+    /// Retry policy for transient provider failures
+    ///
+    /// Defaults to [`RetryPolicy::none`]; set [`RetryPolicy::new`] to retry.
+    pub retry: RetryPolicy,
+    /// How long model lists are cached, in seconds
+    ///
+    /// Defaults to one hour; `0` disables caching.
+    pub model_cache_ttl_seconds: Option<u64>,
+}
+
+impl Default for ClientConfig {
+    fn default() -> Self {
+        Self {
+            default_temperature: None,
+            default_max_tokens: None,
+            timeout_seconds: None,
+            retry: RetryPolicy::none(),
+            model_cache_ttl_seconds: None,
+        }
+    }
 }
 
 impl ClientConfig {
@@ -36,6 +71,103 @@ impl ClientConfig {
         self.timeout_seconds = Some(seconds);
         self
     }
+
+    /// Set retry policy
+    pub fn with_retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Set how long model lists are cached, in seconds (`0` disables caching)
+    pub fn with_model_cache_ttl(mut self, seconds: u64) -> Self {
+        self.model_cache_ttl_seconds = Some(seconds);
+        self
+    }
+
+    /// Model list cache TTL as a duration
+    pub(crate) fn model_cache_ttl(&self) -> Duration {
+        Duration::from_secs(
+            self.model_cache_ttl_seconds
+                .unwrap_or(DEFAULT_MODEL_CACHE_TTL_SECONDS),
+        )
+    }
+
+    /// Request timeout as a duration
+    fn timeout(&self) -> Option<Duration> {
+        self.timeout_seconds.map(Duration::from_secs)
+    }
+
+    /// HTTP settings shared by the providers this configuration creates
+    pub(crate) fn http_config(&self) -> HttpConfig {
+        self.timeout()
+            .map(HttpConfig::from_timeout)
+            .unwrap_or_default()
+    }
+
+    /// Fill options left unset by the request with the client defaults
+    fn apply_defaults(&self, options: &mut GenerateOptions) {
+        options.temperature = options.temperature.or(self.default_temperature);
+        options.max_tokens = options.max_tokens.or(self.default_max_tokens);
+    }
+
+    /// Generate with client defaults, timeout and retry policy applied
+    pub(crate) async fn generate<P: Provider + ?Sized>(
+        &self,
+        provider: &P,
+        mut request: GenerateRequest,
+    ) -> Result<GenerateResponse> {
+        self.apply_defaults(&mut request.options);
+
+        let timeout = self.timeout();
+        self.retry
+            .run(|| with_timeout(timeout, provider.generate(request.clone())))
+            .await
+    }
+
+    /// Stream with client defaults, timeout and retry policy applied
+    ///
+    /// The timeout bounds opening the stream and the idle time between events.
+    pub(crate) async fn stream<P: Provider + ?Sized>(
+        &self,
+        provider: &P,
+        mut request: GenerateRequest,
+    ) -> Result<GenerateStream> {
+        self.apply_defaults(&mut request.options);
+
+        let timeout = self.timeout();
+        self.retry
+            .run_stream(|| async {
+                let stream = with_timeout(timeout, provider.stream(request.clone())).await?;
+                Ok(match timeout {
+                    Some(timeout) => stream.with_idle_timeout(timeout),
+                    None => stream,
+                })
+            })
+            .await
+    }
+
+    /// Embed with timeout and retry policy applied
+    pub(crate) async fn embed<P: Provider + ?Sized>(
+        &self,
+        provider: &P,
+        request: EmbedRequest,
+    ) -> Result<EmbedResponse> {
+        let timeout = self.timeout();
+        self.retry
+            .run(|| with_timeout(timeout, provider.embed(request.clone())))
+            .await
+    }
+
+    /// List models with timeout and retry policy applied
+    pub(crate) async fn list_models<P: Provider + ?Sized>(
+        &self,
+        provider: &P,
+    ) -> Result<Vec<ModelInfo>> {
+        let timeout = self.timeout();
+        self.retry
+            .run(|| with_timeout(timeout, provider.list_models()))
+            .await
+    }
 }
 
 /// Provider configuration for Inference client
@@ -77,10 +209,13 @@ impl InferenceConfig {
     /// let config = InferenceConfig::new()
     ///     .openai("sk-...", None);
     ///
-    /// // With custom base URL (e.g., Azure OpenAI)
+    /// // With custom base URL (e.g., a proxy)
     /// let config = InferenceConfig::new()
-    ///     .openai("sk-...", Some("https://your-endpoint.openai.azure.com/v1".to_string()));
+    ///     .openai("sk-...", Some("https://openai-proxy.example.com/v1".to_string()));
     /// ```
+    ///
+    /// Azure OpenAI uses different URLs and authentication; register an
+    /// [`AzureOpenAIProvider`](crate::providers::openai::AzureOpenAIProvider) instead.
     pub fn openai(mut self, api_key: impl Into<String>, base_url: Option<String>) -> Self {
         let mut config = OpenAIConfig::new(api_key);
         if let Some(url) = base_url {
@@ -204,4 +339,25 @@ impl InferenceConfig {
         self.client_config.timeout_seconds = Some(seconds);
         self
     }
+
+    /// Set how long model lists are cached, in seconds (`0` disables caching)
+    pub fn model_cache_ttl(mut self, seconds: u64) -> Self {
+        self.client_config.model_cache_ttl_seconds = Some(seconds);
+        self
+    }
+
+    /// Set retry policy for all requests
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use stakai::{InferenceConfig, RetryPolicy};
+    /// let config = InferenceConfig::new()
+    ///     .anthropic("sk-ant-...", None)
+    ///     .retry(RetryPolicy::new().with_max_attempts(5));
+    /// ```
+    pub fn retry(mut self, retry: RetryPolicy) -> Self {
+        self.client_config.retry = retry;
+        self
+    }
 }
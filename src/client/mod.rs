@@ -2,19 +2,26 @@
 
 mod builder;
 mod config;
+mod models;
 
 pub use builder::ClientBuilder;
 pub use config::{ClientConfig, InferenceConfig};
 
+use crate::catalog;
 use crate::error::{Error, Result};
 use crate::registry::ProviderRegistry;
-use crate::types::{GenerateRequest, GenerateResponse, GenerateStream};
+use crate::types::{
+    EmbedRequest, EmbedResponse, GenerateRequest, GenerateResponse, GenerateStream, ModelInfo,
+    ResponseFormat,
+};
+use models::ModelCache;
+use serde::de::DeserializeOwned;
 
 /// High-level inference client for AI generation
 pub struct Inference {
     registry: ProviderRegistry,
-    #[allow(dead_code)]
     config: ClientConfig,
+    models: ModelCache,
 }
 
 impl Inference {
@@ -59,6 +66,9 @@ impl Inference {
     ///
     /// * `request` - Generation request with model identifier (e.g., "gpt-4" or "openai:gpt-4")
     ///
+    /// Options the request leaves unset fall back to the client's default
+    /// temperature and max tokens.
+    ///
     /// # Example
     ///
     /// ```rust,no_run
@@ -79,7 +89,57 @@ impl Inference {
 
         let mut req = request.clone();
         req.model = model_id.to_string();
-        provider.generate(req).await
+        self.config.generate(provider.as_ref(), req).await
+    }
+
+    /// Generate a structured response and deserialize it into `T`
+    ///
+    /// Requests JSON output if the request has no `response_format`; set
+    /// [`ResponseFormat::JsonSchema`] to constrain the output to a schema.
+    /// Fails with [`Error::SchemaMismatch`] if the output doesn't match `T`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use stakai::{Inference, GenerateRequest, Message, ResponseFormat, Role};
+    /// # use serde::Deserialize;
+    /// # use serde_json::json;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// #[derive(Deserialize)]
+    /// struct Person {
+    ///     name: String,
+    ///     age: u32,
+    /// }
+    ///
+    /// let client = Inference::new();
+    /// let mut request = GenerateRequest::new(
+    ///     "openai:gpt-4o",
+    ///     vec![Message::new(Role::User, "Alice is 30. Extract the person.")]
+    /// );
+    /// request.options = request.options.response_format(ResponseFormat::json_schema(
+    ///     "person",
+    ///     json!({
+    ///         "type": "object",
+    ///         "properties": {"name": {"type": "string"}, "age": {"type": "integer"}},
+    ///         "required": ["name", "age"]
+    ///     }),
+    /// ));
+    ///
+    /// let person: Person = client.generate_object(&request).await?;
+    /// println!("{} is {}", person.name, person.age);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn generate_object<T: DeserializeOwned>(
+        &self,
+        request: &GenerateRequest,
+    ) -> Result<T> {
+        let mut req = request.clone();
+        req.options
+            .response_format
+            .get_or_insert(ResponseFormat::JsonObject);
+
+        self.generate(&req).await?.object()
     }
 
     /// Generate a streaming response
@@ -116,7 +176,109 @@ impl Inference {
 
         let mut req = request.clone();
         req.model = model_id.to_string();
-        provider.stream(req).await
+        self.config.stream(provider.as_ref(), req).await
+    }
+
+    /// Embed texts
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - Embedding request with model identifier (e.g., "openai:text-embedding-3-small")
+    ///
+    /// Inputs beyond the provider's batch limit are split across several
+    /// requests; embeddings come back in input order.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use stakai::{Inference, EmbedRequest};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Inference::new();
+    /// let request = EmbedRequest::new(
+    ///     "openai:text-embedding-3-small",
+    ///     ["The quick brown fox", "jumps over the lazy dog"],
+    /// )
+    /// .dimensions(256);
+    ///
+    /// let response = client.embed(&request).await?;
+    /// println!("{} embeddings", response.embeddings.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn embed(&self, request: &EmbedRequest) -> Result<EmbedResponse> {
+        let (provider_id, model_id) = self.parse_model(&request.model)?;
+        let provider = self.registry.get_provider(&provider_id)?;
+
+        let mut req = request.clone();
+        req.model = model_id.to_string();
+        self.config.embed(provider.as_ref(), req).await
+    }
+
+    /// List the models offered by a registered provider
+    ///
+    /// Results are cached per provider for the configured model cache TTL.
+    /// Each model's `provider` is the ID the provider is registered under.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use stakai::Inference;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Inference::new();
+    /// for model in client.list_models("openai").await? {
+    ///     println!("{}", model.qualified_id());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn list_models(&self, provider_id: &str) -> Result<Vec<ModelInfo>> {
+        if let Some(models) = self.models.get(provider_id) {
+            return Ok(models);
+        }
+
+        let provider = self.registry.get_provider(provider_id)?;
+        let mut models = self.config.list_models(provider.as_ref()).await?;
+        for model in &mut models {
+            model.provider = provider_id.to_string();
+        }
+
+        self.models.insert(provider_id, models.clone());
+        Ok(models)
+    }
+
+    /// List the models of all registered providers
+    ///
+    /// Providers are queried concurrently; models are grouped by provider ID
+    /// in alphabetical order. Providers that fail are skipped with a warning,
+    /// so one unreachable provider doesn't hide the others' models; an error
+    /// is returned only if every provider fails.
+    pub async fn list_all_models(&self) -> Result<Vec<ModelInfo>> {
+        let mut provider_ids = self.registry.list_providers();
+        provider_ids.sort();
+
+        let results =
+            futures::future::join_all(provider_ids.iter().map(|id| self.list_models(id))).await;
+
+        let mut models = Vec::new();
+        let mut listed = false;
+        let mut first_error = None;
+        for (provider, result) in provider_ids.iter().zip(results) {
+            match result {
+                Ok(provider_models) => {
+                    listed = true;
+                    models.extend(provider_models);
+                }
+                Err(error) => {
+                    tracing::warn!(provider, %error, "listing models failed");
+                    first_error.get_or_insert(error);
+                }
+            }
+        }
+
+        match first_error {
+            Some(error) if !listed => Err(error),
+            _ => Ok(models),
+        }
     }
 
     /// Parse model string into provider and model ID
@@ -124,6 +286,9 @@ impl Inference {
         if let Some((provider, model_id)) = model.split_once(':') {
             // Explicit provider:model format
             Ok((provider.to_string(), model_id))
+        } else if self.registry.has_provider(model) {
+            // Bare provider ID (e.g., a fallback route), which picks the model
+            Ok((model.to_string(), ""))
         } else {
             // Auto-detect provider from model name
             let provider = self.detect_provider(model)?;
@@ -131,19 +296,9 @@ impl Inference {
         }
     }
 
-    /// Detect provider from model name using heuristics
+    /// Detect provider from model name using the model catalog
     pub(crate) fn detect_provider(&self, model: &str) -> Result<String> {
-        let model_lower = model.to_lowercase();
-
-        if model_lower.starts_with("gpt-") || model_lower.starts_with("o1-") {
-            Ok("openai".to_string())
-        } else if model_lower.starts_with("claude-") {
-            Ok("anthropic".to_string())
-        } else if model_lower.starts_with("gemini-") {
-            Ok("google".to_string())
-        } else {
-            Err(Error::UnknownProvider(model.to_string()))
-        }
+        catalog::provider_for(model).ok_or_else(|| Error::UnknownProvider(model.to_string()))
     }
 
     /// Get the provider registry
@@ -0,0 +1,40 @@
+//! Model list cache
+
+use crate::types::ModelInfo;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Per-provider cache of model lists with a fixed time to live
+pub(crate) struct ModelCache {
+    ttl: Duration,
+    entries: Mutex<HashMap<String, (Instant, Vec<ModelInfo>)>>,
+}
+
+impl ModelCache {
+    /// Create an empty cache (a zero TTL disables caching)
+    pub(crate) fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Get the cached models of a provider, if not yet expired
+    pub(crate) fn get(&self, provider: &str) -> Option<Vec<ModelInfo>> {
+        let entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries
+            .get(provider)
+            .filter(|(fetched_at, _)| fetched_at.elapsed() < self.ttl)
+            .map(|(_, models)| models.clone())
+    }
+
+    /// Cache the models of a provider
+    pub(crate) fn insert(&self, provider: &str, models: Vec<ModelInfo>) {
+        if self.ttl.is_zero() {
+            return;
+        }
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries.insert(provider.to_string(), (Instant::now(), models));
+    }
+}
@@ -1,5 +1,6 @@
 //! Error types for the AI SDK
 
+use std::time::Duration;
 use thiserror::Error;
 
 /// Result type alias using the SDK's Error type
@@ -36,14 +37,73 @@ pub enum Error {
     #[error("API key not found for provider: {0}")]
     MissingApiKey(String),
 
+    /// Authentication failed (missing, invalid or expired credentials)
+    #[error("Authentication failed: {0}")]
+    Authentication(Box<ApiError>),
+
+    /// Credentials are valid but lack access to the resource
+    #[error("Permission denied: {0}")]
+    PermissionDenied(Box<ApiError>),
+
+    /// Model or resource not found
+    #[error("Not found: {0}")]
+    NotFound(Box<ApiError>),
+
+    /// Request was rejected as malformed or invalid
+    #[error("Invalid request: {0}")]
+    InvalidRequest(Box<ApiError>),
+
     /// Rate limit exceeded
     #[error("Rate limit exceeded: {0}")]
-    RateLimitExceeded(String),
+    RateLimitExceeded(Box<ApiError>),
+
+    /// Provider is temporarily overloaded
+    #[error("Provider overloaded: {0}")]
+    Overloaded(Box<ApiError>),
+
+    /// Prompt does not fit in the model's context window
+    #[error("Context length exceeded: {0}")]
+    ContextLengthExceeded(Box<ApiError>),
+
+    /// Request or output was blocked by the provider's content policy
+    #[error("Content policy violation: {0}")]
+    ContentPolicy(Box<ApiError>),
+
+    /// Any other error response from a provider API (e.g., HTTP 5xx)
+    #[error("{0}")]
+    Api(Box<ApiError>),
 
     /// Provider-specific error
     #[error("Provider error: {0}")]
     ProviderError(String),
 
+    /// Request or stream exceeded its deadline
+    #[error("Request timed out after {0:?}")]
+    Timeout(Duration),
+
+    /// Structured output is not valid JSON or does not match the requested type
+    #[error("Structured output does not match the schema: {message}")]
+    SchemaMismatch {
+        /// Why the output was rejected
+        message: String,
+        /// The raw model output
+        output: String,
+    },
+
+    /// Recorded cost exceeds the budget of a [`CostTracker`](crate::CostTracker)
+    #[error("Budget exceeded: spent ${spent:.4} of ${budget:.4}")]
+    BudgetExceeded {
+        /// Cost recorded so far in USD
+        spent: f64,
+        /// Budget in USD
+        budget: f64,
+    },
+
+    /// Every fallback target or pool member is unavailable (circuit breaker
+    /// open or ejected)
+    #[error("Circuit breaker open for: {0}")]
+    CircuitOpen(String),
+
     /// Streaming error
     #[error("Streaming error: {0}")]
     StreamError(String),
@@ -72,4 +132,192 @@ impl Error {
     pub fn stream_error(msg: impl Into<String>) -> Self {
         Self::StreamError(msg.into())
     }
+
+    /// Provider error response details, if this error came from a provider API
+    pub fn api_error(&self) -> Option<&ApiError> {
+        match self {
+            Self::Authentication(e)
+            | Self::PermissionDenied(e)
+            | Self::NotFound(e)
+            | Self::InvalidRequest(e)
+            | Self::RateLimitExceeded(e)
+            | Self::Overloaded(e)
+            | Self::ContextLengthExceeded(e)
+            | Self::ContentPolicy(e)
+            | Self::Api(e) => Some(e.as_ref()),
+            _ => None,
+        }
+    }
+
+    /// HTTP status code of the failed response, if any
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            Self::HttpError(e) => e.status().map(|s| s.as_u16()),
+            _ => self.api_error().map(|e| e.status),
+        }
+    }
+
+    /// Delay requested by the provider before retrying, if any
+    pub fn retry_after(&self) -> Option<Duration> {
+        self.api_error().and_then(|e| e.retry_after)
+    }
+
+    /// Provider request ID of the failed response, if any
+    pub fn request_id(&self) -> Option<&str> {
+        self.api_error().and_then(|e| e.request_id.as_deref())
+    }
+
+    /// Check if this error was caused by a connection failure or reset
+    pub fn is_connection_error(&self) -> bool {
+        let Self::HttpError(e) = self else {
+            return false;
+        };
+
+        if e.is_connect() {
+            return true;
+        }
+
+        // Walk the source chain looking for an I/O error from a dropped connection
+        let mut source = std::error::Error::source(e);
+        while let Some(err) = source {
+            if let Some(io) = err.downcast_ref::<std::io::Error>() {
+                return matches!(
+                    io.kind(),
+                    std::io::ErrorKind::ConnectionReset
+                        | std::io::ErrorKind::ConnectionAborted
+                        | std::io::ErrorKind::BrokenPipe
+                        | std::io::ErrorKind::UnexpectedEof
+                );
+            }
+            source = err.source();
+        }
+
+        false
+    }
+}
+
+/// Error response returned by a provider API
+#[derive(Debug, Clone)]
+pub struct ApiError {
+    /// Provider identifier (e.g., "openai", "anthropic")
+    pub provider: String,
+    /// HTTP status code
+    pub status: u16,
+    /// Provider error type (e.g., "overloaded_error", "RESOURCE_EXHAUSTED"), if reported
+    pub error_type: Option<String>,
+    /// Provider error code (e.g., "context_length_exceeded"), if reported
+    pub code: Option<String>,
+    /// Error message
+    pub message: String,
+    /// Provider request ID, if reported
+    pub request_id: Option<String>,
+    /// Delay requested via `Retry-After` / `retry-after-ms`, if any
+    pub retry_after: Option<Duration>,
+}
+
+impl ApiError {
+    /// Create a new API error
+    pub fn new(provider: impl Into<String>, status: u16, message: impl Into<String>) -> Self {
+        Self {
+            provider: provider.into(),
+            status,
+            error_type: None,
+            code: None,
+            message: message.into(),
+            request_id: None,
+            retry_after: None,
+        }
+    }
+
+    /// Set the provider error type
+    pub fn with_error_type(mut self, error_type: impl Into<String>) -> Self {
+        self.error_type = Some(error_type.into());
+        self
+    }
+
+    /// Set the provider error code
+    pub fn with_code(mut self, code: impl Into<String>) -> Self {
+        self.code = Some(code.into());
+        self
+    }
+
+    /// Set the provider request ID
+    pub fn with_request_id(mut self, request_id: impl Into<String>) -> Self {
+        self.request_id = Some(request_id.into());
+        self
+    }
+
+    /// Set the requested retry delay
+    pub fn with_retry_after(mut self, retry_after: Duration) -> Self {
+        self.retry_after = Some(retry_after);
+        self
+    }
+
+    /// Build an API error from a non-success HTTP response, consuming its body
+    ///
+    /// The raw body becomes the message; providers refine it by parsing
+    /// their own error envelope.
+    pub async fn from_response(provider: impl Into<String>, response: reqwest::Response) -> Self {
+        let status = response.status().as_u16();
+        let headers = response.headers();
+        let retry_after = crate::retry::parse_retry_after(headers);
+        let request_id = crate::http::request_id(headers);
+        let message = response.text().await.unwrap_or_default();
+
+        Self {
+            request_id,
+            retry_after,
+            ..Self::new(provider, status, message)
+        }
+    }
+}
+
+impl std::fmt::Display for ApiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} API error {}: {}",
+            self.provider, self.status, self.message
+        )
+    }
+}
+
+impl std::error::Error for ApiError {}
+
+impl From<ApiError> for Error {
+    /// Classify an API error by HTTP status
+    ///
+    /// Providers detect kinds that share a status (context length, content
+    /// policy) from their error envelope before falling back to this.
+    fn from(error: ApiError) -> Self {
+        let error = Box::new(error);
+        match error.status {
+            400 | 413 | 422 => Self::InvalidRequest(error),
+            401 => Self::Authentication(error),
+            403 => Self::PermissionDenied(error),
+            404 => Self::NotFound(error),
+            429 => Self::RateLimitExceeded(error),
+            529 => Self::Overloaded(error),
+            _ => Self::Api(error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_by_status() {
+        let error = |status| Error::from(ApiError::new("test", status, "error"));
+
+        assert!(matches!(error(400), Error::InvalidRequest(_)));
+        assert!(matches!(error(401), Error::Authentication(_)));
+        assert!(matches!(error(403), Error::PermissionDenied(_)));
+        assert!(matches!(error(404), Error::NotFound(_)));
+        assert!(matches!(error(429), Error::RateLimitExceeded(_)));
+        assert!(matches!(error(529), Error::Overloaded(_)));
+        assert!(matches!(error(500), Error::Api(_)));
+        assert_eq!(error(503).status(), Some(503));
+    }
 }
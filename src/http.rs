@@ -0,0 +1,121 @@
+//! Shared HTTP settings for provider clients
+
+use crate::error::{Error, Result};
+use crate::types::ResponseMetadata;
+use reqwest::header::HeaderMap;
+use reqwest::{Client, Response};
+use serde::de::DeserializeOwned;
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+/// HTTP settings used to build a provider's `reqwest::Client`
+///
+/// # Example
+///
+/// ```rust
+/// use stakai::http::HttpConfig;
+/// use stakai::providers::openai::OpenAIConfig;
+/// use std::time::Duration;
+///
+/// let config = OpenAIConfig::new("sk-...").with_http_config(
+///     HttpConfig::new()
+///         .with_connect_timeout(Duration::from_secs(5))
+///         .with_read_timeout(Duration::from_secs(60)),
+/// );
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct HttpConfig {
+    /// Timeout for establishing a connection
+    pub connect_timeout: Option<Duration>,
+    /// Timeout for each read from the connection (bounds stalled responses and streams)
+    pub read_timeout: Option<Duration>,
+}
+
+impl HttpConfig {
+    /// Create new default HTTP settings (no timeouts)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Use the same timeout for connecting and for every read
+    pub fn from_timeout(timeout: Duration) -> Self {
+        Self {
+            connect_timeout: Some(timeout),
+            read_timeout: Some(timeout),
+        }
+    }
+
+    /// Set connect timeout
+    pub fn with_connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Set read timeout
+    pub fn with_read_timeout(mut self, timeout: Duration) -> Self {
+        self.read_timeout = Some(timeout);
+        self
+    }
+
+    /// Fill settings left unset from `defaults`
+    pub(crate) fn or(self, defaults: &HttpConfig) -> Self {
+        Self {
+            connect_timeout: self.connect_timeout.or(defaults.connect_timeout),
+            read_timeout: self.read_timeout.or(defaults.read_timeout),
+        }
+    }
+
+    /// Build a `reqwest::Client` with these settings
+    pub(crate) fn build_client(&self) -> Result<Client> {
+        let mut builder = Client::builder();
+
+        if let Some(timeout) = self.connect_timeout {
+            builder = builder.connect_timeout(timeout);
+        }
+        if let Some(timeout) = self.read_timeout {
+            builder = builder.read_timeout(timeout);
+        }
+
+        Ok(builder.build()?)
+    }
+}
+
+/// Run a future with an optional deadline, failing with [`Error::Timeout`] when it elapses
+pub(crate) async fn with_timeout<T>(
+    timeout: Option<Duration>,
+    future: impl Future<Output = Result<T>>,
+) -> Result<T> {
+    match timeout {
+        Some(duration) => tokio::time::timeout(duration, future)
+            .await
+            .map_err(|_| Error::Timeout(duration))?,
+        None => future.await,
+    }
+}
+
+/// Provider request ID from response headers
+pub(crate) fn request_id(headers: &HeaderMap) -> Option<String> {
+    ["x-request-id", "request-id", "x-amzn-requestid"]
+        .iter()
+        .find_map(|name| headers.get(*name).and_then(|v| v.to_str().ok()))
+        .map(str::to_string)
+}
+
+/// Read a successful JSON response from `provider`, along with its metadata
+///
+/// Latency covers the full body; the raw body is kept if `include_raw` is set.
+pub(crate) async fn read_json<T: DeserializeOwned>(
+    provider: &str,
+    response: Response,
+    started: Instant,
+    include_raw: bool,
+) -> Result<(T, ResponseMetadata)> {
+    let mut metadata = ResponseMetadata::from_http(provider, &response, started);
+    let body = response.bytes().await?;
+    metadata.latency = Some(started.elapsed());
+
+    if include_raw {
+        metadata.raw = Some(serde_json::from_slice(&body)?);
+    }
+    Ok((serde_json::from_slice(&body)?, metadata))
+}
@@ -33,19 +33,26 @@
 //! }
 //! ```
 
+pub mod catalog;
 pub mod client;
 pub mod error;
+pub mod http;
 pub mod provider;
 pub mod providers;
 pub mod registry;
+pub mod retry;
 pub mod types;
 
 // Re-export commonly used types
 pub use client::{Inference, InferenceConfig};
 pub use error::{Error, Result};
+pub use retry::RetryPolicy;
 pub use types::{
-    ContentPart, FinishReason, GenerateRequest, GenerateResponse, GenerateStream, Headers, Message,
-    ResponseContent, Role, StreamEvent, Tool, ToolChoice, ToolFunction, Usage,
+    CacheControl, Citation, ContentFilterResults, ContentPart, Cost, CostTracker, Document,
+    EmbedRequest, EmbedResponse, FinishReason, GenerateRequest, GenerateResponse, GenerateStream,
+    Headers, Message, ModelInfo, ProviderOptions, ReasoningConfig, ReasoningEffort,
+    ResponseContent, ResponseFormat, ResponseMetadata, Role, StreamEvent, Tool, ToolChoice,
+    ToolFunction, Usage,
 };
 
 /// Prelude module for convenient imports
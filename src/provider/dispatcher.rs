@@ -1,16 +1,22 @@
 //! Static dispatcher for zero-cost provider abstraction
 
+use crate::client::{ClientConfig, InferenceConfig};
 use crate::error::{Error, Result};
-use crate::types::{GenerateRequest, GenerateResponse, GenerateStream};
+use crate::providers::{
+    anthropic::AnthropicProvider, gemini::GeminiProvider, openai::OpenAIProvider,
+};
+use crate::types::{
+    EmbedRequest, EmbedResponse, GenerateRequest, GenerateResponse, GenerateStream,
+};
 
 /// Provider kind for static dispatch
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ProviderKind {
     /// OpenAI provider
     OpenAI,
-    /// Anthropic provider (future)
+    /// Anthropic provider
     Anthropic,
-    /// Google Gemini provider (future)
+    /// Google Gemini provider
     Google,
 }
 
@@ -39,21 +45,141 @@ impl std::str::FromStr for ProviderKind {
 }
 
 /// Static dispatcher for compile-time provider routing
-pub struct ProviderDispatcher;
+///
+/// Holds the concrete built-in providers by value and routes on [`ProviderKind`]
+/// with a `match`, avoiding the `Arc<dyn Provider>` lookup done by the registry.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use stakai::provider::{ProviderDispatcher, ProviderKind};
+/// use stakai::{GenerateRequest, InferenceConfig, Message, Role};
+///
+/// # async fn example() -> Result<(), stakai::Error> {
+/// let dispatcher = ProviderDispatcher::new(InferenceConfig::new().openai("sk-...", None))?;
+///
+/// let request = GenerateRequest::new("gpt-4", vec![Message::new(Role::User, "Hello!")]);
+/// let response = dispatcher.generate(ProviderKind::OpenAI, request).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Default)]
+pub struct ProviderDispatcher {
+    openai: Option<OpenAIProvider>,
+    anthropic: Option<AnthropicProvider>,
+    gemini: Option<GeminiProvider>,
+    config: ClientConfig,
+}
 
 impl ProviderDispatcher {
+    /// Create a dispatcher from the providers configured in an [`InferenceConfig`]
+    ///
+    /// Client defaults, timeout and retry policy from the configuration apply to
+    /// every request.
+    pub fn new(config: InferenceConfig) -> Result<Self> {
+        let http = config.client_config.http_config();
+
+        Ok(Self {
+            openai: config
+                .openai_config
+                .map(|mut c| {
+                    c.http = c.http.or(&http);
+                    OpenAIProvider::new(c)
+                })
+                .transpose()?,
+            anthropic: config
+                .anthropic_config
+                .map(|mut c| {
+                    c.http = c.http.or(&http);
+                    AnthropicProvider::new(c)
+                })
+                .transpose()?,
+            gemini: config
+                .gemini_config
+                .map(|mut c| {
+                    c.http = c.http.or(&http);
+                    GeminiProvider::new(c)
+                })
+                .transpose()?,
+            config: config.client_config,
+        })
+    }
+
+    /// Check if a provider kind is configured
+    pub fn has_provider(&self, kind: ProviderKind) -> bool {
+        match kind {
+            ProviderKind::OpenAI => self.openai.is_some(),
+            ProviderKind::Anthropic => self.anthropic.is_some(),
+            ProviderKind::Google => self.gemini.is_some(),
+        }
+    }
+
     /// Generate using static dispatch
     pub async fn generate(
-        _kind: ProviderKind,
-        _request: GenerateRequest,
+        &self,
+        kind: ProviderKind,
+        request: GenerateRequest,
     ) -> Result<GenerateResponse> {
-        // Will be implemented when providers are added
-        Err(Error::Other("Not implemented yet".to_string()))
+        match kind {
+            ProviderKind::OpenAI => {
+                let provider = configured(&self.openai, kind)?;
+                self.config.generate(provider, request).await
+            }
+            ProviderKind::Anthropic => {
+                let provider = configured(&self.anthropic, kind)?;
+                self.config.generate(provider, request).await
+            }
+            ProviderKind::Google => {
+                let provider = configured(&self.gemini, kind)?;
+                self.config.generate(provider, request).await
+            }
+        }
     }
 
     /// Stream using static dispatch
-    pub async fn stream(_kind: ProviderKind, _request: GenerateRequest) -> Result<GenerateStream> {
-        // Will be implemented when providers are added
-        Err(Error::Other("Not implemented yet".to_string()))
+    pub async fn stream(
+        &self,
+        kind: ProviderKind,
+        request: GenerateRequest,
+    ) -> Result<GenerateStream> {
+        match kind {
+            ProviderKind::OpenAI => {
+                let provider = configured(&self.openai, kind)?;
+                self.config.stream(provider, request).await
+            }
+            ProviderKind::Anthropic => {
+                let provider = configured(&self.anthropic, kind)?;
+                self.config.stream(provider, request).await
+            }
+            ProviderKind::Google => {
+                let provider = configured(&self.gemini, kind)?;
+                self.config.stream(provider, request).await
+            }
+        }
     }
+
+    /// Embed using static dispatch
+    pub async fn embed(&self, kind: ProviderKind, request: EmbedRequest) -> Result<EmbedResponse> {
+        match kind {
+            ProviderKind::OpenAI => {
+                let provider = configured(&self.openai, kind)?;
+                self.config.embed(provider, request).await
+            }
+            ProviderKind::Anthropic => {
+                let provider = configured(&self.anthropic, kind)?;
+                self.config.embed(provider, request).await
+            }
+            ProviderKind::Google => {
+                let provider = configured(&self.gemini, kind)?;
+                self.config.embed(provider, request).await
+            }
+        }
+    }
+}
+
+/// Get a configured provider or a `ProviderNotFound` error
+fn configured<P>(provider: &Option<P>, kind: ProviderKind) -> Result<&P> {
+    provider
+        .as_ref()
+        .ok_or_else(|| Error::ProviderNotFound(kind.as_str().to_string()))
 }
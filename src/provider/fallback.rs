@@ -0,0 +1,480 @@
+//! Fallback chains across providers and models
+
+use super::Provider;
+use crate::catalog;
+use crate::error::{Error, Result};
+use crate::registry::ProviderRegistry;
+use crate::retry::RetryPolicy;
+use crate::types::{
+    ContentPart, GenerateRequest, GenerateResponse, GenerateStream, Headers, MessageContent,
+    StreamEvent,
+};
+use async_trait::async_trait;
+use futures::StreamExt;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Stops sending requests to a failing target for a cooldown period
+///
+/// Opens after `failure_threshold` consecutive failures. Once the cooldown
+/// has elapsed the breaker is half-open: exactly one probe request is let
+/// through while others are still blocked. The probe's success closes the
+/// breaker, its failure opens it again. A probe that never reports back is
+/// replaced by a new one after another cooldown.
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    failure_threshold: u32,
+    cooldown: Duration,
+    state: Mutex<BreakerState>,
+}
+
+#[derive(Debug)]
+enum BreakerState {
+    /// Requests flow, counting consecutive failures
+    Closed { failures: u32 },
+    /// Requests are blocked until the cooldown ends
+    Open { until: Instant },
+    /// A probe request is in flight; others are blocked until it reports or expires
+    HalfOpen { until: Instant },
+}
+
+impl Default for BreakerState {
+    fn default() -> Self {
+        Self::Closed { failures: 0 }
+    }
+}
+
+impl CircuitBreaker {
+    /// Create a breaker opening after `failure_threshold` consecutive failures
+    pub fn new(failure_threshold: u32, cooldown: Duration) -> Self {
+        Self {
+            failure_threshold: failure_threshold.max(1),
+            cooldown,
+            state: Mutex::default(),
+        }
+    }
+
+    /// Check if requests are currently blocked
+    ///
+    /// Returns `false` once the cooldown has elapsed, though only the caller
+    /// winning [`try_acquire`](Self::try_acquire) gets to send the probe.
+    pub fn is_open(&self) -> bool {
+        match *self.lock() {
+            BreakerState::Closed { .. } => false,
+            BreakerState::Open { until } | BreakerState::HalfOpen { until } => {
+                Instant::now() < until
+            }
+        }
+    }
+
+    /// Claim permission to send a request
+    ///
+    /// Always granted while closed; once the cooldown has elapsed, granted to
+    /// a single caller, whose request is the probe.
+    pub fn try_acquire(&self) -> bool {
+        let mut state = self.lock();
+        match *state {
+            BreakerState::Closed { .. } => true,
+            BreakerState::Open { until } | BreakerState::HalfOpen { until }
+                if Instant::now() >= until =>
+            {
+                *state = BreakerState::HalfOpen {
+                    until: Instant::now() + self.cooldown,
+                };
+                true
+            }
+            BreakerState::Open { .. } | BreakerState::HalfOpen { .. } => false,
+        }
+    }
+
+    /// Record a successful request, closing the breaker
+    pub fn record_success(&self) {
+        *self.lock() = BreakerState::default();
+    }
+
+    /// Record a failed request, opening the breaker at the failure threshold
+    /// or when the probe failed
+    pub fn record_failure(&self) {
+        let mut state = self.lock();
+        let failures = match *state {
+            BreakerState::Closed { failures } => failures + 1,
+            BreakerState::Open { .. } | BreakerState::HalfOpen { .. } => self.failure_threshold,
+        };
+        *state = if failures >= self.failure_threshold {
+            BreakerState::Open {
+                until: Instant::now() + self.cooldown,
+            }
+        } else {
+            BreakerState::Closed { failures }
+        };
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BreakerState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// A `provider:model` target of a [`FallbackProvider`]
+pub struct FallbackTarget {
+    label: String,
+    provider: Arc<dyn Provider>,
+    model: String,
+    breaker: Option<CircuitBreaker>,
+}
+
+impl FallbackTarget {
+    /// Target `model` on `provider`; an empty model uses the requested one
+    pub fn new(provider: Arc<dyn Provider>, model: impl Into<String>) -> Self {
+        let label = provider.provider_id().to_string();
+        Self::labeled(label, provider, model.into())
+    }
+
+    /// Stop trying this target while its circuit breaker is open
+    pub fn with_circuit_breaker(mut self, breaker: CircuitBreaker) -> Self {
+        self.breaker = Some(breaker);
+        self
+    }
+
+    fn labeled(label: String, provider: Arc<dyn Provider>, model: String) -> Self {
+        let label = if model.is_empty() {
+            label
+        } else {
+            format!("{}:{}", label, model)
+        };
+
+        Self {
+            label,
+            provider,
+            model,
+            breaker: None,
+        }
+    }
+
+    /// Translate a request for this target: set its model, fit max tokens
+    /// to the model's output limit from the catalog and, unless the target
+    /// runs on the `primary` provider, drop reasoning signed by it
+    ///
+    /// Provider options are passed through unchanged: they are keyed by
+    /// provider, so each target applies only its own.
+    fn translate(&self, request: &GenerateRequest, primary: &str) -> GenerateRequest {
+        let mut request = request.clone();
+        if !self.model.is_empty() {
+            request.model = self.model.clone();
+        }
+
+        let limit = catalog::lookup(&request.model).and_then(|model| model.max_output_tokens);
+        if let (Some(max_tokens), Some(limit)) = (request.options.max_tokens, limit) {
+            request.options.max_tokens = Some(max_tokens.min(limit));
+        }
+
+        if self.provider.provider_id() != primary {
+            strip_signed_reasoning(&mut request);
+        }
+        request
+    }
+
+    /// Check if this target may be tried, claiming the probe of a half-open breaker
+    fn is_available(&self) -> bool {
+        self.breaker
+            .as_ref()
+            .is_none_or(CircuitBreaker::try_acquire)
+    }
+
+    fn record(&self, success: bool) {
+        if let Some(breaker) = &self.breaker {
+            if success {
+                breaker.record_success();
+            } else {
+                breaker.record_failure();
+            }
+        }
+    }
+}
+
+/// Remove reasoning signed or redacted by another provider, which would be
+/// rejected, and the signatures on tool calls
+fn strip_signed_reasoning(request: &mut GenerateRequest) {
+    for message in &mut request.messages {
+        let MessageContent::Parts(parts) = &mut message.content else {
+            continue;
+        };
+        parts.retain(|part| {
+            !matches!(part, ContentPart::Reasoning { signature, data, .. }
+                if signature.is_some() || data.is_some())
+        });
+        for part in parts {
+            if let ContentPart::ToolCall { signature, .. } = part {
+                *signature = None;
+            }
+        }
+    }
+}
+
+/// Provider trying an ordered list of targets until one succeeds
+///
+/// Moves to the next target on rate limits, overload, server, connection,
+/// timeout, context length and availability (authentication, permission, not
+/// found) errors; other errors are returned as is. Streams fall back only
+/// until their first event other than `Start`, so no content is ever
+/// repeated. The serving target is recorded in
+/// [`ResponseMetadata::target`](crate::ResponseMetadata::target).
+///
+/// Targets get their model set and max tokens fitted. Reasoning signed by one
+/// provider is rejected by others, so targets on a provider other than the
+/// first target's drop signed and redacted reasoning from the conversation.
+///
+/// # Example
+///
+/// ```rust
+/// use stakai::provider::{CircuitBreaker, FallbackProvider, FallbackTarget};
+/// use stakai::providers::anthropic::{AnthropicConfig, AnthropicProvider};
+/// use stakai::providers::openai::{OpenAIConfig, OpenAIProvider};
+/// use stakai::Inference;
+/// use std::sync::Arc;
+/// use std::time::Duration;
+///
+/// let openai = Arc::new(OpenAIProvider::new(OpenAIConfig::new("sk-..."))?);
+/// let anthropic = Arc::new(AnthropicProvider::new(AnthropicConfig::new("sk-ant-..."))?);
+///
+/// let fallback = FallbackProvider::new()
+///     .with_target(
+///         FallbackTarget::new(openai, "gpt-4o")
+///             .with_circuit_breaker(CircuitBreaker::new(3, Duration::from_secs(30))),
+///     )
+///     .with_target(FallbackTarget::new(anthropic, "claude-sonnet-4-20250514"));
+///
+/// let client = Inference::builder().register_provider("smart", fallback).build()?;
+/// // Requests for "smart" (or "smart:any-model") go through the chain
+/// # Ok::<(), stakai::Error>(())
+/// ```
+#[derive(Default)]
+pub struct FallbackProvider {
+    targets: Vec<FallbackTarget>,
+}
+
+impl FallbackProvider {
+    /// Create an empty fallback chain
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a target to the chain
+    pub fn with_target(mut self, target: FallbackTarget) -> Self {
+        self.targets.push(target);
+        self
+    }
+
+    /// Check if an error moves the request on to the next target
+    pub fn should_fall_back(error: &Error) -> bool {
+        RetryPolicy::default().is_retryable(error)
+            || matches!(
+                error,
+                Error::Api(_)
+                    | Error::Timeout(_)
+                    | Error::Authentication(_)
+                    | Error::PermissionDenied(_)
+                    | Error::NotFound(_)
+                    | Error::ContextLengthExceeded(_)
+                    | Error::CircuitOpen(_)
+            )
+    }
+
+    /// Run `attempt` with the provider, label and translated request of each
+    /// available target until one succeeds
+    async fn run<T, F, Fut>(&self, request: &GenerateRequest, mut attempt: F) -> Result<T>
+    where
+        F: FnMut(Arc<dyn Provider>, String, GenerateRequest) -> Fut,
+        Fut: std::future::Future<Output = Result<T>>,
+    {
+        let mut last_error = None;
+        let mut skipped = Vec::new();
+        // Reasoning in the conversation is assumed to be signed by the first target
+        let primary = self
+            .targets
+            .first()
+            .map_or("", |target| target.provider.provider_id());
+
+        for target in &self.targets {
+            if !target.is_available() {
+                skipped.push(target.label.as_str());
+                continue;
+            }
+
+            let label = target.label.clone();
+            match attempt(
+                target.provider.clone(),
+                label,
+                target.translate(request, primary),
+            )
+            .await
+            {
+                Ok(value) => {
+                    target.record(true);
+                    return Ok(value);
+                }
+                Err(error) if Self::should_fall_back(&error) => {
+                    target.record(false);
+                    last_error = Some(error);
+                }
+                Err(error) => {
+                    // The target answered, so it counts as healthy (and a probe reports back)
+                    target.record(true);
+                    return Err(error);
+                }
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| {
+            if skipped.is_empty() {
+                Error::ConfigError("fallback chain has no targets".to_string())
+            } else {
+                Error::CircuitOpen(skipped.join(", "))
+            }
+        }))
+    }
+}
+
+#[async_trait]
+impl Provider for FallbackProvider {
+    fn provider_id(&self) -> &str {
+        "fallback"
+    }
+
+    fn build_headers(&self, custom_headers: Option<&Headers>) -> Headers {
+        custom_headers.cloned().unwrap_or_default()
+    }
+
+    async fn generate(&self, request: GenerateRequest) -> Result<GenerateResponse> {
+        self.run(&request, |provider, label, request| async move {
+            let mut response = provider.generate(request).await?;
+            response
+                .metadata
+                .get_or_insert_with(Default::default)
+                .target = Some(label);
+            Ok(response)
+        })
+        .await
+    }
+
+    async fn stream(&self, request: GenerateRequest) -> Result<GenerateStream> {
+        self.run(&request, |provider, label, request| async move {
+            let stream = provider.stream(request).await?.ready().await?;
+            Ok(GenerateStream::new(Box::pin(stream.map(move |event| {
+                event.map(|mut event| {
+                    if let StreamEvent::Start { metadata, .. } = &mut event {
+                        metadata.target = Some(label.clone());
+                    }
+                    event
+                })
+            }))))
+        })
+        .await
+    }
+}
+
+/// Fallback chain of `provider:model` targets resolved against the registry
+///
+/// Registered with [`ClientBuilder::fallback`](crate::client::ClientBuilder::fallback).
+#[derive(Debug, Clone)]
+pub struct FallbackRoute {
+    targets: Vec<String>,
+    circuit_breaker: Option<(u32, Duration)>,
+}
+
+impl FallbackRoute {
+    /// Create a route trying `targets` in order (e.g., "openai:gpt-4o")
+    ///
+    /// A target without a model (e.g., "openai") uses the requested model.
+    pub fn new<I, S>(targets: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            targets: targets.into_iter().map(Into::into).collect(),
+            circuit_breaker: None,
+        }
+    }
+
+    /// Give every target its own circuit breaker
+    pub fn with_circuit_breaker(mut self, failure_threshold: u32, cooldown: Duration) -> Self {
+        self.circuit_breaker = Some((failure_threshold, cooldown));
+        self
+    }
+
+    /// Build the fallback provider from the providers in `registry`
+    pub fn resolve(&self, registry: &ProviderRegistry) -> Result<FallbackProvider> {
+        let mut fallback = FallbackProvider::new();
+        for target in &self.targets {
+            let (id, model) = target.split_once(':').unwrap_or((target, ""));
+            let mut target =
+                FallbackTarget::labeled(id.to_string(), registry.get_provider(id)?, model.into());
+            if let Some((failure_threshold, cooldown)) = self.circuit_breaker {
+                target =
+                    target.with_circuit_breaker(CircuitBreaker::new(failure_threshold, cooldown));
+            }
+            fallback = fallback.with_target(target);
+        }
+        Ok(fallback)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_circuit_breaker() {
+        let breaker = CircuitBreaker::new(2, Duration::from_secs(60));
+        breaker.record_failure();
+        assert!(!breaker.is_open());
+        breaker.record_failure();
+        assert!(breaker.is_open());
+
+        breaker.record_success();
+        assert!(!breaker.is_open());
+    }
+
+    #[test]
+    fn test_circuit_breaker_half_open() {
+        let breaker = CircuitBreaker::new(1, Duration::ZERO);
+        breaker.record_failure();
+        // Cooldown elapsed: a trial request is let through
+        assert!(!breaker.is_open());
+    }
+
+    #[test]
+    fn test_circuit_breaker_lets_one_probe_through() {
+        let breaker = CircuitBreaker::new(1, Duration::from_millis(200));
+        breaker.record_failure();
+        assert!(!breaker.try_acquire());
+        std::thread::sleep(Duration::from_millis(210));
+
+        // Concurrent callers after the cooldown: only one gets to probe
+        let granted = std::thread::scope(|scope| {
+            let callers: Vec<_> = (0..16)
+                .map(|_| scope.spawn(|| breaker.try_acquire()))
+                .collect();
+            callers
+                .into_iter()
+                .map(|caller| caller.join().unwrap())
+                .filter(|granted| *granted)
+                .count()
+        });
+        assert_eq!(granted, 1);
+        assert!(!breaker.try_acquire());
+
+        // The failed probe opens the breaker for another cooldown
+        breaker.record_failure();
+        assert!(breaker.is_open());
+        assert!(!breaker.try_acquire());
+        std::thread::sleep(Duration::from_millis(210));
+
+        // A successful probe closes it
+        assert!(breaker.try_acquire());
+        breaker.record_success();
+        assert!(!breaker.is_open());
+        assert!(breaker.try_acquire());
+        assert!(breaker.try_acquire());
+    }
+}
@@ -0,0 +1,140 @@
+//! Middleware layers around providers
+
+use super::Provider;
+use crate::error::{Error, Result};
+use crate::types::{
+    EmbedRequest, EmbedResponse, GenerateRequest, GenerateResponse, GenerateStream, Headers,
+    ModelInfo, StreamEvent,
+};
+use async_trait::async_trait;
+use futures::StreamExt;
+use std::sync::Arc;
+
+/// Middleware intercepting the requests and responses of a provider
+///
+/// Every hook defaults to doing nothing, so a layer implements only the ones
+/// it needs. `provider` is the registry ID of the wrapped provider.
+/// Layers are added with [`ClientBuilder::layer`](crate::client::ClientBuilder::layer)
+/// and wrap every registered provider, so each retry attempt passes through them.
+///
+/// # Example
+///
+/// ```rust
+/// use stakai::provider::ProviderLayer;
+/// use stakai::{GenerateRequest, Inference, Result};
+///
+/// struct ForceTemperature(f32);
+///
+/// #[async_trait::async_trait]
+/// impl ProviderLayer for ForceTemperature {
+///     async fn on_request(&self, _provider: &str, request: &mut GenerateRequest) -> Result<()> {
+///         request.options.temperature = Some(self.0);
+///         Ok(())
+///     }
+/// }
+///
+/// let client = Inference::builder().layer(ForceTemperature(0.0)).build()?;
+/// # Ok::<(), stakai::Error>(())
+/// ```
+#[async_trait]
+pub trait ProviderLayer: Send + Sync {
+    /// Inspect or rewrite a request before it is dispatched; an error aborts the request
+    async fn on_request(&self, provider: &str, request: &mut GenerateRequest) -> Result<()> {
+        let _ = (provider, request);
+        Ok(())
+    }
+
+    /// Inspect or rewrite a (non-streaming) response
+    fn on_response(&self, provider: &str, response: &mut GenerateResponse) {
+        let _ = (provider, response);
+    }
+
+    /// Inspect or rewrite a stream event
+    fn on_stream_event(&self, provider: &str, event: &mut StreamEvent) {
+        let _ = (provider, event);
+    }
+
+    /// Observe an error returned by the provider or its stream
+    fn on_error(&self, provider: &str, error: &Error) {
+        let _ = (provider, error);
+    }
+}
+
+/// A provider wrapped by a [`ProviderLayer`]
+///
+/// Generation goes through the layer's hooks; embeddings and model listing
+/// are passed to the inner provider unchanged.
+pub struct LayeredProvider {
+    id: String,
+    inner: Arc<dyn Provider>,
+    layer: Arc<dyn ProviderLayer>,
+}
+
+impl LayeredProvider {
+    /// Wrap a provider registered under `id` with a layer
+    pub fn new(
+        id: impl Into<String>,
+        inner: Arc<dyn Provider>,
+        layer: Arc<dyn ProviderLayer>,
+    ) -> Self {
+        Self {
+            id: id.into(),
+            inner,
+            layer,
+        }
+    }
+
+    fn report<T>(&self, result: Result<T>) -> Result<T> {
+        if let Err(error) = &result {
+            self.layer.on_error(&self.id, error);
+        }
+        result
+    }
+}
+
+#[async_trait]
+impl Provider for LayeredProvider {
+    fn provider_id(&self) -> &str {
+        self.inner.provider_id()
+    }
+
+    fn build_headers(&self, custom_headers: Option<&Headers>) -> Headers {
+        self.inner.build_headers(custom_headers)
+    }
+
+    async fn generate(&self, mut request: GenerateRequest) -> Result<GenerateResponse> {
+        self.layer.on_request(&self.id, &mut request).await?;
+
+        let mut response = self.report(self.inner.generate(request).await)?;
+        self.layer.on_response(&self.id, &mut response);
+        Ok(response)
+    }
+
+    async fn stream(&self, mut request: GenerateRequest) -> Result<GenerateStream> {
+        self.layer.on_request(&self.id, &mut request).await?;
+
+        let stream = self.report(self.inner.stream(request).await)?;
+        let layer = self.layer.clone();
+        let id = self.id.clone();
+        Ok(GenerateStream::new(Box::pin(stream.map(
+            move |event| match event {
+                Ok(mut event) => {
+                    layer.on_stream_event(&id, &mut event);
+                    Ok(event)
+                }
+                Err(error) => {
+                    layer.on_error(&id, &error);
+                    Err(error)
+                }
+            },
+        ))))
+    }
+
+    async fn embed(&self, request: EmbedRequest) -> Result<EmbedResponse> {
+        self.inner.embed(request).await
+    }
+
+    async fn list_models(&self) -> Result<Vec<ModelInfo>> {
+        self.inner.list_models().await
+    }
+}
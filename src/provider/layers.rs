@@ -0,0 +1,145 @@
+//! Built-in provider layers
+
+use super::ProviderLayer;
+use crate::error::{Error, Result};
+use crate::types::{GenerateRequest, GenerateResponse, Headers, StreamEvent};
+use async_trait::async_trait;
+
+/// Logs requests, responses and errors through `tracing`
+///
+/// Only metadata (model, message count, usage, finish reason, timings) is
+/// logged, never message content. Requests and responses are logged at debug
+/// level, errors at warn level.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LoggingLayer;
+
+impl LoggingLayer {
+    /// Create a logging layer
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl ProviderLayer for LoggingLayer {
+    async fn on_request(&self, provider: &str, request: &mut GenerateRequest) -> Result<()> {
+        tracing::debug!(
+            provider,
+            model = %request.model,
+            messages = request.messages.len(),
+            "generate request"
+        );
+        Ok(())
+    }
+
+    fn on_response(&self, provider: &str, response: &mut GenerateResponse) {
+        let metadata = response.metadata.as_ref();
+        tracing::debug!(
+            provider,
+            model = response.model(),
+            request_id = metadata.and_then(|m| m.request_id.as_deref()),
+            finish_reason = ?response.finish_reason,
+            prompt_tokens = response.usage.prompt_tokens,
+            completion_tokens = response.usage.completion_tokens,
+            latency = ?metadata.and_then(|m| m.latency),
+            "generate response"
+        );
+    }
+
+    fn on_stream_event(&self, provider: &str, event: &mut StreamEvent) {
+        match event {
+            StreamEvent::Start { metadata, .. } => tracing::debug!(
+                provider,
+                model = metadata.model.as_deref(),
+                request_id = metadata.request_id.as_deref(),
+                latency = ?metadata.latency,
+                time_to_first_token = ?metadata.time_to_first_token,
+                "stream started"
+            ),
+            StreamEvent::Finish { usage, reason } => tracing::debug!(
+                provider,
+                finish_reason = ?reason,
+                prompt_tokens = usage.prompt_tokens,
+                completion_tokens = usage.completion_tokens,
+                "stream finished"
+            ),
+            _ => {}
+        }
+    }
+
+    fn on_error(&self, provider: &str, error: &Error) {
+        tracing::warn!(provider, %error, "provider request failed");
+    }
+}
+
+/// Adds default headers to every request
+///
+/// Headers set on the request itself take precedence.
+#[derive(Debug, Clone, Default)]
+pub struct DefaultHeadersLayer {
+    headers: Headers,
+}
+
+impl DefaultHeadersLayer {
+    /// Create a layer adding `headers` to every request
+    pub fn new(headers: impl Into<Headers>) -> Self {
+        Self {
+            headers: headers.into(),
+        }
+    }
+}
+
+#[async_trait]
+impl ProviderLayer for DefaultHeadersLayer {
+    async fn on_request(&self, _provider: &str, request: &mut GenerateRequest) -> Result<()> {
+        let mut headers = self.headers.clone();
+        if let Some(custom) = &request.options.headers {
+            headers.merge_with(custom);
+        }
+        request.options.headers = Some(headers);
+        Ok(())
+    }
+}
+
+/// Tags every request with a unique ID header (`x-request-id` by default)
+///
+/// Requests that already carry the header keep their ID.
+#[derive(Debug, Clone)]
+pub struct RequestIdLayer {
+    header: String,
+}
+
+impl RequestIdLayer {
+    /// Default request ID header
+    pub const DEFAULT_HEADER: &'static str = "x-request-id";
+
+    /// Create a layer setting the `x-request-id` header
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the header name
+    pub fn with_header(mut self, header: impl Into<String>) -> Self {
+        self.header = header.into();
+        self
+    }
+}
+
+impl Default for RequestIdLayer {
+    fn default() -> Self {
+        Self {
+            header: Self::DEFAULT_HEADER.to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl ProviderLayer for RequestIdLayer {
+    async fn on_request(&self, _provider: &str, request: &mut GenerateRequest) -> Result<()> {
+        let headers = request.options.headers.get_or_insert_with(Headers::new);
+        if headers.get(&self.header).is_none() {
+            headers.insert(&self.header, uuid::Uuid::new_v4().to_string());
+        }
+        Ok(())
+    }
+}
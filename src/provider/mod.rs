@@ -1,7 +1,15 @@
-//! Provider trait and dispatcher
+//! Provider trait, dispatcher, middleware layers, fallback chains and pools
 
 mod dispatcher;
+mod fallback;
+mod layer;
+mod layers;
+mod pool;
 mod trait_def;
 
 pub use dispatcher::{ProviderDispatcher, ProviderKind};
+pub use fallback::{CircuitBreaker, FallbackProvider, FallbackRoute, FallbackTarget};
+pub use layer::{LayeredProvider, ProviderLayer};
+pub use layers::{DefaultHeadersLayer, LoggingLayer, RequestIdLayer};
+pub use pool::{PoolStrategy, PooledProvider};
 pub use trait_def::Provider;
@@ -0,0 +1,298 @@
+//! Load balancing across API keys and endpoints
+
+use super::Provider;
+use crate::error::{Error, Result};
+use crate::types::{
+    EmbedRequest, EmbedResponse, GenerateRequest, GenerateResponse, GenerateStream, Headers,
+    ModelInfo,
+};
+use async_trait::async_trait;
+use futures::StreamExt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// How a [`PooledProvider`] picks the member serving a request
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PoolStrategy {
+    /// Rotate through members in order
+    #[default]
+    RoundRobin,
+    /// Pick the member with the fewest requests in flight
+    LeastInFlight,
+    /// Rotate through members in proportion to their weights
+    Weighted,
+}
+
+/// A provider in a pool, usually configured with its own key or endpoint
+struct PoolMember {
+    provider: Arc<dyn Provider>,
+    weight: usize,
+    in_flight: AtomicUsize,
+    ejected_until: Mutex<Option<Instant>>,
+}
+
+impl PoolMember {
+    fn is_available(&self, now: Instant) -> bool {
+        self.ejected_until
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .is_none_or(|until| now >= until)
+    }
+
+    fn eject(&self, duration: Duration) {
+        *self.ejected_until.lock().unwrap_or_else(|e| e.into_inner()) =
+            Some(Instant::now() + duration);
+    }
+}
+
+/// Counts a request in flight on a member until dropped
+struct InFlight(Arc<PoolMember>);
+
+impl InFlight {
+    fn new(member: Arc<PoolMember>) -> Self {
+        member.in_flight.fetch_add(1, Ordering::SeqCst);
+        Self(member)
+    }
+}
+
+impl Drop for InFlight {
+    fn drop(&mut self) {
+        self.0.in_flight.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Provider spreading requests across several providers of the same kind
+///
+/// Each member is typically the same provider configured with a different API
+/// key or endpoint. A member answering with a rate limit (429) or an
+/// authentication error (401) is ejected for a cooldown and the request moves
+/// on to the next member, so callers only see an error once every member has
+/// failed. Rate limit ejections honor the provider's `retry-after` when it is
+/// longer than the cooldown. Streams count as in flight until they are dropped.
+///
+/// # Example
+///
+/// ```rust
+/// use stakai::provider::{PoolStrategy, PooledProvider};
+/// use stakai::providers::openai::{OpenAIConfig, OpenAIProvider};
+/// use stakai::Inference;
+///
+/// let pool = PooledProvider::new(PoolStrategy::LeastInFlight)
+///     .with_member(OpenAIProvider::new(OpenAIConfig::new("sk-first"))?)
+///     .with_member(OpenAIProvider::new(OpenAIConfig::new("sk-second"))?);
+///
+/// let client = Inference::builder().register_provider("openai", pool).build()?;
+/// # Ok::<(), stakai::Error>(())
+/// ```
+pub struct PooledProvider {
+    strategy: PoolStrategy,
+    members: Vec<Arc<PoolMember>>,
+    next: AtomicUsize,
+    rate_limit_cooldown: Duration,
+    auth_cooldown: Duration,
+}
+
+impl PooledProvider {
+    /// Default ejection after a rate limit
+    pub const DEFAULT_RATE_LIMIT_COOLDOWN: Duration = Duration::from_secs(30);
+
+    /// Default ejection after an authentication error
+    pub const DEFAULT_AUTH_COOLDOWN: Duration = Duration::from_secs(300);
+
+    /// Create an empty pool using `strategy`
+    pub fn new(strategy: PoolStrategy) -> Self {
+        Self {
+            strategy,
+            members: Vec::new(),
+            next: AtomicUsize::new(0),
+            rate_limit_cooldown: Self::DEFAULT_RATE_LIMIT_COOLDOWN,
+            auth_cooldown: Self::DEFAULT_AUTH_COOLDOWN,
+        }
+    }
+
+    /// Add a member with weight 1
+    pub fn with_member<P: Provider + 'static>(self, provider: P) -> Self {
+        self.with_weighted_member(provider, 1)
+    }
+
+    /// Add a member with a weight, used by [`PoolStrategy::Weighted`]
+    pub fn with_weighted_member<P: Provider + 'static>(self, provider: P, weight: u32) -> Self {
+        self.with_shared_member(Arc::new(provider), weight)
+    }
+
+    /// Add a shared member with a weight
+    pub fn with_shared_member(mut self, provider: Arc<dyn Provider>, weight: u32) -> Self {
+        self.members.push(Arc::new(PoolMember {
+            provider,
+            weight: weight.max(1) as usize,
+            in_flight: AtomicUsize::new(0),
+            ejected_until: Mutex::new(None),
+        }));
+        self
+    }
+
+    /// Set how long a member is ejected after a rate limit
+    pub fn with_rate_limit_cooldown(mut self, cooldown: Duration) -> Self {
+        self.rate_limit_cooldown = cooldown;
+        self
+    }
+
+    /// Set how long a member is ejected after an authentication error
+    pub fn with_auth_cooldown(mut self, cooldown: Duration) -> Self {
+        self.auth_cooldown = cooldown;
+        self
+    }
+
+    /// Number of members in the pool
+    pub fn len(&self) -> usize {
+        self.members.len()
+    }
+
+    /// Check if the pool has no members
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+
+    /// Available members in the order they should be tried
+    fn candidates(&self) -> Result<Vec<Arc<PoolMember>>> {
+        if self.members.is_empty() {
+            return Err(Error::ConfigError(
+                "provider pool has no members".to_string(),
+            ));
+        }
+
+        let now = Instant::now();
+        let available: Vec<_> = self
+            .members
+            .iter()
+            .filter(|member| member.is_available(now))
+            .cloned()
+            .collect();
+        if available.is_empty() {
+            let ejected: Vec<_> = (0..self.members.len())
+                .map(|i| format!("{}[{}]", self.provider_id(), i))
+                .collect();
+            return Err(Error::CircuitOpen(ejected.join(", ")));
+        }
+
+        let first = match self.strategy {
+            PoolStrategy::RoundRobin => self.next.fetch_add(1, Ordering::Relaxed) % available.len(),
+            PoolStrategy::LeastInFlight => {
+                // Scan from a rotating offset so ties (e.g., an idle pool) spread out
+                let offset = self.next.fetch_add(1, Ordering::Relaxed);
+                (0..available.len())
+                    .map(|i| (i + offset) % available.len())
+                    .min_by_key(|&i| available[i].in_flight.load(Ordering::SeqCst))
+                    .unwrap_or(0)
+            }
+            PoolStrategy::Weighted => {
+                let total: usize = available.iter().map(|member| member.weight).sum();
+                let mut slot = self.next.fetch_add(1, Ordering::Relaxed) % total;
+                available
+                    .iter()
+                    .position(|member| {
+                        let hit = slot < member.weight;
+                        slot = slot.saturating_sub(member.weight);
+                        hit
+                    })
+                    .unwrap_or(0)
+            }
+        };
+
+        let mut ordered = available;
+        ordered.rotate_left(first);
+        Ok(ordered)
+    }
+
+    /// Eject `member` if `error` means its key or endpoint should rest
+    ///
+    /// Goes by the HTTP status as well as the error kind, since a 429 may be
+    /// classified otherwise (e.g. OpenAI's `insufficient_quota`).
+    fn should_eject(&self, member: &PoolMember, error: &Error) -> bool {
+        let cooldown = match (error, error.status()) {
+            (Error::RateLimitExceeded(_), _) | (_, Some(429)) => self
+                .rate_limit_cooldown
+                .max(error.retry_after().unwrap_or_default()),
+            (Error::Authentication(_), _) | (_, Some(401)) => self.auth_cooldown,
+            _ => return false,
+        };
+        member.eject(cooldown);
+        true
+    }
+
+    /// Run `attempt` on each candidate member until one succeeds or fails
+    /// without being ejected
+    async fn run<T, F, Fut>(&self, mut attempt: F) -> Result<T>
+    where
+        F: FnMut(Arc<PoolMember>) -> Fut,
+        Fut: std::future::Future<Output = Result<T>>,
+    {
+        let mut last_error = None;
+        for member in self.candidates()? {
+            match attempt(member.clone()).await {
+                Err(error) if self.should_eject(&member, &error) => last_error = Some(error),
+                result => return result,
+            }
+        }
+        Err(last_error.unwrap_or_else(|| Error::Other("provider pool exhausted".to_string())))
+    }
+}
+
+#[async_trait]
+impl Provider for PooledProvider {
+    fn provider_id(&self) -> &str {
+        self.members
+            .first()
+            .map_or("pool", |member| member.provider.provider_id())
+    }
+
+    fn build_headers(&self, custom_headers: Option<&Headers>) -> Headers {
+        match self.members.first() {
+            Some(member) => member.provider.build_headers(custom_headers),
+            None => custom_headers.cloned().unwrap_or_default(),
+        }
+    }
+
+    async fn generate(&self, request: GenerateRequest) -> Result<GenerateResponse> {
+        self.run(|member| {
+            let request = request.clone();
+            async move {
+                let _in_flight = InFlight::new(member.clone());
+                member.provider.generate(request).await
+            }
+        })
+        .await
+    }
+
+    async fn stream(&self, request: GenerateRequest) -> Result<GenerateStream> {
+        self.run(|member| {
+            let request = request.clone();
+            async move {
+                let in_flight = InFlight::new(member.clone());
+                let stream = member.provider.stream(request).await?;
+                Ok(GenerateStream::new(Box::pin(stream.map(move |event| {
+                    let _ = &in_flight;
+                    event
+                }))))
+            }
+        })
+        .await
+    }
+
+    async fn embed(&self, request: EmbedRequest) -> Result<EmbedResponse> {
+        self.run(|member| {
+            let request = request.clone();
+            async move {
+                let _in_flight = InFlight::new(member.clone());
+                member.provider.embed(request).await
+            }
+        })
+        .await
+    }
+
+    async fn list_models(&self) -> Result<Vec<ModelInfo>> {
+        self.run(|member| async move { member.provider.list_models().await })
+            .await
+    }
+}
@@ -1,7 +1,10 @@
 //! Provider trait definition
 
-use crate::error::Result;
-use crate::types::{GenerateRequest, GenerateResponse, GenerateStream, Headers};
+use crate::error::{Error, Result};
+use crate::types::{
+    EmbedRequest, EmbedResponse, GenerateRequest, GenerateResponse, GenerateStream, Headers,
+    ModelInfo,
+};
 use async_trait::async_trait;
 
 /// Trait for AI provider implementations
@@ -20,8 +23,19 @@ pub trait Provider: Send + Sync {
     /// Generate a streaming response
     async fn stream(&self, request: GenerateRequest) -> Result<GenerateStream>;
 
+    /// Embed texts (optional)
+    ///
+    /// Providers without an embeddings API return an error.
+    async fn embed(&self, request: EmbedRequest) -> Result<EmbedResponse> {
+        let _ = request;
+        Err(Error::provider_error(format!(
+            "{} does not support embeddings",
+            self.provider_id()
+        )))
+    }
+
     /// List available models (optional)
-    async fn list_models(&self) -> Result<Vec<String>> {
+    async fn list_models(&self) -> Result<Vec<ModelInfo>> {
         Ok(vec![])
     }
 }
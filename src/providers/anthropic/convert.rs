@@ -1,59 +1,62 @@
 //! Conversion between unified types and Anthropic types
 
-use super::types::{infer_max_tokens, AnthropicMessage, AnthropicRequest, AnthropicResponse};
+use super::types::{
+    infer_max_tokens, AnthropicMessage, AnthropicModel, AnthropicRequest, AnthropicResponse,
+    AnthropicUsage, ThinkingConfig,
+};
 use crate::error::{Error, Result};
 use crate::types::{
-    ContentPart, FinishReason, GenerateRequest, GenerateResponse, Message, ResponseContent, Role,
-    Usage,
+    merge_json, ContentPart, FinishReason, GenerateRequest, GenerateResponse, Message, ModelInfo,
+    ProviderOptions, ResponseContent, ResponseFormat, ResponseMetadata, Role, Usage,
 };
 use serde_json::{json, Value};
 
 /// Convert unified request to Anthropic request
 pub fn to_anthropic_request(req: &GenerateRequest, stream: bool) -> Result<AnthropicRequest> {
-    // Extract system messages and combine them
-    let system_messages: Vec<String> = req
-        .messages
-        .iter()
-        .filter(|m| m.role == Role::System)
-        .filter_map(|m| m.text())
-        .collect();
-
-    let system = if system_messages.is_empty() {
-        None
-    } else {
-        Some(system_messages.join("\n\n"))
-    };
+    let system = to_anthropic_system(&req.messages)?;
 
     // Convert non-system messages
-    let messages: Vec<AnthropicMessage> = req
-        .messages
-        .iter()
-        .filter(|m| m.role != Role::System)
-        .map(to_anthropic_message)
-        .collect::<Result<Vec<_>>>()?;
+    let messages = to_anthropic_messages(&req.messages)?;
 
     // Determine max_tokens (required by Anthropic!)
-    let max_tokens = req
+    let mut max_tokens = req
         .options
         .max_tokens
         .unwrap_or_else(|| infer_max_tokens(&req.model));
 
+    // The thinking budget counts towards max_tokens and must stay below it
+    let thinking = req
+        .options
+        .reasoning
+        .as_ref()
+        .map(|reasoning| ThinkingConfig {
+            type_: "enabled".to_string(),
+            budget_tokens: reasoning.resolved_budget(),
+        });
+    if let Some(thinking) = &thinking {
+        if max_tokens <= thinking.budget_tokens {
+            max_tokens += thinking.budget_tokens;
+        }
+    }
+
     // Convert tools to Anthropic format
-    let tools = req.options.tools.as_ref().map(|tools| {
+    let mut tools = req.options.tools.as_ref().map(|tools| {
         tools
             .iter()
             .map(|tool| {
-                json!({
+                let mut value = json!({
                     "name": tool.function.name,
                     "description": tool.function.description,
                     "input_schema": tool.function.parameters,
-                })
+                });
+                apply_provider_options(&mut value, &tool.provider_options);
+                value
             })
             .collect::<Vec<_>>()
     });
 
     // Convert tool_choice to Anthropic format
-    let tool_choice = req.options.tool_choice.as_ref().map(|choice| match choice {
+    let mut tool_choice = req.options.tool_choice.as_ref().map(|choice| match choice {
         crate::types::ToolChoice::Auto => json!({"type": "auto"}),
         crate::types::ToolChoice::None => json!({"type": "none"}),
         crate::types::ToolChoice::Required { name } => json!({
@@ -62,6 +65,23 @@ pub fn to_anthropic_request(req: &GenerateRequest, stream: bool) -> Result<Anthr
         }),
     });
 
+    // Anthropic has no JSON mode, so force a tool whose input is the response
+    if let Some((name, schema)) = structured_output_tool(req.options.response_format.as_ref()) {
+        tools.get_or_insert_with(Vec::new).push(json!({
+            "name": name,
+            "description": "Respond with a JSON object matching the input schema",
+            "input_schema": schema,
+        }));
+        tool_choice = Some(json!({"type": "tool", "name": name}));
+    }
+
+    // Extended thinking rejects forced tool use, so let the model choose the tool
+    if thinking.is_some() {
+        if let Some(choice) = tool_choice.as_mut().filter(|c| c["type"] == "tool") {
+            *choice = json!({"type": "auto"});
+        }
+    }
+
     Ok(AnthropicRequest {
         model: req.model.clone(),
         messages,
@@ -69,104 +89,149 @@ pub fn to_anthropic_request(req: &GenerateRequest, stream: bool) -> Result<Anthr
         system,
         temperature: req.options.temperature,
         top_p: req.options.top_p,
+        top_k: req.options.top_k,
         stop_sequences: req.options.stop_sequences.clone(),
         stream: if stream { Some(true) } else { None },
-        thinking: None, // TODO: Add reasoning support via options
+        thinking,
         tools,
         tool_choice,
     })
 }
 
+/// Name and input schema of the tool forced to produce structured output, if any
+pub(super) fn structured_output_tool(format: Option<&ResponseFormat>) -> Option<(&str, Value)> {
+    match format? {
+        ResponseFormat::Text => None,
+        ResponseFormat::JsonObject => Some(("json_response", json!({"type": "object"}))),
+        ResponseFormat::JsonSchema { name, schema, .. } => Some((name, schema.clone())),
+    }
+}
+
+/// Turn the forced structured output tool call back into JSON text
+pub(super) fn into_structured_output(
+    mut response: GenerateResponse,
+    tool_name: &str,
+) -> GenerateResponse {
+    for content in &mut response.content {
+        if let ResponseContent::ToolCall(call) = content {
+            if call.name == tool_name {
+                *content = ResponseContent::Text {
+                    text: call.arguments.to_string(),
+                };
+            }
+        }
+    }
+
+    if response.finish_reason == FinishReason::ToolCalls && response.tool_calls().is_empty() {
+        response.finish_reason = FinishReason::Stop;
+    }
+    response
+}
+
+/// Convert system messages to the Anthropic system prompt
+///
+/// System messages are joined into a single string unless they carry
+/// provider options (e.g., cache breakpoints), which need text blocks.
+fn to_anthropic_system(messages: &[Message]) -> Result<Option<Value>> {
+    let system: Vec<&Message> = messages.iter().filter(|m| m.role == Role::System).collect();
+
+    let has_options = system.iter().any(|m| {
+        !m.provider_options.is_empty() || m.parts().iter().any(|p| !p.provider_options().is_empty())
+    });
+    if !has_options {
+        let texts: Vec<String> = system.iter().filter_map(|m| m.text()).collect();
+        return Ok((!texts.is_empty()).then(|| Value::String(texts.join("\n\n"))));
+    }
+
+    let mut blocks = Vec::new();
+    for msg in system {
+        let mut message_blocks = msg
+            .parts()
+            .iter()
+            .filter(|part| matches!(part, ContentPart::Text { .. }))
+            .map(to_anthropic_block)
+            .collect::<Result<Vec<_>>>()?;
+        if let Some(last) = message_blocks.last_mut() {
+            apply_provider_options(last, &msg.provider_options);
+        }
+        blocks.extend(message_blocks);
+    }
+
+    Ok(Some(Value::Array(blocks)))
+}
+
+/// Merge the Anthropic entry of provider options into a request object
+fn apply_provider_options(target: &mut Value, options: &ProviderOptions) {
+    if let Some(options) = options.get("anthropic") {
+        merge_json(target, options.clone());
+    }
+}
+
+/// Convert unified messages to Anthropic messages
+///
+/// Tool results become `tool_result` blocks in a user turn. Anthropic requires
+/// strict user/assistant alternation, so consecutive tool results (and a user
+/// message directly following them) are merged into a single user turn.
+fn to_anthropic_messages(messages: &[Message]) -> Result<Vec<AnthropicMessage>> {
+    let mut result: Vec<AnthropicMessage> = Vec::new();
+    let mut after_tool = false;
+
+    for msg in messages.iter().filter(|m| m.role != Role::System) {
+        let message = to_anthropic_message(msg)?;
+        let is_tool = msg.role == Role::Tool;
+
+        match result.last_mut() {
+            Some(last) if last.role == message.role && (is_tool || after_tool) => {
+                let mut blocks = into_blocks(std::mem::take(&mut last.content));
+                blocks.extend(into_blocks(message.content));
+                last.content = Value::Array(blocks);
+            }
+            _ => result.push(message),
+        }
+
+        after_tool = is_tool;
+    }
+
+    Ok(result)
+}
+
 /// Convert unified message to Anthropic message
 fn to_anthropic_message(msg: &Message) -> Result<AnthropicMessage> {
     let role = match msg.role {
-        Role::User => "user",
+        Role::User | Role::Tool => "user",
         Role::Assistant => "assistant",
         Role::System => {
             return Err(Error::invalid_response(
                 "System messages should be filtered out",
             ))
         }
-        Role::Tool => {
-            return Err(Error::invalid_response(
-                "Tool messages not yet supported for Anthropic",
-            ))
-        }
     };
 
     // Convert content parts
     let parts = msg.parts();
-    let content = if parts.len() == 1 {
-        // Single content - use simple string format
-        match &parts[0] {
-            ContentPart::Text { text } => Value::String(text.clone()),
-            ContentPart::Image { url, detail: _ } => {
-                // Anthropic uses structured content for images
-                json!([{
-                    "type": "image",
-                    "source": parse_image_source(url)?,
-                }])
-            }
-            ContentPart::ToolCall {
-                id,
-                name,
-                arguments,
-            } => {
-                // Tool call as structured content
-                json!([{
-                    "type": "tool_use",
-                    "id": id,
-                    "name": name,
-                    "input": arguments
-                }])
-            }
-            ContentPart::ToolResult {
-                tool_call_id,
-                content,
-            } => {
-                // Tool result as structured content
-                json!([{
-                    "type": "tool_result",
-                    "tool_use_id": tool_call_id,
-                    "content": content
-                }])
+    let content = match parts.as_slice() {
+        // Single text content - use simple string format
+        [ContentPart::Text {
+            text,
+            provider_options,
+        }] if msg.role != Role::Tool
+            && provider_options.is_empty()
+            && msg.provider_options.is_empty() =>
+        {
+            Value::String(text.clone())
+        }
+        // Anything else - use structured content
+        _ => {
+            let mut blocks = parts
+                .iter()
+                .map(to_anthropic_block)
+                .collect::<Result<Vec<_>>>()?;
+            // Message options (e.g., cache breakpoints) apply to its last block
+            if let Some(last) = blocks.last_mut() {
+                apply_provider_options(last, &msg.provider_options);
             }
+            Value::Array(blocks)
         }
-    } else {
-        // Multiple content parts - use array format
-        let content_parts: Vec<Value> = parts
-            .iter()
-            .map(|part| match part {
-                ContentPart::Text { text } => Ok(json!({
-                    "type": "text",
-                    "text": text
-                })),
-                ContentPart::Image { url, detail: _ } => Ok(json!({
-                    "type": "image",
-                    "source": parse_image_source(url)?
-                })),
-                ContentPart::ToolCall {
-                    id,
-                    name,
-                    arguments,
-                } => Ok(json!({
-                    "type": "tool_use",
-                    "id": id,
-                    "name": name,
-                    "input": arguments
-                })),
-                ContentPart::ToolResult {
-                    tool_call_id,
-                    content,
-                } => Ok(json!({
-                    "type": "tool_result",
-                    "tool_use_id": tool_call_id,
-                    "content": content
-                })),
-            })
-            .collect::<Result<Vec<_>>>()?;
-
-        Value::Array(content_parts)
     };
 
     Ok(AnthropicMessage {
@@ -175,6 +240,84 @@ fn to_anthropic_message(msg: &Message) -> Result<AnthropicMessage> {
     })
 }
 
+/// Convert unified content part to Anthropic content block
+fn to_anthropic_block(part: &ContentPart) -> Result<Value> {
+    let mut block = match part {
+        ContentPart::Text { text, .. } => json!({
+            "type": "text",
+            "text": text
+        }),
+        ContentPart::Image { url, .. } => json!({
+            "type": "image",
+            "source": parse_image_source(url)?
+        }),
+        ContentPart::ToolCall {
+            id,
+            name,
+            arguments,
+            ..
+        } => json!({
+            "type": "tool_use",
+            "id": id,
+            "name": name,
+            "input": arguments
+        }),
+        ContentPart::ToolResult {
+            tool_call_id,
+            content,
+            is_error,
+            ..
+        } => {
+            // Anthropic accepts text (or content blocks) here, so serialize other JSON
+            let content = match content {
+                Value::String(text) => Value::String(text.clone()),
+                other => Value::String(other.to_string()),
+            };
+
+            let mut block = json!({
+                "type": "tool_result",
+                "tool_use_id": tool_call_id,
+                "content": content
+            });
+            if *is_error {
+                block["is_error"] = Value::Bool(true);
+            }
+            block
+        }
+        // Redacted thinking is sent back as the opaque block it came from
+        ContentPart::Reasoning {
+            data: Some(data), ..
+        } => json!({
+            "type": "redacted_thinking",
+            "data": data
+        }),
+        ContentPart::Reasoning {
+            text, signature, ..
+        } => {
+            let mut block = json!({
+                "type": "thinking",
+                "thinking": text
+            });
+            if let Some(signature) = signature {
+                block["signature"] = Value::String(signature.clone());
+            }
+            block
+        }
+    };
+
+    apply_provider_options(&mut block, part.provider_options());
+    Ok(block)
+}
+
+/// Normalize Anthropic message content to an array of content blocks
+fn into_blocks(content: Value) -> Vec<Value> {
+    match content {
+        Value::Array(blocks) => blocks,
+        Value::String(text) => vec![json!({"type": "text", "text": text})],
+        other => vec![other],
+    }
+}
+
 /// Parse image URL to Anthropic image source format
 fn parse_image_source(url: &str) -> Result<Value> {
     if url.starts_with("data:") {
@@ -214,8 +357,15 @@ pub fn from_anthropic_response(resp: AnthropicResponse) -> Result<GenerateRespon
                 .text
                 .as_ref()
                 .map(|t| ResponseContent::Text { text: t.clone() }),
-            "thinking" => c.thinking.as_ref().map(|t| ResponseContent::Text {
-                text: format!("[Thinking: {}]", t),
+            "thinking" => c.thinking.as_ref().map(|t| ResponseContent::Reasoning {
+                text: t.clone(),
+                signature: c.signature.clone(),
+                data: None,
+            }),
+            "redacted_thinking" => c.data.as_ref().map(|data| ResponseContent::Reasoning {
+                text: String::new(),
+                signature: None,
+                data: Some(data.clone()),
             }),
             "tool_use" => {
                 // Anthropic tool call format
@@ -223,6 +373,7 @@ pub fn from_anthropic_response(resp: AnthropicResponse) -> Result<GenerateRespon
                     id: c.id.clone().unwrap_or_default(),
                     name: c.name.clone().unwrap_or_default(),
                     arguments: c.input.clone().unwrap_or(json!({})),
+                    signature: None,
                 }))
             }
             _ => None,
@@ -245,25 +396,52 @@ pub fn from_anthropic_response(resp: AnthropicResponse) -> Result<GenerateRespon
 
     Ok(GenerateResponse {
         content,
-        usage: Usage {
-            prompt_tokens: resp.usage.input_tokens,
-            completion_tokens: resp.usage.output_tokens,
-            total_tokens: resp.usage.input_tokens + resp.usage.output_tokens,
-        },
+        usage: from_anthropic_usage(&resp.usage),
         finish_reason,
-        metadata: Some(json!({
-            "id": resp.id,
-            "model": resp.model,
-        })),
+        citations: Vec::new(),
+        metadata: Some(ResponseMetadata {
+            id: Some(resp.id),
+            model: Some(resp.model),
+            ..Default::default()
+        }),
     })
 }
 
+/// Convert Anthropic usage to unified usage
+///
+/// Anthropic reports cache reads and writes apart from `input_tokens`; they
+/// are counted in the prompt tokens here.
+pub fn from_anthropic_usage(usage: &AnthropicUsage) -> Usage {
+    let prompt_tokens =
+        usage.input_tokens + usage.cache_creation_input_tokens + usage.cache_read_input_tokens;
+
+    Usage {
+        prompt_tokens,
+        completion_tokens: usage.output_tokens,
+        total_tokens: prompt_tokens + usage.output_tokens,
+        cached_tokens: usage.cache_read_input_tokens,
+        cache_creation_tokens: usage.cache_creation_input_tokens,
+        reasoning_tokens: 0,
+    }
+}
+
+/// Convert Anthropic model to unified model info
+pub fn from_anthropic_model(model: AnthropicModel) -> ModelInfo {
+    ModelInfo {
+        display_name: model.display_name,
+        context_window: model.max_input_tokens,
+        max_output_tokens: model.max_tokens,
+        ..ModelInfo::new("anthropic", model.id)
+    }
+}
+
 /// Parse Anthropic stop reason to unified finish reason
-fn parse_stop_reason(reason: &Option<String>) -> Option<FinishReason> {
+pub(super) fn parse_stop_reason(reason: &Option<String>) -> Option<FinishReason> {
     reason.as_ref().and_then(|r| match r.as_str() {
         "end_turn" => Some(FinishReason::Stop),
         "max_tokens" => Some(FinishReason::Length),
         "stop_sequence" => Some(FinishReason::Stop),
+        "tool_use" => Some(FinishReason::ToolCalls),
         _ => None,
     })
 }
@@ -281,6 +459,234 @@ mod tests {
         assert_eq!(infer_max_tokens("claude-3-opus"), 4096);
     }
 
+    #[test]
+    fn test_tool_results_merge_into_user_turn() {
+        let messages = vec![
+            Message::new(Role::User, "What's the weather in Paris and Rome?"),
+            Message::new(
+                Role::Assistant,
+                vec![
+                    ContentPart::tool_call("toolu_1", "get_weather", json!({"city": "Paris"})),
+                    ContentPart::tool_call("toolu_2", "get_weather", json!({"city": "Rome"})),
+                ],
+            ),
+            Message::new(
+                Role::Tool,
+                vec![ContentPart::tool_result("toolu_1", json!({"temp": 18}))],
+            ),
+            Message::new(
+                Role::Tool,
+                vec![ContentPart::tool_error(
+                    "toolu_2",
+                    json!("Service unavailable"),
+                )],
+            ),
+            Message::new(Role::User, "Thanks!"),
+        ];
+
+        let result = to_anthropic_messages(&messages).unwrap();
+        let roles: Vec<&str> = result.iter().map(|m| m.role.as_str()).collect();
+        assert_eq!(roles, ["user", "assistant", "user"]);
+
+        assert_eq!(
+            result[2].content,
+            json!([
+                {"type": "tool_result", "tool_use_id": "toolu_1", "content": "{\"temp\":18}"},
+                {
+                    "type": "tool_result",
+                    "tool_use_id": "toolu_2",
+                    "content": "Service unavailable",
+                    "is_error": true
+                },
+                {"type": "text", "text": "Thanks!"}
+            ])
+        );
+    }
+
+    #[test]
+    fn test_reasoning_maps_to_thinking() {
+        let mut req = GenerateRequest::new(
+            "claude-sonnet-4",
+            vec![
+                Message::new(Role::User, "Solve it"),
+                Message::new(
+                    Role::Assistant,
+                    vec![
+                        ContentPart::reasoning("Let me think.", Some("sig_1".to_string())),
+                        ContentPart::text("42"),
+                    ],
+                ),
+                Message::new(Role::User, "Why?"),
+            ],
+        );
+        req.options = crate::types::GenerateOptions::new()
+            .max_tokens(1024)
+            .reasoning_budget(2048);
+
+        let result = to_anthropic_request(&req, false).unwrap();
+        let thinking = result.thinking.unwrap();
+        assert_eq!(thinking.type_, "enabled");
+        assert_eq!(thinking.budget_tokens, 2048);
+        assert!(result.max_tokens > thinking.budget_tokens);
+
+        assert_eq!(
+            result.messages[1].content,
+            json!([
+                {"type": "thinking", "thinking": "Let me think.", "signature": "sig_1"},
+                {"type": "text", "text": "42"}
+            ])
+        );
+    }
+
+    #[test]
+    fn test_thinking_relaxes_forced_tool_choice() {
+        let tool = crate::types::Tool::function("get_weather", "Get the weather")
+            .parameters(json!({"type": "object"}));
+        let mut req = GenerateRequest::new("claude-sonnet-4", vec![Message::new(Role::User, "Hi")]);
+        req.options = crate::types::GenerateOptions::new()
+            .add_tool(tool)
+            .tool_choice(crate::types::ToolChoice::Required {
+                name: "get_weather".to_string(),
+            });
+
+        let result = to_anthropic_request(&req, false).unwrap();
+        assert_eq!(
+            result.tool_choice,
+            Some(json!({"type": "tool", "name": "get_weather"}))
+        );
+
+        req.options = req.options.reasoning_budget(2048);
+        let result = to_anthropic_request(&req, false).unwrap();
+        assert_eq!(result.tool_choice, Some(json!({"type": "auto"})));
+
+        // The structured output tool is offered rather than forced too
+        req.options = crate::types::GenerateOptions::new()
+            .reasoning_budget(2048)
+            .response_format(crate::types::ResponseFormat::json_schema(
+                "answer",
+                json!({"type": "object"}),
+            ));
+        let result = to_anthropic_request(&req, false).unwrap();
+        assert_eq!(result.tool_choice, Some(json!({"type": "auto"})));
+        assert_eq!(result.tools.unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_redacted_thinking_round_trip() {
+        let resp: AnthropicResponse = serde_json::from_value(json!({
+            "id": "msg_1",
+            "type": "message",
+            "role": "assistant",
+            "model": "claude-sonnet-4",
+            "content": [
+                {"type": "redacted_thinking", "data": "EmwKAhgBEgy3va3pzix"},
+                {"type": "text", "text": "42"}
+            ],
+            "stop_reason": "end_turn",
+            "usage": {"input_tokens": 10, "output_tokens": 5}
+        }))
+        .unwrap();
+
+        let response = from_anthropic_response(resp).unwrap();
+        assert!(matches!(
+            &response.content[0],
+            ResponseContent::Reasoning { text, data: Some(data), .. }
+                if text.is_empty() && data == "EmwKAhgBEgy3va3pzix"
+        ));
+
+        let req = GenerateRequest::new(
+            "claude-sonnet-4",
+            vec![
+                Message::new(Role::User, "Solve it"),
+                response.to_message(),
+                Message::new(Role::User, "Why?"),
+            ],
+        );
+        let result = to_anthropic_request(&req, false).unwrap();
+        assert_eq!(
+            result.messages[1].content,
+            json!([
+                {"type": "redacted_thinking", "data": "EmwKAhgBEgy3va3pzix"},
+                {"type": "text", "text": "42"}
+            ])
+        );
+    }
+
+    #[test]
+    fn test_cache_control_breakpoints() {
+        use crate::types::{CacheControl, Tool};
+
+        let mut req = GenerateRequest::new(
+            "claude-sonnet-4",
+            vec![
+                Message::new(Role::System, "You are a code reviewer."),
+                Message::new(Role::System, "<50k tokens of repository context>")
+                    .with_cache_control(CacheControl::ephemeral().ttl("1h")),
+                Message::new(
+                    Role::User,
+                    vec![
+                        ContentPart::text("Earlier diff")
+                            .with_cache_control(CacheControl::ephemeral()),
+                        ContentPart::text("Review this"),
+                    ],
+                ),
+                Message::new(Role::Assistant, "Looks good")
+                    .with_cache_control(CacheControl::ephemeral()),
+            ],
+        );
+        req.options = crate::types::GenerateOptions::new().add_tool(
+            Tool::function("read_file", "Read a file")
+                .with_cache_control(CacheControl::ephemeral()),
+        );
+
+        let result = to_anthropic_request(&req, false).unwrap();
+
+        assert_eq!(
+            result.system,
+            Some(json!([
+                {"type": "text", "text": "You are a code reviewer."},
+                {
+                    "type": "text",
+                    "text": "<50k tokens of repository context>",
+                    "cache_control": {"type": "ephemeral", "ttl": "1h"}
+                }
+            ]))
+        );
+        assert_eq!(
+            result.messages[0].content,
+            json!([
+                {"type": "text", "text": "Earlier diff", "cache_control": {"type": "ephemeral"}},
+                {"type": "text", "text": "Review this"}
+            ])
+        );
+        assert_eq!(
+            result.messages[1].content,
+            json!([
+                {"type": "text", "text": "Looks good", "cache_control": {"type": "ephemeral"}}
+            ])
+        );
+        assert_eq!(
+            result.tools.unwrap()[0]["cache_control"],
+            json!({"type": "ephemeral"})
+        );
+    }
+
+    #[test]
+    fn test_system_without_options_stays_a_string() {
+        let req = GenerateRequest::new(
+            "claude-sonnet-4",
+            vec![
+                Message::new(Role::System, "First"),
+                Message::new(Role::System, "Second"),
+                Message::new(Role::User, "Hi"),
+            ],
+        );
+
+        let result = to_anthropic_request(&req, false).unwrap();
+        assert_eq!(result.system, Some(json!("First\n\nSecond")));
+        assert_eq!(result.messages[0].content, json!("Hi"));
+    }
+
     #[test]
     fn test_parse_image_source() {
         let data_url = "data:image/png;base64,iVBORw0KGgoAAAANS";
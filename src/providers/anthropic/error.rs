@@ -0,0 +1,111 @@
+//! Anthropic error response parsing
+
+use super::types::AnthropicErrorDetail;
+use crate::error::{ApiError, Error};
+use reqwest::Response;
+use serde::Deserialize;
+
+/// Anthropic error response envelope
+#[derive(Debug, Deserialize)]
+pub struct AnthropicErrorResponse {
+    pub error: AnthropicErrorDetail,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+}
+
+/// Convert a non-success Anthropic response into a classified error
+pub(crate) async fn from_response(response: Response) -> Error {
+    let mut error = ApiError::from_response("anthropic", response).await;
+
+    if let Ok(envelope) = serde_json::from_str::<AnthropicErrorResponse>(&error.message) {
+        error.error_type = Some(envelope.error.type_);
+        error.message = envelope.error.message;
+        error.request_id = error.request_id.or(envelope.request_id);
+    }
+
+    classify(error)
+}
+
+/// Convert an `error` event received mid-stream into a classified error
+pub(crate) fn from_stream_error(detail: AnthropicErrorDetail) -> Error {
+    classify(
+        ApiError::new(
+            "anthropic",
+            error_type_status(&detail.type_),
+            detail.message,
+        )
+        .with_error_type(detail.type_),
+    )
+}
+
+/// Classify an Anthropic API error by its error type, falling back to HTTP status
+fn classify(error: ApiError) -> Error {
+    match error.error_type.as_deref() {
+        Some("overloaded_error") => Error::Overloaded(Box::new(error)),
+        Some("rate_limit_error") => Error::RateLimitExceeded(Box::new(error)),
+        Some("authentication_error") => Error::Authentication(Box::new(error)),
+        Some("permission_error") => Error::PermissionDenied(Box::new(error)),
+        Some("not_found_error") => Error::NotFound(Box::new(error)),
+        Some("invalid_request_error") if is_context_length_message(&error.message) => {
+            Error::ContextLengthExceeded(Box::new(error))
+        }
+        Some("invalid_request_error") | Some("request_too_large") => {
+            Error::InvalidRequest(Box::new(error))
+        }
+        _ => error.into(),
+    }
+}
+
+/// Anthropic reports context overflows as invalid requests with these messages
+fn is_context_length_message(message: &str) -> bool {
+    message.contains("prompt is too long") || message.contains("exceed context limit")
+}
+
+/// HTTP status Anthropic documents for an error type
+pub(crate) fn error_type_status(error_type: &str) -> u16 {
+    match error_type {
+        "invalid_request_error" => 400,
+        "authentication_error" => 401,
+        "permission_error" => 403,
+        "not_found_error" => 404,
+        "request_too_large" => 413,
+        "rate_limit_error" => 429,
+        "overloaded_error" => 529,
+        _ => 500,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_context_length() {
+        let error = classify(
+            ApiError::new(
+                "anthropic",
+                400,
+                "prompt is too long: 210000 tokens > 200000 maximum",
+            )
+            .with_error_type("invalid_request_error"),
+        );
+        assert!(matches!(error, Error::ContextLengthExceeded(_)));
+
+        let error = classify(
+            ApiError::new("anthropic", 400, "messages: roles must alternate")
+                .with_error_type("invalid_request_error"),
+        );
+        assert!(matches!(error, Error::InvalidRequest(_)));
+    }
+
+    #[test]
+    fn test_stream_error_status() {
+        let error = from_stream_error(AnthropicErrorDetail {
+            type_: "rate_limit_error".to_string(),
+            message: "Too many requests".to_string(),
+        });
+
+        assert!(matches!(error, Error::RateLimitExceeded(_)));
+        assert_eq!(error.status(), Some(429));
+    }
+}
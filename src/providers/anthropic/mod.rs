@@ -1,6 +1,7 @@
 //! Anthropic provider module
 
 mod convert;
+mod error;
 mod provider;
 mod stream;
 mod types;
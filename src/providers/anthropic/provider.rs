@@ -1,19 +1,33 @@
 //! Anthropic provider implementation
 
-use super::convert::{from_anthropic_response, to_anthropic_request};
+use super::convert::{
+    from_anthropic_model, from_anthropic_response, into_structured_output, structured_output_tool,
+    to_anthropic_request,
+};
+use super::error::from_response;
 use super::stream::create_stream;
-use super::types::{AnthropicConfig, AnthropicResponse};
+use super::types::{AnthropicConfig, AnthropicModelList, AnthropicResponse};
 use crate::error::{Error, Result};
+use crate::http::read_json;
 use crate::provider::Provider;
-use crate::types::{GenerateRequest, GenerateResponse, GenerateStream, Headers};
+use crate::providers::vertex::TokenCache;
+use crate::types::{
+    GenerateRequest, GenerateResponse, GenerateStream, Headers, ModelInfo, ResponseMetadata,
+};
 use async_trait::async_trait;
-use reqwest::Client;
-use reqwest_eventsource::EventSource;
+use reqwest::{Client, Response};
+use serde_json::json;
+use std::time::Instant;
 
 /// Anthropic provider
+///
+/// Uses the Anthropic API with an API key, or Claude on Vertex AI with service
+/// account tokens when the config has a [`VertexConfig`](crate::providers::vertex::VertexConfig).
 pub struct AnthropicProvider {
     config: AnthropicConfig,
     client: Client,
+    /// Access tokens for Vertex AI
+    vertex_auth: Option<TokenCache>,
 }
 
 impl AnthropicProvider {
@@ -23,14 +37,25 @@ impl AnthropicProvider {
     /// Default API version
     pub const DEFAULT_VERSION: &'static str = "2023-06-01";
 
+    /// API version sent in the request body on Vertex AI
+    pub const VERTEX_VERSION: &'static str = "vertex-2023-10-16";
+
     /// Create a new Anthropic provider
     pub fn new(config: AnthropicConfig) -> Result<Self> {
-        if config.api_key.is_empty() {
-            return Err(Error::MissingApiKey("anthropic".to_string()));
-        }
-
-        let client = Client::new();
-        Ok(Self { config, client })
+        let vertex_auth = match &config.vertex {
+            Some(vertex) => Some(TokenCache::new("anthropic", vertex)?),
+            None if config.api_key.is_empty() => {
+                return Err(Error::MissingApiKey("anthropic".to_string()))
+            }
+            None => None,
+        };
+
+        let client = config.http.build_client()?;
+        Ok(Self {
+            config,
+            client,
+            vertex_auth,
+        })
     }
 
     /// Create provider from environment
@@ -40,6 +65,49 @@ impl AnthropicProvider {
 
         Self::new(AnthropicConfig::new(api_key))
     }
+
+    /// Send a messages request, to the public API or a Vertex AI model
+    async fn send(&self, request: &GenerateRequest, stream: bool) -> Result<Response> {
+        let mut anthropic_req = request
+            .options
+            .provider_options
+            .apply(self.provider_id(), &to_anthropic_request(request, stream)?)?;
+
+        let url = match &self.config.vertex {
+            Some(vertex) => {
+                // Vertex takes the model from the URL and the version from the body
+                if let Some(body) = anthropic_req.as_object_mut() {
+                    body.remove("model");
+                    body.insert("anthropic_version".to_string(), json!(Self::VERTEX_VERSION));
+                }
+                let method = if stream {
+                    "streamRawPredict"
+                } else {
+                    "rawPredict"
+                };
+                vertex.model_url("anthropic", &request.model, method)
+            }
+            None => format!("{}/messages", self.config.base_url.trim_end_matches('/')),
+        };
+
+        let headers = self.build_headers(request.options.headers.as_ref());
+
+        let mut builder = self
+            .client
+            .post(&url)
+            .headers(headers.to_reqwest_headers())
+            .json(&anthropic_req);
+        if let Some(auth) = &self.vertex_auth {
+            builder = builder.bearer_auth(auth.token(&self.client).await?);
+        }
+
+        let response = builder.send().await?;
+        if !response.status().is_success() {
+            return Err(from_response(response).await);
+        }
+
+        Ok(response)
+    }
 }
 
 #[async_trait]
@@ -51,9 +119,12 @@ impl Provider for AnthropicProvider {
     fn build_headers(&self, custom_headers: Option<&Headers>) -> Headers {
         let mut headers = Headers::new();
 
-        // Anthropic uses x-api-key header
-        headers.insert("x-api-key", &self.config.api_key);
-        headers.insert("anthropic-version", &self.config.anthropic_version);
+        // Anthropic uses x-api-key header; Vertex AI uses a bearer token and
+        // takes the version in the body
+        if self.config.vertex.is_none() {
+            headers.insert("x-api-key", &self.config.api_key);
+            headers.insert("anthropic-version", &self.config.anthropic_version);
+        }
         headers.insert("Content-Type", "application/json");
 
         // Add beta features if any
@@ -70,58 +141,77 @@ impl Provider for AnthropicProvider {
     }
 
     async fn generate(&self, request: GenerateRequest) -> Result<GenerateResponse> {
-        let url = format!("{}messages", self.config.base_url);
-        let anthropic_req = to_anthropic_request(&request, false)?;
-
-        let headers = self.build_headers(request.options.headers.as_ref());
-
-        let response = self
-            .client
-            .post(&url)
-            .headers(headers.to_reqwest_headers())
-            .json(&anthropic_req)
-            .send()
-            .await?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let error_text = response.text().await.unwrap_or_default();
-            return Err(Error::provider_error(format!(
-                "Anthropic API error {}: {}",
-                status, error_text
-            )));
-        }
-
-        let anthropic_resp: AnthropicResponse = response.json().await?;
-        from_anthropic_response(anthropic_resp)
+        let started = Instant::now();
+        let response = self.send(&request, false).await?;
+
+        let (anthropic_resp, metadata): (AnthropicResponse, _) = read_json(
+            self.provider_id(),
+            response,
+            started,
+            request.options.include_raw_response,
+        )
+        .await?;
+        let response = from_anthropic_response(anthropic_resp)?.with_http_metadata(metadata);
+
+        Ok(
+            match structured_output_tool(request.options.response_format.as_ref()) {
+                Some((tool_name, _)) => into_structured_output(response, tool_name),
+                None => response,
+            },
+        )
     }
 
     async fn stream(&self, request: GenerateRequest) -> Result<GenerateStream> {
-        let url = format!("{}messages", self.config.base_url);
-        let anthropic_req = to_anthropic_request(&request, true)?;
-
-        let headers = self.build_headers(request.options.headers.as_ref());
-
-        let req_builder = self
-            .client
-            .post(&url)
-            .headers(headers.to_reqwest_headers())
-            .json(&anthropic_req);
+        let started = Instant::now();
+        let response = self.send(&request, true).await?;
+
+        let structured_output = structured_output_tool(request.options.response_format.as_ref())
+            .map(|(tool_name, _)| tool_name.to_string());
+        let metadata = ResponseMetadata::from_http(self.provider_id(), &response, started);
+        Ok(create_stream(response, structured_output)
+            .await?
+            .with_metadata(metadata, started))
+    }
 
-        let event_source = EventSource::new(req_builder)
-            .map_err(|e| Error::stream_error(format!("Failed to create event source: {}", e)))?;
+    async fn list_models(&self) -> Result<Vec<ModelInfo>> {
+        // Vertex AI publisher models aren't listed per project
+        if self.config.vertex.is_some() {
+            return Ok(Vec::new());
+        }
 
-        create_stream(event_source).await
-    }
+        let url = format!("{}/models", self.config.base_url.trim_end_matches('/'));
+        let headers = self.build_headers(None);
+
+        // The models endpoint is paginated by model ID
+        let mut models = Vec::new();
+        let mut after_id: Option<String> = None;
+        loop {
+            let mut query = vec![("limit", "1000".to_string())];
+            if let Some(after_id) = after_id.take() {
+                query.push(("after_id", after_id));
+            }
+
+            let response = self
+                .client
+                .get(&url)
+                .headers(headers.to_reqwest_headers())
+                .query(&query)
+                .send()
+                .await?;
+
+            if !response.status().is_success() {
+                return Err(from_response(response).await);
+            }
+
+            let page: AnthropicModelList = response.json().await?;
+            models.extend(page.data.into_iter().map(from_anthropic_model));
+
+            match page.last_id {
+                Some(last_id) if page.has_more => after_id = Some(last_id),
+                _ => break,
+            }
+        }
 
-    async fn list_models(&self) -> Result<Vec<String>> {
-        // Anthropic doesn't have a models endpoint, return known models
-        Ok(vec![
-            "claude-3-5-sonnet-20241022".to_string(),
-            "claude-3-5-haiku-20241022".to_string(),
-            "claude-3-opus-20240229".to_string(),
-            "claude-3-sonnet-20240229".to_string(),
-            "claude-3-haiku-20240307".to_string(),
-        ])
+        Ok(models)
     }
 }
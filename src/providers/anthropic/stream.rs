@@ -1,136 +1,205 @@
 //! Anthropic streaming support
 
-use super::types::AnthropicStreamEvent;
+use super::convert::{from_anthropic_usage, parse_stop_reason};
+use super::error::from_stream_error;
+use super::types::{AnthropicErrorDetail, AnthropicStreamEvent};
 use crate::error::{Error, Result};
-use crate::types::{GenerateStream, StreamEvent, Usage};
+use crate::types::{
+    FinishReason, GenerateStream, ResponseMetadata, StreamEvent, ToolCallAccumulator, Usage,
+};
+use eventsource_stream::{EventStreamError, Eventsource};
 use futures::stream::StreamExt;
-use reqwest_eventsource::{Event, EventSource};
+use reqwest::Response;
 
-/// Create a stream from Anthropic EventSource
-pub async fn create_stream(mut event_source: EventSource) -> Result<GenerateStream> {
+/// Create a stream from an Anthropic SSE response
+///
+/// `structured_output` names the tool forced to produce structured output;
+/// its input is streamed as text deltas instead of a tool call.
+pub async fn create_stream(
+    response: Response,
+    structured_output: Option<String>,
+) -> Result<GenerateStream> {
     let stream = async_stream::stream! {
-        let mut accumulated_usage = Usage::default();
+        let mut event_source = response.bytes_stream().eventsource();
+        let mut state = StreamState {
+            structured_output,
+            ..Default::default()
+        };
 
         while let Some(event) = event_source.next().await {
             match event {
-                Ok(Event::Open) => {
-                    // Connection opened
-                    continue;
-                }
-                Ok(Event::Message(message)) => {
+                Ok(message) => {
                     // Parse the event
                     if message.data == "[DONE]" {
                         break;
                     }
 
                     match serde_json::from_str::<AnthropicStreamEvent>(&message.data) {
-                        Ok(event) => {
-                            if let Some(stream_event) = process_anthropic_event(event, &mut accumulated_usage) {
-                                yield Ok(stream_event);
+                        Ok(event) => match process_anthropic_event(event, &mut state) {
+                            Ok(events) => {
+                                for event in events {
+                                    yield Ok(event);
+                                }
                             }
-                        }
+                            Err(e) => {
+                                yield Err(e);
+                                break;
+                            }
+                        },
                         Err(e) => {
                             yield Err(Error::stream_error(format!("Failed to parse event: {}", e)));
                             break;
                         }
                     }
                 }
+                Err(EventStreamError::Transport(e)) => {
+                    yield Err(Error::HttpError(e));
+                    break;
+                }
                 Err(e) => {
                     yield Err(Error::stream_error(format!("Stream error: {}", e)));
                     break;
                 }
             }
         }
-
-        event_source.close();
     };
 
     Ok(GenerateStream::new(Box::pin(stream)))
 }
 
-/// Process Anthropic stream event and convert to unified StreamEvent
+/// State carried across Anthropic stream events
+struct StreamState {
+    usage: Usage,
+    tool_calls: ToolCallAccumulator,
+    finish_reason: FinishReason,
+    /// Name of the structured output tool
+    structured_output: Option<String>,
+    /// Content block index of the structured output tool call
+    structured_output_index: Option<u32>,
+}
+
+impl Default for StreamState {
+    fn default() -> Self {
+        Self {
+            usage: Usage::default(),
+            tool_calls: ToolCallAccumulator::new(),
+            finish_reason: FinishReason::Stop,
+            structured_output: None,
+            structured_output_index: None,
+        }
+    }
+}
+
+/// Process Anthropic stream event and convert to unified StreamEvents
 fn process_anthropic_event(
     event: AnthropicStreamEvent,
-    accumulated_usage: &mut Usage,
-) -> Option<StreamEvent> {
-    match event.type_.as_str() {
+    state: &mut StreamState,
+) -> Result<Vec<StreamEvent>> {
+    let index = event.index.unwrap_or(0);
+
+    let stream_event = match event.type_.as_str() {
         "message_start" => {
-            // Message started - could extract usage info
-            if let Some(message) = event.message {
-                accumulated_usage.prompt_tokens = message.usage.input_tokens;
-            }
-            None // Don't emit event for message start
+            // Message started - carries the message ID, model, and input and cache usage
+            event.message.map(|message| {
+                state.usage = from_anthropic_usage(&message.usage);
+                StreamEvent::start_with_metadata(
+                    &message.id,
+                    ResponseMetadata {
+                        id: Some(message.id.clone()),
+                        model: Some(message.model),
+                        ..Default::default()
+                    },
+                )
+            })
         }
         "content_block_start" => {
             // Content block started - check if it's a tool use
-            if let Some(block) = event.content_block {
-                if block.type_ == "tool_use" {
-                    return Some(StreamEvent::tool_call_start(
-                        block.id.unwrap_or_default(),
-                        block.name.unwrap_or_default(),
-                    ));
+            match event.content_block {
+                Some(block)
+                    if block.type_ == "tool_use"
+                        && block.name.is_some()
+                        && block.name == state.structured_output =>
+                {
+                    state.structured_output_index = Some(index);
+                    None
                 }
+                Some(block) if block.type_ == "tool_use" => Some(state.tool_calls.start(
+                    index,
+                    block.id.unwrap_or_default(),
+                    block.name.unwrap_or_default(),
+                )),
+                // Redacted thinking arrives whole, with no deltas
+                Some(block) if block.type_ == "redacted_thinking" => block
+                    .data
+                    .map(|data| StreamEvent::redacted_reasoning("", data)),
+                _ => None,
             }
-            None
         }
         "content_block_delta" => {
             // Content delta - this is where we get text chunks or tool input
-            if let Some(delta) = event.delta {
-                match delta.type_.as_str() {
-                    "text_delta" => {
-                        if let Some(text) = delta.text {
-                            return Some(StreamEvent::text_delta("", text));
-                        }
-                    }
-                    "thinking_delta" => {
-                        if let Some(thinking) = delta.thinking {
-                            return Some(StreamEvent::text_delta(
-                                "",
-                                format!("[Thinking: {}]", thinking),
-                            ));
-                        }
-                    }
-                    "input_json_delta" => {
-                        // Tool call arguments delta
-                        if let Some(partial_json) = delta.text {
-                            let index = event.index.unwrap_or(0);
-                            return Some(StreamEvent::tool_call_delta(
-                                index.to_string(),
-                                partial_json,
-                            ));
-                        }
-                    }
-                    _ => {}
-                }
+            let delta = event.delta.unwrap_or_default();
+            match delta.type_.as_str() {
+                "text_delta" => delta.text.map(|text| StreamEvent::text_delta("", text)),
+                "thinking_delta" => delta
+                    .thinking
+                    .map(|thinking| StreamEvent::reasoning_delta("", thinking)),
+                "signature_delta" => delta
+                    .signature
+                    .map(|signature| StreamEvent::reasoning_signature("", signature)),
+                // Structured output is streamed as text
+                "input_json_delta" if state.structured_output_index == Some(index) => delta
+                    .partial_json
+                    .filter(|partial_json| !partial_json.is_empty())
+                    .map(|partial_json| StreamEvent::text_delta("", partial_json)),
+                // Tool call arguments delta
+                "input_json_delta" => delta
+                    .partial_json
+                    .and_then(|partial_json| state.tool_calls.delta(index, &partial_json)),
+                _ => None,
             }
-            None
         }
         "content_block_stop" => {
-            // Content block finished
-            None
+            // Content block finished - completes the tool call, if it was one
+            state.tool_calls.end(index)
         }
         "message_delta" => {
-            // Message delta - could have usage updates
+            // Message delta - carries the stop reason and usage updates
+            if let Some(reason) = event.delta.and_then(|d| parse_stop_reason(&d.stop_reason)) {
+                state.finish_reason = match reason {
+                    // The forced structured output tool call is the answer
+                    FinishReason::ToolCalls if state.structured_output_index.is_some() => {
+                        FinishReason::Stop
+                    }
+                    reason => reason,
+                };
+            }
             if let Some(usage) = event.usage {
-                accumulated_usage.completion_tokens = usage.output_tokens;
-                accumulated_usage.total_tokens =
-                    accumulated_usage.prompt_tokens + usage.output_tokens;
+                state.usage.completion_tokens = usage.output_tokens;
+                state.usage.total_tokens = state.usage.prompt_tokens + usage.output_tokens;
             }
             None
         }
         "message_stop" => {
-            // Message finished - emit final usage
-            Some(StreamEvent::finish(
-                accumulated_usage.clone(),
-                crate::types::FinishReason::Stop,
-            ))
+            // Message finished - complete any open tool calls and emit final usage
+            let mut events = state.tool_calls.end_all();
+            events.push(StreamEvent::finish(
+                state.usage.clone(),
+                state.finish_reason,
+            ));
+            return Ok(events);
         }
         "error" => {
-            // Error event
-            Some(StreamEvent::error("Anthropic API error"))
+            // Error event (e.g. overloaded_error sent after the stream opened)
+            let detail = event.error.unwrap_or_else(|| AnthropicErrorDetail {
+                type_: "api_error".to_string(),
+                message: "Anthropic API error".to_string(),
+            });
+            return Err(from_stream_error(detail));
         }
         _ => None,
-    }
+    };
+
+    Ok(stream_event.into_iter().collect())
 }
 
 #[cfg(test)]
@@ -141,7 +210,7 @@ mod tests {
 
     #[test]
     fn test_process_text_delta() {
-        let mut usage = Usage::default();
+        let mut state = StreamState::default();
         let event = AnthropicStreamEvent {
             type_: "content_block_delta".to_string(),
             message: None,
@@ -151,16 +220,83 @@ mod tests {
                 type_: "text_delta".to_string(),
                 text: Some("Hello".to_string()),
                 thinking: None,
+                signature: None,
                 partial_json: None,
+                stop_reason: None,
             }),
             usage: None,
+            error: None,
         };
 
-        let result = process_anthropic_event(event, &mut usage);
-        assert!(result.is_some());
+        let result = process_anthropic_event(event, &mut state).unwrap();
+        assert_eq!(result.len(), 1);
 
-        if let Some(StreamEvent::TextDelta { delta, .. }) = result {
+        if let StreamEvent::TextDelta { delta, .. } = &result[0] {
             assert_eq!(delta, "Hello");
         }
     }
+
+    #[test]
+    fn test_process_thinking_deltas() {
+        let mut state = StreamState::default();
+        let events = [
+            r#"{"type":"content_block_start","index":0,"content_block":{"type":"thinking","thinking":""}}"#,
+            r#"{"type":"content_block_delta","index":0,"delta":{"type":"thinking_delta","thinking":"Let me think."}}"#,
+            r#"{"type":"content_block_delta","index":0,"delta":{"type":"signature_delta","signature":"sig_1"}}"#,
+            r#"{"type":"content_block_stop","index":0}"#,
+        ];
+
+        let result: Vec<StreamEvent> = events
+            .iter()
+            .flat_map(|data| {
+                let event: AnthropicStreamEvent = serde_json::from_str(data).unwrap();
+                process_anthropic_event(event, &mut state).unwrap()
+            })
+            .collect();
+
+        assert_eq!(result.len(), 2);
+        assert!(matches!(
+            &result[0],
+            StreamEvent::ReasoningDelta { delta, signature: None, .. } if delta == "Let me think."
+        ));
+        assert!(matches!(
+            &result[1],
+            StreamEvent::ReasoningDelta { signature: Some(sig), .. } if sig == "sig_1"
+        ));
+    }
+
+    #[test]
+    fn test_process_redacted_thinking() {
+        let mut state = StreamState::default();
+        let event: AnthropicStreamEvent = serde_json::from_str(
+            r#"{"type":"content_block_start","index":0,"content_block":{"type":"redacted_thinking","data":"EmwKAhgBEgy3va3pzix"}}"#,
+        )
+        .unwrap();
+
+        let result = process_anthropic_event(event, &mut state).unwrap();
+        assert_eq!(result.len(), 1);
+        assert!(matches!(
+            &result[0],
+            StreamEvent::ReasoningDelta { delta, data: Some(data), .. }
+                if delta.is_empty() && data == "EmwKAhgBEgy3va3pzix"
+        ));
+    }
+
+    #[test]
+    fn test_process_overloaded_error() {
+        let mut state = StreamState::default();
+        let event: AnthropicStreamEvent = serde_json::from_str(
+            r#"{"type":"error","error":{"type":"overloaded_error","message":"Overloaded"}}"#,
+        )
+        .unwrap();
+
+        match process_anthropic_event(event, &mut state) {
+            Err(Error::Overloaded(e)) => {
+                assert_eq!(e.status, 529);
+                assert_eq!(e.error_type.as_deref(), Some("overloaded_error"));
+                assert_eq!(e.message, "Overloaded");
+            }
+            other => panic!("Expected Overloaded, got {:?}", other),
+        }
+    }
 }
@@ -1,5 +1,7 @@
 //! Anthropic-specific types
 
+use crate::http::HttpConfig;
+use crate::providers::vertex::VertexConfig;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
@@ -14,6 +16,10 @@ pub struct AnthropicConfig {
     pub anthropic_version: String,
     /// Beta features to enable (e.g., ["prompt-caching-2024-07-31"])
     pub beta_features: Vec<String>,
+    /// Vertex AI project and service account, used instead of the API key
+    pub vertex: Option<VertexConfig>,
+    /// HTTP client settings (timeouts)
+    pub http: HttpConfig,
 }
 
 impl AnthropicConfig {
@@ -24,6 +30,16 @@ impl AnthropicConfig {
             base_url: "https://api.anthropic.com/v1".to_string(),
             anthropic_version: "2023-06-01".to_string(),
             beta_features: vec![],
+            vertex: None,
+            http: HttpConfig::default(),
+        }
+    }
+
+    /// Create new config for Claude on Vertex AI
+    pub fn vertex(vertex: VertexConfig) -> Self {
+        Self {
+            vertex: Some(vertex),
+            ..Self::new("")
         }
     }
 
@@ -33,6 +49,12 @@ impl AnthropicConfig {
         self
     }
 
+    /// Set HTTP client settings
+    pub fn with_http_config(mut self, http: HttpConfig) -> Self {
+        self.http = http;
+        self
+    }
+
     /// Set API version
     pub fn with_version(mut self, version: impl Into<String>) -> Self {
         self.anthropic_version = version.into();
@@ -58,13 +80,16 @@ pub struct AnthropicRequest {
     pub model: String,
     pub messages: Vec<AnthropicMessage>,
     pub max_tokens: u32, // Required by Anthropic
+    /// Plain string, or text blocks when they carry `cache_control`
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub system: Option<String>,
+    pub system: Option<Value>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub temperature: Option<f32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub top_p: Option<f32>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_k: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub stop_sequences: Option<Vec<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub stream: Option<bool>,
@@ -108,11 +133,17 @@ pub struct AnthropicResponse {
 #[derive(Debug, Deserialize)]
 pub struct AnthropicContent {
     #[serde(rename = "type")]
-    pub type_: String, // "text" | "thinking" | "tool_use"
+    pub type_: String, // "text" | "thinking" | "redacted_thinking" | "tool_use"
     #[serde(skip_serializing_if = "Option::is_none")]
     pub text: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub thinking: Option<String>,
+    /// Signature of a `thinking` block
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+    /// Encrypted content of a `redacted_thinking` block
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub id: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -124,8 +155,16 @@ pub struct AnthropicContent {
 /// Anthropic usage statistics
 #[derive(Debug, Deserialize)]
 pub struct AnthropicUsage {
+    /// Absent from `message_delta` events, which only report output tokens
+    #[serde(default)]
     pub input_tokens: u32,
     pub output_tokens: u32,
+    /// Input tokens written to the prompt cache (not included in `input_tokens`)
+    #[serde(default)]
+    pub cache_creation_input_tokens: u32,
+    /// Input tokens read from the prompt cache (not included in `input_tokens`)
+    #[serde(default)]
+    pub cache_read_input_tokens: u32,
 }
 
 /// Anthropic streaming event
@@ -143,31 +182,67 @@ pub struct AnthropicStreamEvent {
     pub delta: Option<AnthropicDelta>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub usage: Option<AnthropicUsage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<AnthropicErrorDetail>,
 }
 
-/// Anthropic delta content
+/// Anthropic error details (from `error` stream events and error responses)
 #[derive(Debug, Deserialize)]
+pub struct AnthropicErrorDetail {
+    #[serde(rename = "type")]
+    pub type_: String,
+    pub message: String,
+}
+
+/// Anthropic delta content
+#[derive(Debug, Default, Deserialize)]
 #[allow(dead_code)]
 pub struct AnthropicDelta {
-    #[serde(rename = "type")]
+    /// Content block delta type; absent from `message_delta` events
+    #[serde(rename = "type", default)]
     pub type_: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub text: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub thinking: Option<String>,
+    /// Signature of the current thinking block (on `signature_delta`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub partial_json: Option<String>,
+    /// Stop reason (on `message_delta` events)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stop_reason: Option<String>,
 }
 
-/// Infer max_tokens based on model name
+/// Anthropic model list response (one page)
+#[derive(Debug, Deserialize)]
+pub struct AnthropicModelList {
+    pub data: Vec<AnthropicModel>,
+    #[serde(default)]
+    pub has_more: bool,
+    #[serde(default)]
+    pub last_id: Option<String>,
+}
+
+/// Anthropic model
+#[derive(Debug, Deserialize)]
+pub struct AnthropicModel {
+    pub id: String,
+    #[serde(default)]
+    pub display_name: Option<String>,
+    #[serde(default)]
+    pub max_input_tokens: Option<u32>,
+    #[serde(default)]
+    pub max_tokens: Option<u32>,
+}
+
+/// Fallback max_tokens for models missing from the catalog
+const DEFAULT_MAX_TOKENS: u32 = 4096;
+
+/// Infer max_tokens from the model catalog
 pub fn infer_max_tokens(model: &str) -> u32 {
-    if model.contains("opus-4-5") || model.contains("sonnet-4") || model.contains("haiku-4") {
-        64000
-    } else if model.contains("opus-4") {
-        32000
-    } else if model.contains("3-5") {
-        8192
-    } else {
-        4096
-    }
+    crate::catalog::lookup(model)
+        .and_then(|entry| entry.max_output_tokens)
+        .unwrap_or(DEFAULT_MAX_TOKENS)
 }
@@ -0,0 +1,376 @@
+//! Conversion between unified types and Bedrock Converse types
+
+use super::types::{
+    BedrockInferenceConfig, BedrockMessage, BedrockUsage, ConverseRequest, ConverseResponse,
+};
+use crate::error::{Error, Result};
+use crate::types::{
+    ContentPart, FinishReason, GenerateRequest, GenerateResponse, Message, ResponseContent, Role,
+    ToolCall, ToolChoice, Usage,
+};
+use serde_json::{json, Value};
+
+/// Convert unified request to Bedrock Converse request
+pub fn to_converse_request(req: &GenerateRequest) -> Result<ConverseRequest> {
+    let system: Vec<Value> = req
+        .messages
+        .iter()
+        .filter(|msg| msg.role == Role::System)
+        .filter_map(|msg| msg.text())
+        .map(|text| json!({ "text": text }))
+        .collect();
+
+    // Model-specific fields, understood by Anthropic models
+    let mut additional_fields = serde_json::Map::new();
+    if let Some(top_k) = req.options.top_k {
+        additional_fields.insert("top_k".to_string(), json!(top_k));
+    }
+
+    // The thinking budget counts towards max_tokens and must stay below it
+    let mut max_tokens = req.options.max_tokens;
+    if let Some(reasoning) = &req.options.reasoning {
+        let budget = reasoning.resolved_budget();
+        additional_fields.insert(
+            "thinking".to_string(),
+            json!({ "type": "enabled", "budget_tokens": budget }),
+        );
+        max_tokens = max_tokens.map(|max| if max <= budget { max + budget } else { max });
+    }
+
+    let tool_config = req.options.tools.as_ref().map(|tools| {
+        let tools: Vec<Value> = tools
+            .iter()
+            .map(|tool| {
+                json!({
+                    "toolSpec": {
+                        "name": tool.function.name,
+                        "description": tool.function.description,
+                        "inputSchema": { "json": tool.function.parameters },
+                    }
+                })
+            })
+            .collect();
+
+        // Converse can't disable tools, so `None` falls back to the default
+        let tool_choice = req
+            .options
+            .tool_choice
+            .as_ref()
+            .and_then(|choice| match choice {
+                ToolChoice::Auto => Some(json!({ "auto": {} })),
+                ToolChoice::None => None,
+                ToolChoice::Required { name } => Some(json!({ "tool": { "name": name } })),
+            });
+
+        let mut config = json!({ "tools": tools });
+        if let Some(tool_choice) = tool_choice {
+            config["toolChoice"] = tool_choice;
+        }
+        config
+    });
+
+    Ok(ConverseRequest {
+        messages: to_bedrock_messages(&req.messages)?,
+        system: (!system.is_empty()).then_some(system),
+        inference_config: Some(BedrockInferenceConfig {
+            max_tokens,
+            temperature: req.options.temperature,
+            top_p: req.options.top_p,
+            stop_sequences: req.options.stop_sequences.clone(),
+        }),
+        tool_config,
+        additional_model_request_fields: (!additional_fields.is_empty())
+            .then_some(Value::Object(additional_fields)),
+    })
+}
+
+/// Convert unified messages to Bedrock messages
+///
+/// Converse requires roles to alternate, so consecutive messages of the same
+/// role (e.g., a tool result followed by a user message) are merged.
+fn to_bedrock_messages(messages: &[Message]) -> Result<Vec<BedrockMessage>> {
+    let mut result: Vec<BedrockMessage> = Vec::new();
+
+    for msg in messages.iter().filter(|m| m.role != Role::System) {
+        let role = match msg.role {
+            Role::Assistant => "assistant",
+            _ => "user",
+        };
+        let content = msg
+            .parts()
+            .iter()
+            .filter_map(|part| to_bedrock_block(part).transpose())
+            .collect::<Result<Vec<_>>>()?;
+
+        match result.last_mut() {
+            Some(last) if last.role == role => last.content.extend(content),
+            _ => result.push(BedrockMessage {
+                role: role.to_string(),
+                content,
+            }),
+        }
+    }
+
+    Ok(result)
+}
+
+/// Convert a unified content part to a Bedrock content block
+fn to_bedrock_block(part: &ContentPart) -> Result<Option<Value>> {
+    Ok(Some(match part {
+        ContentPart::Text { text, .. } => json!({ "text": text }),
+        ContentPart::Image { url, .. } => json!({ "image": parse_image(url)? }),
+        ContentPart::ToolCall {
+            id,
+            name,
+            arguments,
+            ..
+        } => json!({
+            "toolUse": {
+                "toolUseId": id,
+                "name": name,
+                "input": arguments,
+            }
+        }),
+        ContentPart::ToolResult {
+            tool_call_id,
+            content,
+            is_error,
+            ..
+        } => {
+            // JSON results must be objects; anything else is sent as text
+            let content = match content {
+                Value::String(text) => json!({ "text": text }),
+                Value::Object(_) => json!({ "json": content }),
+                other => json!({ "text": other.to_string() }),
+            };
+            json!({
+                "toolResult": {
+                    "toolUseId": tool_call_id,
+                    "content": [content],
+                    "status": if *is_error { "error" } else { "success" },
+                }
+            })
+        }
+        // Reasoning can only be sent back with its signature
+        ContentPart::Reasoning {
+            text,
+            signature: Some(signature),
+            ..
+        } => json!({
+            "reasoningContent": {
+                "reasoningText": { "text": text, "signature": signature }
+            }
+        }),
+        ContentPart::Reasoning { .. } => return Ok(None),
+    }))
+}
+
+/// Parse a data URL into a Bedrock image
+fn parse_image(url: &str) -> Result<Value> {
+    // Data URL format: data:image/png;base64,iVBORw0KG...
+    let (media_type, data) = url
+        .strip_prefix("data:")
+        .and_then(|rest| rest.split_once(','))
+        .and_then(|(media_type, data)| Some((media_type.strip_suffix(";base64")?, data)))
+        .ok_or_else(|| {
+            Error::invalid_response("Bedrock requires base64-encoded images, not URLs")
+        })?;
+
+    let format = media_type.strip_prefix("image/").unwrap_or(media_type);
+    Ok(json!({
+        "format": format,
+        "source": { "bytes": data }
+    }))
+}
+
+/// Convert Bedrock Converse response to unified response
+pub fn from_converse_response(resp: ConverseResponse) -> GenerateResponse {
+    let mut content = Vec::new();
+
+    for block in resp.output.message.content {
+        if let Some(text) = block.text {
+            content.push(ResponseContent::Text { text });
+        } else if let Some(tool_use) = block.tool_use {
+            content.push(ResponseContent::ToolCall(ToolCall {
+                id: tool_use.tool_use_id,
+                name: tool_use.name,
+                arguments: tool_use.input,
+                signature: None,
+            }));
+        } else if let Some(reasoning) = block.reasoning_content.and_then(|r| r.reasoning_text) {
+            content.push(ResponseContent::Reasoning {
+                text: reasoning.text,
+                signature: reasoning.signature,
+                data: None,
+            });
+        }
+    }
+
+    GenerateResponse {
+        content,
+        usage: resp
+            .usage
+            .as_ref()
+            .map(from_bedrock_usage)
+            .unwrap_or_default(),
+        finish_reason: parse_stop_reason(&resp.stop_reason),
+        citations: Vec::new(),
+        metadata: None,
+    }
+}
+
+/// Parse Bedrock stop reason
+pub fn parse_stop_reason(reason: &str) -> FinishReason {
+    match reason {
+        "end_turn" | "stop_sequence" => FinishReason::Stop,
+        "max_tokens" => FinishReason::Length,
+        "tool_use" => FinishReason::ToolCalls,
+        "guardrail_intervened" | "content_filtered" => FinishReason::ContentFilter,
+        _ => FinishReason::Other,
+    }
+}
+
+/// Convert Bedrock usage to unified usage
+///
+/// Input tokens exclude cache reads and writes, which are added back.
+pub fn from_bedrock_usage(usage: &BedrockUsage) -> Usage {
+    let prompt_tokens =
+        usage.input_tokens + usage.cache_read_input_tokens + usage.cache_write_input_tokens;
+
+    Usage {
+        prompt_tokens,
+        completion_tokens: usage.output_tokens,
+        total_tokens: prompt_tokens + usage.output_tokens,
+        cached_tokens: usage.cache_read_input_tokens,
+        cache_creation_tokens: usage.cache_write_input_tokens,
+        reasoning_tokens: 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{GenerateOptions, ReasoningConfig, Tool};
+
+    #[test]
+    fn test_messages_alternate_roles() {
+        let messages = [
+            Message::new(Role::System, "Be brief"),
+            Message::new(Role::User, "Weather in Paris?"),
+            Message::new(
+                Role::Assistant,
+                vec![
+                    ContentPart::reasoning("Unsigned", None),
+                    ContentPart::tool_call("call_1", "get_weather", json!({"city": "Paris"})),
+                ],
+            ),
+            Message::new(
+                Role::Tool,
+                vec![ContentPart::tool_result("call_1", json!({"temp": 20}))],
+            ),
+            Message::new(Role::User, "And tomorrow?"),
+        ];
+        let request = GenerateRequest::new("anthropic.claude-3-haiku", messages.to_vec());
+
+        let value = serde_json::to_value(to_converse_request(&request).unwrap()).unwrap();
+
+        assert_eq!(value["system"], json!([{"text": "Be brief"}]));
+        assert_eq!(value["messages"].as_array().unwrap().len(), 3);
+        assert_eq!(
+            value["messages"][1],
+            json!({
+                "role": "assistant",
+                "content": [{"toolUse": {
+                    "toolUseId": "call_1",
+                    "name": "get_weather",
+                    "input": {"city": "Paris"}
+                }}]
+            })
+        );
+        assert_eq!(
+            value["messages"][2]["content"],
+            json!([
+                {"toolResult": {
+                    "toolUseId": "call_1",
+                    "content": [{"json": {"temp": 20}}],
+                    "status": "success"
+                }},
+                {"text": "And tomorrow?"}
+            ])
+        );
+    }
+
+    #[test]
+    fn test_request_options() {
+        let options = GenerateOptions::new()
+            .max_tokens(1000)
+            .add_tool(Tool::function("lookup", "Look up a word").parameters(json!({})))
+            .tool_choice(ToolChoice::Required {
+                name: "lookup".to_string(),
+            })
+            .reasoning(ReasoningConfig::new().budget_tokens(2048));
+        let request = GenerateRequest {
+            options,
+            ..GenerateRequest::new(
+                "anthropic.claude-3-7-sonnet",
+                vec![Message::new(
+                    Role::User,
+                    vec![ContentPart::image("data:image/png;base64,iVBORw0KG")],
+                )],
+            )
+        };
+
+        let value = serde_json::to_value(to_converse_request(&request).unwrap()).unwrap();
+
+        assert_eq!(
+            value["messages"][0]["content"][0],
+            json!({"image": {"format": "png", "source": {"bytes": "iVBORw0KG"}}})
+        );
+        assert_eq!(value["inferenceConfig"]["maxTokens"], 3048);
+        assert_eq!(
+            value["additionalModelRequestFields"]["thinking"],
+            json!({"type": "enabled", "budget_tokens": 2048})
+        );
+        assert_eq!(
+            value["toolConfig"]["tools"][0]["toolSpec"]["inputSchema"],
+            json!({"json": {}})
+        );
+        assert_eq!(
+            value["toolConfig"]["toolChoice"],
+            json!({"tool": {"name": "lookup"}})
+        );
+    }
+
+    #[test]
+    fn test_response_content_and_usage() {
+        let resp: ConverseResponse = serde_json::from_value(json!({
+            "output": {"message": {"role": "assistant", "content": [
+                {"reasoningContent": {"reasoningText": {"text": "Hmm", "signature": "sig"}}},
+                {"text": "Let me check."},
+                {"toolUse": {"toolUseId": "tooluse_1", "name": "lookup", "input": {"q": "x"}}}
+            ]}},
+            "stopReason": "tool_use",
+            "usage": {
+                "inputTokens": 10,
+                "outputTokens": 5,
+                "totalTokens": 45,
+                "cacheReadInputTokens": 30
+            },
+            "metrics": {"latencyMs": 100}
+        }))
+        .unwrap();
+
+        let response = from_converse_response(resp);
+
+        assert_eq!(response.finish_reason, FinishReason::ToolCalls);
+        assert!(matches!(
+            &response.content[0],
+            ResponseContent::Reasoning { text, signature: Some(sig), data: None } if text == "Hmm" && sig == "sig"
+        ));
+        assert_eq!(response.text(), "Let me check.");
+        assert_eq!(response.tool_calls()[0].id, "tooluse_1");
+        assert_eq!(response.usage.prompt_tokens, 40);
+        assert_eq!(response.usage.cached_tokens, 30);
+        assert_eq!(response.usage.total_tokens, 45);
+    }
+}
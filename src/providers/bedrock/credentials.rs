@@ -0,0 +1,153 @@
+//! AWS credentials from the environment or a shared credentials file
+
+use crate::error::{Error, Result};
+use std::path::{Path, PathBuf};
+
+/// AWS credentials used to sign Bedrock requests
+#[derive(Debug, Clone)]
+pub struct AwsCredentials {
+    /// Access key ID
+    pub access_key_id: String,
+    /// Secret access key
+    pub secret_access_key: String,
+    /// Session token for temporary credentials
+    pub session_token: Option<String>,
+}
+
+impl AwsCredentials {
+    /// Create credentials from an access key pair
+    pub fn new(access_key_id: impl Into<String>, secret_access_key: impl Into<String>) -> Self {
+        Self {
+            access_key_id: access_key_id.into(),
+            secret_access_key: secret_access_key.into(),
+            session_token: None,
+        }
+    }
+
+    /// Set session token
+    pub fn with_session_token(mut self, session_token: impl Into<String>) -> Self {
+        self.session_token = Some(session_token.into());
+        self
+    }
+
+    /// Read `AWS_ACCESS_KEY_ID`, `AWS_SECRET_ACCESS_KEY` and `AWS_SESSION_TOKEN`
+    pub fn from_env() -> Option<Self> {
+        let access_key_id = std::env::var("AWS_ACCESS_KEY_ID").ok()?;
+        let secret_access_key = std::env::var("AWS_SECRET_ACCESS_KEY").ok()?;
+        if access_key_id.is_empty() || secret_access_key.is_empty() {
+            return None;
+        }
+
+        let credentials = Self::new(access_key_id, secret_access_key);
+        Some(match std::env::var("AWS_SESSION_TOKEN") {
+            Ok(token) if !token.is_empty() => credentials.with_session_token(token),
+            _ => credentials,
+        })
+    }
+
+    /// Read a profile from the shared credentials file
+    ///
+    /// The file is `AWS_SHARED_CREDENTIALS_FILE`, or `~/.aws/credentials`.
+    pub fn from_profile(profile: &str) -> Result<Self> {
+        let path = match std::env::var("AWS_SHARED_CREDENTIALS_FILE") {
+            Ok(path) => PathBuf::from(path),
+            Err(_) => std::env::var("HOME")
+                .map(|home| Path::new(&home).join(".aws").join("credentials"))
+                .map_err(|_| Error::ConfigError("cannot locate ~/.aws/credentials".to_string()))?,
+        };
+        Self::from_profile_file(path, profile)
+    }
+
+    /// Read a profile from a credentials file in the AWS INI format
+    pub fn from_profile_file(path: impl AsRef<Path>, profile: &str) -> Result<Self> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| Error::ConfigError(format!("cannot read {}: {}", path.display(), e)))?;
+        Self::parse_profile(&contents, profile).ok_or_else(|| {
+            Error::ConfigError(format!(
+                "profile {:?} has no credentials in {}",
+                profile,
+                path.display()
+            ))
+        })
+    }
+
+    /// Load credentials from the environment, then from the profile named by
+    /// `AWS_PROFILE` (or `default`)
+    pub fn load() -> Result<Self> {
+        if let Some(credentials) = Self::from_env() {
+            return Ok(credentials);
+        }
+
+        let profile = std::env::var("AWS_PROFILE").unwrap_or_else(|_| "default".to_string());
+        Self::from_profile(&profile)
+    }
+
+    /// Find the credentials of `profile` in INI `contents`
+    fn parse_profile(contents: &str, profile: &str) -> Option<Self> {
+        let mut in_profile = false;
+        let mut access_key_id = None;
+        let mut secret_access_key = None;
+        let mut session_token = None;
+
+        for line in contents.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+                continue;
+            }
+            if let Some(section) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                in_profile = section.trim() == profile;
+                continue;
+            }
+            if !in_profile {
+                continue;
+            }
+
+            if let Some((key, value)) = line.split_once('=') {
+                let value = value.trim().to_string();
+                match key.trim() {
+                    "aws_access_key_id" => access_key_id = Some(value),
+                    "aws_secret_access_key" => secret_access_key = Some(value),
+                    "aws_session_token" => session_token = Some(value),
+                    _ => {}
+                }
+            }
+        }
+
+        Some(Self {
+            access_key_id: access_key_id?,
+            secret_access_key: secret_access_key?,
+            session_token,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_profile() {
+        let contents = "\
+[default]
+aws_access_key_id = AKIDDEFAULT
+aws_secret_access_key = default-secret
+
+# Temporary credentials
+[work]
+aws_access_key_id=AKIDWORK
+aws_secret_access_key=work-secret
+aws_session_token=work-token
+";
+
+        let default = AwsCredentials::parse_profile(contents, "default").unwrap();
+        assert_eq!(default.access_key_id, "AKIDDEFAULT");
+        assert_eq!(default.secret_access_key, "default-secret");
+        assert_eq!(default.session_token, None);
+
+        let work = AwsCredentials::parse_profile(contents, "work").unwrap();
+        assert_eq!(work.access_key_id, "AKIDWORK");
+        assert_eq!(work.session_token.as_deref(), Some("work-token"));
+
+        assert!(AwsCredentials::parse_profile(contents, "missing").is_none());
+    }
+}
@@ -0,0 +1,121 @@
+//! Bedrock error response parsing
+
+use crate::error::{ApiError, Error};
+use reqwest::Response;
+use serde::Deserialize;
+
+/// Bedrock error response body
+#[derive(Debug, Deserialize)]
+pub struct BedrockErrorResponse {
+    #[serde(alias = "Message")]
+    pub message: String,
+}
+
+/// Convert a non-success Bedrock response into a classified error
+pub(crate) async fn from_response(response: Response) -> Error {
+    // e.g. "ValidationException:http://internal.amazon.com/coral/com.amazon.bedrock/"
+    let error_type = response
+        .headers()
+        .get("x-amzn-errortype")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split(':').next())
+        .map(str::to_string);
+
+    let mut error = ApiError::from_response("bedrock", response).await;
+    if let Ok(body) = serde_json::from_str::<BedrockErrorResponse>(&error.message) {
+        error.message = body.message;
+    }
+    error.error_type = error_type;
+
+    classify(error)
+}
+
+/// Convert an exception received mid-stream into a classified error
+pub(crate) fn from_stream_exception(exception_type: &str, message: String) -> Error {
+    // Stream exceptions are named like `throttlingException`
+    let status = match exception_type.to_ascii_lowercase().as_str() {
+        "throttlingexception" => 429,
+        "validationexception" => 400,
+        "modeltimeoutexception" => 408,
+        "modelstreamerrorexception" => 424,
+        "serviceunavailableexception" => 503,
+        _ => 500,
+    };
+
+    classify(ApiError::new("bedrock", status, message).with_error_type(exception_type))
+}
+
+/// Classify a Bedrock API error by its exception type, falling back to HTTP status
+fn classify(error: ApiError) -> Error {
+    let error_type = error
+        .error_type
+        .as_deref()
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+
+    match error_type.as_str() {
+        "throttlingexception" | "servicequotaexceededexception" => {
+            Error::RateLimitExceeded(Box::new(error))
+        }
+        "unrecognizedclientexception"
+        | "expiredtokenexception"
+        | "invalidsignatureexception"
+        | "incompletesignatureexception" => Error::Authentication(Box::new(error)),
+        "accessdeniedexception" => Error::PermissionDenied(Box::new(error)),
+        "resourcenotfoundexception" => Error::NotFound(Box::new(error)),
+        "validationexception" if is_context_length_message(&error.message) => {
+            Error::ContextLengthExceeded(Box::new(error))
+        }
+        _ => error.into(),
+    }
+}
+
+/// Bedrock reports context overflows as validation errors with these messages
+fn is_context_length_message(message: &str) -> bool {
+    let message = message.to_lowercase();
+    message.contains("too long") || message.contains("context length")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_by_exception_type() {
+        let error = classify(
+            ApiError::new(
+                "bedrock",
+                403,
+                "The security token included in the request is invalid.",
+            )
+            .with_error_type("UnrecognizedClientException"),
+        );
+        assert!(matches!(error, Error::Authentication(_)));
+
+        let error = classify(
+            ApiError::new("bedrock", 400, "Input is too long for requested model.")
+                .with_error_type("ValidationException"),
+        );
+        assert!(matches!(error, Error::ContextLengthExceeded(_)));
+
+        let error = classify(
+            ApiError::new("bedrock", 400, "Malformed input request")
+                .with_error_type("ValidationException"),
+        );
+        assert!(matches!(error, Error::InvalidRequest(_)));
+    }
+
+    #[test]
+    fn test_stream_exception() {
+        match from_stream_exception("throttlingException", "Too many requests".to_string()) {
+            Error::RateLimitExceeded(e) => {
+                assert_eq!(e.status, 429);
+                assert_eq!(e.error_type.as_deref(), Some("throttlingException"));
+            }
+            other => panic!("Expected RateLimitExceeded, got {:?}", other),
+        }
+
+        let error = from_stream_exception("modelStreamErrorException", "boom".to_string());
+        assert!(matches!(error, Error::Api(e) if e.status == 424));
+    }
+}
@@ -0,0 +1,238 @@
+//! Decoder for the AWS event stream binary framing
+//!
+//! Each message is `total length (u32) | headers length (u32) | prelude CRC32 |
+//! headers | payload | message CRC32`, all big-endian.
+
+use crate::error::{Error, Result};
+use bytes::{Buf, Bytes, BytesMut};
+
+/// Length of the prelude (two lengths and their CRC)
+const PRELUDE_LEN: usize = 12;
+
+/// Length of the prelude plus the trailing message CRC
+const OVERHEAD_LEN: usize = PRELUDE_LEN + 4;
+
+/// A decoded event stream message
+#[derive(Debug)]
+pub(crate) struct EventMessage {
+    /// String-valued headers (`:message-type`, `:event-type`, ...)
+    headers: Vec<(String, String)>,
+    pub payload: Bytes,
+}
+
+impl EventMessage {
+    /// Value of the string header `name`
+    pub(crate) fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header, _)| header == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Buffers response bytes and splits them into messages
+#[derive(Debug, Default)]
+pub(crate) struct EventStreamDecoder {
+    buffer: BytesMut,
+}
+
+impl EventStreamDecoder {
+    /// Append bytes received from the response
+    pub(crate) fn push(&mut self, chunk: &[u8]) {
+        self.buffer.extend_from_slice(chunk);
+    }
+
+    /// Decode the next complete message, if one is buffered
+    pub(crate) fn next_message(&mut self) -> Result<Option<EventMessage>> {
+        if self.buffer.len() < PRELUDE_LEN {
+            return Ok(None);
+        }
+
+        let total_len = read_u32(&self.buffer[0..4]) as usize;
+        let headers_len = read_u32(&self.buffer[4..8]) as usize;
+        if crc32fast::hash(&self.buffer[0..8]) != read_u32(&self.buffer[8..12]) {
+            return Err(Error::stream_error(
+                "Event stream prelude checksum mismatch",
+            ));
+        }
+        if total_len < OVERHEAD_LEN + headers_len {
+            return Err(Error::stream_error(format!(
+                "Invalid event stream message length {}",
+                total_len
+            )));
+        }
+        if self.buffer.len() < total_len {
+            return Ok(None);
+        }
+
+        let mut message = self.buffer.split_to(total_len).freeze();
+        let expected_crc = read_u32(&message[total_len - 4..]);
+        if crc32fast::hash(&message[..total_len - 4]) != expected_crc {
+            return Err(Error::stream_error(
+                "Event stream message checksum mismatch",
+            ));
+        }
+
+        message.advance(PRELUDE_LEN);
+        let mut headers = message.split_to(headers_len);
+        let payload = message.split_to(total_len - OVERHEAD_LEN - headers_len);
+
+        Ok(Some(EventMessage {
+            headers: parse_headers(&mut headers)?,
+            payload,
+        }))
+    }
+}
+
+/// Parse the header block, keeping string values only
+fn parse_headers(block: &mut Bytes) -> Result<Vec<(String, String)>> {
+    let truncated = || Error::stream_error("Truncated event stream header");
+
+    let mut headers = Vec::new();
+    while block.has_remaining() {
+        let name_len = block.get_u8() as usize;
+        if block.remaining() < name_len + 1 {
+            return Err(truncated());
+        }
+        let name = String::from_utf8_lossy(&block.split_to(name_len)).into_owned();
+
+        // Value sizes by type: booleans carry no value, byte arrays and
+        // strings are prefixed with a u16 length
+        let value_len = match block.get_u8() {
+            0 | 1 => 0,
+            2 => 1,
+            3 => 2,
+            4 => 4,
+            5 | 8 => 8,
+            9 => 16,
+            6 | 7 => {
+                if block.remaining() < 2 {
+                    return Err(truncated());
+                }
+                let len = block.get_u16() as usize;
+                if block.remaining() < len {
+                    return Err(truncated());
+                }
+                let value = block.split_to(len);
+                headers.push((name, String::from_utf8_lossy(&value).into_owned()));
+                continue;
+            }
+            other => {
+                return Err(Error::stream_error(format!(
+                    "Unknown event stream header type {}",
+                    other
+                )))
+            }
+        };
+        if block.remaining() < value_len {
+            return Err(truncated());
+        }
+        block.advance(value_len);
+    }
+
+    Ok(headers)
+}
+
+fn read_u32(bytes: &[u8]) -> u32 {
+    u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// Encode a message with string headers
+    pub(crate) fn encode(headers: &[(&str, &str)], payload: &[u8]) -> Vec<u8> {
+        let mut header_block = Vec::new();
+        for (name, value) in headers {
+            header_block.push(name.len() as u8);
+            header_block.extend_from_slice(name.as_bytes());
+            header_block.push(7);
+            header_block.extend_from_slice(&(value.len() as u16).to_be_bytes());
+            header_block.extend_from_slice(value.as_bytes());
+        }
+
+        let total_len = (OVERHEAD_LEN + header_block.len() + payload.len()) as u32;
+        let mut message = Vec::new();
+        message.extend_from_slice(&total_len.to_be_bytes());
+        message.extend_from_slice(&(header_block.len() as u32).to_be_bytes());
+        message.extend_from_slice(&crc32fast::hash(&message).to_be_bytes());
+        message.extend_from_slice(&header_block);
+        message.extend_from_slice(payload);
+        message.extend_from_slice(&crc32fast::hash(&message).to_be_bytes());
+        message
+    }
+
+    #[test]
+    fn test_decode_split_messages() {
+        let mut bytes = encode(
+            &[(":message-type", "event"), (":event-type", "messageStart")],
+            br#"{"role":"assistant"}"#,
+        );
+        bytes.extend(encode(&[(":event-type", "messageStop")], b"{}"));
+
+        // Feed one byte at a time to cover messages split across chunks
+        let mut decoder = EventStreamDecoder::default();
+        let mut messages = Vec::new();
+        for byte in bytes {
+            decoder.push(&[byte]);
+            while let Some(message) = decoder.next_message().unwrap() {
+                messages.push(message);
+            }
+        }
+
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].header(":message-type"), Some("event"));
+        assert_eq!(messages[0].header(":event-type"), Some("messageStart"));
+        assert_eq!(&messages[0].payload[..], br#"{"role":"assistant"}"#);
+        assert_eq!(messages[1].header(":event-type"), Some("messageStop"));
+    }
+
+    #[test]
+    fn test_skips_non_string_headers() {
+        // A boolean and a timestamp header ahead of the event type
+        let header_block = [
+            &[4u8][..],
+            b"flag",
+            &[0],
+            &[2],
+            b"ts",
+            &[8],
+            &[0, 0, 0, 0, 0, 0, 0, 1],
+            &[11],
+            b":event-type",
+            &[7, 0, 8],
+            b"metadata",
+        ]
+        .concat();
+        let total_len = (OVERHEAD_LEN + header_block.len() + 2) as u32;
+        let mut message = Vec::new();
+        message.extend_from_slice(&total_len.to_be_bytes());
+        message.extend_from_slice(&(header_block.len() as u32).to_be_bytes());
+        message.extend_from_slice(&crc32fast::hash(&message).to_be_bytes());
+        message.extend_from_slice(&header_block);
+        message.extend_from_slice(b"{}");
+        message.extend_from_slice(&crc32fast::hash(&message).to_be_bytes());
+
+        let mut decoder = EventStreamDecoder::default();
+        decoder.push(&message);
+        let decoded = decoder.next_message().unwrap().unwrap();
+
+        assert_eq!(decoded.header(":event-type"), Some("metadata"));
+        assert_eq!(decoded.header("flag"), None);
+    }
+
+    #[test]
+    fn test_checksum_mismatch() {
+        let mut bytes = encode(&[(":event-type", "messageStop")], b"{}");
+        let last = bytes.len() - 5;
+        bytes[last] ^= 0xff;
+
+        let mut decoder = EventStreamDecoder::default();
+        decoder.push(&bytes);
+        assert!(matches!(
+            decoder.next_message(),
+            Err(Error::StreamError(message)) if message.contains("checksum")
+        ));
+    }
+}
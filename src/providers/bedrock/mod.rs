@@ -0,0 +1,14 @@
+//! AWS Bedrock provider module
+
+mod convert;
+mod credentials;
+mod error;
+mod event_stream;
+mod provider;
+mod sigv4;
+mod stream;
+mod types;
+
+pub use credentials::AwsCredentials;
+pub use provider::BedrockProvider;
+pub use types::BedrockConfig;
@@ -0,0 +1,148 @@
+//! Bedrock provider implementation
+
+use super::convert::{from_converse_response, to_converse_request};
+use super::error::from_response;
+use super::sigv4::{uri_encode, SigV4};
+use super::stream::create_stream;
+use super::types::{BedrockConfig, ConverseResponse};
+use crate::error::{Error, Result};
+use crate::http::read_json;
+use crate::provider::Provider;
+use crate::types::{GenerateRequest, GenerateResponse, GenerateStream, Headers, ResponseMetadata};
+use async_trait::async_trait;
+use reqwest::{Client, Response, Url};
+use std::time::{Instant, SystemTime};
+
+/// AWS Bedrock provider (Converse and ConverseStream APIs)
+///
+/// Requests are signed with SigV4 using the configured AWS credentials.
+/// Model IDs are Bedrock model or inference profile IDs, e.g.
+/// `bedrock:anthropic.claude-3-5-haiku-20241022-v1:0`.
+pub struct BedrockProvider {
+    config: BedrockConfig,
+    client: Client,
+}
+
+impl BedrockProvider {
+    /// Service name used when signing requests
+    pub const SIGNING_SERVICE: &'static str = "bedrock";
+
+    /// Create a new Bedrock provider
+    pub fn new(config: BedrockConfig) -> Result<Self> {
+        if config.credentials.access_key_id.is_empty()
+            || config.credentials.secret_access_key.is_empty()
+        {
+            return Err(Error::MissingApiKey("bedrock".to_string()));
+        }
+
+        let client = config.http.build_client()?;
+        Ok(Self { config, client })
+    }
+
+    /// Create provider from the AWS environment (region, credentials or profile)
+    pub fn from_env() -> Result<Self> {
+        Self::new(BedrockConfig::from_env()?)
+    }
+
+    /// Build URL for a model action (`converse` or `converse-stream`)
+    fn model_url(&self, model: &str, action: &str) -> Result<Url> {
+        let url = format!(
+            "{}/model/{}/{}",
+            self.config.base_url.trim_end_matches('/'),
+            uri_encode(model),
+            action
+        );
+        Url::parse(&url).map_err(|e| Error::ConfigError(format!("invalid Bedrock URL: {}", e)))
+    }
+
+    /// Sign and send a request
+    async fn send(&self, request: &GenerateRequest, action: &str) -> Result<Response> {
+        let url = self.model_url(&request.model, action)?;
+        let body = serde_json::to_vec(
+            &request
+                .options
+                .provider_options
+                .apply(self.provider_id(), &to_converse_request(request)?)?,
+        )?;
+
+        let mut headers = self
+            .build_headers(request.options.headers.as_ref())
+            .to_reqwest_headers();
+        SigV4 {
+            credentials: &self.config.credentials,
+            region: &self.config.region,
+            service: Self::SIGNING_SERVICE,
+            time: self.config.signing_time.unwrap_or_else(SystemTime::now),
+        }
+        .sign("POST", &url, &mut headers, &body);
+
+        Ok(self
+            .client
+            .post(url)
+            .headers(headers)
+            .body(body)
+            .send()
+            .await?)
+    }
+}
+
+#[async_trait]
+impl Provider for BedrockProvider {
+    fn provider_id(&self) -> &str {
+        "bedrock"
+    }
+
+    fn build_headers(&self, custom_headers: Option<&Headers>) -> Headers {
+        let mut headers = Headers::new();
+
+        // Authorization is added when the request is signed
+        headers.insert("Content-Type", "application/json");
+
+        if let Some(custom) = custom_headers {
+            headers.merge_with(custom);
+        }
+
+        headers
+    }
+
+    async fn generate(&self, request: GenerateRequest) -> Result<GenerateResponse> {
+        let started = Instant::now();
+        let response = self.send(&request, "converse").await?;
+
+        if !response.status().is_success() {
+            return Err(from_response(response).await);
+        }
+
+        let (converse_resp, metadata): (ConverseResponse, _) = read_json(
+            self.provider_id(),
+            response,
+            started,
+            request.options.include_raw_response,
+        )
+        .await?;
+        let mut response = from_converse_response(converse_resp).with_http_metadata(metadata);
+        // Bedrock doesn't echo the model back
+        if let Some(metadata) = response.metadata.as_mut() {
+            metadata.model.get_or_insert(request.model);
+        }
+
+        Ok(response)
+    }
+
+    async fn stream(&self, request: GenerateRequest) -> Result<GenerateStream> {
+        let started = Instant::now();
+        let response = self.send(&request, "converse-stream").await?;
+
+        if !response.status().is_success() {
+            return Err(from_response(response).await);
+        }
+
+        let metadata = ResponseMetadata {
+            model: Some(request.model),
+            ..ResponseMetadata::from_http(self.provider_id(), &response, started)
+        };
+        Ok(create_stream(response)
+            .await?
+            .with_metadata(metadata, started))
+    }
+}
@@ -0,0 +1,273 @@
+//! AWS Signature Version 4 request signing
+
+use super::credentials::AwsCredentials;
+use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION};
+use reqwest::Url;
+use ring::{digest, hmac};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const ALGORITHM: &str = "AWS4-HMAC-SHA256";
+
+/// Signs requests to one AWS service in one region at a given time
+pub(crate) struct SigV4<'a> {
+    pub credentials: &'a AwsCredentials,
+    pub region: &'a str,
+    pub service: &'a str,
+    pub time: SystemTime,
+}
+
+impl SigV4<'_> {
+    /// Add `x-amz-date`, the session token and `authorization` to `headers`
+    ///
+    /// Every header already in `headers` is signed, along with the host taken
+    /// from `url`, so headers must not change after signing.
+    pub(crate) fn sign(&self, method: &str, url: &Url, headers: &mut HeaderMap, body: &[u8]) {
+        let (date, amz_date) = format_time(self.time);
+        headers.insert("x-amz-date", header_value(&amz_date));
+        if let Some(token) = &self.credentials.session_token {
+            headers.insert("x-amz-security-token", header_value(token));
+        }
+
+        let (canonical_headers, signed_headers) = canonical_headers(url, headers);
+        let canonical_request = format!(
+            "{}\n{}\n{}\n{}\n{}\n{}",
+            method,
+            canonical_uri(url),
+            canonical_query(url),
+            canonical_headers,
+            signed_headers,
+            hex(digest::digest(&digest::SHA256, body).as_ref()),
+        );
+
+        let scope = format!("{}/{}/{}/aws4_request", date, self.region, self.service);
+        let string_to_sign = format!(
+            "{}\n{}\n{}\n{}",
+            ALGORITHM,
+            amz_date,
+            scope,
+            hex(digest::digest(&digest::SHA256, canonical_request.as_bytes()).as_ref()),
+        );
+
+        let mut key = format!("AWS4{}", self.credentials.secret_access_key).into_bytes();
+        for part in [date.as_str(), self.region, self.service, "aws4_request"] {
+            key = hmac_sha256(&key, part.as_bytes());
+        }
+        let signature = hex(&hmac_sha256(&key, string_to_sign.as_bytes()));
+
+        headers.insert(
+            AUTHORIZATION,
+            header_value(&format!(
+                "{} Credential={}/{}, SignedHeaders={}, Signature={}",
+                ALGORITHM, self.credentials.access_key_id, scope, signed_headers, signature
+            )),
+        );
+    }
+}
+
+/// Percent-encode everything but unreserved characters, as SigV4 requires
+pub(crate) fn uri_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+/// Path with each (already encoded) segment encoded again, as every service
+/// but S3 expects
+fn canonical_uri(url: &Url) -> String {
+    let path = url.path();
+    if path.is_empty() || path == "/" {
+        return "/".to_string();
+    }
+    path.split('/')
+        .map(uri_encode)
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Query parameters encoded and sorted by name, then value
+fn canonical_query(url: &Url) -> String {
+    let mut pairs: Vec<(String, String)> = url
+        .query_pairs()
+        .map(|(name, value)| (uri_encode(&name), uri_encode(&value)))
+        .collect();
+    pairs.sort();
+    pairs
+        .iter()
+        .map(|(name, value)| format!("{}={}", name, value))
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+/// Canonical header block and the `;`-separated list of signed header names
+fn canonical_headers(url: &Url, headers: &HeaderMap) -> (String, String) {
+    let host = match url.port() {
+        Some(port) => format!("{}:{}", url.host_str().unwrap_or_default(), port),
+        None => url.host_str().unwrap_or_default().to_string(),
+    };
+
+    let mut entries: Vec<(String, String)> = vec![("host".to_string(), host)];
+    for name in headers.keys() {
+        let values: Vec<String> = headers
+            .get_all(name)
+            .iter()
+            .map(|value| {
+                // Trim and collapse runs of spaces
+                String::from_utf8_lossy(value.as_bytes())
+                    .split_whitespace()
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .collect();
+        entries.push((name.as_str().to_string(), values.join(",")));
+    }
+    entries.sort();
+
+    let canonical = entries
+        .iter()
+        .map(|(name, value)| format!("{}:{}\n", name, value))
+        .collect();
+    let signed = entries
+        .iter()
+        .map(|(name, _)| name.as_str())
+        .collect::<Vec<_>>()
+        .join(";");
+    (canonical, signed)
+}
+
+/// `(YYYYMMDD, YYYYMMDDTHHMMSSZ)` in UTC
+fn format_time(time: SystemTime) -> (String, String) {
+    let secs = time
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs());
+    let days = (secs / 86_400) as i64;
+    let secs_of_day = secs % 86_400;
+
+    // Civil date from days since the epoch (Howard Hinnant's algorithm)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    let date = format!("{:04}{:02}{:02}", year, month, day);
+    let amz_date = format!(
+        "{}T{:02}{:02}{:02}Z",
+        date,
+        secs_of_day / 3_600,
+        secs_of_day / 60 % 60,
+        secs_of_day % 60
+    );
+    (date, amz_date)
+}
+
+fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let key = hmac::Key::new(hmac::HMAC_SHA256, key);
+    hmac::sign(&key, data).as_ref().to_vec()
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn header_value(value: &str) -> HeaderValue {
+    HeaderValue::from_str(value).unwrap_or_else(|_| HeaderValue::from_static(""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    /// Credentials and time of the AWS SigV4 test suite
+    fn suite_signer(credentials: &AwsCredentials) -> SigV4<'_> {
+        SigV4 {
+            credentials,
+            region: "us-east-1",
+            service: "service",
+            time: UNIX_EPOCH + Duration::from_secs(1_440_938_160),
+        }
+    }
+
+    fn suite_credentials() -> AwsCredentials {
+        AwsCredentials::new("AKIDEXAMPLE", "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY")
+    }
+
+    #[test]
+    fn test_format_time() {
+        let (date, amz_date) = format_time(UNIX_EPOCH + Duration::from_secs(1_440_938_160));
+        assert_eq!(date, "20150830");
+        assert_eq!(amz_date, "20150830T123600Z");
+
+        let (_, amz_date) = format_time(UNIX_EPOCH + Duration::from_secs(951_827_696));
+        assert_eq!(amz_date, "20000229T123456Z");
+    }
+
+    #[test]
+    fn test_get_vanilla() {
+        let credentials = suite_credentials();
+        let url = Url::parse("https://example.amazonaws.com/").unwrap();
+        let mut headers = HeaderMap::new();
+
+        suite_signer(&credentials).sign("GET", &url, &mut headers, b"");
+
+        assert_eq!(headers["x-amz-date"], "20150830T123600Z");
+        assert_eq!(
+            headers[AUTHORIZATION],
+            "AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20150830/us-east-1/service/aws4_request, \
+             SignedHeaders=host;x-amz-date, \
+             Signature=5fa00fa31553b73ebf1942676e86291e8372ff2a2260956d9b8aae1d763fbf31"
+        );
+    }
+
+    #[test]
+    fn test_get_vanilla_query_order() {
+        let credentials = suite_credentials();
+        let url = Url::parse("https://example.amazonaws.com/?Param2=value2&Param1=value1").unwrap();
+        let mut headers = HeaderMap::new();
+
+        suite_signer(&credentials).sign("GET", &url, &mut headers, b"");
+
+        assert!(headers[AUTHORIZATION].to_str().unwrap().ends_with(
+            "Signature=b97d918cfa904a5beff61c982a1b6f458b799221646efd99d3219ec94cdf2500"
+        ));
+    }
+
+    #[test]
+    fn test_session_token_is_signed() {
+        let credentials = suite_credentials().with_session_token("token");
+        let url = Url::parse("https://example.amazonaws.com/").unwrap();
+        let mut headers = HeaderMap::new();
+
+        suite_signer(&credentials).sign("GET", &url, &mut headers, b"");
+
+        assert_eq!(headers["x-amz-security-token"], "token");
+        assert!(headers[AUTHORIZATION]
+            .to_str()
+            .unwrap()
+            .contains("SignedHeaders=host;x-amz-date;x-amz-security-token,"));
+    }
+
+    #[test]
+    fn test_canonical_uri_double_encodes() {
+        let url = Url::parse(&format!(
+            "https://bedrock-runtime.us-east-1.amazonaws.com/model/{}/converse",
+            uri_encode("anthropic.claude-3-haiku-20240307-v1:0")
+        ))
+        .unwrap();
+
+        assert_eq!(
+            canonical_uri(&url),
+            "/model/anthropic.claude-3-haiku-20240307-v1%253A0/converse"
+        );
+    }
+}
@@ -0,0 +1,243 @@
+//! Bedrock ConverseStream support
+
+use super::convert::{from_bedrock_usage, parse_stop_reason};
+use super::error::from_stream_exception;
+use super::event_stream::{EventMessage, EventStreamDecoder};
+use super::types::BedrockStreamEvent;
+use crate::error::{Error, Result};
+use crate::types::{FinishReason, GenerateStream, StreamEvent, ToolCallAccumulator, Usage};
+use futures::StreamExt;
+use reqwest::Response;
+
+/// Create a stream from a Bedrock event stream response
+pub async fn create_stream(response: Response) -> Result<GenerateStream> {
+    let stream = async_stream::stream! {
+        let mut body = response.bytes_stream();
+        let mut decoder = EventStreamDecoder::default();
+        let mut state = StreamState::default();
+
+        'body: while let Some(chunk) = body.next().await {
+            match chunk {
+                Ok(chunk) => decoder.push(&chunk),
+                Err(e) => {
+                    yield Err(Error::HttpError(e));
+                    break;
+                }
+            }
+
+            loop {
+                match decoder.next_message() {
+                    Ok(Some(message)) => match process_bedrock_message(message, &mut state) {
+                        Ok(events) => {
+                            for event in events {
+                                yield Ok(event);
+                            }
+                        }
+                        Err(e) => {
+                            yield Err(e);
+                            break 'body;
+                        }
+                    },
+                    Ok(None) => break,
+                    Err(e) => {
+                        yield Err(e);
+                        break 'body;
+                    }
+                }
+            }
+        }
+
+        // Finish streams that stopped without a metadata event
+        for event in state.finish() {
+            yield Ok(event);
+        }
+    };
+
+    Ok(GenerateStream::new(Box::pin(stream)))
+}
+
+/// State carried across Bedrock stream events
+#[derive(Default)]
+struct StreamState {
+    usage: Usage,
+    tool_calls: ToolCallAccumulator,
+    /// Set by `messageStop`
+    finish_reason: Option<FinishReason>,
+}
+
+impl StreamState {
+    /// Complete open tool calls and emit the finish event, once
+    fn finish(&mut self) -> Vec<StreamEvent> {
+        let Some(reason) = self.finish_reason.take() else {
+            return Vec::new();
+        };
+
+        let mut events = self.tool_calls.end_all();
+        events.push(StreamEvent::finish(std::mem::take(&mut self.usage), reason));
+        events
+    }
+}
+
+/// Process a Bedrock event stream message and convert to unified StreamEvents
+fn process_bedrock_message(
+    message: EventMessage,
+    state: &mut StreamState,
+) -> Result<Vec<StreamEvent>> {
+    let event: BedrockStreamEvent = serde_json::from_slice(&message.payload)
+        .map_err(|e| Error::stream_error(format!("Failed to parse event: {}", e)))?;
+
+    match message.header(":message-type") {
+        Some("exception") => {
+            return Err(from_stream_exception(
+                message.header(":exception-type").unwrap_or_default(),
+                event.message.unwrap_or_default(),
+            ))
+        }
+        Some("error") => {
+            return Err(Error::stream_error(format!(
+                "{}: {}",
+                message.header(":error-code").unwrap_or("error"),
+                message.header(":error-message").unwrap_or_default()
+            )))
+        }
+        _ => {}
+    }
+
+    let index = event.content_block_index.unwrap_or(0);
+    let stream_event = match message.header(":event-type").unwrap_or_default() {
+        "messageStart" => Some(StreamEvent::start("")),
+        "contentBlockStart" => event
+            .start
+            .and_then(|start| start.tool_use)
+            .map(|tool_use| {
+                state
+                    .tool_calls
+                    .start(index, tool_use.tool_use_id, tool_use.name)
+            }),
+        "contentBlockDelta" => {
+            let delta = event.delta.unwrap_or_default();
+            if let Some(text) = delta.text {
+                Some(StreamEvent::text_delta("", text))
+            } else if let Some(tool_use) = delta.tool_use {
+                state.tool_calls.delta(index, &tool_use.input)
+            } else if let Some(reasoning) = delta.reasoning_content {
+                match (reasoning.text, reasoning.signature) {
+                    (Some(text), _) => Some(StreamEvent::reasoning_delta("", text)),
+                    (None, Some(signature)) => {
+                        Some(StreamEvent::reasoning_signature("", signature))
+                    }
+                    (None, None) => None,
+                }
+            } else {
+                None
+            }
+        }
+        "contentBlockStop" => state.tool_calls.end(index),
+        "messageStop" => {
+            // Usage follows in the metadata event
+            state.finish_reason = event.stop_reason.as_deref().map(parse_stop_reason);
+            None
+        }
+        "metadata" => {
+            if let Some(usage) = &event.usage {
+                state.usage = from_bedrock_usage(usage);
+            }
+            return Ok(state.finish());
+        }
+        _ => None,
+    };
+
+    Ok(stream_event.into_iter().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::event_stream::tests::encode;
+    use super::*;
+    use serde_json::json;
+
+    fn process(events: &[(&str, serde_json::Value)]) -> Result<Vec<StreamEvent>> {
+        let mut state = StreamState::default();
+        let mut decoder = EventStreamDecoder::default();
+        for (event_type, payload) in events {
+            decoder.push(&encode(
+                &[(":message-type", "event"), (":event-type", event_type)],
+                payload.to_string().as_bytes(),
+            ));
+        }
+
+        let mut result = Vec::new();
+        while let Some(message) = decoder.next_message()? {
+            result.extend(process_bedrock_message(message, &mut state)?);
+        }
+        result.extend(state.finish());
+        Ok(result)
+    }
+
+    #[test]
+    fn test_process_tool_use_events() {
+        let result = process(&[
+            ("messageStart", json!({"role": "assistant"})),
+            ("contentBlockDelta", json!({"contentBlockIndex": 0, "delta": {"text": "Checking"}})),
+            ("contentBlockStop", json!({"contentBlockIndex": 0})),
+            ("contentBlockStart", json!({"contentBlockIndex": 1, "start": {"toolUse": {"toolUseId": "tooluse_1", "name": "lookup"}}})),
+            ("contentBlockDelta", json!({"contentBlockIndex": 1, "delta": {"toolUse": {"input": "{\"q\":"}}})),
+            ("contentBlockDelta", json!({"contentBlockIndex": 1, "delta": {"toolUse": {"input": "\"x\"}"}}})),
+            ("contentBlockStop", json!({"contentBlockIndex": 1})),
+            ("messageStop", json!({"stopReason": "tool_use"})),
+            ("metadata", json!({"usage": {"inputTokens": 8, "outputTokens": 4, "totalTokens": 12}, "metrics": {"latencyMs": 10}})),
+        ])
+        .unwrap();
+
+        assert_eq!(result.len(), 7);
+        assert!(matches!(&result[1], StreamEvent::TextDelta { delta, .. } if delta == "Checking"));
+        assert!(matches!(
+            &result[5],
+            StreamEvent::ToolCallEnd { id, arguments, .. }
+                if id == "tooluse_1" && arguments == &json!({"q": "x"})
+        ));
+        assert!(matches!(
+            &result[6],
+            StreamEvent::Finish { usage, reason: FinishReason::ToolCalls } if usage.total_tokens == 12
+        ));
+    }
+
+    #[test]
+    fn test_finish_without_metadata() {
+        let result = process(&[
+            ("contentBlockDelta", json!({"contentBlockIndex": 0, "delta": {"reasoningContent": {"text": "Hmm"}}})),
+            ("contentBlockDelta", json!({"contentBlockIndex": 0, "delta": {"reasoningContent": {"signature": "sig"}}})),
+            ("messageStop", json!({"stopReason": "end_turn"})),
+        ])
+        .unwrap();
+
+        assert!(matches!(&result[0], StreamEvent::ReasoningDelta { delta, .. } if delta == "Hmm"));
+        assert!(matches!(
+            &result[1],
+            StreamEvent::ReasoningDelta { signature: Some(sig), .. } if sig == "sig"
+        ));
+        assert!(matches!(
+            &result[2],
+            StreamEvent::Finish {
+                reason: FinishReason::Stop,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_process_exception() {
+        let mut decoder = EventStreamDecoder::default();
+        decoder.push(&encode(
+            &[
+                (":message-type", "exception"),
+                (":exception-type", "throttlingException"),
+            ],
+            br#"{"message":"Too many requests, please wait before trying again."}"#,
+        ));
+        let message = decoder.next_message().unwrap().unwrap();
+
+        let result = process_bedrock_message(message, &mut StreamState::default());
+        assert!(matches!(result, Err(Error::RateLimitExceeded(_))));
+    }
+}
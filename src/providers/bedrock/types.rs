@@ -0,0 +1,242 @@
+//! Bedrock-specific types
+
+use super::credentials::AwsCredentials;
+use crate::error::{Error, Result};
+use crate::http::HttpConfig;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::time::SystemTime;
+
+/// Configuration for Bedrock provider
+#[derive(Debug, Clone)]
+pub struct BedrockConfig {
+    /// AWS region (e.g., us-east-1)
+    pub region: String,
+    /// Credentials used to sign requests
+    pub credentials: AwsCredentials,
+    /// Base URL (default: https://bedrock-runtime.{region}.amazonaws.com)
+    pub base_url: String,
+    /// Fixed signing time instead of the system clock (for tests)
+    pub signing_time: Option<SystemTime>,
+    /// HTTP client settings (timeouts)
+    pub http: HttpConfig,
+}
+
+impl BedrockConfig {
+    /// Create new config for a region
+    pub fn new(region: impl Into<String>, credentials: AwsCredentials) -> Self {
+        let region = region.into();
+        Self {
+            base_url: format!("https://bedrock-runtime.{}.amazonaws.com", region),
+            region,
+            credentials,
+            signing_time: None,
+            http: HttpConfig::default(),
+        }
+    }
+
+    /// Create config from `AWS_REGION` (or `AWS_DEFAULT_REGION`) and
+    /// [`AwsCredentials::load`]
+    pub fn from_env() -> Result<Self> {
+        let region = ["AWS_REGION", "AWS_DEFAULT_REGION"]
+            .iter()
+            .find_map(|name| std::env::var(name).ok().filter(|region| !region.is_empty()))
+            .ok_or_else(|| Error::ConfigError("AWS_REGION is not set".to_string()))?;
+
+        Ok(Self::new(region, AwsCredentials::load()?))
+    }
+
+    /// Set base URL (e.g., a VPC endpoint)
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into();
+        self
+    }
+
+    /// Set HTTP client settings
+    pub fn with_http_config(mut self, http: HttpConfig) -> Self {
+        self.http = http;
+        self
+    }
+
+    /// Sign every request as if made at `time`
+    pub fn with_signing_time(mut self, time: SystemTime) -> Self {
+        self.signing_time = Some(time);
+        self
+    }
+}
+
+/// Bedrock Converse request (the model ID is part of the URL)
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConverseRequest {
+    pub messages: Vec<BedrockMessage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub system: Option<Vec<Value>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub inference_config: Option<BedrockInferenceConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_config: Option<Value>,
+    /// Model-specific fields (e.g., Anthropic's `top_k` and `thinking`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub additional_model_request_fields: Option<Value>,
+}
+
+/// Bedrock message
+#[derive(Debug, Serialize)]
+pub struct BedrockMessage {
+    pub role: String,
+    pub content: Vec<Value>,
+}
+
+/// Bedrock inference parameters
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BedrockInferenceConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stop_sequences: Option<Vec<String>>,
+}
+
+/// Bedrock Converse response
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConverseResponse {
+    pub output: ConverseOutput,
+    pub stop_reason: String,
+    #[serde(default)]
+    pub usage: Option<BedrockUsage>,
+}
+
+/// Output of a Converse response
+#[derive(Debug, Deserialize)]
+pub struct ConverseOutput {
+    pub message: BedrockResponseMessage,
+}
+
+/// Assistant message in a Converse response
+#[derive(Debug, Deserialize)]
+pub struct BedrockResponseMessage {
+    #[serde(default)]
+    pub content: Vec<BedrockContentBlock>,
+}
+
+/// Bedrock content block (exactly one field is set)
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BedrockContentBlock {
+    #[serde(default)]
+    pub text: Option<String>,
+    #[serde(default)]
+    pub tool_use: Option<BedrockToolUse>,
+    #[serde(default)]
+    pub reasoning_content: Option<BedrockReasoningContent>,
+}
+
+/// Bedrock tool use block
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BedrockToolUse {
+    pub tool_use_id: String,
+    pub name: String,
+    #[serde(default)]
+    pub input: Value,
+}
+
+/// Bedrock reasoning block
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BedrockReasoningContent {
+    #[serde(default)]
+    pub reasoning_text: Option<BedrockReasoningText>,
+}
+
+/// Bedrock reasoning text
+#[derive(Debug, Deserialize)]
+pub struct BedrockReasoningText {
+    pub text: String,
+    #[serde(default)]
+    pub signature: Option<String>,
+}
+
+/// Bedrock usage statistics
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BedrockUsage {
+    #[serde(default)]
+    pub input_tokens: u32,
+    #[serde(default)]
+    pub output_tokens: u32,
+    #[serde(default)]
+    pub cache_read_input_tokens: u32,
+    #[serde(default)]
+    pub cache_write_input_tokens: u32,
+}
+
+/// Payload of a ConverseStream event; which fields are set depends on the
+/// `:event-type` header
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BedrockStreamEvent {
+    #[serde(default)]
+    pub content_block_index: Option<u32>,
+    #[serde(default)]
+    pub start: Option<BedrockBlockStart>,
+    #[serde(default)]
+    pub delta: Option<BedrockBlockDelta>,
+    #[serde(default)]
+    pub stop_reason: Option<String>,
+    #[serde(default)]
+    pub usage: Option<BedrockUsage>,
+    /// Set on exception events
+    #[serde(default)]
+    pub message: Option<String>,
+}
+
+/// Start of a streamed content block
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BedrockBlockStart {
+    #[serde(default)]
+    pub tool_use: Option<BedrockToolUseStart>,
+}
+
+/// Start of a streamed tool use block
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BedrockToolUseStart {
+    pub tool_use_id: String,
+    pub name: String,
+}
+
+/// Delta of a streamed content block
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BedrockBlockDelta {
+    #[serde(default)]
+    pub text: Option<String>,
+    #[serde(default)]
+    pub tool_use: Option<BedrockToolUseDelta>,
+    #[serde(default)]
+    pub reasoning_content: Option<BedrockReasoningDelta>,
+}
+
+/// Tool input fragment (partial JSON)
+#[derive(Debug, Deserialize)]
+pub struct BedrockToolUseDelta {
+    #[serde(default)]
+    pub input: String,
+}
+
+/// Reasoning fragment, or the signature closing the reasoning block
+#[derive(Debug, Deserialize)]
+pub struct BedrockReasoningDelta {
+    #[serde(default)]
+    pub text: Option<String>,
+    #[serde(default)]
+    pub signature: Option<String>,
+}
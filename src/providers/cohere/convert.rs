@@ -0,0 +1,478 @@
+//! Conversion between unified types and Cohere types
+
+use super::types::{
+    CohereCitation, CohereEmbedRequest, CohereEmbedResponse, CohereFunction, CohereMessage,
+    CohereModel, CohereRequest, CohereResponse, CohereThinking, CohereToolCall, CohereUsage,
+};
+use crate::types::{
+    Citation, ContentPart, Document, EmbedRequest, EmbedResponse, FinishReason, GenerateRequest,
+    GenerateResponse, Message, ModelInfo, ResponseContent, ResponseFormat, ResponseMetadata, Role,
+    ToolCall, ToolChoice, Usage,
+};
+use serde_json::{json, Value};
+
+/// Convert unified request to Cohere request
+pub fn to_cohere_request(req: &GenerateRequest, stream: bool) -> CohereRequest {
+    // Convert tools to Cohere format (same shape as OpenAI)
+    let tools = req.options.tools.as_ref().map(|tools| {
+        tools
+            .iter()
+            .map(|tool| {
+                json!({
+                    "type": "function",
+                    "function": {
+                        "name": tool.function.name,
+                        "description": tool.function.description,
+                        "parameters": tool.function.parameters,
+                    }
+                })
+            })
+            .collect::<Vec<_>>()
+    });
+
+    // Cohere can't force a specific tool, only require one
+    let tool_choice = req
+        .options
+        .tool_choice
+        .as_ref()
+        .and_then(|choice| match choice {
+            ToolChoice::Auto => None,
+            ToolChoice::None => Some("NONE".to_string()),
+            ToolChoice::Required { .. } => Some("REQUIRED".to_string()),
+        });
+
+    CohereRequest {
+        model: req.model.clone(),
+        messages: req.messages.iter().flat_map(to_cohere_messages).collect(),
+        documents: req
+            .options
+            .documents
+            .as_ref()
+            .map(|documents| documents.iter().map(to_cohere_document).collect()),
+        tools,
+        tool_choice,
+        temperature: req.options.temperature,
+        max_tokens: req.options.max_tokens,
+        p: req.options.top_p,
+        k: req.options.top_k,
+        stop_sequences: req.options.stop_sequences.clone(),
+        frequency_penalty: req.options.frequency_penalty,
+        presence_penalty: req.options.presence_penalty,
+        response_format: req
+            .options
+            .response_format
+            .as_ref()
+            .and_then(|format| match format {
+                ResponseFormat::Text => None,
+                ResponseFormat::JsonObject => Some(json!({"type": "json_object"})),
+                ResponseFormat::JsonSchema { schema, .. } => Some(json!({
+                    "type": "json_object",
+                    "json_schema": schema,
+                })),
+            }),
+        thinking: req
+            .options
+            .reasoning
+            .as_ref()
+            .map(|reasoning| CohereThinking {
+                type_: "enabled".to_string(),
+                token_budget: Some(reasoning.resolved_budget()),
+            }),
+        stream: stream.then_some(true),
+    }
+}
+
+/// Convert a unified document to a Cohere document
+fn to_cohere_document(document: &Document) -> Value {
+    let mut data = json!({ "text": document.text });
+    if let Some(title) = &document.title {
+        data["title"] = json!(title);
+    }
+
+    let mut value = json!({ "data": data });
+    if let Some(id) = &document.id {
+        value["id"] = json!(id);
+    }
+    value
+}
+
+/// Convert a unified message to Cohere messages
+///
+/// Cohere takes one `tool` message per tool result, so a message carrying
+/// several results expands into several messages.
+fn to_cohere_messages(msg: &Message) -> Vec<CohereMessage> {
+    let parts = msg.parts();
+
+    let tool_results: Vec<CohereMessage> = parts
+        .iter()
+        .filter_map(|part| match part {
+            ContentPart::ToolResult {
+                tool_call_id,
+                content,
+                ..
+            } => Some(CohereMessage {
+                role: "tool".to_string(),
+                content: Some(match content {
+                    Value::String(_) => content.clone(),
+                    other => json!(other.to_string()),
+                }),
+                tool_plan: None,
+                tool_calls: None,
+                tool_call_id: Some(tool_call_id.clone()),
+            }),
+            _ => None,
+        })
+        .collect();
+    if !tool_results.is_empty() {
+        return tool_results;
+    }
+
+    let role = match msg.role {
+        Role::System => "system",
+        Role::User => "user",
+        Role::Assistant => "assistant",
+        Role::Tool => "tool",
+    };
+
+    let tool_calls: Vec<CohereToolCall> = parts
+        .iter()
+        .filter_map(|part| match part {
+            ContentPart::ToolCall {
+                id,
+                name,
+                arguments,
+                ..
+            } => Some(CohereToolCall {
+                id: id.clone(),
+                type_: "function".to_string(),
+                function: CohereFunction {
+                    name: name.clone(),
+                    arguments: arguments.to_string(),
+                },
+            }),
+            _ => None,
+        })
+        .collect();
+
+    // Reasoning ahead of tool calls is sent back as the tool plan
+    let tool_plan = if tool_calls.is_empty() {
+        None
+    } else {
+        let plan: String = parts
+            .iter()
+            .filter_map(|part| match part {
+                ContentPart::Reasoning { text, .. } => Some(text.as_str()),
+                _ => None,
+            })
+            .collect();
+        (!plan.is_empty()).then_some(plan)
+    };
+
+    let content_items: Vec<Value> = parts
+        .iter()
+        .filter_map(|part| match part {
+            ContentPart::Text { text, .. } => Some(json!({"type": "text", "text": text})),
+            ContentPart::Image { url, .. } => Some(json!({
+                "type": "image_url",
+                "image_url": { "url": url }
+            })),
+            _ => None,
+        })
+        .collect();
+
+    // A single text part is sent as a plain string
+    let content = match content_items.as_slice() {
+        [] => None,
+        [item] if item["type"] == "text" => Some(item["text"].clone()),
+        _ => Some(json!(content_items)),
+    };
+
+    vec![CohereMessage {
+        role: role.to_string(),
+        content,
+        tool_plan,
+        tool_calls: (!tool_calls.is_empty()).then_some(tool_calls),
+        tool_call_id: None,
+    }]
+}
+
+/// Convert Cohere response to unified response
+pub fn from_cohere_response(resp: CohereResponse) -> GenerateResponse {
+    let mut content = Vec::new();
+
+    if let Some(plan) = resp.message.tool_plan.filter(|plan| !plan.is_empty()) {
+        content.push(ResponseContent::Reasoning {
+            text: plan,
+            signature: None,
+            data: None,
+        });
+    }
+
+    for item in resp.message.content {
+        match item.type_.as_str() {
+            "thinking" => {
+                if let Some(text) = item.thinking.filter(|text| !text.is_empty()) {
+                    content.push(ResponseContent::Reasoning {
+                        text,
+                        signature: None,
+                        data: None,
+                    });
+                }
+            }
+            _ => {
+                if let Some(text) = item.text.filter(|text| !text.is_empty()) {
+                    content.push(ResponseContent::Text { text });
+                }
+            }
+        }
+    }
+
+    for tc in resp.message.tool_calls {
+        content.push(ResponseContent::ToolCall(ToolCall {
+            id: tc.id,
+            name: tc.function.name,
+            arguments: serde_json::from_str(&tc.function.arguments).unwrap_or_else(|_| json!({})),
+            signature: None,
+        }));
+    }
+
+    GenerateResponse {
+        content,
+        usage: resp
+            .usage
+            .as_ref()
+            .map(from_cohere_usage)
+            .unwrap_or_default(),
+        finish_reason: parse_finish_reason(resp.finish_reason.as_deref()),
+        citations: resp
+            .message
+            .citations
+            .into_iter()
+            .map(from_cohere_citation)
+            .collect(),
+        metadata: Some(ResponseMetadata {
+            id: Some(resp.id),
+            ..Default::default()
+        }),
+    }
+}
+
+/// Convert Cohere citation to unified citation, keeping the source IDs
+pub fn from_cohere_citation(citation: CohereCitation) -> Citation {
+    Citation {
+        start: citation.start,
+        end: citation.end,
+        text: citation.text,
+        sources: citation
+            .sources
+            .into_iter()
+            .filter_map(|source| source.id)
+            .collect(),
+    }
+}
+
+/// Parse Cohere finish reason
+pub fn parse_finish_reason(reason: Option<&str>) -> FinishReason {
+    match reason {
+        Some("COMPLETE") | Some("STOP_SEQUENCE") => FinishReason::Stop,
+        Some("MAX_TOKENS") => FinishReason::Length,
+        Some("TOOL_CALL") => FinishReason::ToolCalls,
+        Some("ERROR_TOXIC") => FinishReason::ContentFilter,
+        _ => FinishReason::Other,
+    }
+}
+
+/// Convert Cohere usage to unified usage
+///
+/// Prefers the raw token counts, falling back to billed units.
+pub fn from_cohere_usage(usage: &CohereUsage) -> Usage {
+    let tokens = match usage.tokens.as_ref().or(usage.billed_units.as_ref()) {
+        Some(tokens) => tokens,
+        None => return Usage::default(),
+    };
+    let prompt_tokens = tokens.input_tokens as u32;
+    let completion_tokens = tokens.output_tokens as u32;
+
+    Usage {
+        prompt_tokens,
+        completion_tokens,
+        total_tokens: prompt_tokens + completion_tokens,
+        ..Default::default()
+    }
+}
+
+/// Convert a batch of a unified embedding request to a Cohere embed request
+pub fn to_cohere_embed_request(
+    req: &EmbedRequest,
+    texts: &[String],
+    input_type: &str,
+) -> CohereEmbedRequest {
+    CohereEmbedRequest {
+        model: req.model.clone(),
+        texts: texts.to_vec(),
+        input_type: input_type.to_string(),
+        embedding_types: vec!["float".to_string()],
+        output_dimension: req.dimensions,
+    }
+}
+
+/// Convert Cohere embed response to unified response
+pub fn from_cohere_embed_response(resp: CohereEmbedResponse) -> EmbedResponse {
+    let prompt_tokens = resp
+        .meta
+        .and_then(|meta| meta.billed_units)
+        .map_or(0, |units| units.input_tokens as u32);
+
+    EmbedResponse {
+        embeddings: resp.embeddings.float,
+        usage: Usage {
+            prompt_tokens,
+            total_tokens: prompt_tokens,
+            ..Default::default()
+        },
+    }
+}
+
+/// Convert Cohere model to unified model info
+pub fn from_cohere_model(model: CohereModel) -> ModelInfo {
+    let has = |feature: &str| model.features.iter().any(|f| f == feature);
+    let supports_tools = has("tools");
+    let supports_reasoning = has("reasoning");
+
+    ModelInfo {
+        context_window: model.context_length.map(|length| length as u32),
+        supports_tools: Some(supports_tools),
+        supports_reasoning: Some(supports_reasoning),
+        ..ModelInfo::new("cohere", model.name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{GenerateOptions, Tool};
+
+    #[test]
+    fn test_tool_round_trip_messages() {
+        let messages = [
+            Message::new(Role::System, "Be brief"),
+            Message::new(Role::User, "Weather in Paris and Rome?"),
+            Message::new(
+                Role::Assistant,
+                vec![
+                    ContentPart::reasoning("I will look both up", None),
+                    ContentPart::tool_call("call_1", "get_weather", json!({"city": "Paris"})),
+                    ContentPart::tool_call("call_2", "get_weather", json!({"city": "Rome"})),
+                ],
+            ),
+            Message::new(
+                Role::Tool,
+                vec![
+                    ContentPart::tool_result("call_1", json!({"temp": 20})),
+                    ContentPart::tool_result("call_2", json!("Sunny")),
+                ],
+            ),
+        ];
+
+        let converted: Vec<CohereMessage> = messages.iter().flat_map(to_cohere_messages).collect();
+        let value = serde_json::to_value(&converted).unwrap();
+
+        assert_eq!(value[0], json!({"role": "system", "content": "Be brief"}));
+        assert_eq!(value[2]["tool_plan"], "I will look both up");
+        assert!(value[2].get("content").is_none());
+        assert_eq!(
+            value[2]["tool_calls"][0],
+            json!({
+                "id": "call_1",
+                "type": "function",
+                "function": {"name": "get_weather", "arguments": "{\"city\":\"Paris\"}"}
+            })
+        );
+        assert_eq!(
+            value[3],
+            json!({"role": "tool", "content": "{\"temp\":20}", "tool_call_id": "call_1"})
+        );
+        assert_eq!(
+            value[4],
+            json!({"role": "tool", "content": "Sunny", "tool_call_id": "call_2"})
+        );
+    }
+
+    #[test]
+    fn test_request_options() {
+        let options = GenerateOptions::new()
+            .add_tool(Tool::function("lookup", "Look up a word").parameters(json!({})))
+            .tool_choice(ToolChoice::Required {
+                name: "lookup".to_string(),
+            })
+            .add_document(Document::new("Paris is in France").with_id("doc_1"))
+            .add_document(Document::new("Rome is in Italy").with_title("Italy"));
+        let request = GenerateRequest {
+            options,
+            ..GenerateRequest::new("command-r", vec![Message::new(Role::User, "Hi")])
+        };
+
+        let value = serde_json::to_value(to_cohere_request(&request, false)).unwrap();
+
+        assert_eq!(value["tool_choice"], "REQUIRED");
+        assert_eq!(value["tools"][0]["function"]["name"], "lookup");
+        assert_eq!(
+            value["documents"],
+            json!([
+                {"id": "doc_1", "data": {"text": "Paris is in France"}},
+                {"data": {"title": "Italy", "text": "Rome is in Italy"}}
+            ])
+        );
+        assert!(value.get("stream").is_none());
+    }
+
+    #[test]
+    fn test_response_with_citations_and_tool_calls() {
+        let resp: CohereResponse = serde_json::from_value(json!({
+            "id": "resp_1",
+            "finish_reason": "TOOL_CALL",
+            "message": {
+                "role": "assistant",
+                "tool_plan": "Search first",
+                "content": [{"type": "text", "text": "Paris is in France."}],
+                "tool_calls": [{
+                    "id": "call_1",
+                    "type": "function",
+                    "function": {"name": "search", "arguments": "{\"q\":\"Paris\"}"}
+                }],
+                "citations": [{
+                    "start": 0,
+                    "end": 5,
+                    "text": "Paris",
+                    "sources": [{"type": "document", "id": "doc_1", "document": {}}]
+                }]
+            },
+            "usage": {
+                "billed_units": {"input_tokens": 10, "output_tokens": 4},
+                "tokens": {"input_tokens": 120, "output_tokens": 5}
+            }
+        }))
+        .unwrap();
+
+        let response = from_cohere_response(resp);
+
+        assert_eq!(response.finish_reason, FinishReason::ToolCalls);
+        assert!(matches!(
+            &response.content[0],
+            ResponseContent::Reasoning { text, .. } if text == "Search first"
+        ));
+        assert_eq!(response.text(), "Paris is in France.");
+        assert_eq!(response.tool_calls()[0].arguments, json!({"q": "Paris"}));
+        assert_eq!(
+            response.citations,
+            vec![Citation {
+                start: 0,
+                end: 5,
+                text: "Paris".to_string(),
+                sources: vec!["doc_1".to_string()],
+            }]
+        );
+        assert_eq!(response.usage.prompt_tokens, 120);
+        assert_eq!(response.usage.total_tokens, 125);
+    }
+}
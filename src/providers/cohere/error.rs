@@ -0,0 +1,85 @@
+//! Cohere error response parsing
+
+use crate::error::{ApiError, Error};
+use reqwest::Response;
+use serde::Deserialize;
+
+/// Cohere error response body
+#[derive(Debug, Deserialize)]
+pub struct CohereErrorResponse {
+    #[serde(default)]
+    pub id: Option<String>,
+    pub message: String,
+}
+
+/// Convert a non-success Cohere response into a classified error
+pub(crate) async fn from_response(response: Response) -> Error {
+    let error = ApiError::from_response("cohere", response).await;
+    classify(parse_body(error))
+}
+
+/// Convert an error reported by a stream's `message-end` event
+pub(crate) fn from_stream_error(message: String) -> Error {
+    classify(ApiError::new("cohere", 500, message))
+}
+
+/// Refine an API error from the Cohere error body in its message, if present
+fn parse_body(mut error: ApiError) -> ApiError {
+    if let Ok(body) = serde_json::from_str::<CohereErrorResponse>(&error.message) {
+        error.message = body.message;
+        error.request_id = error.request_id.or(body.id);
+    }
+    error
+}
+
+/// Classify a Cohere API error by its message, falling back to HTTP status
+fn classify(error: ApiError) -> Error {
+    // 498 is Cohere's status for an invalid token
+    if error.status == 498 {
+        return Error::Authentication(Box::new(error));
+    }
+
+    let message = error.message.to_lowercase();
+    if message.contains("too many tokens") || message.contains("context length") {
+        return Error::ContextLengthExceeded(Box::new(error));
+    }
+
+    error.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(status: u16, body: &str) -> Error {
+        classify(parse_body(ApiError::new("cohere", status, body)))
+    }
+
+    #[test]
+    fn test_parse_context_length_error() {
+        let error = parse(
+            400,
+            r#"{"id":"req_1","message":"too many tokens: total number of tokens in the prompt cannot exceed 128000"}"#,
+        );
+
+        match error {
+            Error::ContextLengthExceeded(e) => {
+                assert_eq!(e.request_id.as_deref(), Some("req_1"));
+                assert!(e.message.starts_with("too many tokens"));
+            }
+            other => panic!("Expected ContextLengthExceeded, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_falls_back_to_status() {
+        let error = parse(498, r#"{"id":"req_2","message":"invalid api token"}"#);
+        assert!(matches!(error, Error::Authentication(e) if e.message == "invalid api token"));
+
+        let error = parse(429, r#"{"message":"trial key rate limit"}"#);
+        assert!(matches!(error, Error::RateLimitExceeded(_)));
+
+        let error = parse(502, "<html>Bad Gateway</html>");
+        assert!(matches!(error, Error::Api(e) if e.message == "<html>Bad Gateway</html>"));
+    }
+}
@@ -0,0 +1,10 @@
+//! Cohere provider module
+
+mod convert;
+mod error;
+mod provider;
+mod stream;
+mod types;
+
+pub use provider::CohereProvider;
+pub use types::CohereConfig;
@@ -0,0 +1,216 @@
+//! Cohere provider implementation
+
+use super::convert::{
+    from_cohere_embed_response, from_cohere_model, from_cohere_response, to_cohere_embed_request,
+    to_cohere_request,
+};
+use super::error::from_response;
+use super::stream::create_stream;
+use super::types::{CohereConfig, CohereEmbedResponse, CohereModelList, CohereResponse};
+use crate::error::{Error, Result};
+use crate::http::read_json;
+use crate::provider::Provider;
+use crate::types::{
+    EmbedRequest, EmbedResponse, GenerateRequest, GenerateResponse, GenerateStream, Headers,
+    ModelInfo, ResponseMetadata,
+};
+use async_trait::async_trait;
+use reqwest::Client;
+use std::time::Instant;
+
+/// Cohere provider (v2 Chat and Embed APIs)
+///
+/// Documents set with [`GenerateOptions::add_document`](crate::types::GenerateOptions::add_document)
+/// are sent for grounded generation, and the citations Cohere returns are
+/// exposed as [`GenerateResponse::citations`] or
+/// [`StreamEvent::Citation`](crate::StreamEvent::Citation) events.
+pub struct CohereProvider {
+    config: CohereConfig,
+    client: Client,
+}
+
+impl CohereProvider {
+    /// Environment variable for API key
+    pub const API_KEY_ENV: &'static str = "COHERE_API_KEY";
+
+    /// Maximum number of texts per embed request
+    pub const MAX_EMBED_BATCH: usize = 96;
+
+    /// Create a new Cohere provider
+    pub fn new(config: CohereConfig) -> Result<Self> {
+        if config.api_key.is_empty() {
+            return Err(Error::MissingApiKey("cohere".to_string()));
+        }
+
+        let client = config.http.build_client()?;
+        Ok(Self { config, client })
+    }
+
+    /// Create provider from environment
+    pub fn from_env() -> Result<Self> {
+        let api_key = std::env::var(Self::API_KEY_ENV)
+            .map_err(|_| Error::MissingApiKey("cohere".to_string()))?;
+
+        Self::new(CohereConfig::new(api_key))
+    }
+
+    /// Build URL for a Cohere API path
+    fn url(&self, path: &str) -> String {
+        format!("{}{}", self.config.base_url.trim_end_matches('/'), path)
+    }
+}
+
+#[async_trait]
+impl Provider for CohereProvider {
+    fn provider_id(&self) -> &str {
+        "cohere"
+    }
+
+    fn build_headers(&self, custom_headers: Option<&Headers>) -> Headers {
+        let mut headers = Headers::new();
+
+        headers.insert("Authorization", format!("Bearer {}", self.config.api_key));
+        headers.insert("Content-Type", "application/json");
+
+        if let Some(custom) = custom_headers {
+            headers.merge_with(custom);
+        }
+
+        headers
+    }
+
+    async fn generate(&self, request: GenerateRequest) -> Result<GenerateResponse> {
+        let url = self.url("/v2/chat");
+        let cohere_req = request
+            .options
+            .provider_options
+            .apply(self.provider_id(), &to_cohere_request(&request, false))?;
+
+        let headers = self.build_headers(request.options.headers.as_ref());
+
+        let started = Instant::now();
+        let response = self
+            .client
+            .post(&url)
+            .headers(headers.to_reqwest_headers())
+            .json(&cohere_req)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(from_response(response).await);
+        }
+
+        let (cohere_resp, metadata): (CohereResponse, _) = read_json(
+            self.provider_id(),
+            response,
+            started,
+            request.options.include_raw_response,
+        )
+        .await?;
+        let mut response = from_cohere_response(cohere_resp).with_http_metadata(metadata);
+        // Cohere doesn't echo the model back
+        if let Some(metadata) = response.metadata.as_mut() {
+            metadata.model.get_or_insert(request.model);
+        }
+
+        Ok(response)
+    }
+
+    async fn stream(&self, request: GenerateRequest) -> Result<GenerateStream> {
+        let url = self.url("/v2/chat");
+        let cohere_req = request
+            .options
+            .provider_options
+            .apply(self.provider_id(), &to_cohere_request(&request, true))?;
+
+        let headers = self.build_headers(request.options.headers.as_ref());
+
+        let started = Instant::now();
+        let response = self
+            .client
+            .post(&url)
+            .headers(headers.to_reqwest_headers())
+            .json(&cohere_req)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(from_response(response).await);
+        }
+
+        let metadata = ResponseMetadata::from_http(self.provider_id(), &response, started);
+        Ok(create_stream(response)
+            .await?
+            .with_metadata(metadata, started))
+    }
+
+    async fn embed(&self, request: EmbedRequest) -> Result<EmbedResponse> {
+        let url = self.url("/v2/embed");
+        let headers = self.build_headers(request.headers.as_ref());
+
+        let mut result = EmbedResponse::default();
+        for batch in request.input.chunks(Self::MAX_EMBED_BATCH) {
+            let response = self
+                .client
+                .post(&url)
+                .headers(headers.to_reqwest_headers())
+                .json(&to_cohere_embed_request(
+                    &request,
+                    batch,
+                    &self.config.embed_input_type,
+                ))
+                .send()
+                .await?;
+
+            if !response.status().is_success() {
+                return Err(from_response(response).await);
+            }
+
+            let embed_resp: CohereEmbedResponse = response.json().await?;
+            result.extend(from_cohere_embed_response(embed_resp));
+        }
+
+        Ok(result)
+    }
+
+    async fn list_models(&self) -> Result<Vec<ModelInfo>> {
+        let url = self.url("/v1/models");
+        let headers = self.build_headers(None);
+
+        // Only chat models; the models endpoint is paginated by token
+        let mut models = Vec::new();
+        let mut page_token: Option<String> = None;
+        loop {
+            let mut query = vec![
+                ("endpoint", "chat".to_string()),
+                ("page_size", "1000".to_string()),
+            ];
+            if let Some(page_token) = page_token.take() {
+                query.push(("page_token", page_token));
+            }
+
+            let response = self
+                .client
+                .get(&url)
+                .headers(headers.to_reqwest_headers())
+                .query(&query)
+                .send()
+                .await?;
+
+            if !response.status().is_success() {
+                return Err(from_response(response).await);
+            }
+
+            let page: CohereModelList = response.json().await?;
+            models.extend(page.models.into_iter().map(from_cohere_model));
+
+            match page.next_page_token {
+                Some(token) if !token.is_empty() => page_token = Some(token),
+                _ => break,
+            }
+        }
+
+        Ok(models)
+    }
+}
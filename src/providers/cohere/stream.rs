@@ -0,0 +1,214 @@
+//! Cohere streaming support
+
+use super::convert::{from_cohere_citation, from_cohere_usage, parse_finish_reason};
+use super::error::from_stream_error;
+use super::types::CohereStreamEvent;
+use crate::error::{Error, Result};
+use crate::types::{GenerateStream, StreamEvent, ToolCallAccumulator};
+use eventsource_stream::{EventStreamError, Eventsource};
+use futures::StreamExt;
+use reqwest::Response;
+
+/// Create a stream from a Cohere SSE response
+pub async fn create_stream(response: Response) -> Result<GenerateStream> {
+    let stream = async_stream::stream! {
+        let mut event_source = response.bytes_stream().eventsource();
+        let mut state = StreamState::default();
+
+        while let Some(event) = event_source.next().await {
+            match event {
+                Ok(message) => {
+                    if message.data == "[DONE]" {
+                        break;
+                    }
+
+                    match serde_json::from_str::<CohereStreamEvent>(&message.data) {
+                        Ok(event) => match process_cohere_event(event, &mut state) {
+                            Ok(events) => {
+                                for event in events {
+                                    yield Ok(event);
+                                }
+                            }
+                            Err(e) => {
+                                yield Err(e);
+                                break;
+                            }
+                        },
+                        Err(e) => {
+                            yield Err(Error::stream_error(format!("Failed to parse event: {}", e)));
+                            break;
+                        }
+                    }
+                }
+                Err(EventStreamError::Transport(e)) => {
+                    yield Err(Error::HttpError(e));
+                    break;
+                }
+                Err(e) => {
+                    yield Err(Error::stream_error(format!("Stream error: {}", e)));
+                    break;
+                }
+            }
+        }
+    };
+
+    Ok(GenerateStream::new(Box::pin(stream)))
+}
+
+/// State carried across Cohere stream events
+#[derive(Default)]
+struct StreamState {
+    /// Response ID from `message-start`
+    id: String,
+    tool_calls: ToolCallAccumulator,
+}
+
+/// Process Cohere stream event and convert to unified StreamEvents
+fn process_cohere_event(
+    event: CohereStreamEvent,
+    state: &mut StreamState,
+) -> Result<Vec<StreamEvent>> {
+    let index = event.index.unwrap_or(0);
+    let delta = event.delta.unwrap_or_default();
+    let message = delta.message.unwrap_or_default();
+
+    let stream_event = match event.type_.as_str() {
+        "message-start" => {
+            state.id = event.id.unwrap_or_default();
+            Some(StreamEvent::start(&state.id))
+        }
+        "content-delta" => message.content.and_then(|content| match content.thinking {
+            Some(thinking) => Some(StreamEvent::reasoning_delta(&state.id, thinking)),
+            None => content
+                .text
+                .filter(|text| !text.is_empty())
+                .map(|text| StreamEvent::text_delta(&state.id, text)),
+        }),
+        "tool-plan-delta" => message
+            .tool_plan
+            .filter(|plan| !plan.is_empty())
+            .map(|plan| StreamEvent::reasoning_delta(&state.id, plan)),
+        "tool-call-start" => {
+            // The first fragment carries the ID and name, and possibly some arguments
+            let call = message.tool_calls.unwrap_or_default();
+            let mut events = vec![state.tool_calls.start(index, call.id, call.function.name)];
+            events.extend(state.tool_calls.delta(index, &call.function.arguments));
+            return Ok(events);
+        }
+        "tool-call-delta" => message
+            .tool_calls
+            .and_then(|call| state.tool_calls.delta(index, &call.function.arguments)),
+        "tool-call-end" => state.tool_calls.end(index),
+        "citation-start" => message
+            .citations
+            .map(|citation| StreamEvent::citation(&state.id, from_cohere_citation(citation))),
+        "message-end" => {
+            if delta.finish_reason.as_deref() == Some("ERROR") {
+                return Err(from_stream_error(
+                    delta
+                        .error
+                        .unwrap_or_else(|| "Cohere API error".to_string()),
+                ));
+            }
+
+            // Message finished - complete any open tool calls and emit final usage
+            let mut events = state.tool_calls.end_all();
+            events.push(StreamEvent::finish(
+                delta
+                    .usage
+                    .as_ref()
+                    .map(from_cohere_usage)
+                    .unwrap_or_default(),
+                parse_finish_reason(delta.finish_reason.as_deref()),
+            ));
+            return Ok(events);
+        }
+        _ => None,
+    };
+
+    Ok(stream_event.into_iter().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::FinishReason;
+    use serde_json::json;
+
+    fn process(events: &[serde_json::Value]) -> Result<Vec<StreamEvent>> {
+        let mut state = StreamState::default();
+        let mut result = Vec::new();
+        for event in events {
+            let event: CohereStreamEvent = serde_json::from_value(event.clone()).unwrap();
+            result.extend(process_cohere_event(event, &mut state)?);
+        }
+        Ok(result)
+    }
+
+    #[test]
+    fn test_process_tool_call_events() {
+        let result = process(&[
+            json!({"type": "message-start", "id": "msg_1", "delta": {"message": {"role": "assistant"}}}),
+            json!({"type": "tool-plan-delta", "delta": {"message": {"tool_plan": "Look it up"}}}),
+            json!({"type": "tool-call-start", "index": 0, "delta": {"message": {"tool_calls": {
+                "id": "call_1", "type": "function",
+                "function": {"name": "search", "arguments": ""}
+            }}}}),
+            json!({"type": "tool-call-delta", "index": 0, "delta": {"message": {"tool_calls": {
+                "function": {"arguments": "{\"q\":\"Paris\"}"}
+            }}}}),
+            json!({"type": "tool-call-end", "index": 0}),
+            json!({"type": "message-end", "delta": {
+                "finish_reason": "TOOL_CALL",
+                "usage": {"tokens": {"input_tokens": 12, "output_tokens": 3}}
+            }}),
+        ])
+        .unwrap();
+
+        assert_eq!(result.len(), 6);
+        assert!(matches!(&result[0], StreamEvent::Start { id, .. } if id == "msg_1"));
+        assert!(
+            matches!(&result[1], StreamEvent::ReasoningDelta { delta, .. } if delta == "Look it up")
+        );
+        assert!(
+            matches!(&result[2], StreamEvent::ToolCallStart { id, name } if id == "call_1" && name == "search")
+        );
+        assert!(matches!(
+            &result[4],
+            StreamEvent::ToolCallEnd { id, arguments, .. }
+                if id == "call_1" && arguments == &json!({"q": "Paris"})
+        ));
+        assert!(matches!(
+            &result[5],
+            StreamEvent::Finish { usage, reason: FinishReason::ToolCalls } if usage.total_tokens == 15
+        ));
+    }
+
+    #[test]
+    fn test_process_citation_event() {
+        let result = process(&[json!({
+            "type": "citation-start",
+            "index": 0,
+            "delta": {"message": {"citations": {
+                "start": 0, "end": 5, "text": "Paris",
+                "sources": [{"type": "document", "id": "doc_1"}]
+            }}}
+        })])
+        .unwrap();
+
+        assert!(matches!(
+            &result[0],
+            StreamEvent::Citation { citation, .. } if citation.sources == vec!["doc_1".to_string()]
+        ));
+    }
+
+    #[test]
+    fn test_process_error_finish() {
+        let result = process(&[json!({
+            "type": "message-end",
+            "delta": {"finish_reason": "ERROR", "error": "internal failure"}
+        })]);
+
+        assert!(matches!(result, Err(Error::Api(e)) if e.message == "internal failure"));
+    }
+}
@@ -0,0 +1,298 @@
+//! Cohere-specific types
+
+use crate::http::HttpConfig;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Configuration for Cohere provider
+#[derive(Debug, Clone)]
+pub struct CohereConfig {
+    /// API key
+    pub api_key: String,
+    /// Base URL, without the API version (default: https://api.cohere.com)
+    pub base_url: String,
+    /// Input type sent with embedding requests (default: search_document)
+    pub embed_input_type: String,
+    /// HTTP client settings (timeouts)
+    pub http: HttpConfig,
+}
+
+impl CohereConfig {
+    /// Create new config with API key
+    pub fn new(api_key: impl Into<String>) -> Self {
+        Self {
+            api_key: api_key.into(),
+            base_url: "https://api.cohere.com".to_string(),
+            embed_input_type: "search_document".to_string(),
+            http: HttpConfig::default(),
+        }
+    }
+
+    /// Set base URL
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into();
+        self
+    }
+
+    /// Set HTTP client settings
+    pub fn with_http_config(mut self, http: HttpConfig) -> Self {
+        self.http = http;
+        self
+    }
+
+    /// Set embedding input type (e.g., "search_query", "classification")
+    pub fn with_embed_input_type(mut self, input_type: impl Into<String>) -> Self {
+        self.embed_input_type = input_type.into();
+        self
+    }
+}
+
+impl Default for CohereConfig {
+    fn default() -> Self {
+        Self::new(std::env::var("COHERE_API_KEY").unwrap_or_else(|_| String::new()))
+    }
+}
+
+/// Cohere v2 chat request
+#[derive(Debug, Serialize)]
+pub struct CohereRequest {
+    pub model: String,
+    pub messages: Vec<CohereMessage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub documents: Option<Vec<Value>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tools: Option<Vec<Value>>,
+    /// "REQUIRED" or "NONE"; unset lets the model decide
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_choice: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub p: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub k: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stop_sequences: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub frequency_penalty: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub presence_penalty: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response_format: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thinking: Option<CohereThinking>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stream: Option<bool>,
+}
+
+/// Cohere reasoning settings
+#[derive(Debug, Serialize)]
+pub struct CohereThinking {
+    #[serde(rename = "type")]
+    pub type_: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token_budget: Option<u32>,
+}
+
+/// Cohere chat message
+#[derive(Debug, Serialize)]
+pub struct CohereMessage {
+    pub role: String,
+    /// Plain string, or typed content items
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_plan: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_calls: Option<Vec<CohereToolCall>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_call_id: Option<String>,
+}
+
+/// Cohere tool call (complete, or a streamed fragment)
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct CohereToolCall {
+    #[serde(default)]
+    pub id: String,
+    #[serde(rename = "type", default = "function_type")]
+    pub type_: String,
+    #[serde(default)]
+    pub function: CohereFunction,
+}
+
+fn function_type() -> String {
+    "function".to_string()
+}
+
+/// Cohere function call
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct CohereFunction {
+    #[serde(default)]
+    pub name: String,
+    /// JSON string
+    #[serde(default)]
+    pub arguments: String,
+}
+
+/// Cohere v2 chat response
+#[derive(Debug, Deserialize)]
+pub struct CohereResponse {
+    pub id: String,
+    #[serde(default)]
+    pub finish_reason: Option<String>,
+    pub message: CohereResponseMessage,
+    #[serde(default)]
+    pub usage: Option<CohereUsage>,
+}
+
+/// Assistant message in a Cohere response
+#[derive(Debug, Deserialize)]
+pub struct CohereResponseMessage {
+    #[serde(default)]
+    pub content: Vec<CohereContent>,
+    #[serde(default)]
+    pub tool_plan: Option<String>,
+    #[serde(default)]
+    pub tool_calls: Vec<CohereToolCall>,
+    #[serde(default)]
+    pub citations: Vec<CohereCitation>,
+}
+
+/// Cohere response content item ("text" or "thinking")
+#[derive(Debug, Default, Deserialize)]
+pub struct CohereContent {
+    #[serde(rename = "type", default)]
+    pub type_: String,
+    #[serde(default)]
+    pub text: Option<String>,
+    #[serde(default)]
+    pub thinking: Option<String>,
+}
+
+/// Span of the response backed by documents or tool results
+#[derive(Debug, Deserialize)]
+pub struct CohereCitation {
+    pub start: usize,
+    pub end: usize,
+    #[serde(default)]
+    pub text: String,
+    #[serde(default)]
+    pub sources: Vec<CohereSource>,
+}
+
+/// Document or tool result cited by a citation
+#[derive(Debug, Deserialize)]
+pub struct CohereSource {
+    #[serde(default)]
+    pub id: Option<String>,
+}
+
+/// Cohere usage statistics
+#[derive(Debug, Default, Deserialize)]
+pub struct CohereUsage {
+    #[serde(default)]
+    pub billed_units: Option<CohereTokens>,
+    #[serde(default)]
+    pub tokens: Option<CohereTokens>,
+}
+
+/// Cohere token counts (sent as numbers that may be fractional)
+#[derive(Debug, Default, Deserialize)]
+pub struct CohereTokens {
+    #[serde(default)]
+    pub input_tokens: f64,
+    #[serde(default)]
+    pub output_tokens: f64,
+}
+
+/// Cohere streaming event
+#[derive(Debug, Deserialize)]
+pub struct CohereStreamEvent {
+    #[serde(rename = "type")]
+    pub type_: String,
+    #[serde(default)]
+    pub id: Option<String>,
+    #[serde(default)]
+    pub index: Option<u32>,
+    #[serde(default)]
+    pub delta: Option<CohereStreamDelta>,
+}
+
+/// Payload of a Cohere streaming event
+#[derive(Debug, Default, Deserialize)]
+pub struct CohereStreamDelta {
+    #[serde(default)]
+    pub message: Option<CohereDeltaMessage>,
+    #[serde(default)]
+    pub finish_reason: Option<String>,
+    #[serde(default)]
+    pub usage: Option<CohereUsage>,
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+/// Message fragment of a Cohere streaming event
+#[derive(Debug, Default, Deserialize)]
+pub struct CohereDeltaMessage {
+    #[serde(default)]
+    pub content: Option<CohereContent>,
+    #[serde(default)]
+    pub tool_plan: Option<String>,
+    #[serde(default)]
+    pub tool_calls: Option<CohereToolCall>,
+    #[serde(default)]
+    pub citations: Option<CohereCitation>,
+}
+
+/// Cohere v2 embed request
+#[derive(Debug, Serialize)]
+pub struct CohereEmbedRequest {
+    pub model: String,
+    pub texts: Vec<String>,
+    pub input_type: String,
+    pub embedding_types: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output_dimension: Option<u32>,
+}
+
+/// Cohere v2 embed response
+#[derive(Debug, Deserialize)]
+pub struct CohereEmbedResponse {
+    pub embeddings: CohereEmbeddings,
+    #[serde(default)]
+    pub meta: Option<CohereMeta>,
+}
+
+/// Embeddings by type; only floats are requested
+#[derive(Debug, Deserialize)]
+pub struct CohereEmbeddings {
+    #[serde(default)]
+    pub float: Vec<Vec<f32>>,
+}
+
+/// Cohere response metadata
+#[derive(Debug, Deserialize)]
+pub struct CohereMeta {
+    #[serde(default)]
+    pub billed_units: Option<CohereTokens>,
+}
+
+/// Cohere model list response
+#[derive(Debug, Deserialize)]
+pub struct CohereModelList {
+    pub models: Vec<CohereModel>,
+    #[serde(default)]
+    pub next_page_token: Option<String>,
+}
+
+/// Cohere model
+#[derive(Debug, Deserialize)]
+pub struct CohereModel {
+    pub name: String,
+    #[serde(default)]
+    pub context_length: Option<f64>,
+    #[serde(default)]
+    pub features: Vec<String>,
+}
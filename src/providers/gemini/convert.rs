@@ -1,14 +1,17 @@
 //! Conversion between unified types and Gemini types
 
 use super::types::{
-    GeminiContent, GeminiGenerationConfig, GeminiInlineData, GeminiPart, GeminiRequest,
-    GeminiResponse,
+    GeminiBatchEmbedRequest, GeminiContent, GeminiEmbedRequest, GeminiGenerationConfig,
+    GeminiInlineData, GeminiModel, GeminiPart, GeminiRequest, GeminiResponse, GeminiThinkingConfig,
+    GeminiUsageMetadata,
 };
 use crate::error::{Error, Result};
 use crate::types::{
-    ContentPart, FinishReason, GenerateRequest, GenerateResponse, Message, ResponseContent, Role,
-    Usage,
+    ContentPart, EmbedRequest, FinishReason, GenerateRequest, GenerateResponse, Message,
+    MessageContent, ModelInfo, ResponseContent, ResponseFormat, ResponseMetadata, Role, Usage,
 };
+use serde_json::Value;
+use std::collections::HashMap;
 
 /// Convert unified request to Gemini request
 pub fn to_gemini_request(req: &GenerateRequest) -> Result<GeminiRequest> {
@@ -17,13 +20,33 @@ pub fn to_gemini_request(req: &GenerateRequest) -> Result<GeminiRequest> {
     // Gemini doesn't have separate system messages - prepend to first user message
     let contents = convert_messages(&req.messages)?;
 
+    let (response_mime_type, response_schema) = match &req.options.response_format {
+        None | Some(ResponseFormat::Text) => (None, None),
+        Some(ResponseFormat::JsonObject) => (Some("application/json".to_string()), None),
+        Some(ResponseFormat::JsonSchema { schema, .. }) => (
+            Some("application/json".to_string()),
+            Some(to_gemini_schema(schema)?),
+        ),
+    };
+
     let generation_config = Some(GeminiGenerationConfig {
         temperature: req.options.temperature,
         top_p: req.options.top_p,
-        top_k: None, // Gemini-specific, not in unified options
+        top_k: req.options.top_k,
         max_output_tokens: req.options.max_tokens,
         stop_sequences: req.options.stop_sequences.clone(),
-        response_mime_type: None,
+        frequency_penalty: req.options.frequency_penalty,
+        presence_penalty: req.options.presence_penalty,
+        response_mime_type,
+        response_schema,
+        thinking_config: req
+            .options
+            .reasoning
+            .as_ref()
+            .map(|reasoning| GeminiThinkingConfig {
+                thinking_budget: reasoning.resolved_budget(),
+                include_thoughts: true,
+            }),
     });
 
     // Convert tools to Gemini format
@@ -56,15 +79,100 @@ pub fn to_gemini_request(req: &GenerateRequest) -> Result<GeminiRequest> {
     Ok(GeminiRequest {
         contents,
         generation_config,
-        safety_settings: None, // Could be added to options later
+        safety_settings: None, // Set through provider options
         tools,
         tool_config,
     })
 }
 
+/// Convert a JSON Schema to Gemini's OpenAPI-style schema
+///
+/// Gemini rejects JSON Schema keywords outside its subset, so drop the
+/// common ones that only constrain validation. It doesn't resolve `$ref`
+/// either, so local references (e.g. to `$defs`) are inlined; recursive and
+/// external references can't be, and are rejected.
+fn to_gemini_schema(schema: &Value) -> Result<Value> {
+    inline_schema(schema, schema, &mut Vec::new())
+}
+
+/// Convert `schema`, inlining references into `root`; `resolving` holds the
+/// references being inlined, to detect recursion
+fn inline_schema<'a>(
+    schema: &'a Value,
+    root: &'a Value,
+    resolving: &mut Vec<&'a str>,
+) -> Result<Value> {
+    const UNSUPPORTED: [&str; 6] = [
+        "$schema",
+        "$id",
+        "additionalProperties",
+        "$ref",
+        "$defs",
+        "definitions",
+    ];
+
+    match schema {
+        Value::Object(map) => {
+            let mut result = serde_json::Map::new();
+
+            if let Some(reference) = map.get("$ref").and_then(Value::as_str) {
+                if resolving.contains(&reference) {
+                    return Err(Error::provider_error(format!(
+                        "Gemini does not support recursive schemas ({})",
+                        reference
+                    )));
+                }
+                let target = reference
+                    .strip_prefix('#')
+                    .and_then(|pointer| root.pointer(pointer))
+                    .ok_or_else(|| {
+                        Error::provider_error(format!(
+                            "Cannot resolve schema reference {} for Gemini",
+                            reference
+                        ))
+                    })?;
+
+                resolving.push(reference);
+                if let Value::Object(inlined) = inline_schema(target, root, resolving)? {
+                    result = inlined;
+                }
+                resolving.pop();
+            }
+
+            // Keywords next to a reference (e.g. a description) refine the inlined schema
+            for (key, value) in map {
+                if UNSUPPORTED.contains(&key.as_str()) {
+                    continue;
+                }
+                let value = match (key.as_str(), value) {
+                    // Keys of this map are names, not keywords
+                    ("properties", Value::Object(schemas)) => Value::Object(
+                        schemas
+                            .iter()
+                            .map(|(name, schema)| {
+                                Ok((name.clone(), inline_schema(schema, root, resolving)?))
+                            })
+                            .collect::<Result<_>>()?,
+                    ),
+                    _ => inline_schema(value, root, resolving)?,
+                };
+                result.insert(key.clone(), value);
+            }
+            Ok(Value::Object(result))
+        }
+        Value::Array(items) => Ok(Value::Array(
+            items
+                .iter()
+                .map(|item| inline_schema(item, root, resolving))
+                .collect::<Result<_>>()?,
+        )),
+        other => Ok(other.clone()),
+    }
+}
+
 /// Convert messages to Gemini format
 fn convert_messages(messages: &[Message]) -> Result<Vec<GeminiContent>> {
-    let mut result = Vec::new();
+    let mut result: Vec<GeminiContent> = Vec::new();
     let mut system_text = String::new();
 
     // Collect system messages
@@ -79,6 +187,10 @@ fn convert_messages(messages: &[Message]) -> Result<Vec<GeminiContent>> {
         }
     }
 
+    // Gemini matches function responses to calls by name, so remember the
+    // function name behind every tool call ID seen so far
+    let mut tool_names: HashMap<&str, &str> = HashMap::new();
+
     // Convert non-system messages
     let mut first_user_message = true;
     for msg in messages {
@@ -86,7 +198,15 @@ fn convert_messages(messages: &[Message]) -> Result<Vec<GeminiContent>> {
             continue; // Already handled
         }
 
-        let mut content = to_gemini_content(msg)?;
+        let mut content = to_gemini_content(msg, &tool_names)?;
+
+        if let MessageContent::Parts(parts) = &msg.content {
+            for part in parts {
+                if let ContentPart::ToolCall { id, name, .. } = part {
+                    tool_names.insert(id, name);
+                }
+            }
+        }
 
         // Prepend system message to first user message
         if first_user_message && content.role == "user" && !system_text.is_empty() {
@@ -97,11 +217,23 @@ fn convert_messages(messages: &[Message]) -> Result<Vec<GeminiContent>> {
                     inline_data: None,
                     function_call: None,
                     function_response: None,
+                    thought: None,
+                    thought_signature: None,
                 },
             );
             first_user_message = false;
         }
 
+        // Responses to parallel function calls must share a single turn
+        if msg.role == Role::Tool {
+            if let Some(last) = result.last_mut() {
+                if last.parts.iter().all(|p| p.function_response.is_some()) {
+                    last.parts.append(&mut content.parts);
+                    continue;
+                }
+            }
+        }
+
         result.push(content);
     }
 
@@ -109,51 +241,56 @@ fn convert_messages(messages: &[Message]) -> Result<Vec<GeminiContent>> {
 }
 
 /// Convert unified message to Gemini content
-fn to_gemini_content(msg: &Message) -> Result<GeminiContent> {
+///
+/// `tool_names` maps earlier tool call IDs to their function names.
+fn to_gemini_content(msg: &Message, tool_names: &HashMap<&str, &str>) -> Result<GeminiContent> {
     let role = match msg.role {
-        Role::User | Role::System => "user",
+        // Function responses are sent back in a user turn
+        Role::User | Role::System | Role::Tool => "user",
         Role::Assistant => "model", // Gemini uses "model" instead of "assistant"
-        Role::Tool => {
-            return Err(Error::invalid_response(
-                "Tool messages not yet supported for Gemini",
-            ))
-        }
     };
 
     let content_parts = msg.parts();
     let parts: Vec<GeminiPart> = content_parts
         .iter()
         .map(|part| match part {
-            ContentPart::Text { text } => GeminiPart {
+            ContentPart::Text { text, .. } => Ok(GeminiPart {
                 text: Some(text.clone()),
                 inline_data: None,
                 function_call: None,
                 function_response: None,
-            },
-            ContentPart::Image { url, detail: _ } => {
+                thought: None,
+                thought_signature: None,
+            }),
+            ContentPart::Image { url, .. } => {
                 // Parse image data
                 match parse_image_data(url) {
-                    Ok(inline_data) => GeminiPart {
+                    Ok(inline_data) => Ok(GeminiPart {
                         text: None,
                         inline_data: Some(inline_data),
                         function_call: None,
                         function_response: None,
-                    },
-                    Err(_) => GeminiPart {
+                        thought: None,
+                        thought_signature: None,
+                    }),
+                    Err(_) => Ok(GeminiPart {
                         text: Some(format!("[Image: {}]", url)),
                         inline_data: None,
                         function_call: None,
                         function_response: None,
-                    },
+                        thought: None,
+                        thought_signature: None,
+                    }),
                 }
             }
             ContentPart::ToolCall {
-                id: _,
                 name,
                 arguments,
+                signature,
+                ..
             } => {
                 // Gemini function call
-                GeminiPart {
+                Ok(GeminiPart {
                     text: None,
                     inline_data: None,
                     function_call: Some(super::types::GeminiFunctionCall {
@@ -161,39 +298,58 @@ fn to_gemini_content(msg: &Message) -> Result<GeminiContent> {
                         args: arguments.clone(),
                     }),
                     function_response: None,
-                }
+                    thought: None,
+                    thought_signature: signature.clone(),
+                })
             }
             ContentPart::ToolResult {
-                tool_call_id: _,
+                tool_call_id,
                 content,
+                is_error,
+                ..
             } => {
-                // Gemini function response
-                // Note: Gemini doesn't use call IDs, just function names
-                // We'll extract the function name from the content if possible
-                let (name, response) = if let Some(obj) = content.as_object() {
-                    let name = obj
-                        .get("name")
-                        .and_then(|v| v.as_str())
-                        .unwrap_or("unknown")
-                        .to_string();
-                    let response = obj.get("result").cloned().unwrap_or(content.clone());
-                    (name, response)
+                // Gemini doesn't use call IDs, so answer with the name of the matching call
+                let name = tool_names.get(tool_call_id.as_str()).ok_or_else(|| {
+                    Error::invalid_response(format!(
+                        "No tool call found for tool result {}",
+                        tool_call_id
+                    ))
+                })?;
+
+                // The response must be a JSON object
+                let response = if *is_error {
+                    serde_json::json!({ "error": content })
+                } else if content.is_object() {
+                    content.clone()
                 } else {
-                    ("unknown".to_string(), content.clone())
+                    serde_json::json!({ "result": content })
                 };
 
-                GeminiPart {
+                Ok(GeminiPart {
                     text: None,
                     inline_data: None,
                     function_call: None,
                     function_response: Some(super::types::GeminiFunctionResponse {
-                        name,
+                        name: name.to_string(),
                         response,
                     }),
-                }
+                    thought: None,
+                    thought_signature: None,
+                })
             }
+            ContentPart::Reasoning {
+                text, signature, ..
+            } => Ok(GeminiPart {
+                text: Some(text.clone()),
+                inline_data: None,
+                function_call: None,
+                function_response: None,
+                // Signature-only blocks come from answer parts, not thoughts
+                thought: (!text.is_empty()).then_some(true),
+                thought_signature: signature.clone(),
+            }),
         })
-        .collect();
+        .collect::<Result<_>>()?;
 
     Ok(GeminiContent {
         role: role.to_string(),
@@ -240,15 +396,32 @@ pub fn from_gemini_response(resp: GeminiResponse) -> Result<GenerateResponse> {
 
     for part in &candidate.content.parts {
         if let Some(text) = &part.text {
-            content.push(ResponseContent::Text { text: text.clone() });
+            if part.thought == Some(true) {
+                content.push(ResponseContent::Reasoning {
+                    text: text.clone(),
+                    signature: part.thought_signature.clone(),
+                    data: None,
+                });
+            } else {
+                content.push(ResponseContent::Text { text: text.clone() });
+                // A signature on an answer part is kept as a signature-only reasoning block
+                if let Some(signature) = &part.thought_signature {
+                    content.push(ResponseContent::Reasoning {
+                        text: String::new(),
+                        signature: Some(signature.clone()),
+                        data: None,
+                    });
+                }
+            }
         }
 
         if let Some(function_call) = &part.function_call {
             // Gemini doesn't provide IDs, so we generate one
             content.push(ResponseContent::ToolCall(ToolCall {
-                id: format!("call_{}", uuid::Uuid::new_v4()),
+                id: new_tool_call_id(),
                 name: function_call.name.clone(),
                 arguments: function_call.args.clone(),
+                signature: part.thought_signature.clone(),
             }));
         }
     }
@@ -260,11 +433,7 @@ pub fn from_gemini_response(resp: GeminiResponse) -> Result<GenerateResponse> {
     let usage = resp
         .usage_metadata
         .as_ref()
-        .map(|u| Usage {
-            prompt_tokens: u.prompt_token_count.unwrap_or(0),
-            completion_tokens: u.candidates_token_count.unwrap_or(0),
-            total_tokens: u.total_token_count.unwrap_or(0),
-        })
+        .map(from_gemini_usage)
         .unwrap_or_default();
 
     // Determine finish reason - function_call should be ToolCalls
@@ -281,10 +450,40 @@ pub fn from_gemini_response(resp: GeminiResponse) -> Result<GenerateResponse> {
         content,
         usage,
         finish_reason,
-        metadata: None,
+        citations: Vec::new(),
+        metadata: Some(ResponseMetadata {
+            id: resp.response_id,
+            model: resp.model_version,
+            ..Default::default()
+        }),
     })
 }
 
+/// Convert Gemini usage metadata to unified usage
+///
+/// Gemini reports thought tokens apart from candidate tokens; they are
+/// counted in the completion tokens here.
+pub fn from_gemini_usage(usage: &GeminiUsageMetadata) -> Usage {
+    let reasoning_tokens = usage.thoughts_token_count.unwrap_or(0);
+
+    Usage {
+        prompt_tokens: usage.prompt_token_count.unwrap_or(0),
+        completion_tokens: usage.candidates_token_count.unwrap_or(0) + reasoning_tokens,
+        total_tokens: usage.total_token_count.unwrap_or(0),
+        cached_tokens: usage.cached_content_token_count.unwrap_or(0),
+        cache_creation_tokens: 0,
+        reasoning_tokens,
+    }
+}
+
+/// Generate an ID for a function call
+///
+/// Gemini matches function responses by name, so the ID only has to be unique
+/// within the conversation; callers pass it back unchanged in `ToolResult`s.
+pub(super) fn new_tool_call_id() -> String {
+    format!("call_{}", uuid::Uuid::new_v4())
+}
+
 /// Parse Gemini finish reason to unified finish reason
 pub(super) fn parse_finish_reason(reason: &Option<String>) -> Option<FinishReason> {
     reason.as_ref().and_then(|r| match r.as_str() {
@@ -295,9 +494,312 @@ pub(super) fn parse_finish_reason(reason: &Option<String>) -> Option<FinishReaso
     })
 }
 
+/// Convert unified embedding request for a single text to Gemini request
+pub fn to_gemini_embed_request(req: &EmbedRequest, text: &str) -> GeminiEmbedRequest {
+    GeminiEmbedRequest {
+        model: Some(format!("models/{}", req.model)),
+        content: GeminiContent {
+            role: "user".to_string(),
+            parts: vec![GeminiPart {
+                text: Some(text.to_string()),
+                inline_data: None,
+                function_call: None,
+                function_response: None,
+                thought: None,
+                thought_signature: None,
+            }],
+        },
+        output_dimensionality: req.dimensions,
+    }
+}
+
+/// Convert a batch of a unified embedding request to Gemini batch request
+pub fn to_gemini_batch_embed_request(
+    req: &EmbedRequest,
+    input: &[String],
+) -> GeminiBatchEmbedRequest {
+    GeminiBatchEmbedRequest {
+        requests: input
+            .iter()
+            .map(|text| to_gemini_embed_request(req, text))
+            .collect(),
+    }
+}
+
+/// Convert Gemini model to unified model info
+pub fn from_gemini_model(model: GeminiModel) -> ModelInfo {
+    let id = model
+        .name
+        .strip_prefix("models/")
+        .unwrap_or(&model.name)
+        .to_string();
+
+    ModelInfo {
+        display_name: model.display_name,
+        context_window: model.input_token_limit,
+        max_output_tokens: model.output_token_limit,
+        supports_reasoning: model.thinking,
+        ..ModelInfo::new("google", id)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_tool_results_map_to_function_names() {
+        let messages = vec![
+            Message::new(Role::User, "Weather in Paris and time in Rome?"),
+            Message::new(
+                Role::Assistant,
+                vec![
+                    ContentPart::tool_call("call_1", "get_weather", json!({"city": "Paris"})),
+                    ContentPart::tool_call("call_2", "get_time", json!({"city": "Rome"})),
+                ],
+            ),
+            Message::new(
+                Role::Tool,
+                vec![ContentPart::tool_result("call_2", json!("10:00"))],
+            ),
+            Message::new(
+                Role::Tool,
+                vec![ContentPart::tool_error("call_1", json!("unavailable"))],
+            ),
+        ];
+
+        let contents = convert_messages(&messages).unwrap();
+        assert_eq!(contents.len(), 3);
+        assert_eq!(contents[2].role, "user");
+
+        let responses: Vec<_> = contents[2]
+            .parts
+            .iter()
+            .map(|p| p.function_response.as_ref().unwrap())
+            .collect();
+        assert_eq!(responses[0].name, "get_time");
+        assert_eq!(responses[0].response, json!({"result": "10:00"}));
+        assert_eq!(responses[1].name, "get_weather");
+        assert_eq!(responses[1].response, json!({"error": "unavailable"}));
+    }
+
+    #[test]
+    fn test_tool_result_without_call() {
+        let messages = vec![Message::new(
+            Role::Tool,
+            vec![ContentPart::tool_result(
+                "call_missing",
+                json!({"ok": true}),
+            )],
+        )];
+
+        assert!(convert_messages(&messages).is_err());
+    }
+
+    #[test]
+    fn test_generated_tool_call_ids_round_trip() {
+        let resp: GeminiResponse = serde_json::from_value(json!({
+            "candidates": [{
+                "content": {
+                    "role": "model",
+                    "parts": [{"functionCall": {"name": "get_weather", "args": {"city": "Paris"}}}]
+                },
+                "finishReason": "STOP"
+            }]
+        }))
+        .unwrap();
+
+        let response = from_gemini_response(resp).unwrap();
+        let call = response.tool_calls()[0].clone();
+        assert!(call.id.starts_with("call_"));
+
+        let messages = vec![
+            Message::new(Role::User, "Weather in Paris?"),
+            Message::new(
+                Role::Assistant,
+                vec![ContentPart::tool_call(&call.id, &call.name, call.arguments)],
+            ),
+            Message::new(
+                Role::Tool,
+                vec![ContentPart::tool_result(&call.id, json!({"temp": 18}))],
+            ),
+        ];
+
+        let contents = convert_messages(&messages).unwrap();
+        let response = contents[2].parts[0].function_response.as_ref().unwrap();
+        assert_eq!(response.name, "get_weather");
+        assert_eq!(response.response, json!({"temp": 18}));
+    }
+
+    #[test]
+    fn test_thoughts_round_trip() {
+        let resp: GeminiResponse = serde_json::from_value(json!({
+            "candidates": [{
+                "content": {
+                    "role": "model",
+                    "parts": [
+                        {"text": "Comparing the options.", "thought": true, "thoughtSignature": "sig_1"},
+                        {"text": "Option B."}
+                    ]
+                },
+                "finishReason": "STOP"
+            }]
+        }))
+        .unwrap();
+
+        let response = from_gemini_response(resp).unwrap();
+        assert_eq!(
+            response.reasoning().as_deref(),
+            Some("Comparing the options.")
+        );
+        assert_eq!(response.text(), "Option B.");
+
+        let mut req = GenerateRequest::new(
+            "gemini-2.5-flash",
+            vec![
+                Message::new(Role::User, "Which option?"),
+                response.to_message(),
+                Message::new(Role::User, "Why?"),
+            ],
+        );
+        req.options = crate::types::GenerateOptions::new()
+            .reasoning_effort(crate::types::ReasoningEffort::Low);
+
+        let gemini_req = to_gemini_request(&req).unwrap();
+        let thought = &gemini_req.contents[1].parts[0];
+        assert_eq!(thought.thought, Some(true));
+        assert_eq!(thought.thought_signature.as_deref(), Some("sig_1"));
+
+        let thinking = gemini_req
+            .generation_config
+            .and_then(|c| c.thinking_config)
+            .unwrap();
+        assert_eq!(thinking.thinking_budget, 1024);
+        assert!(thinking.include_thoughts);
+    }
+
+    #[test]
+    fn test_thought_signatures_on_calls_and_text_round_trip() {
+        let resp: GeminiResponse = serde_json::from_value(json!({
+            "candidates": [{
+                "content": {
+                    "role": "model",
+                    "parts": [
+                        {"functionCall": {"name": "get_weather", "args": {"city": "Paris"}}, "thoughtSignature": "sig_call"},
+                        {"text": "Checking.", "thoughtSignature": "sig_text"}
+                    ]
+                },
+                "finishReason": "STOP"
+            }]
+        }))
+        .unwrap();
+
+        let response = from_gemini_response(resp).unwrap();
+        assert_eq!(
+            response.tool_calls()[0].signature.as_deref(),
+            Some("sig_call")
+        );
+        assert_eq!(response.text(), "Checking.");
+
+        let req = GenerateRequest::new(
+            "gemini-2.5-flash",
+            vec![
+                Message::new(Role::User, "Weather in Paris?"),
+                response.to_message(),
+            ],
+        );
+        let gemini_req = to_gemini_request(&req).unwrap();
+        let parts = &gemini_req.contents[1].parts;
+        assert!(parts[0].function_call.is_some());
+        assert_eq!(parts[0].thought_signature.as_deref(), Some("sig_call"));
+        assert_eq!(parts[1].text.as_deref(), Some("Checking."));
+        // The answer's signature follows it in a part of its own, not marked as a thought
+        assert_eq!(parts[2].text.as_deref(), Some(""));
+        assert_eq!(parts[2].thought, None);
+        assert_eq!(parts[2].thought_signature.as_deref(), Some("sig_text"));
+    }
+
+    #[test]
+    fn test_to_gemini_schema_drops_unsupported_keywords() {
+        let schema = json!({
+            "$schema": "http://json-schema.org/draft-07/schema#",
+            "type": "object",
+            "properties": {
+                "additionalProperties": {"type": "boolean"},
+                "tags": {
+                    "type": "array",
+                    "items": {"type": "object", "additionalProperties": false}
+                }
+            },
+            "additionalProperties": false
+        });
+
+        assert_eq!(
+            to_gemini_schema(&schema).unwrap(),
+            json!({
+                "type": "object",
+                "properties": {
+                    "additionalProperties": {"type": "boolean"},
+                    "tags": {"type": "array", "items": {"type": "object"}}
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn test_to_gemini_schema_inlines_refs() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "home": {"$ref": "#/$defs/Address", "description": "Home address"},
+                "offices": {"type": "array", "items": {"$ref": "#/definitions/Address"}}
+            },
+            "$defs": {
+                "Address": {
+                    "type": "object",
+                    "properties": {"city": {"type": "string"}}
+                }
+            },
+            "definitions": {
+                "Address": {"$ref": "#/$defs/Address"}
+            }
+        });
+
+        let address = json!({"type": "object", "properties": {"city": {"type": "string"}}});
+        let mut home = address.clone();
+        home["description"] = json!("Home address");
+        assert_eq!(
+            to_gemini_schema(&schema).unwrap(),
+            json!({
+                "type": "object",
+                "properties": {
+                    "home": home,
+                    "offices": {"type": "array", "items": address}
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn test_to_gemini_schema_rejects_recursive_refs() {
+        let schema = json!({
+            "$ref": "#/$defs/Node",
+            "$defs": {
+                "Node": {
+                    "type": "object",
+                    "properties": {"children": {"type": "array", "items": {"$ref": "#/$defs/Node"}}}
+                }
+            }
+        });
+        assert!(matches!(
+            to_gemini_schema(&schema),
+            Err(Error::ProviderError(message)) if message.contains("recursive")
+        ));
+
+        let external = json!({"$ref": "https://example.com/schema.json"});
+        assert!(to_gemini_schema(&external).is_err());
+    }
 
     #[test]
     fn test_parse_image_data() {
@@ -0,0 +1,147 @@
+//! Gemini error response parsing
+
+use crate::error::{ApiError, Error};
+use reqwest::Response;
+use serde::Deserialize;
+use std::time::Duration;
+
+/// Gemini (Google API) error response envelope
+#[derive(Debug, Deserialize)]
+pub struct GeminiErrorResponse {
+    pub error: GeminiErrorBody,
+}
+
+/// Gemini error details
+#[derive(Debug, Deserialize)]
+pub struct GeminiErrorBody {
+    pub message: String,
+    /// Canonical status (e.g., "RESOURCE_EXHAUSTED", "INVALID_ARGUMENT")
+    pub status: Option<String>,
+    #[serde(default)]
+    pub details: Vec<serde_json::Value>,
+}
+
+/// Convert a non-success Gemini response into a classified error
+pub(crate) async fn from_response(response: Response) -> Error {
+    let error = ApiError::from_response("google", response).await;
+    classify(parse_envelope(error))
+}
+
+/// Refine an API error from the Gemini error envelope in its message, if present
+fn parse_envelope(mut error: ApiError) -> ApiError {
+    let Ok(envelope) = serde_json::from_str::<GeminiErrorResponse>(&error.message) else {
+        return error;
+    };
+
+    // Structured details carry the machine-readable reason and retry delay
+    for detail in &envelope.error.details {
+        if let Some(reason) = detail.get("reason").and_then(|r| r.as_str()) {
+            error.code = Some(reason.to_string());
+        }
+        if error.retry_after.is_none() {
+            error.retry_after = detail
+                .get("retryDelay")
+                .and_then(|d| d.as_str())
+                .and_then(parse_duration);
+        }
+    }
+
+    error.error_type = envelope.error.status;
+    error.message = envelope.error.message;
+    error
+}
+
+/// Classify a Gemini API error by its canonical status, falling back to HTTP status
+fn classify(error: ApiError) -> Error {
+    if error.code.as_deref() == Some("API_KEY_INVALID") {
+        return Error::Authentication(Box::new(error));
+    }
+
+    match error.error_type.as_deref() {
+        Some("UNAUTHENTICATED") => Error::Authentication(Box::new(error)),
+        Some("PERMISSION_DENIED") => Error::PermissionDenied(Box::new(error)),
+        Some("NOT_FOUND") => Error::NotFound(Box::new(error)),
+        Some("RESOURCE_EXHAUSTED") => Error::RateLimitExceeded(Box::new(error)),
+        Some("UNAVAILABLE") => Error::Overloaded(Box::new(error)),
+        Some("INVALID_ARGUMENT") | Some("FAILED_PRECONDITION")
+            if error
+                .message
+                .contains("exceeds the maximum number of tokens") =>
+        {
+            Error::ContextLengthExceeded(Box::new(error))
+        }
+        Some("INVALID_ARGUMENT") | Some("FAILED_PRECONDITION") => {
+            Error::InvalidRequest(Box::new(error))
+        }
+        _ => error.into(),
+    }
+}
+
+/// Parse a protobuf JSON duration (e.g., "30s", "1.5s")
+///
+/// Negative, non-finite and out of range values are ignored.
+fn parse_duration(value: &str) -> Option<Duration> {
+    let seconds: f64 = value.strip_suffix('s')?.parse().ok()?;
+    Duration::try_from_secs_f64(seconds).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(status: u16, body: &str) -> Error {
+        classify(parse_envelope(ApiError::new("google", status, body)))
+    }
+
+    #[test]
+    fn test_parse_rate_limit_with_retry_delay() {
+        let error = parse(
+            429,
+            r#"{"error":{"code":429,"message":"Quota exceeded","status":"RESOURCE_EXHAUSTED","details":[{"@type":"type.googleapis.com/google.rpc.RetryInfo","retryDelay":"17s"}]}}"#,
+        );
+
+        assert!(matches!(error, Error::RateLimitExceeded(_)));
+        assert_eq!(error.retry_after(), Some(Duration::from_secs(17)));
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("1.5s"), Some(Duration::from_millis(1500)));
+        assert_eq!(parse_duration("30"), None);
+        assert_eq!(parse_duration("-1s"), None);
+        assert_eq!(parse_duration("1e300s"), None);
+
+        let error = parse(
+            429,
+            r#"{"error":{"code":429,"message":"Quota exceeded","status":"RESOURCE_EXHAUSTED","details":[{"@type":"type.googleapis.com/google.rpc.RetryInfo","retryDelay":"1e300s"}]}}"#,
+        );
+        assert!(matches!(error, Error::RateLimitExceeded(_)));
+        assert_eq!(error.retry_after(), None);
+    }
+
+    #[test]
+    fn test_parse_invalid_api_key() {
+        let error = parse(
+            400,
+            r#"{"error":{"code":400,"message":"API key not valid.","status":"INVALID_ARGUMENT","details":[{"@type":"type.googleapis.com/google.rpc.ErrorInfo","reason":"API_KEY_INVALID"}]}}"#,
+        );
+
+        match error {
+            Error::Authentication(e) => {
+                assert_eq!(e.code.as_deref(), Some("API_KEY_INVALID"));
+                assert_eq!(e.message, "API key not valid.");
+            }
+            other => panic!("Expected Authentication, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_context_length() {
+        let error = parse(
+            400,
+            r#"{"error":{"code":400,"message":"The input token count (1200000) exceeds the maximum number of tokens allowed (1048576).","status":"INVALID_ARGUMENT"}}"#,
+        );
+
+        assert!(matches!(error, Error::ContextLengthExceeded(_)));
+    }
+}
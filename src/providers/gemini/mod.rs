@@ -1,6 +1,7 @@
 //! Gemini provider module
 
 mod convert;
+mod error;
 mod provider;
 mod stream;
 mod types;
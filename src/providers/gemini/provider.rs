@@ -1,32 +1,60 @@
 //! Gemini provider implementation
 
-use super::convert::{from_gemini_response, to_gemini_request};
+use super::convert::{
+    from_gemini_model, from_gemini_response, to_gemini_batch_embed_request,
+    to_gemini_embed_request, to_gemini_request,
+};
+use super::error::from_response;
 use super::stream::create_stream;
-use super::types::{GeminiConfig, GeminiResponse};
+use super::types::{
+    GeminiBatchEmbedResponse, GeminiConfig, GeminiEmbedResponse, GeminiModelList, GeminiResponse,
+};
 use crate::error::{Error, Result};
+use crate::http::read_json;
 use crate::provider::Provider;
-use crate::types::{GenerateRequest, GenerateResponse, GenerateStream, Headers};
+use crate::providers::vertex::TokenCache;
+use crate::types::{
+    EmbedRequest, EmbedResponse, GenerateRequest, GenerateResponse, GenerateStream, Headers,
+    ModelInfo, ResponseMetadata,
+};
 use async_trait::async_trait;
 use reqwest::Client;
+use std::time::Instant;
 
 /// Gemini provider
+///
+/// Uses the Gemini API with an API key, or Vertex AI with service account
+/// tokens when the config has a [`VertexConfig`](crate::providers::vertex::VertexConfig).
 pub struct GeminiProvider {
     config: GeminiConfig,
     client: Client,
+    /// Access tokens for Vertex AI
+    vertex_auth: Option<TokenCache>,
 }
 
 impl GeminiProvider {
     /// Environment variable for API key
     pub const API_KEY_ENV: &'static str = "GEMINI_API_KEY";
 
+    /// Maximum number of inputs per batch embedding request
+    pub const MAX_EMBED_BATCH: usize = 100;
+
     /// Create a new Gemini provider
     pub fn new(config: GeminiConfig) -> Result<Self> {
-        if config.api_key.is_empty() {
-            return Err(Error::MissingApiKey("gemini".to_string()));
-        }
+        let vertex_auth = match &config.vertex {
+            Some(vertex) => Some(TokenCache::new("google", vertex)?),
+            None if config.api_key.is_empty() => {
+                return Err(Error::MissingApiKey("gemini".to_string()))
+            }
+            None => None,
+        };
 
-        let client = Client::new();
-        Ok(Self { config, client })
+        let client = config.http.build_client()?;
+        Ok(Self {
+            config,
+            client,
+            vertex_auth,
+        })
     }
 
     /// Create provider from environment
@@ -39,16 +67,56 @@ impl GeminiProvider {
 
     /// Build URL for Gemini API
     fn get_url(&self, model: &str, stream: bool) -> String {
+        // Without alt=sse, streamGenerateContent returns one incrementally written JSON array
         let action = if stream {
-            "streamGenerateContent"
+            "streamGenerateContent?alt=sse"
         } else {
             "generateContent"
         };
+        self.method_url(model, action)
+    }
+
+    /// Build URL for a model method, e.g. `embedContent`
+    fn method_url(&self, model: &str, method: &str) -> String {
+        if let Some(vertex) = &self.config.vertex {
+            return vertex.model_url("google", model, method);
+        }
+
+        let separator = if method.contains('?') { '&' } else { '?' };
         format!(
-            "{}models/{}:{}?key={}",
-            self.config.base_url, model, action, self.config.api_key
+            "{}/models/{}:{}{}key={}",
+            self.config.base_url.trim_end_matches('/'),
+            model,
+            method,
+            separator,
+            self.config.api_key
         )
     }
+
+    /// Send a request to a model method, failing on error responses
+    async fn post<T: serde::Serialize>(
+        &self,
+        url: &str,
+        headers: &Headers,
+        body: &T,
+    ) -> Result<reqwest::Response> {
+        let mut builder = self
+            .client
+            .post(url)
+            .headers(headers.to_reqwest_headers())
+            .json(body);
+        if let Some(auth) = &self.vertex_auth {
+            builder = builder.bearer_auth(auth.token(&self.client).await?);
+        }
+
+        let response = builder.send().await?;
+
+        if !response.status().is_success() {
+            return Err(from_response(response).await);
+        }
+
+        Ok(response)
+    }
 }
 
 #[async_trait]
@@ -74,64 +142,116 @@ impl Provider for GeminiProvider {
 
     async fn generate(&self, request: GenerateRequest) -> Result<GenerateResponse> {
         let url = self.get_url(&request.model, false);
-        let gemini_req = to_gemini_request(&request)?;
+        let gemini_req = request
+            .options
+            .provider_options
+            .apply(self.provider_id(), &to_gemini_request(&request)?)?;
 
         let headers = self.build_headers(request.options.headers.as_ref());
 
-        let response = self
-            .client
-            .post(&url)
-            .headers(headers.to_reqwest_headers())
-            .json(&gemini_req)
-            .send()
-            .await?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let error_text = response.text().await.unwrap_or_default();
-            return Err(Error::provider_error(format!(
-                "Gemini API error {}: {}",
-                status, error_text
-            )));
-        }
-
-        let gemini_resp: GeminiResponse = response.json().await?;
-        from_gemini_response(gemini_resp)
+        let started = Instant::now();
+        let response = self.post(&url, &headers, &gemini_req).await?;
+        let (gemini_resp, metadata): (GeminiResponse, _) = read_json(
+            self.provider_id(),
+            response,
+            started,
+            request.options.include_raw_response,
+        )
+        .await?;
+        Ok(from_gemini_response(gemini_resp)?.with_http_metadata(metadata))
     }
 
     async fn stream(&self, request: GenerateRequest) -> Result<GenerateStream> {
         let url = self.get_url(&request.model, true);
-        let gemini_req = to_gemini_request(&request)?;
+        let gemini_req = request
+            .options
+            .provider_options
+            .apply(self.provider_id(), &to_gemini_request(&request)?)?;
 
         let headers = self.build_headers(request.options.headers.as_ref());
 
-        let response = self
-            .client
-            .post(&url)
-            .headers(headers.to_reqwest_headers())
-            .json(&gemini_req)
-            .send()
-            .await?;
+        let started = Instant::now();
+        let response = self.post(&url, &headers, &gemini_req).await?;
+        let metadata = ResponseMetadata::from_http(self.provider_id(), &response, started);
+        Ok(create_stream(response)
+            .await?
+            .with_metadata(metadata, started))
+    }
 
-        if !response.status().is_success() {
-            let status = response.status();
-            let error_text = response.text().await.unwrap_or_default();
-            return Err(Error::provider_error(format!(
-                "Gemini API error {}: {}",
-                status, error_text
-            )));
+    async fn embed(&self, request: EmbedRequest) -> Result<EmbedResponse> {
+        if self.config.vertex.is_some() {
+            return Err(Error::provider_error(
+                "google does not support embeddings in Vertex AI mode",
+            ));
         }
 
-        create_stream(response).await
+        let headers = self.build_headers(request.headers.as_ref());
+
+        // Gemini doesn't report token usage for embeddings
+        let mut result = EmbedResponse::default();
+        if let [text] = request.input.as_slice() {
+            let url = self.method_url(&request.model, "embedContent");
+            let body = to_gemini_embed_request(&request, text);
+            let response: GeminiEmbedResponse =
+                self.post(&url, &headers, &body).await?.json().await?;
+            result.embeddings.push(response.embedding.values);
+            return Ok(result);
+        }
+
+        let url = self.method_url(&request.model, "batchEmbedContents");
+        for batch in request.input.chunks(Self::MAX_EMBED_BATCH) {
+            let body = to_gemini_batch_embed_request(&request, batch);
+            let response: GeminiBatchEmbedResponse =
+                self.post(&url, &headers, &body).await?.json().await?;
+            result
+                .embeddings
+                .extend(response.embeddings.into_iter().map(|e| e.values));
+        }
+
+        Ok(result)
     }
 
-    async fn list_models(&self) -> Result<Vec<String>> {
-        // Gemini has a models endpoint, but for simplicity return known models
-        Ok(vec![
-            "gemini-2.0-flash-exp".to_string(),
-            "gemini-1.5-pro".to_string(),
-            "gemini-1.5-flash".to_string(),
-            "gemini-1.0-pro".to_string(),
-        ])
+    async fn list_models(&self) -> Result<Vec<ModelInfo>> {
+        // Vertex AI publisher models aren't listed per project
+        if self.config.vertex.is_some() {
+            return Ok(Vec::new());
+        }
+
+        let url = format!("{}/models", self.config.base_url.trim_end_matches('/'));
+        let headers = self.build_headers(None);
+
+        let mut models = Vec::new();
+        let mut page_token: Option<String> = None;
+        loop {
+            let mut query = vec![
+                ("key", self.config.api_key.clone()),
+                ("pageSize", "1000".to_string()),
+            ];
+            if let Some(page_token) = page_token.take() {
+                query.push(("pageToken", page_token));
+            }
+
+            let response = self
+                .client
+                .get(&url)
+                .headers(headers.to_reqwest_headers())
+                .query(&query)
+                .send()
+                .await?;
+
+            if !response.status().is_success() {
+                return Err(from_response(response).await);
+            }
+
+            let page: GeminiModelList = response.json().await?;
+            models.extend(page.models.into_iter().map(from_gemini_model));
+
+            match page.next_page_token {
+                Some(token) if !token.is_empty() => page_token = Some(token),
+                _ => break,
+            }
+        }
+
+        Ok(models)
     }
 }
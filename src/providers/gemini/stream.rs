@@ -1,54 +1,47 @@
 //! Gemini streaming support
 
+use super::convert::{from_gemini_usage, new_tool_call_id, parse_finish_reason};
 use super::types::GeminiResponse;
 use crate::error::{Error, Result};
-use crate::types::{FinishReason, GenerateStream, StreamEvent, Usage};
+use crate::types::{
+    FinishReason, GenerateStream, ResponseMetadata, StreamEvent, ToolCallAccumulator, Usage,
+};
+use eventsource_stream::{EventStreamError, Eventsource};
 use futures::stream::StreamExt;
 use reqwest::Response;
 
-/// Create a stream from Gemini response
-/// Gemini uses JSON streaming (not SSE) - each line is a complete JSON object
+/// Create a stream from a Gemini SSE response (`alt=sse`)
+///
+/// Each event carries a complete `GenerateContentResponse` chunk.
 pub async fn create_stream(response: Response) -> Result<GenerateStream> {
     let stream = async_stream::stream! {
-        let mut accumulated_usage = Usage::default();
-        let mut stream_id = String::new();
-
-        let mut bytes_stream = response.bytes_stream();
-        let mut buffer = Vec::new();
-
-        while let Some(chunk_result) = bytes_stream.next().await {
-            match chunk_result {
-                Ok(chunk) => {
-                    buffer.extend_from_slice(&chunk);
-
-                    // Process complete lines
-                    while let Some(newline_pos) = buffer.iter().position(|&b| b == b'\n') {
-                        let line_bytes = buffer.drain(..=newline_pos).collect::<Vec<_>>();
-                        let line = String::from_utf8_lossy(&line_bytes);
-                        let line = line.trim();
-
-                        if line.is_empty() {
-                            continue;
-                        }
+        let mut event_source = response.bytes_stream().eventsource();
+        let mut state = StreamState::default();
+
+        while let Some(event) = event_source.next().await {
+            match event {
+                Ok(message) => {
+                    if message.data.trim().is_empty() {
+                        continue;
+                    }
 
-                        // Parse JSON response
-                        match serde_json::from_str::<GeminiResponse>(line) {
-                            Ok(gemini_resp) => {
-                                if let Some(event) = process_gemini_response(
-                                    gemini_resp,
-                                    &mut accumulated_usage,
-                                    &mut stream_id
-                                ) {
-                                    yield Ok(event);
-                                }
-                            }
-                            Err(e) => {
-                                yield Err(Error::stream_error(format!("Failed to parse JSON: {}", e)));
-                                break;
+                    // Parse JSON response
+                    match serde_json::from_str::<GeminiResponse>(&message.data) {
+                        Ok(gemini_resp) => {
+                            for event in process_gemini_response(gemini_resp, &mut state) {
+                                yield Ok(event);
                             }
                         }
+                        Err(e) => {
+                            yield Err(Error::stream_error(format!("Failed to parse JSON: {}", e)));
+                            break;
+                        }
                     }
                 }
+                Err(EventStreamError::Transport(e)) => {
+                    yield Err(Error::HttpError(e));
+                    break;
+                }
                 Err(e) => {
                     yield Err(Error::stream_error(format!("Stream error: {}", e)));
                     break;
@@ -56,74 +49,127 @@ pub async fn create_stream(response: Response) -> Result<GenerateStream> {
             }
         }
 
-        // Emit final usage if we have any
-        if accumulated_usage.total_tokens > 0 {
-            yield Ok(StreamEvent::finish(accumulated_usage, FinishReason::Stop));
+        // Emit a single final event once the stream is complete
+        if state.finish_reason.is_some() || state.usage.total_tokens > 0 {
+            let reason = if state.has_tool_calls {
+                FinishReason::ToolCalls
+            } else {
+                state.finish_reason.unwrap_or(FinishReason::Stop)
+            };
+            yield Ok(StreamEvent::finish(state.usage, reason));
         }
     };
 
     Ok(GenerateStream::new(Box::pin(stream)))
 }
 
-/// Process Gemini response and convert to unified StreamEvent
-fn process_gemini_response(
-    resp: GeminiResponse,
-    accumulated_usage: &mut Usage,
-    stream_id: &mut String,
-) -> Option<StreamEvent> {
+/// State carried across Gemini stream chunks
+#[derive(Default)]
+struct StreamState {
+    stream_id: String,
+    usage: Usage,
+    tool_calls: ToolCallAccumulator,
+    next_tool_index: u32,
+    has_tool_calls: bool,
+    finish_reason: Option<FinishReason>,
+}
+
+/// Process Gemini response and convert to unified StreamEvents
+fn process_gemini_response(resp: GeminiResponse, state: &mut StreamState) -> Vec<StreamEvent> {
     // Update usage if available
-    if let Some(usage) = resp.usage_metadata {
-        accumulated_usage.prompt_tokens = usage.prompt_token_count.unwrap_or(0);
-        accumulated_usage.completion_tokens = usage.candidates_token_count.unwrap_or(0);
-        accumulated_usage.total_tokens = usage.total_token_count.unwrap_or(0);
+    if let Some(usage) = &resp.usage_metadata {
+        state.usage = from_gemini_usage(usage);
     }
 
     // Get first candidate
-    let candidate = resp.candidates.first()?;
+    let Some(candidate) = resp.candidates.first() else {
+        return Vec::new();
+    };
+
+    let mut events = Vec::new();
 
     // Check if this is the start
-    if stream_id.is_empty() {
-        *stream_id = format!(
-            "gemini-{}",
-            std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap_or_default()
-                .as_secs()
-        );
-        // Don't emit start event, just track ID
+    if state.stream_id.is_empty() {
+        state.stream_id = resp.response_id.clone().unwrap_or_else(|| {
+            format!(
+                "gemini-{}",
+                std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs()
+            )
+        });
+        // Only worth a start event when the chunk reports the model
+        if let Some(model) = &resp.model_version {
+            events.push(StreamEvent::start_with_metadata(
+                &state.stream_id,
+                ResponseMetadata {
+                    id: resp.response_id.clone(),
+                    model: Some(model.clone()),
+                    ..Default::default()
+                },
+            ));
+        }
     }
 
     // Extract text and function calls from parts
     for part in &candidate.content.parts {
         if let Some(text) = &part.text {
-            if !text.is_empty() {
-                return Some(StreamEvent::text_delta(stream_id.clone(), text.clone()));
+            if part.thought == Some(true) {
+                if !text.is_empty() {
+                    events.push(StreamEvent::reasoning_delta(&state.stream_id, text));
+                }
+                if let Some(signature) = &part.thought_signature {
+                    events.push(StreamEvent::reasoning_signature(
+                        &state.stream_id,
+                        signature,
+                    ));
+                }
+            } else {
+                if !text.is_empty() {
+                    events.push(StreamEvent::text_delta(&state.stream_id, text));
+                }
+                // A signature on an answer part is kept as a signature-only reasoning block
+                if let Some(signature) = &part.thought_signature {
+                    events.push(StreamEvent::reasoning_signature(
+                        &state.stream_id,
+                        signature,
+                    ));
+                }
             }
         }
 
-        // Handle function calls (Gemini sends complete function calls, not deltas)
+        // Gemini sends complete function calls, not deltas
         if let Some(function_call) = &part.function_call {
-            let call_id = format!("call_{}", uuid::Uuid::new_v4());
-            return Some(StreamEvent::tool_call_end(
-                call_id,
-                function_call.name.clone(),
-                function_call.args.clone(),
-            ));
+            let index = state.next_tool_index;
+            state.next_tool_index += 1;
+            state.has_tool_calls = true;
+
+            events.push(
+                state
+                    .tool_calls
+                    .start(index, new_tool_call_id(), &function_call.name),
+            );
+            events.extend(
+                state
+                    .tool_calls
+                    .delta(index, &function_call.args.to_string()),
+            );
+            events.extend(
+                state
+                    .tool_calls
+                    .end_with_signature(index, part.thought_signature.clone()),
+            );
         }
     }
 
-    // Check if finished
-    if let Some(finish_reason) = &candidate.finish_reason {
-        let reason = match finish_reason.as_str() {
-            "STOP" => FinishReason::Stop,
-            "MAX_TOKENS" => FinishReason::Length,
-            "SAFETY" => FinishReason::ContentFilter,
-            _ => FinishReason::Other,
-        };
-        return Some(StreamEvent::finish(accumulated_usage.clone(), reason));
+    // Remember why generation finished
+    if candidate.finish_reason.is_some() {
+        state.finish_reason =
+            Some(parse_finish_reason(&candidate.finish_reason).unwrap_or(FinishReason::Other));
     }
 
-    None
+    events
 }
 
 #[cfg(test)]
@@ -133,8 +179,7 @@ mod tests {
 
     #[test]
     fn test_process_gemini_response() {
-        let mut usage = Usage::default();
-        let mut stream_id = String::new();
+        let mut state = StreamState::default();
 
         let resp = GeminiResponse {
             candidates: vec![GeminiCandidate {
@@ -145,18 +190,22 @@ mod tests {
                         inline_data: None,
                         function_call: None,
                         function_response: None,
+                        thought: None,
+                        thought_signature: None,
                     }],
                 },
                 finish_reason: None,
                 safety_ratings: None,
             }],
             usage_metadata: None,
+            model_version: None,
+            response_id: None,
         };
 
-        let result = process_gemini_response(resp, &mut usage, &mut stream_id);
-        assert!(result.is_some());
+        let result = process_gemini_response(resp, &mut state);
+        assert_eq!(result.len(), 1);
 
-        if let Some(StreamEvent::TextDelta { delta, .. }) = result {
+        if let StreamEvent::TextDelta { delta, .. } = &result[0] {
             assert_eq!(delta, "Hello");
         }
     }
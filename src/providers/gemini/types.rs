@@ -1,5 +1,7 @@
 //! Gemini-specific types
 
+use crate::http::HttpConfig;
+use crate::providers::vertex::VertexConfig;
 use serde::{Deserialize, Serialize};
 
 /// Configuration for Gemini provider
@@ -9,6 +11,10 @@ pub struct GeminiConfig {
     pub api_key: String,
     /// Base URL (default: https://generativelanguage.googleapis.com/v1beta)
     pub base_url: String,
+    /// Vertex AI project and service account, used instead of the API key
+    pub vertex: Option<VertexConfig>,
+    /// HTTP client settings (timeouts)
+    pub http: HttpConfig,
 }
 
 impl GeminiConfig {
@@ -17,6 +23,16 @@ impl GeminiConfig {
         Self {
             api_key: api_key.into(),
             base_url: "https://generativelanguage.googleapis.com/v1beta".to_string(),
+            vertex: None,
+            http: HttpConfig::default(),
+        }
+    }
+
+    /// Create new config for Vertex AI
+    pub fn vertex(vertex: VertexConfig) -> Self {
+        Self {
+            vertex: Some(vertex),
+            ..Self::new("")
         }
     }
 
@@ -25,6 +41,12 @@ impl GeminiConfig {
         self.base_url = base_url.into();
         self
     }
+
+    /// Set HTTP client settings
+    pub fn with_http_config(mut self, http: HttpConfig) -> Self {
+        self.http = http;
+        self
+    }
 }
 
 impl Default for GeminiConfig {
@@ -59,12 +81,18 @@ pub struct GeminiContent {
 pub struct GeminiPart {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub text: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "Option::is_none", alias = "inlineData")]
     pub inline_data: Option<GeminiInlineData>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "Option::is_none", alias = "functionCall")]
     pub function_call: Option<GeminiFunctionCall>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "Option::is_none", alias = "functionResponse")]
     pub function_response: Option<GeminiFunctionResponse>,
+    /// Whether `text` is a thought summary rather than the answer
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thought: Option<bool>,
+    /// Signature of the model's reasoning, to be sent back unmodified
+    #[serde(skip_serializing_if = "Option::is_none", alias = "thoughtSignature")]
+    pub thought_signature: Option<String>,
 }
 
 /// Gemini function call
@@ -84,6 +112,7 @@ pub struct GeminiFunctionResponse {
 /// Gemini inline data (for images)
 #[derive(Debug, Serialize, Deserialize)]
 pub struct GeminiInlineData {
+    #[serde(alias = "mimeType")]
     pub mime_type: String,
     pub data: String, // base64 encoded
 }
@@ -102,7 +131,23 @@ pub struct GeminiGenerationConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub stop_sequences: Option<Vec<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub frequency_penalty: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub presence_penalty: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub response_mime_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response_schema: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thinking_config: Option<GeminiThinkingConfig>,
+}
+
+/// Gemini thinking configuration
+#[derive(Debug, Serialize)]
+pub struct GeminiThinkingConfig {
+    pub thinking_budget: u32,
+    /// Return thought summaries alongside the answer
+    pub include_thoughts: bool,
 }
 
 /// Gemini safety setting
@@ -113,20 +158,26 @@ pub struct GeminiSafetySetting {
 }
 
 /// Gemini response
+///
+/// The API responds in camelCase; snake_case is accepted as well.
 #[derive(Debug, Deserialize)]
 pub struct GeminiResponse {
     pub candidates: Vec<GeminiCandidate>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "Option::is_none", alias = "usageMetadata")]
     pub usage_metadata: Option<GeminiUsageMetadata>,
+    #[serde(default, alias = "modelVersion")]
+    pub model_version: Option<String>,
+    #[serde(default, alias = "responseId")]
+    pub response_id: Option<String>,
 }
 
 /// Gemini candidate
 #[derive(Debug, Deserialize)]
 pub struct GeminiCandidate {
     pub content: GeminiContent,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "Option::is_none", alias = "finishReason")]
     pub finish_reason: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "Option::is_none", alias = "safetyRatings")]
     pub safety_ratings: Option<Vec<GeminiSafetyRating>>,
 }
 
@@ -140,10 +191,77 @@ pub struct GeminiSafetyRating {
 /// Gemini usage metadata
 #[derive(Debug, Deserialize)]
 pub struct GeminiUsageMetadata {
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "Option::is_none", alias = "promptTokenCount")]
     pub prompt_token_count: Option<u32>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        alias = "candidatesTokenCount"
+    )]
     pub candidates_token_count: Option<u32>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "Option::is_none", alias = "totalTokenCount")]
     pub total_token_count: Option<u32>,
+    #[serde(default, alias = "cachedContentTokenCount")]
+    pub cached_content_token_count: Option<u32>,
+    /// Reasoning tokens (not included in `candidates_token_count`)
+    #[serde(default, alias = "thoughtsTokenCount")]
+    pub thoughts_token_count: Option<u32>,
+}
+
+/// Gemini embed content request (also an entry of a batch request)
+#[derive(Debug, Serialize)]
+pub struct GeminiEmbedRequest {
+    /// "models/{model}", required in batch requests
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    pub content: GeminiContent,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output_dimensionality: Option<u32>,
+}
+
+/// Gemini batch embed contents request
+#[derive(Debug, Serialize)]
+pub struct GeminiBatchEmbedRequest {
+    pub requests: Vec<GeminiEmbedRequest>,
+}
+
+/// Gemini embed content response
+#[derive(Debug, Deserialize)]
+pub struct GeminiEmbedResponse {
+    pub embedding: GeminiEmbedding,
+}
+
+/// Gemini batch embed contents response
+#[derive(Debug, Deserialize)]
+pub struct GeminiBatchEmbedResponse {
+    pub embeddings: Vec<GeminiEmbedding>,
+}
+
+/// Gemini embedding values
+#[derive(Debug, Deserialize)]
+pub struct GeminiEmbedding {
+    pub values: Vec<f32>,
+}
+
+/// Gemini model list response (one page)
+#[derive(Debug, Deserialize)]
+pub struct GeminiModelList {
+    #[serde(default)]
+    pub models: Vec<GeminiModel>,
+    #[serde(default, alias = "nextPageToken")]
+    pub next_page_token: Option<String>,
+}
+
+/// Gemini model
+#[derive(Debug, Deserialize)]
+pub struct GeminiModel {
+    /// Resource name ("models/{model}")
+    pub name: String,
+    #[serde(default, alias = "displayName")]
+    pub display_name: Option<String>,
+    #[serde(default, alias = "inputTokenLimit")]
+    pub input_token_limit: Option<u32>,
+    #[serde(default, alias = "outputTokenLimit")]
+    pub output_token_limit: Option<u32>,
+    #[serde(default)]
+    pub thinking: Option<bool>,
 }
@@ -1,10 +1,15 @@
 //! Provider implementations
 
 pub mod anthropic;
+pub mod bedrock;
+pub mod cohere;
 pub mod gemini;
 pub mod openai;
+pub mod vertex;
 
 // Re-export providers
 pub use anthropic::AnthropicProvider;
+pub use bedrock::BedrockProvider;
+pub use cohere::CohereProvider;
 pub use gemini::GeminiProvider;
-pub use openai::OpenAIProvider;
+pub use openai::{AzureOpenAIProvider, OpenAICompatibleProvider, OpenAIProvider};
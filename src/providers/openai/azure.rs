@@ -0,0 +1,181 @@
+//! Azure OpenAI provider implementation
+
+use super::convert::{
+    from_openai_embedding_response, from_openai_response, to_openai_embedding_request,
+    to_openai_request,
+};
+use super::error::from_response;
+use super::stream::create_stream;
+use super::types::{AzureOpenAIAuth, AzureOpenAIConfig, ChatCompletionResponse, EmbeddingResponse};
+use crate::error::{Error, Result};
+use crate::http::read_json;
+use crate::provider::Provider;
+use crate::types::{
+    EmbedRequest, EmbedResponse, GenerateRequest, GenerateResponse, GenerateStream, Headers,
+    ResponseMetadata,
+};
+use async_trait::async_trait;
+use reqwest::{Client, Response};
+use serde::Serialize;
+use std::time::Instant;
+
+/// Azure OpenAI provider
+///
+/// Models are served from deployments, mapped with
+/// [`AzureOpenAIConfig::with_deployment`]; unmapped models are used as
+/// deployment names. Content filter verdicts are reported in
+/// [`ResponseMetadata::content_filter`].
+///
+/// # Example
+///
+/// ```rust
+/// use stakai::providers::openai::{AzureOpenAIConfig, AzureOpenAIProvider};
+/// use stakai::Inference;
+///
+/// let config = AzureOpenAIConfig::from_resource("my-resource", "...")
+///     .with_deployment("gpt-4o", "my-gpt-4o");
+/// let client = Inference::builder()
+///     .register_provider("azure", AzureOpenAIProvider::new(config)?)
+///     .build()?;
+/// // client.generate(&GenerateRequest::new("azure:gpt-4o", messages))
+/// # Ok::<(), stakai::Error>(())
+/// ```
+pub struct AzureOpenAIProvider {
+    config: AzureOpenAIConfig,
+    client: Client,
+}
+
+impl AzureOpenAIProvider {
+    /// Create a new Azure OpenAI provider
+    pub fn new(config: AzureOpenAIConfig) -> Result<Self> {
+        if matches!(&config.auth, AzureOpenAIAuth::ApiKey(key) if key.is_empty()) {
+            return Err(Error::MissingApiKey("azure".to_string()));
+        }
+
+        let client = config.http.build_client()?;
+        Ok(Self { config, client })
+    }
+
+    /// Create provider from `AZURE_OPENAI_ENDPOINT` and `AZURE_OPENAI_API_KEY`
+    pub fn from_env() -> Result<Self> {
+        let config = AzureOpenAIConfig::from_env()
+            .ok_or_else(|| Error::MissingApiKey("azure".to_string()))?;
+        Self::new(config)
+    }
+
+    /// Build URL for a deployment operation, e.g. `chat/completions`
+    fn url(&self, model: &str, operation: &str) -> String {
+        format!(
+            "{}/openai/deployments/{}/{}?api-version={}",
+            self.config.endpoint.trim_end_matches('/'),
+            self.config.deployment(model),
+            operation,
+            self.config.api_version
+        )
+    }
+
+    /// Send an authenticated request, failing on error responses
+    async fn post<T: Serialize>(&self, url: &str, headers: &Headers, body: &T) -> Result<Response> {
+        let mut builder = self
+            .client
+            .post(url)
+            .headers(headers.to_reqwest_headers())
+            .json(body);
+        if let AzureOpenAIAuth::EntraId(provider) = &self.config.auth {
+            builder = builder.bearer_auth(provider.token().await?);
+        }
+
+        let response = builder.send().await?;
+        if !response.status().is_success() {
+            return Err(from_response(self.provider_id(), response).await);
+        }
+
+        Ok(response)
+    }
+}
+
+#[async_trait]
+impl Provider for AzureOpenAIProvider {
+    fn provider_id(&self) -> &str {
+        "azure"
+    }
+
+    fn build_headers(&self, custom_headers: Option<&Headers>) -> Headers {
+        let mut headers = Headers::new();
+
+        // Entra ID tokens are added when the request is sent
+        if let AzureOpenAIAuth::ApiKey(key) = &self.config.auth {
+            headers.insert("api-key", key);
+        }
+        headers.insert("Content-Type", "application/json");
+
+        if let Some(custom) = custom_headers {
+            headers.merge_with(custom);
+        }
+
+        headers
+    }
+
+    async fn generate(&self, request: GenerateRequest) -> Result<GenerateResponse> {
+        let url = self.url(&request.model, "chat/completions");
+        let mut chat_req = to_openai_request(&request, false);
+        self.config
+            .quirks
+            .for_request(&request)
+            .apply(&mut chat_req);
+        let chat_req = request
+            .options
+            .provider_options
+            .apply(self.provider_id(), &chat_req)?;
+
+        let headers = self.build_headers(request.options.headers.as_ref());
+
+        let started = Instant::now();
+        let response = self.post(&url, &headers, &chat_req).await?;
+        let (chat_resp, metadata): (ChatCompletionResponse, _) = read_json(
+            self.provider_id(),
+            response,
+            started,
+            request.options.include_raw_response,
+        )
+        .await?;
+        Ok(from_openai_response(chat_resp, &self.config.quirks)?.with_http_metadata(metadata))
+    }
+
+    async fn stream(&self, request: GenerateRequest) -> Result<GenerateStream> {
+        let url = self.url(&request.model, "chat/completions");
+        let mut chat_req = to_openai_request(&request, true);
+        self.config
+            .quirks
+            .for_request(&request)
+            .apply(&mut chat_req);
+        let chat_req = request
+            .options
+            .provider_options
+            .apply(self.provider_id(), &chat_req)?;
+
+        let headers = self.build_headers(request.options.headers.as_ref());
+
+        let started = Instant::now();
+        let response = self.post(&url, &headers, &chat_req).await?;
+        let metadata = ResponseMetadata::from_http(self.provider_id(), &response, started);
+        Ok(create_stream(response, self.config.quirks)
+            .await?
+            .with_metadata(metadata, started))
+    }
+
+    async fn embed(&self, request: EmbedRequest) -> Result<EmbedResponse> {
+        let url = self.url(&request.model, "embeddings");
+        let headers = self.build_headers(request.headers.as_ref());
+
+        let response = self
+            .post(
+                &url,
+                &headers,
+                &to_openai_embedding_request(&request, &request.input),
+            )
+            .await?;
+        let embedding_resp: EmbeddingResponse = response.json().await?;
+        Ok(from_openai_embedding_response(embedding_resp))
+    }
+}
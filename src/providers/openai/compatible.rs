@@ -0,0 +1,195 @@
+//! OpenAI-compatible provider implementation
+
+use super::convert::{
+    from_openai_embedding_response, from_openai_model, from_openai_response,
+    to_openai_embedding_request, to_openai_request,
+};
+use super::error::from_response;
+use super::stream::create_stream;
+use super::types::{
+    ChatCompletionResponse, EmbeddingResponse, ModelList, OpenAICompatibleAuth,
+    OpenAICompatibleConfig,
+};
+use crate::error::{Error, Result};
+use crate::http::read_json;
+use crate::provider::Provider;
+use crate::types::{
+    EmbedRequest, EmbedResponse, GenerateRequest, GenerateResponse, GenerateStream, Headers,
+    ModelInfo, ResponseMetadata,
+};
+use async_trait::async_trait;
+use reqwest::Client;
+use std::time::Instant;
+
+/// Provider for servers speaking the OpenAI Chat Completions API
+///
+/// Requests are built with the OpenAI conversion and adjusted by the
+/// config's [`OpenAIQuirks`](super::OpenAIQuirks). Presets cover Mistral,
+/// xAI, Groq, DeepSeek, Ollama and vLLM.
+///
+/// # Example
+///
+/// ```rust
+/// use stakai::providers::openai::{OpenAICompatibleConfig, OpenAICompatibleProvider};
+/// use stakai::Inference;
+///
+/// let groq = OpenAICompatibleProvider::new(OpenAICompatibleConfig::groq("gsk-..."))?;
+/// let client = Inference::builder().register_provider("groq", groq).build()?;
+/// // client.generate(&GenerateRequest::new("groq:llama-3.3-70b-versatile", messages))
+/// # Ok::<(), stakai::Error>(())
+/// ```
+pub struct OpenAICompatibleProvider {
+    config: OpenAICompatibleConfig,
+    client: Client,
+}
+
+impl OpenAICompatibleProvider {
+    /// Create a new OpenAI-compatible provider
+    pub fn new(config: OpenAICompatibleConfig) -> Result<Self> {
+        if config.api_key.is_empty() && config.auth != OpenAICompatibleAuth::None {
+            return Err(Error::MissingApiKey(config.provider_id));
+        }
+
+        let client = config.http.build_client()?;
+        Ok(Self { config, client })
+    }
+
+    /// Get the provider configuration
+    pub fn config(&self) -> &OpenAICompatibleConfig {
+        &self.config
+    }
+}
+
+#[async_trait]
+impl Provider for OpenAICompatibleProvider {
+    fn provider_id(&self) -> &str {
+        &self.config.provider_id
+    }
+
+    fn build_headers(&self, custom_headers: Option<&Headers>) -> Headers {
+        let mut headers = Headers::new();
+
+        match &self.config.auth {
+            OpenAICompatibleAuth::Bearer => {
+                headers.insert("Authorization", format!("Bearer {}", self.config.api_key));
+            }
+            OpenAICompatibleAuth::Header(name) => headers.insert(name, &self.config.api_key),
+            OpenAICompatibleAuth::None => {}
+        }
+        headers.insert("Content-Type", "application/json");
+        headers.merge_with(&self.config.headers);
+
+        if let Some(custom) = custom_headers {
+            headers.merge_with(custom);
+        }
+
+        headers
+    }
+
+    async fn generate(&self, request: GenerateRequest) -> Result<GenerateResponse> {
+        let url = format!("{}/chat/completions", self.config.base_url);
+        let mut chat_req = to_openai_request(&request, false);
+        self.config.quirks.apply(&mut chat_req);
+        let chat_req = request
+            .options
+            .provider_options
+            .apply(self.provider_id(), &chat_req)?;
+
+        let headers = self.build_headers(request.options.headers.as_ref());
+
+        let started = Instant::now();
+        let response = self
+            .client
+            .post(&url)
+            .headers(headers.to_reqwest_headers())
+            .json(&chat_req)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(from_response(self.provider_id(), response).await);
+        }
+
+        let (chat_resp, metadata): (ChatCompletionResponse, _) = read_json(
+            self.provider_id(),
+            response,
+            started,
+            request.options.include_raw_response,
+        )
+        .await?;
+        Ok(from_openai_response(chat_resp, &self.config.quirks)?.with_http_metadata(metadata))
+    }
+
+    async fn stream(&self, request: GenerateRequest) -> Result<GenerateStream> {
+        let url = format!("{}/chat/completions", self.config.base_url);
+        let mut chat_req = to_openai_request(&request, true);
+        self.config.quirks.apply(&mut chat_req);
+        let chat_req = request
+            .options
+            .provider_options
+            .apply(self.provider_id(), &chat_req)?;
+
+        let headers = self.build_headers(request.options.headers.as_ref());
+
+        let started = Instant::now();
+        let response = self
+            .client
+            .post(&url)
+            .headers(headers.to_reqwest_headers())
+            .json(&chat_req)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(from_response(self.provider_id(), response).await);
+        }
+
+        let metadata = ResponseMetadata::from_http(self.provider_id(), &response, started);
+        Ok(create_stream(response, self.config.quirks)
+            .await?
+            .with_metadata(metadata, started))
+    }
+
+    async fn embed(&self, request: EmbedRequest) -> Result<EmbedResponse> {
+        let url = format!("{}/embeddings", self.config.base_url);
+        let headers = self.build_headers(request.headers.as_ref());
+
+        let response = self
+            .client
+            .post(&url)
+            .headers(headers.to_reqwest_headers())
+            .json(&to_openai_embedding_request(&request, &request.input))
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(from_response(self.provider_id(), response).await);
+        }
+
+        let embedding_resp: EmbeddingResponse = response.json().await?;
+        Ok(from_openai_embedding_response(embedding_resp))
+    }
+
+    async fn list_models(&self) -> Result<Vec<ModelInfo>> {
+        let url = format!("{}/models", self.config.base_url);
+        let headers = self.build_headers(None);
+
+        let response = self
+            .client
+            .get(&url)
+            .headers(headers.to_reqwest_headers())
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(from_response(self.provider_id(), response).await);
+        }
+
+        let models: ModelList = response.json().await?;
+        Ok(models
+            .data
+            .into_iter()
+            .map(|model| from_openai_model(self.provider_id(), model))
+            .collect())
+    }
+}
@@ -3,7 +3,7 @@
 use super::types::*;
 use crate::error::{Error, Result};
 use crate::types::*;
-use serde_json::json;
+use serde_json::{json, Map, Value};
 
 /// Convert SDK request to OpenAI request
 pub fn to_openai_request(req: &GenerateRequest, stream: bool) -> ChatCompletionRequest {
@@ -39,11 +39,40 @@ pub fn to_openai_request(req: &GenerateRequest, stream: bool) -> ChatCompletionR
         messages: req.messages.iter().map(to_openai_message).collect(),
         temperature: req.options.temperature,
         max_tokens: req.options.max_tokens,
+        max_completion_tokens: None,
         top_p: req.options.top_p,
+        frequency_penalty: req.options.frequency_penalty,
+        presence_penalty: req.options.presence_penalty,
         stop: req.options.stop_sequences.clone(),
         stream: Some(stream),
+        stream_options: None,
         tools,
         tool_choice,
+        reasoning_effort: req
+            .options
+            .reasoning
+            .as_ref()
+            .map(|reasoning| reasoning.resolved_effort().as_str().to_string()),
+        response_format: req
+            .options
+            .response_format
+            .as_ref()
+            .map(|format| match format {
+                ResponseFormat::Text => json!({"type": "text"}),
+                ResponseFormat::JsonObject => json!({"type": "json_object"}),
+                ResponseFormat::JsonSchema {
+                    name,
+                    schema,
+                    strict,
+                } => json!({
+                    "type": "json_schema",
+                    "json_schema": {
+                        "name": name,
+                        "schema": schema,
+                        "strict": strict,
+                    }
+                }),
+            }),
     }
 }
 
@@ -56,8 +85,12 @@ fn to_openai_message(msg: &Message) -> ChatMessage {
         Role::Tool => "tool",
     };
 
-    // Get content parts from the message
-    let parts = msg.parts();
+    // Get content parts from the message; reasoning can't be sent back to OpenAI
+    let parts: Vec<ContentPart> = msg
+        .parts()
+        .into_iter()
+        .filter(|part| !matches!(part, ContentPart::Reasoning { .. }))
+        .collect();
 
     // Check if this is a tool result message
     let tool_call_id = parts.iter().find_map(|part| match part {
@@ -73,6 +106,7 @@ fn to_openai_message(msg: &Message) -> ChatMessage {
                 id,
                 name,
                 arguments,
+                ..
             } => Some(OpenAIToolCall {
                 id: id.clone(),
                 type_: "function".to_string(),
@@ -94,8 +128,8 @@ fn to_openai_message(msg: &Message) -> ChatMessage {
     let content = if parts.len() == 1 {
         // Single content part - use string format
         match &parts[0] {
-            ContentPart::Text { text } => Some(json!(text)),
-            ContentPart::Image { url, detail } => Some(json!([{
+            ContentPart::Text { text, .. } => Some(json!(text)),
+            ContentPart::Image { url, detail, .. } => Some(json!([{
                 "type": "image_url",
                 "image_url": {
                     "url": url,
@@ -107,18 +141,23 @@ fn to_openai_message(msg: &Message) -> ChatMessage {
                 }
             }])),
             ContentPart::ToolCall { .. } => None, // Handled via tool_calls field
-            ContentPart::ToolResult { content, .. } => Some(content.clone()),
+            // OpenAI expects tool message content as text
+            ContentPart::ToolResult { content, .. } => Some(match content {
+                serde_json::Value::String(_) => content.clone(),
+                other => json!(other.to_string()),
+            }),
+            ContentPart::Reasoning { .. } => None,
         }
     } else {
         // Multiple content parts - use array format
         Some(json!(parts
             .iter()
             .filter_map(|part| match part {
-                ContentPart::Text { text } => Some(json!({
+                ContentPart::Text { text, .. } => Some(json!({
                     "type": "text",
                     "text": text
                 })),
-                ContentPart::Image { url, detail } => Some(json!({
+                ContentPart::Image { url, detail, .. } => Some(json!({
                     "type": "image_url",
                     "image_url": {
                         "url": url,
@@ -131,6 +170,7 @@ fn to_openai_message(msg: &Message) -> ChatMessage {
                 })),
                 ContentPart::ToolCall { .. } => None, // Handled via tool_calls field
                 ContentPart::ToolResult { .. } => None, // Handled separately via tool_call_id
+                ContentPart::Reasoning { .. } => None,
             })
             .collect::<Vec<_>>()))
     };
@@ -141,19 +181,24 @@ fn to_openai_message(msg: &Message) -> ChatMessage {
         name: msg.name.clone(),
         tool_calls,
         tool_call_id,
+        reasoning_content: None,
+        reasoning: None,
     }
 }
 
 /// Convert OpenAI response to SDK response
-pub fn from_openai_response(resp: ChatCompletionResponse) -> Result<GenerateResponse> {
+pub fn from_openai_response(
+    resp: ChatCompletionResponse,
+    quirks: &OpenAIQuirks,
+) -> Result<GenerateResponse> {
     let choice = resp
         .choices
         .first()
         .ok_or_else(|| Error::invalid_response("No choices in response"))?;
 
-    let content = parse_message_content(&choice.message)?;
+    let content = parse_message_content(&choice.message, quirks)?;
 
-    let finish_reason = match choice.finish_reason.as_deref() {
+    let mut finish_reason = match choice.finish_reason.as_deref() {
         Some("stop") => FinishReason::Stop,
         Some("length") => FinishReason::Length,
         Some("content_filter") => FinishReason::ContentFilter,
@@ -161,26 +206,80 @@ pub fn from_openai_response(resp: ChatCompletionResponse) -> Result<GenerateResp
         _ => FinishReason::Other,
     };
 
+    // Azure OpenAI reports content filter verdicts alongside the completion
+    let content_filter = (!resp.prompt_filter_results.is_empty()
+        || choice.content_filter_results.is_some())
+    .then(|| ContentFilterResults {
+        prompt: resp
+            .prompt_filter_results
+            .iter()
+            .flat_map(|result| from_content_filter_results(&result.content_filter_results))
+            .collect(),
+        completion: choice
+            .content_filter_results
+            .as_ref()
+            .map(from_content_filter_results)
+            .unwrap_or_default(),
+    });
+    if content_filter
+        .as_ref()
+        .is_some_and(|filter| filter.completion.iter().any(|r| r.filtered))
+    {
+        finish_reason = FinishReason::ContentFilter;
+    }
+
     Ok(GenerateResponse {
         content,
-        usage: Usage {
-            prompt_tokens: resp.usage.prompt_tokens,
-            completion_tokens: resp.usage.completion_tokens,
-            total_tokens: resp.usage.total_tokens,
-        },
+        usage: from_openai_usage(&resp.usage),
         finish_reason,
-        metadata: Some(json!({
-            "id": resp.id,
-            "model": resp.model,
-            "created": resp.created,
-        })),
+        citations: Vec::new(),
+        metadata: Some(ResponseMetadata {
+            id: Some(resp.id),
+            model: Some(resp.model),
+            created: Some(resp.created),
+            content_filter,
+            ..Default::default()
+        }),
     })
 }
 
+/// Convert Azure OpenAI content filter results keyed by category
+///
+/// Categories without a verdict (e.g., an `error` entry) are skipped.
+pub(crate) fn from_content_filter_results(
+    results: &Map<String, Value>,
+) -> Vec<ContentFilterResult> {
+    results
+        .iter()
+        .filter_map(|(category, result)| {
+            Some(ContentFilterResult {
+                category: category.clone(),
+                filtered: result.get("filtered")?.as_bool()?,
+                severity: result
+                    .get("severity")
+                    .and_then(Value::as_str)
+                    .map(str::to_string),
+                detected: result.get("detected").and_then(Value::as_bool),
+            })
+        })
+        .collect()
+}
+
 /// Parse message content from OpenAI format
-fn parse_message_content(msg: &ChatMessage) -> Result<Vec<ResponseContent>> {
+fn parse_message_content(msg: &ChatMessage, quirks: &OpenAIQuirks) -> Result<Vec<ResponseContent>> {
     let mut content = Vec::new();
 
+    // Handle reasoning from OpenAI-compatible servers that return it
+    if let Some(reasoning) = quirks.reasoning(&msg.reasoning_content, &msg.reasoning) {
+        if !reasoning.is_empty() {
+            content.push(ResponseContent::Reasoning {
+                text: reasoning.to_string(),
+                signature: None,
+                data: None,
+            });
+        }
+    }
+
     // Handle string content
     if let Some(content_value) = &msg.content {
         if let Some(text) = content_value.as_str() {
@@ -200,9 +299,63 @@ fn parse_message_content(msg: &ChatMessage) -> Result<Vec<ResponseContent>> {
                 name: tc.function.name.clone(),
                 arguments: serde_json::from_str(&tc.function.arguments)
                     .unwrap_or_else(|_| json!({})),
+                signature: None,
             }));
         }
     }
 
     Ok(content)
 }
+
+/// Convert a batch of an SDK embedding request to an OpenAI embeddings request
+pub fn to_openai_embedding_request(req: &EmbedRequest, input: &[String]) -> EmbeddingRequest {
+    EmbeddingRequest {
+        model: req.model.clone(),
+        input: input.to_vec(),
+        dimensions: req.dimensions,
+        encoding_format: "float".to_string(),
+    }
+}
+
+/// Convert OpenAI embeddings response to SDK response
+pub fn from_openai_embedding_response(mut resp: EmbeddingResponse) -> EmbedResponse {
+    // Embeddings are documented to be in input order, but carry their index anyway
+    resp.data.sort_by_key(|data| data.index);
+
+    EmbedResponse {
+        embeddings: resp.data.into_iter().map(|data| data.embedding).collect(),
+        usage: resp
+            .usage
+            .map(|usage| Usage {
+                prompt_tokens: usage.prompt_tokens,
+                total_tokens: usage.total_tokens,
+                ..Default::default()
+            })
+            .unwrap_or_default(),
+    }
+}
+
+/// Convert OpenAI usage to SDK usage
+pub fn from_openai_usage(usage: &ChatUsage) -> Usage {
+    Usage {
+        prompt_tokens: usage.prompt_tokens,
+        completion_tokens: usage.completion_tokens,
+        total_tokens: usage.total_tokens,
+        cached_tokens: usage
+            .prompt_tokens_details
+            .as_ref()
+            .map_or(0, |details| details.cached_tokens),
+        cache_creation_tokens: 0,
+        reasoning_tokens: usage
+            .completion_tokens_details
+            .as_ref()
+            .map_or(0, |details| details.reasoning_tokens),
+    }
+}
+
+/// Convert OpenAI model to SDK model info
+///
+/// OpenAI only reports model IDs; limits and capabilities are left unset.
+pub fn from_openai_model(provider: &str, model: OpenAIModel) -> ModelInfo {
+    ModelInfo::new(provider, model.id)
+}
@@ -1,23 +1,109 @@
-//! OpenAI-specific errors
+//! OpenAI error response parsing
 
-use thiserror::Error;
+use crate::error::{ApiError, Error};
+use reqwest::Response;
+use serde::Deserialize;
 
-/// OpenAI provider errors
-#[derive(Error, Debug)]
-pub enum OpenAIError {
-    /// HTTP error
-    #[error("HTTP error: {0}")]
-    Http(#[from] reqwest::Error),
+/// OpenAI error response envelope
+#[derive(Debug, Deserialize)]
+pub struct OpenAIErrorResponse {
+    pub error: OpenAIErrorBody,
+}
+
+/// OpenAI error details
+#[derive(Debug, Deserialize)]
+pub struct OpenAIErrorBody {
+    pub message: String,
+    #[serde(rename = "type")]
+    pub type_: Option<String>,
+    /// Usually a string, but some compatible servers send numbers
+    pub code: Option<serde_json::Value>,
+}
+
+/// Convert a non-success OpenAI response into a classified error
+pub(crate) async fn from_response(provider: &str, response: Response) -> Error {
+    let error = ApiError::from_response(provider, response).await;
+    classify(parse_envelope(error))
+}
+
+/// Refine an API error from the OpenAI error envelope in its message, if present
+fn parse_envelope(mut error: ApiError) -> ApiError {
+    if let Ok(envelope) = serde_json::from_str::<OpenAIErrorResponse>(&error.message) {
+        error.error_type = envelope.error.type_;
+        error.code = envelope.error.code.and_then(|code| match code {
+            serde_json::Value::String(s) => Some(s),
+            serde_json::Value::Null => None,
+            other => Some(other.to_string()),
+        });
+        error.message = envelope.error.message;
+    }
+    error
+}
+
+/// Classify an OpenAI API error by its error code, falling back to HTTP status
+pub(crate) fn classify(error: ApiError) -> Error {
+    match error.code.as_deref() {
+        Some("context_length_exceeded") => Error::ContextLengthExceeded(Box::new(error)),
+        Some("content_policy_violation") | Some("content_filter") => {
+            Error::ContentPolicy(Box::new(error))
+        }
+        Some("rate_limit_exceeded") => Error::RateLimitExceeded(Box::new(error)),
+        // Sent with HTTP 429, but an exhausted quota won't recover on retry
+        Some("insufficient_quota") => Error::PermissionDenied(Box::new(error)),
+        Some("invalid_api_key") => Error::Authentication(Box::new(error)),
+        Some("model_not_found") => Error::NotFound(Box::new(error)),
+        _ => error.into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(status: u16, body: &str) -> Error {
+        classify(parse_envelope(ApiError::new("openai", status, body)))
+    }
+
+    #[test]
+    fn test_parse_context_length_error() {
+        let error = parse(
+            400,
+            r#"{"error":{"message":"This model's maximum context length is 8192 tokens.","type":"invalid_request_error","param":"messages","code":"context_length_exceeded"}}"#,
+        );
+
+        match error {
+            Error::ContextLengthExceeded(e) => {
+                assert_eq!(e.error_type.as_deref(), Some("invalid_request_error"));
+                assert_eq!(e.code.as_deref(), Some("context_length_exceeded"));
+                assert_eq!(
+                    e.message,
+                    "This model's maximum context length is 8192 tokens."
+                );
+            }
+            other => panic!("Expected ContextLengthExceeded, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_falls_back_to_status() {
+        let error = parse(
+            401,
+            r#"{"error":{"message":"Incorrect API key provided","type":"invalid_request_error","param":null,"code":null}}"#,
+        );
+        assert!(matches!(error, Error::Authentication(e) if e.code.is_none()));
 
-    /// JSON parsing error
-    #[error("JSON error: {0}")]
-    Json(#[from] serde_json::Error),
+        let error = parse(502, "<html>Bad Gateway</html>");
+        assert!(matches!(error, Error::Api(e) if e.message == "<html>Bad Gateway</html>"));
+    }
 
-    /// API error response
-    #[error("OpenAI API error: {0}")]
-    ApiError(String),
+    #[test]
+    fn test_insufficient_quota_is_not_retryable() {
+        let error = parse(
+            429,
+            r#"{"error":{"message":"You exceeded your current quota, please check your plan and billing details.","type":"insufficient_quota","param":null,"code":"insufficient_quota"}}"#,
+        );
 
-    /// Invalid configuration
-    #[error("Invalid configuration: {0}")]
-    InvalidConfig(String),
+        assert!(matches!(&error, Error::PermissionDenied(e) if e.status == 429));
+        assert!(!crate::retry::RetryPolicy::default().is_retryable(&error));
+    }
 }
@@ -1,11 +1,17 @@
-//! OpenAI provider implementation
+//! OpenAI provider implementation, plus Azure OpenAI and OpenAI-compatible servers
 
+mod azure;
+mod compatible;
 mod convert;
 mod error;
 mod provider;
 mod stream;
 mod types;
 
-pub use error::OpenAIError;
+pub use azure::AzureOpenAIProvider;
+pub use compatible::OpenAICompatibleProvider;
 pub use provider::OpenAIProvider;
-pub use types::OpenAIConfig;
+pub use types::{
+    AzureOpenAIAuth, AzureOpenAIConfig, AzureTokenProvider, OpenAICompatibleAuth,
+    OpenAICompatibleConfig, OpenAIConfig, OpenAIQuirks,
+};
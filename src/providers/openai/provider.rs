@@ -1,14 +1,24 @@
 //! OpenAI provider implementation
 
-use super::convert::{from_openai_response, to_openai_request};
+use super::convert::{
+    from_openai_embedding_response, from_openai_model, from_openai_response,
+    to_openai_embedding_request, to_openai_request,
+};
+use super::error::from_response;
 use super::stream::create_stream;
-use super::types::{ChatCompletionResponse, OpenAIConfig};
+use super::types::{
+    ChatCompletionResponse, EmbeddingResponse, ModelList, OpenAIConfig, OpenAIQuirks,
+};
 use crate::error::{Error, Result};
+use crate::http::read_json;
 use crate::provider::Provider;
-use crate::types::{GenerateRequest, GenerateResponse, GenerateStream, Headers};
+use crate::types::{
+    EmbedRequest, EmbedResponse, GenerateRequest, GenerateResponse, GenerateStream, Headers,
+    ModelInfo, ResponseMetadata,
+};
 use async_trait::async_trait;
 use reqwest::Client;
-use reqwest_eventsource::EventSource;
+use std::time::Instant;
 
 /// OpenAI provider
 pub struct OpenAIProvider {
@@ -17,13 +27,16 @@ pub struct OpenAIProvider {
 }
 
 impl OpenAIProvider {
+    /// Maximum number of inputs per embeddings request
+    pub const MAX_EMBED_BATCH: usize = 2048;
+
     /// Create a new OpenAI provider
     pub fn new(config: OpenAIConfig) -> Result<Self> {
         if config.api_key.is_empty() {
             return Err(Error::MissingApiKey("openai".to_string()));
         }
 
-        let client = Client::new();
+        let client = config.http.build_client()?;
         Ok(Self { config, client })
     }
 
@@ -58,10 +71,18 @@ impl Provider for OpenAIProvider {
 
     async fn generate(&self, request: GenerateRequest) -> Result<GenerateResponse> {
         let url = format!("{}/chat/completions", self.config.base_url);
-        let openai_req = to_openai_request(&request, false);
+        let mut openai_req = to_openai_request(&request, false);
+        OpenAIQuirks::default()
+            .for_request(&request)
+            .apply(&mut openai_req);
+        let openai_req = request
+            .options
+            .provider_options
+            .apply(self.provider_id(), &openai_req)?;
 
         let headers = self.build_headers(request.options.headers.as_ref());
 
+        let started = Instant::now();
         let response = self
             .client
             .post(&url)
@@ -71,42 +92,97 @@ impl Provider for OpenAIProvider {
             .await?;
 
         if !response.status().is_success() {
-            let status = response.status();
-            let error_text = response.text().await.unwrap_or_default();
-            return Err(Error::provider_error(format!(
-                "OpenAI API error {}: {}",
-                status, error_text
-            )));
+            return Err(from_response(self.provider_id(), response).await);
         }
 
-        let openai_resp: ChatCompletionResponse = response.json().await?;
-        from_openai_response(openai_resp)
+        let (openai_resp, metadata): (ChatCompletionResponse, _) = read_json(
+            self.provider_id(),
+            response,
+            started,
+            request.options.include_raw_response,
+        )
+        .await?;
+        Ok(from_openai_response(openai_resp, &OpenAIQuirks::default())?
+            .with_http_metadata(metadata))
     }
 
     async fn stream(&self, request: GenerateRequest) -> Result<GenerateStream> {
         let url = format!("{}/chat/completions", self.config.base_url);
-        let openai_req = to_openai_request(&request, true);
+        let mut openai_req = to_openai_request(&request, true);
+        OpenAIQuirks::default()
+            .for_request(&request)
+            .apply(&mut openai_req);
+        let openai_req = request
+            .options
+            .provider_options
+            .apply(self.provider_id(), &openai_req)?;
 
         let headers = self.build_headers(request.options.headers.as_ref());
 
-        let req_builder = self
+        let started = Instant::now();
+        let response = self
             .client
             .post(&url)
             .headers(headers.to_reqwest_headers())
-            .json(&openai_req);
+            .json(&openai_req)
+            .send()
+            .await?;
 
-        let event_source = EventSource::new(req_builder)
-            .map_err(|e| Error::stream_error(format!("Failed to create event source: {}", e)))?;
+        if !response.status().is_success() {
+            return Err(from_response(self.provider_id(), response).await);
+        }
+
+        let metadata = ResponseMetadata::from_http(self.provider_id(), &response, started);
+        Ok(create_stream(response, OpenAIQuirks::default())
+            .await?
+            .with_metadata(metadata, started))
+    }
+
+    async fn embed(&self, request: EmbedRequest) -> Result<EmbedResponse> {
+        let url = format!("{}/embeddings", self.config.base_url);
+        let headers = self.build_headers(request.headers.as_ref());
+
+        let mut result = EmbedResponse::default();
+        for batch in request.input.chunks(Self::MAX_EMBED_BATCH) {
+            let response = self
+                .client
+                .post(&url)
+                .headers(headers.to_reqwest_headers())
+                .json(&to_openai_embedding_request(&request, batch))
+                .send()
+                .await?;
+
+            if !response.status().is_success() {
+                return Err(from_response(self.provider_id(), response).await);
+            }
+
+            let embedding_resp: EmbeddingResponse = response.json().await?;
+            result.extend(from_openai_embedding_response(embedding_resp));
+        }
 
-        create_stream(event_source).await
+        Ok(result)
     }
 
-    async fn list_models(&self) -> Result<Vec<String>> {
-        // Simplified - in production, call /v1/models endpoint
-        Ok(vec![
-            "gpt-4".to_string(),
-            "gpt-4-turbo-preview".to_string(),
-            "gpt-3.5-turbo".to_string(),
-        ])
+    async fn list_models(&self) -> Result<Vec<ModelInfo>> {
+        let url = format!("{}/models", self.config.base_url);
+        let headers = self.build_headers(None);
+
+        let response = self
+            .client
+            .get(&url)
+            .headers(headers.to_reqwest_headers())
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(from_response(self.provider_id(), response).await);
+        }
+
+        let models: ModelList = response.json().await?;
+        Ok(models
+            .data
+            .into_iter()
+            .map(|model| from_openai_model(self.provider_id(), model))
+            .collect())
     }
 }
@@ -1,32 +1,48 @@
 //! OpenAI streaming implementation
 
-use super::types::ChatCompletionChunk;
+use super::convert::{from_content_filter_results, from_openai_usage};
+use super::types::{ChatCompletionChunk, OpenAIQuirks};
 use crate::error::{Error, Result};
-use crate::types::{FinishReason, GenerateStream, StreamEvent, Usage};
+use crate::types::ToolCallAccumulator;
+use crate::types::{
+    ContentFilterResult, ContentFilterResults, FinishReason, GenerateStream, ResponseMetadata,
+    StreamEvent, Usage,
+};
+use eventsource_stream::{EventStreamError, Eventsource};
 use futures::StreamExt;
-use reqwest_eventsource::{Event, EventSource};
+use reqwest::Response;
 
-/// Create a streaming response from OpenAI
-pub async fn create_stream(event_source: EventSource) -> Result<GenerateStream> {
+/// Create a streaming response from OpenAI or a compatible server
+pub async fn create_stream(response: Response, quirks: OpenAIQuirks) -> Result<GenerateStream> {
     let stream = async_stream::stream! {
-        let mut event_stream = event_source;
-        let mut accumulated_usage: Option<Usage> = None;
+        let mut event_stream = response.bytes_stream().eventsource();
+        let mut state = StreamState::default();
 
         while let Some(event) = event_stream.next().await {
             match event {
-                Ok(Event::Open) => {
-                    // Connection opened
-                }
-                Ok(Event::Message(message)) => {
+                Ok(message) => {
                     if message.data == "[DONE]" {
                         break;
                     }
 
-                    match parse_chunk(&message.data, &mut accumulated_usage) {
-                        Ok(Some(event)) => yield Ok(event),
-                        Ok(None) => continue,
+                    match parse_chunk(&message.data, &mut state, &quirks) {
+                        Ok(events) => {
+                            for event in events {
+                                yield Ok(event);
+                            }
+                        }
                         Err(e) => yield Err(e),
                     }
+
+                    if state.usage.is_some() {
+                        for event in state.finish() {
+                            yield Ok(event);
+                        }
+                    }
+                }
+                Err(EventStreamError::Transport(e)) => {
+                    yield Err(Error::HttpError(e));
+                    break;
                 }
                 Err(e) => {
                     yield Err(Error::stream_error(format!("Stream error: {}", e)));
@@ -34,77 +50,178 @@ pub async fn create_stream(event_source: EventSource) -> Result<GenerateStream>
                 }
             }
         }
+
+        // Complete tool calls left open by servers that omit finish_reason
+        for event in state.tool_calls.end_all() {
+            yield Ok(event);
+        }
+
+        // Servers that don't report usage finish at [DONE] or the end of the stream
+        for event in state.finish() {
+            yield Ok(event);
+        }
     };
 
     Ok(GenerateStream::new(Box::pin(stream)))
 }
 
+/// State carried across OpenAI stream chunks
+#[derive(Default)]
+struct StreamState {
+    /// Generation ID from the chunks
+    id: String,
+    usage: Option<Usage>,
+    tool_calls: ToolCallAccumulator,
+    /// Finish reason, held until the trailing usage chunk or the end of the stream
+    finish_reason: Option<FinishReason>,
+    /// Azure OpenAI content filter verdicts seen so far
+    content_filter: Option<ContentFilterResults>,
+}
+
+impl StreamState {
+    /// Emit the held finish, preceded by the content filter verdicts, if any
+    fn finish(&mut self) -> Vec<StreamEvent> {
+        let Some(mut reason) = self.finish_reason.take() else {
+            return Vec::new();
+        };
+
+        let mut events = Vec::new();
+        if let Some(results) = self.content_filter.take() {
+            // Azure OpenAI reports filtered completions in the content filter results
+            if results.completion.iter().any(|r| r.filtered) {
+                reason = FinishReason::ContentFilter;
+            }
+            events.push(StreamEvent::content_filter(&self.id, results));
+        }
+        events.push(StreamEvent::finish(
+            self.usage.clone().unwrap_or_default(),
+            reason,
+        ));
+        events
+    }
+
+    /// Record completion verdicts, keeping a category filtered once any chunk was
+    fn add_completion_filter(&mut self, results: Vec<ContentFilterResult>) {
+        let completion = &mut self
+            .content_filter
+            .get_or_insert_with(Default::default)
+            .completion;
+        for result in results {
+            match completion
+                .iter_mut()
+                .find(|r| r.category == result.category)
+            {
+                Some(existing) if existing.filtered && !result.filtered => {}
+                Some(existing) => *existing = result,
+                None => completion.push(result),
+            }
+        }
+    }
+}
+
 /// Parse a streaming chunk from OpenAI
-fn parse_chunk(data: &str, accumulated_usage: &mut Option<Usage>) -> Result<Option<StreamEvent>> {
+fn parse_chunk(
+    data: &str,
+    state: &mut StreamState,
+    quirks: &OpenAIQuirks,
+) -> Result<Vec<StreamEvent>> {
     let chunk: ChatCompletionChunk = serde_json::from_str(data)
         .map_err(|e| Error::invalid_response(format!("Failed to parse chunk: {}", e)))?;
 
     // Capture usage if present (OpenAI sends this in the final chunk when stream_options.include_usage is true)
     if let Some(chat_usage) = chunk.usage {
-        *accumulated_usage = Some(Usage {
-            prompt_tokens: chat_usage.prompt_tokens,
-            completion_tokens: chat_usage.completion_tokens,
-            total_tokens: chat_usage.total_tokens,
-        });
+        state.usage = Some(from_openai_usage(&chat_usage));
+    }
+
+    // Azure OpenAI sends the prompt verdicts first, in a chunk without choices
+    if !chunk.prompt_filter_results.is_empty() {
+        state
+            .content_filter
+            .get_or_insert_with(Default::default)
+            .prompt = chunk
+            .prompt_filter_results
+            .iter()
+            .flat_map(|result| from_content_filter_results(&result.content_filter_results))
+            .collect();
     }
 
     let choice = match chunk.choices.first() {
         Some(c) => c,
-        None => return Ok(None),
+        None => return Ok(Vec::new()),
     };
 
-    // Handle finish reason
-    if let Some(reason) = &choice.finish_reason {
-        let finish_reason = match reason.as_str() {
-            "stop" => FinishReason::Stop,
-            "length" => FinishReason::Length,
-            "content_filter" => FinishReason::ContentFilter,
-            "tool_calls" => FinishReason::ToolCalls,
-            _ => FinishReason::Other,
-        };
+    if state.id.is_empty() {
+        state.id.clone_from(&chunk.id);
+    }
 
-        return Ok(Some(StreamEvent::finish(
-            accumulated_usage.clone().unwrap_or_default(),
-            finish_reason,
-        )));
+    // Completion verdicts come with each chunk of text, and may trail the finish_reason chunk
+    if let Some(results) = &choice.content_filter_results {
+        state.add_completion_filter(from_content_filter_results(results));
     }
 
-    // Handle tool calls
-    if let Some(tool_calls) = &choice.delta.tool_calls {
-        for tc in tool_calls {
-            if let Some(function) = &tc.function {
-                // Tool call started (has name)
-                if let Some(name) = &function.name {
-                    return Ok(Some(StreamEvent::tool_call_start(
-                        tc.id.clone().unwrap_or_default(),
-                        name,
-                    )));
-                }
-                // Tool call arguments delta
-                if let Some(args) = &function.arguments {
-                    return Ok(Some(StreamEvent::tool_call_delta(
-                        tc.id.clone().unwrap_or_default(),
-                        args,
-                    )));
-                }
-            }
+    let mut events = Vec::new();
+
+    // Handle tool calls - a chunk may carry fragments of several parallel calls,
+    // and only the first fragment of each call has its ID and name
+    for tc in choice.delta.tool_calls.iter().flatten() {
+        let function = tc.function.as_ref();
+
+        if !state.tool_calls.contains(tc.index) {
+            let name = function.and_then(|f| f.name.clone()).unwrap_or_default();
+            events.push(
+                state
+                    .tool_calls
+                    .start(tc.index, tc.id.clone().unwrap_or_default(), name),
+            );
+        }
+
+        if let Some(args) = function.and_then(|f| f.arguments.as_deref()) {
+            events.extend(state.tool_calls.delta(tc.index, args));
+        }
+    }
+
+    // Handle reasoning delta (OpenAI-compatible servers such as DeepSeek)
+    if let Some(reasoning) =
+        quirks.reasoning(&choice.delta.reasoning_content, &choice.delta.reasoning)
+    {
+        if !reasoning.is_empty() {
+            events.push(StreamEvent::reasoning_delta(&chunk.id, reasoning));
         }
     }
 
     // Handle content delta
     if let Some(content) = &choice.delta.content {
-        return Ok(Some(StreamEvent::text_delta(chunk.id, content)));
+        if !content.is_empty() {
+            events.push(StreamEvent::text_delta(&chunk.id, content));
+        }
     }
 
     // Start event (role present but no content)
-    if choice.delta.role.is_some() {
-        return Ok(Some(StreamEvent::start(chunk.id)));
+    if events.is_empty() && choice.delta.role.is_some() && choice.finish_reason.is_none() {
+        events.push(StreamEvent::start_with_metadata(
+            &chunk.id,
+            ResponseMetadata {
+                id: Some(chunk.id.clone()),
+                model: Some(chunk.model.clone()),
+                created: Some(chunk.created),
+                ..Default::default()
+            },
+        ));
+    }
+
+    // Handle finish reason
+    if let Some(reason) = &choice.finish_reason {
+        let finish_reason = match reason.as_str() {
+            "stop" => FinishReason::Stop,
+            "length" => FinishReason::Length,
+            "content_filter" => FinishReason::ContentFilter,
+            "tool_calls" => FinishReason::ToolCalls,
+            _ => FinishReason::Other,
+        };
+
+        events.extend(state.tool_calls.end_all());
+        state.finish_reason = Some(finish_reason);
     }
 
-    Ok(None)
+    Ok(events)
 }
@@ -1,6 +1,13 @@
 //! OpenAI-specific types
 
+use crate::error::Result;
+use crate::http::HttpConfig;
+use crate::types::{GenerateRequest, Headers};
+use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
 
 /// Configuration for OpenAI provider
 #[derive(Debug, Clone)]
@@ -11,6 +18,8 @@ pub struct OpenAIConfig {
     pub base_url: String,
     /// Organization ID (optional)
     pub organization: Option<String>,
+    /// HTTP client settings (timeouts)
+    pub http: HttpConfig,
 }
 
 impl OpenAIConfig {
@@ -20,6 +29,7 @@ impl OpenAIConfig {
             api_key: api_key.into(),
             base_url: "https://api.openai.com/v1".to_string(),
             organization: None,
+            http: HttpConfig::default(),
         }
     }
 
@@ -29,6 +39,12 @@ impl OpenAIConfig {
         self
     }
 
+    /// Set HTTP client settings
+    pub fn with_http_config(mut self, http: HttpConfig) -> Self {
+        self.http = http;
+        self
+    }
+
     /// Set organization
     pub fn with_organization(mut self, org: impl Into<String>) -> Self {
         self.organization = Some(org.into());
@@ -42,6 +58,367 @@ impl Default for OpenAIConfig {
     }
 }
 
+/// Wire-format differences between OpenAI-compatible servers
+///
+/// The default matches the OpenAI API.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OpenAIQuirks {
+    /// Send `stream_options.include_usage` so streams end with token usage
+    pub stream_options: bool,
+    /// Send the output token limit as `max_completion_tokens` instead of `max_tokens`
+    pub max_completion_tokens: bool,
+    /// Send `reasoning_effort` when reasoning is requested
+    pub reasoning_effort: bool,
+    /// Read reasoning text from `reasoning_content` (DeepSeek, xAI, vLLM)
+    /// instead of `reasoning` (Groq, Ollama)
+    pub reasoning_content: bool,
+}
+
+impl Default for OpenAIQuirks {
+    fn default() -> Self {
+        Self {
+            stream_options: true,
+            max_completion_tokens: false,
+            reasoning_effort: true,
+            reasoning_content: true,
+        }
+    }
+}
+
+impl OpenAIQuirks {
+    /// Rewrite a chat completion request for the server
+    pub(crate) fn apply(&self, request: &mut ChatCompletionRequest) {
+        if self.stream_options && request.stream == Some(true) {
+            request.stream_options = Some(StreamOptions {
+                include_usage: true,
+            });
+        }
+        if self.max_completion_tokens {
+            request.max_completion_tokens = request.max_tokens.take();
+        }
+        if !self.reasoning_effort {
+            request.reasoning_effort = None;
+        }
+    }
+
+    /// Quirks for a request to OpenAI or Azure OpenAI, whose reasoning models
+    /// (o-series, gpt-5) reject `max_tokens`
+    pub(crate) fn for_request(self, request: &GenerateRequest) -> Self {
+        Self {
+            max_completion_tokens: self.max_completion_tokens
+                || request.options.reasoning.is_some(),
+            ..self
+        }
+    }
+
+    /// Pick the reasoning text from a message or delta
+    pub(crate) fn reasoning<'a>(
+        &self,
+        reasoning_content: &'a Option<String>,
+        reasoning: &'a Option<String>,
+    ) -> Option<&'a str> {
+        if self.reasoning_content {
+            reasoning_content.as_deref()
+        } else {
+            reasoning.as_deref()
+        }
+    }
+}
+
+/// How an OpenAI-compatible server expects the API key
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OpenAICompatibleAuth {
+    /// `Authorization: Bearer <key>`
+    Bearer,
+    /// The key as the value of a custom header (e.g., "api-key")
+    Header(String),
+    /// No authentication (e.g., a local server)
+    None,
+}
+
+/// Configuration for an OpenAI-compatible provider
+#[derive(Debug, Clone)]
+pub struct OpenAICompatibleConfig {
+    /// Provider ID reported in responses and errors (e.g., "groq")
+    pub provider_id: String,
+    /// API key (may be empty when `auth` is [`OpenAICompatibleAuth::None`])
+    pub api_key: String,
+    /// Base URL, including the version prefix (e.g., https://api.groq.com/openai/v1)
+    pub base_url: String,
+    /// Authentication scheme
+    pub auth: OpenAICompatibleAuth,
+    /// Wire-format quirks of the server
+    pub quirks: OpenAIQuirks,
+    /// Headers sent with every request
+    pub headers: Headers,
+    /// HTTP client settings (timeouts)
+    pub http: HttpConfig,
+}
+
+impl OpenAICompatibleConfig {
+    /// Create a config for a server using bearer authentication and OpenAI's wire format
+    pub fn new(provider_id: impl Into<String>, base_url: impl Into<String>) -> Self {
+        Self {
+            provider_id: provider_id.into(),
+            api_key: String::new(),
+            base_url: base_url.into(),
+            auth: OpenAICompatibleAuth::Bearer,
+            quirks: OpenAIQuirks::default(),
+            headers: Headers::new(),
+            http: HttpConfig::default(),
+        }
+    }
+
+    /// Mistral (https://api.mistral.ai/v1)
+    pub fn mistral(api_key: impl Into<String>) -> Self {
+        Self::new("mistral", "https://api.mistral.ai/v1")
+            .with_api_key(api_key)
+            .with_quirks(OpenAIQuirks {
+                stream_options: false,
+                reasoning_effort: false,
+                ..Default::default()
+            })
+    }
+
+    /// xAI (https://api.x.ai/v1)
+    pub fn xai(api_key: impl Into<String>) -> Self {
+        Self::new("xai", "https://api.x.ai/v1")
+            .with_api_key(api_key)
+            .with_quirks(OpenAIQuirks {
+                max_completion_tokens: true,
+                ..Default::default()
+            })
+    }
+
+    /// Groq (https://api.groq.com/openai/v1)
+    pub fn groq(api_key: impl Into<String>) -> Self {
+        Self::new("groq", "https://api.groq.com/openai/v1")
+            .with_api_key(api_key)
+            .with_quirks(OpenAIQuirks {
+                max_completion_tokens: true,
+                reasoning_content: false,
+                ..Default::default()
+            })
+    }
+
+    /// DeepSeek (https://api.deepseek.com/v1)
+    pub fn deepseek(api_key: impl Into<String>) -> Self {
+        Self::new("deepseek", "https://api.deepseek.com/v1")
+            .with_api_key(api_key)
+            .with_quirks(OpenAIQuirks {
+                reasoning_effort: false,
+                ..Default::default()
+            })
+    }
+
+    /// Ollama on its default local address (http://localhost:11434/v1)
+    pub fn ollama() -> Self {
+        Self::new("ollama", "http://localhost:11434/v1")
+            .with_auth(OpenAICompatibleAuth::None)
+            .with_quirks(OpenAIQuirks {
+                reasoning_content: false,
+                ..Default::default()
+            })
+    }
+
+    /// vLLM server at `base_url` (e.g., http://localhost:8000/v1)
+    pub fn vllm(base_url: impl Into<String>) -> Self {
+        Self::new("vllm", base_url).with_auth(OpenAICompatibleAuth::None)
+    }
+
+    /// Set API key
+    pub fn with_api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key = api_key.into();
+        self
+    }
+
+    /// Set base URL
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into();
+        self
+    }
+
+    /// Set authentication scheme
+    pub fn with_auth(mut self, auth: OpenAICompatibleAuth) -> Self {
+        self.auth = auth;
+        self
+    }
+
+    /// Set wire-format quirks
+    pub fn with_quirks(mut self, quirks: OpenAIQuirks) -> Self {
+        self.quirks = quirks;
+        self
+    }
+
+    /// Add a header sent with every request
+    pub fn with_header(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.insert(key, value);
+        self
+    }
+
+    /// Set HTTP client settings
+    pub fn with_http_config(mut self, http: HttpConfig) -> Self {
+        self.http = http;
+        self
+    }
+
+    /// Configs for the vendors set up in the environment
+    ///
+    /// Reads `MISTRAL_API_KEY`, `XAI_API_KEY`, `GROQ_API_KEY`, `DEEPSEEK_API_KEY`,
+    /// `OLLAMA_BASE_URL`, and `VLLM_BASE_URL` (with an optional `VLLM_API_KEY`).
+    pub(crate) fn presets_from_env() -> Vec<Self> {
+        let var = |name: &str| std::env::var(name).ok().filter(|value| !value.is_empty());
+        let mut configs = Vec::new();
+
+        if let Some(api_key) = var("MISTRAL_API_KEY") {
+            configs.push(Self::mistral(api_key));
+        }
+        if let Some(api_key) = var("XAI_API_KEY") {
+            configs.push(Self::xai(api_key));
+        }
+        if let Some(api_key) = var("GROQ_API_KEY") {
+            configs.push(Self::groq(api_key));
+        }
+        if let Some(api_key) = var("DEEPSEEK_API_KEY") {
+            configs.push(Self::deepseek(api_key));
+        }
+        if let Some(base_url) = var("OLLAMA_BASE_URL") {
+            configs.push(Self::ollama().with_base_url(base_url));
+        }
+        if let Some(base_url) = var("VLLM_BASE_URL") {
+            let config = Self::vllm(base_url);
+            configs.push(match var("VLLM_API_KEY") {
+                Some(api_key) => config
+                    .with_auth(OpenAICompatibleAuth::Bearer)
+                    .with_api_key(api_key),
+                None => config,
+            });
+        }
+
+        configs
+    }
+}
+
+/// Source of Microsoft Entra ID access tokens for Azure OpenAI
+///
+/// Called before every request; implementations should cache tokens.
+#[async_trait]
+pub trait AzureTokenProvider: Send + Sync {
+    /// Get a token for the `https://cognitiveservices.azure.com/.default` scope
+    async fn token(&self) -> Result<String>;
+}
+
+/// Authentication for Azure OpenAI
+#[derive(Clone)]
+pub enum AzureOpenAIAuth {
+    /// The resource key, sent in the `api-key` header
+    ApiKey(String),
+    /// Microsoft Entra ID tokens, sent as `Authorization: Bearer`
+    EntraId(Arc<dyn AzureTokenProvider>),
+}
+
+impl std::fmt::Debug for AzureOpenAIAuth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ApiKey(_) => f.write_str("ApiKey(..)"),
+            Self::EntraId(_) => f.write_str("EntraId(..)"),
+        }
+    }
+}
+
+/// Configuration for Azure OpenAI
+#[derive(Debug, Clone)]
+pub struct AzureOpenAIConfig {
+    /// Resource endpoint (e.g., https://my-resource.openai.azure.com)
+    pub endpoint: String,
+    /// Deployment name for each model; other models are used as deployment names
+    pub deployments: HashMap<String, String>,
+    /// API version (default: 2024-10-21)
+    pub api_version: String,
+    /// Authentication scheme
+    pub auth: AzureOpenAIAuth,
+    /// Wire-format quirks of the deployed models
+    pub quirks: OpenAIQuirks,
+    /// HTTP client settings (timeouts)
+    pub http: HttpConfig,
+}
+
+impl AzureOpenAIConfig {
+    /// Default API version
+    pub const DEFAULT_API_VERSION: &'static str = "2024-10-21";
+
+    /// Create new config for a resource endpoint, authenticated with the resource key
+    pub fn new(endpoint: impl Into<String>, api_key: impl Into<String>) -> Self {
+        Self {
+            endpoint: endpoint.into(),
+            deployments: HashMap::new(),
+            api_version: Self::DEFAULT_API_VERSION.to_string(),
+            auth: AzureOpenAIAuth::ApiKey(api_key.into()),
+            quirks: OpenAIQuirks::default(),
+            http: HttpConfig::default(),
+        }
+    }
+
+    /// Create new config for a resource name (https://{resource}.openai.azure.com)
+    pub fn from_resource(resource: impl AsRef<str>, api_key: impl Into<String>) -> Self {
+        Self::new(
+            format!("https://{}.openai.azure.com", resource.as_ref()),
+            api_key,
+        )
+    }
+
+    /// Create config from `AZURE_OPENAI_ENDPOINT`, `AZURE_OPENAI_API_KEY` and,
+    /// optionally, `AZURE_OPENAI_API_VERSION`
+    pub(crate) fn from_env() -> Option<Self> {
+        let var = |name: &str| std::env::var(name).ok().filter(|value| !value.is_empty());
+
+        let config = Self::new(var("AZURE_OPENAI_ENDPOINT")?, var("AZURE_OPENAI_API_KEY")?);
+        Some(match var("AZURE_OPENAI_API_VERSION") {
+            Some(api_version) => config.with_api_version(api_version),
+            None => config,
+        })
+    }
+
+    /// Serve `model` from `deployment`
+    pub fn with_deployment(
+        mut self,
+        model: impl Into<String>,
+        deployment: impl Into<String>,
+    ) -> Self {
+        self.deployments.insert(model.into(), deployment.into());
+        self
+    }
+
+    /// Set API version
+    pub fn with_api_version(mut self, api_version: impl Into<String>) -> Self {
+        self.api_version = api_version.into();
+        self
+    }
+
+    /// Authenticate with Microsoft Entra ID tokens instead of the resource key
+    pub fn with_token_provider(mut self, provider: impl AzureTokenProvider + 'static) -> Self {
+        self.auth = AzureOpenAIAuth::EntraId(Arc::new(provider));
+        self
+    }
+
+    /// Set wire-format quirks
+    pub fn with_quirks(mut self, quirks: OpenAIQuirks) -> Self {
+        self.quirks = quirks;
+        self
+    }
+
+    /// Set HTTP client settings
+    pub fn with_http_config(mut self, http: HttpConfig) -> Self {
+        self.http = http;
+        self
+    }
+
+    /// Deployment serving `model`
+    pub fn deployment<'a>(&'a self, model: &'a str) -> &'a str {
+        self.deployments.get(model).map_or(model, String::as_str)
+    }
+}
+
 /// OpenAI chat completion request
 #[derive(Debug, Serialize)]
 pub struct ChatCompletionRequest {
@@ -52,15 +429,34 @@ pub struct ChatCompletionRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub max_tokens: Option<u32>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_completion_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub top_p: Option<f32>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub frequency_penalty: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub presence_penalty: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub stop: Option<Vec<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub stream: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub stream_options: Option<StreamOptions>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub tools: Option<Vec<serde_json::Value>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tool_choice: Option<serde_json::Value>,
+    /// Reasoning effort for reasoning models ("low" | "medium" | "high")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reasoning_effort: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response_format: Option<serde_json::Value>,
+}
+
+/// OpenAI streaming options
+#[derive(Debug, Serialize)]
+pub struct StreamOptions {
+    pub include_usage: bool,
 }
 
 /// OpenAI chat message
@@ -75,6 +471,12 @@ pub struct ChatMessage {
     pub tool_calls: Option<Vec<OpenAIToolCall>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tool_call_id: Option<String>,
+    /// Reasoning text returned by some OpenAI-compatible servers (e.g. DeepSeek)
+    #[serde(default, skip_serializing)]
+    pub reasoning_content: Option<String>,
+    /// Reasoning text returned by other OpenAI-compatible servers (e.g. Groq)
+    #[serde(default, skip_serializing)]
+    pub reasoning: Option<String>,
 }
 
 /// OpenAI tool call
@@ -103,6 +505,17 @@ pub struct ChatCompletionResponse {
     pub model: String,
     pub choices: Vec<ChatChoice>,
     pub usage: ChatUsage,
+    /// Azure OpenAI content filter verdicts for the prompt
+    #[serde(default)]
+    pub prompt_filter_results: Vec<PromptFilterResult>,
+}
+
+/// Azure OpenAI content filter verdicts for one prompt
+#[derive(Debug, Deserialize)]
+pub struct PromptFilterResult {
+    /// Verdicts keyed by category
+    #[serde(default)]
+    pub content_filter_results: Map<String, Value>,
 }
 
 /// OpenAI chat choice
@@ -112,6 +525,9 @@ pub struct ChatChoice {
     pub index: u32,
     pub message: ChatMessage,
     pub finish_reason: Option<String>,
+    /// Azure OpenAI content filter verdicts keyed by category
+    #[serde(default)]
+    pub content_filter_results: Option<Map<String, Value>>,
 }
 
 /// OpenAI usage statistics
@@ -120,6 +536,24 @@ pub struct ChatUsage {
     pub prompt_tokens: u32,
     pub completion_tokens: u32,
     pub total_tokens: u32,
+    #[serde(default)]
+    pub prompt_tokens_details: Option<PromptTokensDetails>,
+    #[serde(default)]
+    pub completion_tokens_details: Option<CompletionTokensDetails>,
+}
+
+/// Breakdown of OpenAI prompt tokens
+#[derive(Debug, Deserialize)]
+pub struct PromptTokensDetails {
+    #[serde(default)]
+    pub cached_tokens: u32,
+}
+
+/// Breakdown of OpenAI completion tokens
+#[derive(Debug, Deserialize)]
+pub struct CompletionTokensDetails {
+    #[serde(default)]
+    pub reasoning_tokens: u32,
 }
 
 /// OpenAI streaming chunk
@@ -133,6 +567,9 @@ pub struct ChatCompletionChunk {
     pub choices: Vec<ChunkChoice>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub usage: Option<ChatUsage>,
+    /// Azure OpenAI content filter verdicts for the prompt, sent in the first chunk
+    #[serde(default)]
+    pub prompt_filter_results: Vec<PromptFilterResult>,
 }
 
 /// OpenAI chunk choice
@@ -140,17 +577,28 @@ pub struct ChatCompletionChunk {
 #[allow(dead_code)]
 pub struct ChunkChoice {
     pub index: u32,
+    /// Absent from Azure OpenAI chunks that only carry content filter verdicts
+    #[serde(default)]
     pub delta: ChatDelta,
     pub finish_reason: Option<String>,
+    /// Azure OpenAI content filter verdicts keyed by category
+    #[serde(default)]
+    pub content_filter_results: Option<Map<String, Value>>,
 }
 
 /// OpenAI delta content
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Deserialize)]
 pub struct ChatDelta {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub role: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub content: Option<String>,
+    /// Reasoning text delta from some OpenAI-compatible servers (e.g. DeepSeek)
+    #[serde(default)]
+    pub reasoning_content: Option<String>,
+    /// Reasoning text delta from other OpenAI-compatible servers (e.g. Groq)
+    #[serde(default)]
+    pub reasoning: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tool_calls: Option<Vec<OpenAIToolCallDelta>>,
 }
@@ -176,3 +624,50 @@ pub struct OpenAIFunctionCallDelta {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub arguments: Option<String>,
 }
+
+/// OpenAI embeddings request
+#[derive(Debug, Serialize)]
+pub struct EmbeddingRequest {
+    pub model: String,
+    pub input: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dimensions: Option<u32>,
+    pub encoding_format: String, // "float"
+}
+
+/// OpenAI embeddings response
+#[derive(Debug, Deserialize)]
+pub struct EmbeddingResponse {
+    pub data: Vec<EmbeddingData>,
+    #[serde(default)]
+    pub usage: Option<EmbeddingUsage>,
+}
+
+/// A single embedding in an OpenAI embeddings response
+#[derive(Debug, Deserialize)]
+pub struct EmbeddingData {
+    pub index: u32,
+    pub embedding: Vec<f32>,
+}
+
+/// OpenAI embeddings usage statistics
+#[derive(Debug, Deserialize)]
+pub struct EmbeddingUsage {
+    pub prompt_tokens: u32,
+    pub total_tokens: u32,
+}
+
+/// OpenAI model list response
+#[derive(Debug, Deserialize)]
+pub struct ModelList {
+    pub data: Vec<OpenAIModel>,
+}
+
+/// OpenAI model
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+pub struct OpenAIModel {
+    pub id: String,
+    #[serde(default)]
+    pub owned_by: Option<String>,
+}
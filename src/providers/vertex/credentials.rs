@@ -0,0 +1,51 @@
+//! Google Cloud service account keys
+
+use crate::error::{Error, Result};
+use serde::Deserialize;
+use std::path::Path;
+
+/// Service account key, as downloaded from the Google Cloud console
+#[derive(Debug, Clone, Deserialize)]
+pub struct ServiceAccountKey {
+    /// Service account email, the issuer of token requests
+    pub client_email: String,
+    /// RSA private key in PKCS#8 PEM format
+    pub private_key: String,
+    /// ID of the private key
+    #[serde(default)]
+    pub private_key_id: Option<String>,
+    /// Project the service account belongs to
+    #[serde(default)]
+    pub project_id: Option<String>,
+    /// OAuth token endpoint
+    #[serde(default = "default_token_uri")]
+    pub token_uri: String,
+}
+
+fn default_token_uri() -> String {
+    "https://oauth2.googleapis.com/token".to_string()
+}
+
+impl ServiceAccountKey {
+    /// Parse a JSON key
+    pub fn from_json(json: &str) -> Result<Self> {
+        serde_json::from_str(json)
+            .map_err(|e| Error::ConfigError(format!("invalid service account key: {}", e)))
+    }
+
+    /// Read a JSON key file
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| Error::ConfigError(format!("cannot read {}: {}", path.display(), e)))?;
+        Self::from_json(&contents)
+    }
+
+    /// Read the key file named by `GOOGLE_APPLICATION_CREDENTIALS`
+    pub fn from_env() -> Result<Self> {
+        let path = std::env::var("GOOGLE_APPLICATION_CREDENTIALS").map_err(|_| {
+            Error::ConfigError("GOOGLE_APPLICATION_CREDENTIALS is not set".to_string())
+        })?;
+        Self::from_file(path)
+    }
+}
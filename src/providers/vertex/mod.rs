@@ -0,0 +1,9 @@
+//! Google Vertex AI support shared by the Gemini and Anthropic providers
+
+mod auth;
+mod credentials;
+mod types;
+
+pub(crate) use auth::TokenCache;
+pub use credentials::ServiceAccountKey;
+pub use types::VertexConfig;
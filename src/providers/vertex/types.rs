@@ -0,0 +1,82 @@
+//! Vertex AI configuration
+
+use super::credentials::ServiceAccountKey;
+use crate::error::{Error, Result};
+
+/// Vertex AI project, location and service account
+#[derive(Debug, Clone)]
+pub struct VertexConfig {
+    /// Google Cloud project ID
+    pub project_id: String,
+    /// Region (e.g., us-central1) or `global`
+    pub location: String,
+    /// Service account used to obtain access tokens
+    pub credentials: ServiceAccountKey,
+    /// Base URL (default: https://{location}-aiplatform.googleapis.com/v1)
+    pub base_url: String,
+    /// OAuth token endpoint (default: the key's `token_uri`)
+    pub token_url: String,
+}
+
+impl VertexConfig {
+    /// Create new config for a project and location
+    pub fn new(
+        project_id: impl Into<String>,
+        location: impl Into<String>,
+        credentials: ServiceAccountKey,
+    ) -> Self {
+        let location = location.into();
+        let base_url = if location == "global" {
+            "https://aiplatform.googleapis.com/v1".to_string()
+        } else {
+            format!("https://{}-aiplatform.googleapis.com/v1", location)
+        };
+
+        Self {
+            project_id: project_id.into(),
+            location,
+            token_url: credentials.token_uri.clone(),
+            credentials,
+            base_url,
+        }
+    }
+
+    /// Create config from `GOOGLE_APPLICATION_CREDENTIALS`, `GOOGLE_CLOUD_PROJECT`
+    /// (default: the key's project) and `GOOGLE_CLOUD_LOCATION` (default: us-central1)
+    pub fn from_env() -> Result<Self> {
+        let credentials = ServiceAccountKey::from_env()?;
+        let project_id = std::env::var("GOOGLE_CLOUD_PROJECT")
+            .ok()
+            .or_else(|| credentials.project_id.clone())
+            .ok_or_else(|| Error::ConfigError("GOOGLE_CLOUD_PROJECT is not set".to_string()))?;
+        let location =
+            std::env::var("GOOGLE_CLOUD_LOCATION").unwrap_or_else(|_| "us-central1".to_string());
+
+        Ok(Self::new(project_id, location, credentials))
+    }
+
+    /// Set base URL
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into();
+        self
+    }
+
+    /// Set OAuth token endpoint
+    pub fn with_token_url(mut self, token_url: impl Into<String>) -> Self {
+        self.token_url = token_url.into();
+        self
+    }
+
+    /// Build URL for a publisher model method, e.g. `generateContent`
+    pub(crate) fn model_url(&self, publisher: &str, model: &str, method: &str) -> String {
+        format!(
+            "{}/projects/{}/locations/{}/publishers/{}/models/{}:{}",
+            self.base_url.trim_end_matches('/'),
+            self.project_id,
+            self.location,
+            publisher,
+            model,
+            method
+        )
+    }
+}
@@ -1,7 +1,8 @@
 //! Provider registry for runtime provider management
 
 use crate::error::{Error, Result};
-use crate::provider::Provider;
+use crate::http::HttpConfig;
+use crate::provider::{LayeredProvider, Provider, ProviderLayer};
 use std::collections::HashMap;
 use std::sync::Arc;
 
@@ -24,6 +25,18 @@ impl ProviderRegistry {
         self
     }
 
+    /// Wrap every registered provider with a layer
+    pub fn layer(mut self, layer: Arc<dyn ProviderLayer>) -> Self {
+        for (id, provider) in self.providers.iter_mut() {
+            *provider = Arc::new(LayeredProvider::new(
+                id.clone(),
+                provider.clone(),
+                layer.clone(),
+            ));
+        }
+        self
+    }
+
     /// Get a provider by ID
     pub fn get_provider(&self, id: &str) -> Result<Arc<dyn Provider>> {
         self.providers
@@ -41,17 +54,18 @@ impl ProviderRegistry {
     pub fn has_provider(&self, id: &str) -> bool {
         self.providers.contains_key(id)
     }
-}
 
-impl Default for ProviderRegistry {
-    fn default() -> Self {
+    /// Create a registry with the providers whose API keys are set in the environment
+    pub(crate) fn from_env(http: &HttpConfig) -> Self {
         let mut registry = Self::new();
 
         // Register OpenAI if API key is available
         use crate::providers::openai::{OpenAIConfig, OpenAIProvider};
         if let Ok(api_key) = std::env::var("OPENAI_API_KEY") {
             if !api_key.is_empty() {
-                if let Ok(provider) = OpenAIProvider::new(OpenAIConfig::new(api_key)) {
+                if let Ok(provider) =
+                    OpenAIProvider::new(OpenAIConfig::new(api_key).with_http_config(http.clone()))
+                {
                     registry = registry.register("openai", provider);
                 }
             }
@@ -61,7 +75,9 @@ impl Default for ProviderRegistry {
         use crate::providers::anthropic::{AnthropicConfig, AnthropicProvider};
         if let Ok(api_key) = std::env::var("ANTHROPIC_API_KEY") {
             if !api_key.is_empty() {
-                if let Ok(provider) = AnthropicProvider::new(AnthropicConfig::new(api_key)) {
+                if let Ok(provider) = AnthropicProvider::new(
+                    AnthropicConfig::new(api_key).with_http_config(http.clone()),
+                ) {
                     registry = registry.register("anthropic", provider);
                 }
             }
@@ -71,12 +87,59 @@ impl Default for ProviderRegistry {
         use crate::providers::gemini::{GeminiConfig, GeminiProvider};
         if let Ok(api_key) = std::env::var("GEMINI_API_KEY") {
             if !api_key.is_empty() {
-                if let Ok(provider) = GeminiProvider::new(GeminiConfig::new(api_key)) {
+                if let Ok(provider) =
+                    GeminiProvider::new(GeminiConfig::new(api_key).with_http_config(http.clone()))
+                {
                     registry = registry.register("google", provider);
                 }
             }
         }
 
+        // Register Cohere if API key is available
+        use crate::providers::cohere::{CohereConfig, CohereProvider};
+        if let Ok(api_key) = std::env::var("COHERE_API_KEY") {
+            if !api_key.is_empty() {
+                if let Ok(provider) =
+                    CohereProvider::new(CohereConfig::new(api_key).with_http_config(http.clone()))
+                {
+                    registry = registry.register("cohere", provider);
+                }
+            }
+        }
+
+        // Register Bedrock if an AWS region and credentials are available
+        use crate::providers::bedrock::{BedrockConfig, BedrockProvider};
+        if let Ok(config) = BedrockConfig::from_env() {
+            if let Ok(provider) = BedrockProvider::new(config.with_http_config(http.clone())) {
+                registry = registry.register("bedrock", provider);
+            }
+        }
+
+        // Register Azure OpenAI if an endpoint and key are available
+        use crate::providers::openai::{AzureOpenAIConfig, AzureOpenAIProvider};
+        if let Some(config) = AzureOpenAIConfig::from_env() {
+            if let Ok(provider) = AzureOpenAIProvider::new(config.with_http_config(http.clone())) {
+                registry = registry.register("azure", provider);
+            }
+        }
+
+        // Register OpenAI-compatible vendors (Mistral, xAI, Groq, ...) that are configured
+        use crate::providers::openai::{OpenAICompatibleConfig, OpenAICompatibleProvider};
+        for config in OpenAICompatibleConfig::presets_from_env() {
+            let id = config.provider_id.clone();
+            if let Ok(provider) =
+                OpenAICompatibleProvider::new(config.with_http_config(http.clone()))
+            {
+                registry = registry.register(id, provider);
+            }
+        }
+
         registry
     }
 }
+
+impl Default for ProviderRegistry {
+    fn default() -> Self {
+        Self::from_env(&HttpConfig::default())
+    }
+}
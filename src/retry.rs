@@ -0,0 +1,346 @@
+//! Retry policy with exponential backoff
+//!
+//! Transient failures (rate limits, server errors, overloaded providers and
+//! dropped connections) are retried with exponential backoff. Delays requested
+//! by the provider through `Retry-After` / `retry-after-ms` headers take
+//! precedence over the computed backoff; a request whose provider asks to wait
+//! longer than the policy's maximum delay is not retried.
+//!
+//! Clients don't retry unless a policy is set with `with_retry`.
+
+use crate::error::{Error, Result};
+use crate::types::GenerateStream;
+use reqwest::header::HeaderMap;
+use std::future::Future;
+use std::time::{Duration, SystemTime};
+
+/// Retry policy for provider requests
+///
+/// # Example
+///
+/// ```rust
+/// use stakai::retry::RetryPolicy;
+/// use std::time::Duration;
+///
+/// let policy = RetryPolicy::new()
+///     .with_max_attempts(5)
+///     .with_base_delay(Duration::from_millis(250))
+///     .with_max_delay(Duration::from_secs(10));
+/// ```
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Maximum number of attempts, including the first one (1 disables retries)
+    pub max_attempts: u32,
+    /// Delay before the first retry; doubled on every subsequent retry
+    pub base_delay: Duration,
+    /// Upper bound for any delay; failures asking for a longer `Retry-After` are not retried
+    pub max_delay: Duration,
+    /// Randomize delays to avoid synchronized retries across clients
+    pub jitter: bool,
+    /// Retry on rate limit responses (HTTP 429)
+    pub retry_rate_limits: bool,
+    /// Retry on server errors (HTTP 5xx)
+    pub retry_server_errors: bool,
+    /// Retry when the provider reports it is overloaded (e.g., Anthropic `overloaded_error`)
+    pub retry_overloaded: bool,
+    /// Retry when the connection could not be established or was reset
+    pub retry_connection_errors: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(8),
+            jitter: true,
+            retry_rate_limits: true,
+            retry_server_errors: true,
+            retry_overloaded: true,
+            retry_connection_errors: true,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Create the default retry policy (3 attempts, 500ms base delay)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a policy that never retries
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Self::default()
+        }
+    }
+
+    /// Set maximum number of attempts (including the first one)
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Set base backoff delay
+    pub fn with_base_delay(mut self, delay: Duration) -> Self {
+        self.base_delay = delay;
+        self
+    }
+
+    /// Set maximum backoff delay
+    pub fn with_max_delay(mut self, delay: Duration) -> Self {
+        self.max_delay = delay;
+        self
+    }
+
+    /// Enable or disable jitter
+    pub fn with_jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Enable or disable retrying rate limit responses
+    pub fn retry_rate_limits(mut self, enabled: bool) -> Self {
+        self.retry_rate_limits = enabled;
+        self
+    }
+
+    /// Enable or disable retrying server errors
+    pub fn retry_server_errors(mut self, enabled: bool) -> Self {
+        self.retry_server_errors = enabled;
+        self
+    }
+
+    /// Enable or disable retrying overloaded responses
+    pub fn retry_overloaded(mut self, enabled: bool) -> Self {
+        self.retry_overloaded = enabled;
+        self
+    }
+
+    /// Enable or disable retrying connection failures
+    pub fn retry_connection_errors(mut self, enabled: bool) -> Self {
+        self.retry_connection_errors = enabled;
+        self
+    }
+
+    /// Check if an error should be retried under this policy
+    pub fn is_retryable(&self, error: &Error) -> bool {
+        match error {
+            Error::RateLimitExceeded(_) => self.retry_rate_limits,
+            Error::Overloaded(_) => self.retry_overloaded,
+            Error::Api(e) => e.status >= 500 && self.retry_server_errors,
+            _ => error.is_connection_error() && self.retry_connection_errors,
+        }
+    }
+
+    /// Delay before the given retry (1-based), honoring any provider-requested delay
+    ///
+    /// Never longer than `max_delay`.
+    pub fn delay_for(&self, retry: u32, error: &Error) -> Duration {
+        if let Some(retry_after) = error.retry_after() {
+            return retry_after.min(self.max_delay);
+        }
+
+        let exponent = retry.saturating_sub(1).min(31);
+        let delay = self
+            .base_delay
+            .saturating_mul(1 << exponent)
+            .min(self.max_delay);
+
+        if self.jitter {
+            // Equal jitter: keep half of the delay, randomize the other half
+            delay / 2 + delay.mul_f64(random_fraction() / 2.0)
+        } else {
+            delay
+        }
+    }
+
+    /// Run an operation, retrying retryable failures
+    pub(crate) async fn run<T, F, Fut>(&self, mut operation: F) -> Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let mut attempt = 1;
+        loop {
+            match operation().await {
+                Ok(value) => return Ok(value),
+                Err(error) => {
+                    if attempt >= self.max_attempts || !self.is_retryable(&error) {
+                        return Err(error);
+                    }
+                    // Retrying sooner than the provider asked is futile
+                    if error
+                        .retry_after()
+                        .is_some_and(|delay| delay > self.max_delay)
+                    {
+                        return Err(error);
+                    }
+                    tokio::time::sleep(self.delay_for(attempt, &error)).await;
+                    attempt += 1;
+                }
+            }
+        }
+    }
+
+    /// Open a stream, retrying retryable failures until its first event is received
+    ///
+    /// `Start` events don't count, as they carry no content. Once the first
+    /// event has been received it is handed to the caller, and any later
+    /// failure is surfaced through the stream without retrying.
+    pub(crate) async fn run_stream<F, Fut>(&self, mut open: F) -> Result<GenerateStream>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<GenerateStream>>,
+    {
+        self.run(|| {
+            let opening = open();
+            async move { opening.await?.ready().await }
+        })
+        .await
+    }
+}
+
+/// Parse the provider-requested retry delay from response headers
+///
+/// Supports `retry-after-ms` (milliseconds) and `retry-after` (seconds or an HTTP date).
+pub(crate) fn parse_retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let header = |name: &str| headers.get(name).and_then(|v| v.to_str().ok());
+
+    // Negative, non-finite and out of range values are ignored
+    if let Some(ms) = header("retry-after-ms").and_then(|v| v.trim().parse::<f64>().ok()) {
+        if let Ok(delay) = Duration::try_from_secs_f64(ms / 1000.0) {
+            return Some(delay);
+        }
+    }
+
+    let value = header("retry-after")?.trim();
+    if let Ok(seconds) = value.parse::<f64>() {
+        return Duration::try_from_secs_f64(seconds).ok();
+    }
+
+    httpdate::parse_http_date(value)
+        .ok()
+        .map(|date| date.duration_since(SystemTime::now()).unwrap_or_default())
+}
+
+/// Random value in `[0, 1)` without pulling in an RNG dependency
+fn random_fraction() -> f64 {
+    use std::hash::{BuildHasher, Hasher};
+
+    let mut hasher = std::collections::hash_map::RandomState::new().build_hasher();
+    hasher.write_u128(
+        SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos(),
+    );
+    (hasher.finish() >> 11) as f64 / (1u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ApiError;
+    use reqwest::header::HeaderValue;
+
+    fn api_error(status: u16) -> Error {
+        ApiError::new("test", status, "error").into()
+    }
+
+    #[test]
+    fn test_retryable_classification() {
+        let policy = RetryPolicy::new();
+
+        assert!(policy.is_retryable(&api_error(429)));
+        assert!(policy.is_retryable(&api_error(500)));
+        assert!(policy.is_retryable(&api_error(529)));
+        assert!(!policy.is_retryable(&api_error(400)));
+        assert!(!policy.is_retryable(&api_error(401)));
+        assert!(!policy.is_retryable(&Error::invalid_response("bad json")));
+
+        let overloaded = Error::Overloaded(Box::new(ApiError::new("anthropic", 529, "Overloaded")));
+        assert!(policy.is_retryable(&overloaded));
+        assert!(!policy
+            .clone()
+            .retry_overloaded(false)
+            .is_retryable(&overloaded));
+        assert!(!policy
+            .retry_server_errors(false)
+            .is_retryable(&api_error(503)));
+    }
+
+    #[test]
+    fn test_exponential_backoff() {
+        let policy = RetryPolicy::new()
+            .with_base_delay(Duration::from_millis(100))
+            .with_max_delay(Duration::from_millis(350))
+            .with_jitter(false);
+        let error = api_error(500);
+
+        assert_eq!(policy.delay_for(1, &error), Duration::from_millis(100));
+        assert_eq!(policy.delay_for(2, &error), Duration::from_millis(200));
+        assert_eq!(policy.delay_for(3, &error), Duration::from_millis(350));
+    }
+
+    #[test]
+    fn test_jitter_bounds() {
+        let policy = RetryPolicy::new().with_base_delay(Duration::from_millis(100));
+        let delay = policy.delay_for(1, &api_error(500));
+
+        assert!(delay >= Duration::from_millis(50));
+        assert!(delay <= Duration::from_millis(100));
+    }
+
+    #[test]
+    fn test_retry_after_overrides_backoff() {
+        let error: Error = ApiError::new("test", 429, "slow down")
+            .with_retry_after(Duration::from_secs(3))
+            .into();
+
+        assert_eq!(
+            RetryPolicy::new().delay_for(1, &error),
+            Duration::from_secs(3)
+        );
+        assert_eq!(
+            RetryPolicy::new()
+                .with_max_delay(Duration::from_secs(1))
+                .delay_for(1, &error),
+            Duration::from_secs(1)
+        );
+    }
+
+    #[test]
+    fn test_parse_retry_after() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(parse_retry_after(&headers), None);
+
+        headers.insert("retry-after", HeaderValue::from_static("2"));
+        assert_eq!(parse_retry_after(&headers), Some(Duration::from_secs(2)));
+
+        headers.insert("retry-after-ms", HeaderValue::from_static("1500"));
+        assert_eq!(
+            parse_retry_after(&headers),
+            Some(Duration::from_millis(1500))
+        );
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "retry-after",
+            HeaderValue::from_static("Wed, 21 Oct 2015 07:28:00 GMT"),
+        );
+        assert_eq!(parse_retry_after(&headers), Some(Duration::ZERO));
+
+        // Values too large for a Duration are not usable
+        let mut headers = HeaderMap::new();
+        headers.insert("retry-after", HeaderValue::from_static("1e300"));
+        assert_eq!(parse_retry_after(&headers), None);
+
+        headers.insert("retry-after-ms", HeaderValue::from_static("1e300"));
+        assert_eq!(parse_retry_after(&headers), None);
+
+        headers.insert("retry-after", HeaderValue::from_static("3"));
+        assert_eq!(parse_retry_after(&headers), Some(Duration::from_secs(3)));
+    }
+}
@@ -0,0 +1,149 @@
+//! Cost accounting from token usage
+
+use super::{GenerateResponse, Usage};
+use crate::error::{Error, Result};
+use serde::{Deserialize, Serialize};
+use std::iter::Sum;
+use std::ops::{Add, AddAssign};
+use std::sync::Mutex;
+
+/// Cost of token usage in USD, broken down by token category
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Cost {
+    /// Uncached input tokens
+    pub input: f64,
+    /// Input tokens read from the prompt cache
+    pub cached_input: f64,
+    /// Input tokens written to the prompt cache
+    pub cache_creation: f64,
+    /// Output tokens, excluding reasoning
+    pub output: f64,
+    /// Reasoning tokens
+    pub reasoning: f64,
+}
+
+impl Cost {
+    /// Total cost in USD
+    pub fn total(&self) -> f64 {
+        self.input + self.cached_input + self.cache_creation + self.output + self.reasoning
+    }
+}
+
+impl Add for Cost {
+    type Output = Cost;
+
+    fn add(mut self, other: Cost) -> Cost {
+        self += other;
+        self
+    }
+}
+
+impl AddAssign for Cost {
+    fn add_assign(&mut self, other: Cost) {
+        self.input += other.input;
+        self.cached_input += other.cached_input;
+        self.cache_creation += other.cache_creation;
+        self.output += other.output;
+        self.reasoning += other.reasoning;
+    }
+}
+
+impl Sum for Cost {
+    fn sum<I: Iterator<Item = Cost>>(iter: I) -> Cost {
+        iter.fold(Cost::default(), Add::add)
+    }
+}
+
+/// Accumulates usage and cost across requests, e.g. for a session or tenant
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use stakai::{CostTracker, GenerateRequest, Inference, Message, Role};
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = Inference::new();
+/// let tracker = CostTracker::new().with_budget(5.0);
+///
+/// let request = GenerateRequest::new("openai:gpt-4o", vec![Message::new(Role::User, "Hi")]);
+/// tracker.check_budget()?;
+/// let response = client.generate(&request).await?;
+/// tracker.record("gpt-4o", &response.usage);
+///
+/// println!("spent ${:.4}", tracker.cost().total());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default)]
+pub struct CostTracker {
+    budget: Option<f64>,
+    totals: Mutex<(Usage, Cost)>,
+}
+
+impl CostTracker {
+    /// Create a tracker without a budget
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set a budget in USD, enforced by [`CostTracker::check_budget`]
+    pub fn with_budget(mut self, budget: f64) -> Self {
+        self.budget = Some(budget);
+        self
+    }
+
+    /// Record usage of a model and return its cost
+    ///
+    /// Usage of models without catalog pricing is counted at zero cost.
+    pub fn record(&self, model: &str, usage: &Usage) -> Cost {
+        let cost = usage.cost(model).unwrap_or_default();
+        self.add(usage, cost);
+        cost
+    }
+
+    /// Record the usage of a response, priced by the model it reports
+    pub fn record_response(&self, response: &GenerateResponse) -> Cost {
+        let cost = response.cost().unwrap_or_default();
+        self.add(&response.usage, cost);
+        cost
+    }
+
+    /// Total usage recorded so far
+    pub fn usage(&self) -> Usage {
+        self.totals
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .0
+            .clone()
+    }
+
+    /// Total cost recorded so far
+    pub fn cost(&self) -> Cost {
+        self.totals.lock().unwrap_or_else(|e| e.into_inner()).1
+    }
+
+    /// Budget left in USD, if a budget is set
+    pub fn remaining(&self) -> Option<f64> {
+        self.budget.map(|budget| budget - self.cost().total())
+    }
+
+    /// Fail with [`Error::BudgetExceeded`] if the recorded cost exceeds the budget
+    pub fn check_budget(&self) -> Result<()> {
+        match self.budget {
+            Some(budget) => {
+                let spent = self.cost().total();
+                if spent > budget {
+                    Err(Error::BudgetExceeded { spent, budget })
+                } else {
+                    Ok(())
+                }
+            }
+            None => Ok(()),
+        }
+    }
+
+    fn add(&self, usage: &Usage, cost: Cost) {
+        let mut totals = self.totals.lock().unwrap_or_else(|e| e.into_inner());
+        totals.0 += usage.clone();
+        totals.1 += cost;
+    }
+}
@@ -0,0 +1,69 @@
+//! Embedding request and response types
+
+use super::{Headers, Usage};
+use serde::{Deserialize, Serialize};
+
+/// Request for embedding one or more texts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmbedRequest {
+    /// Model identifier (can be provider-prefixed like "openai:text-embedding-3-small")
+    #[serde(skip)]
+    pub model: String,
+
+    /// Texts to embed
+    pub input: Vec<String>,
+
+    /// Number of dimensions of the output embeddings (if the model supports it)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dimensions: Option<u32>,
+
+    /// Custom HTTP headers
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub headers: Option<Headers>,
+}
+
+impl EmbedRequest {
+    /// Create a new request with model and input texts
+    pub fn new(
+        model: impl Into<String>,
+        input: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        Self {
+            model: model.into(),
+            input: input.into_iter().map(Into::into).collect(),
+            dimensions: None,
+            headers: None,
+        }
+    }
+
+    /// Set output dimensions
+    pub fn dimensions(mut self, dimensions: u32) -> Self {
+        self.dimensions = Some(dimensions);
+        self
+    }
+
+    /// Add a single header
+    pub fn add_header(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers
+            .get_or_insert_with(Headers::new)
+            .insert(key, value);
+        self
+    }
+}
+
+/// Response from an embedding request
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EmbedResponse {
+    /// One embedding per input text, in input order
+    pub embeddings: Vec<Vec<f32>>,
+    /// Token usage statistics (zero if the provider doesn't report it)
+    pub usage: Usage,
+}
+
+impl EmbedResponse {
+    /// Merge the response to a later batch of the same request into this one
+    pub(crate) fn extend(&mut self, other: EmbedResponse) {
+        self.embeddings.extend(other.embeddings);
+        self.usage += other.usage;
+    }
+}
@@ -5,6 +5,8 @@ use std::collections::HashMap;
 
 /// HTTP headers map (single value per header name)
 /// Headers can be layered; later values override earlier ones
+///
+/// Header names are case-insensitive: inserting `X-Request-Id` replaces `x-request-id`.
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct Headers {
     inner: HashMap<String, String>,
@@ -16,28 +18,37 @@ impl Headers {
         Self::default()
     }
 
-    /// Insert a header
+    /// Insert a header, replacing any value set under the same name in another case
     pub fn insert(&mut self, key: impl Into<String>, value: impl Into<String>) {
-        self.inner.insert(key.into(), value.into());
+        let key = key.into();
+        if let Some(existing) = self.find_key(&key).filter(|k| **k != key).cloned() {
+            self.inner.remove(&existing);
+        }
+        self.inner.insert(key, value.into());
     }
 
     /// Merge headers from overlay (consuming)
     pub fn merge(&mut self, overlay: Headers) {
         for (k, v) in overlay.inner {
-            self.inner.insert(k, v);
+            self.insert(k, v);
         }
     }
 
     /// Merge headers from overlay (borrowing)
     pub fn merge_with(&mut self, overlay: &Headers) {
         for (k, v) in &overlay.inner {
-            self.inner.insert(k.clone(), v.clone());
+            self.insert(k.clone(), v.clone());
         }
     }
 
-    /// Get a header value
+    /// Get a header value, matching the name case-insensitively
     pub fn get(&self, key: &str) -> Option<&String> {
-        self.inner.get(key)
+        self.find_key(key).and_then(|k| self.inner.get(k))
+    }
+
+    /// Find the stored name equal to `key` ignoring case
+    fn find_key(&self, key: &str) -> Option<&String> {
+        self.inner.keys().find(|k| k.eq_ignore_ascii_case(key))
     }
 
     /// Check if headers is empty
@@ -71,6 +82,18 @@ impl Headers {
 }
 
 // From implementations for ergonomics
+impl From<&reqwest::header::HeaderMap> for Headers {
+    fn from(map: &reqwest::header::HeaderMap) -> Self {
+        let mut headers = Headers::new();
+        for (name, value) in map {
+            if let Ok(value) = value.to_str() {
+                headers.insert(name.as_str(), value);
+            }
+        }
+        headers
+    }
+}
+
 impl From<(String, String)> for Headers {
     fn from((key, value): (String, String)) -> Self {
         let mut headers = Headers::new();
@@ -149,6 +172,19 @@ mod tests {
         assert_eq!(headers1.len(), 2);
     }
 
+    #[test]
+    fn test_headers_names_are_case_insensitive() {
+        let mut headers = Headers::new();
+        headers.insert("Content-Type", "text/plain");
+        headers.merge_with(&("content-type", "application/json").into());
+
+        assert_eq!(headers.len(), 1);
+        assert_eq!(
+            headers.get("CONTENT-TYPE"),
+            Some(&"application/json".to_string())
+        );
+    }
+
     #[test]
     fn test_headers_from_tuple() {
         let headers: Headers = ("x-api-key", "test").into();
@@ -1,9 +1,15 @@
 //! Message types for AI conversations
 
+use super::{CacheControl, ProviderOptions};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
 /// A message in a conversation
+///
+/// Build messages with [`Message::new`] and the `with_*` methods; fields may
+/// be added in minor releases.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[non_exhaustive]
 pub struct Message {
     /// The role of the message sender
     pub role: Role,
@@ -13,6 +19,9 @@ pub struct Message {
     /// Optional name for the message sender
     #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
+    /// Provider-specific options (e.g., Anthropic `cache_control`)
+    #[serde(default, skip_serializing_if = "ProviderOptions::is_empty")]
+    pub provider_options: ProviderOptions,
 }
 
 /// Message content can be either a simple string or structured parts
@@ -40,7 +49,7 @@ impl MessageContent {
             MessageContent::Parts(parts) => parts
                 .iter()
                 .filter_map(|part| match part {
-                    ContentPart::Text { text } => Some(text.clone()),
+                    ContentPart::Text { text, .. } => Some(text.clone()),
                     _ => None,
                 })
                 .reduce(|mut acc, text| {
@@ -92,9 +101,28 @@ impl Message {
             role,
             content: content.into(),
             name: None,
+            provider_options: ProviderOptions::default(),
         }
     }
 
+    /// Set the name of the message sender
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Set provider-specific options, merged into any already set
+    pub fn with_provider_options(mut self, provider: impl Into<String>, options: Value) -> Self {
+        self.provider_options.insert(provider, options);
+        self
+    }
+
+    /// Mark the end of this message as a prompt cache breakpoint
+    pub fn with_cache_control(self, cache_control: CacheControl) -> Self {
+        let (provider, options) = cache_control.into_provider_options();
+        self.with_provider_options(provider, options)
+    }
+
     /// Get the text content of the message (if any)
     pub fn text(&self) -> Option<String> {
         self.content.text()
@@ -147,6 +175,9 @@ pub enum ContentPart {
     Text {
         /// The text content
         text: String,
+        /// Provider-specific options
+        #[serde(default, skip_serializing_if = "ProviderOptions::is_empty")]
+        provider_options: ProviderOptions,
     },
     /// Image content
     Image {
@@ -155,6 +186,9 @@ pub enum ContentPart {
         /// Optional detail level for image processing
         #[serde(skip_serializing_if = "Option::is_none")]
         detail: Option<ImageDetail>,
+        /// Provider-specific options
+        #[serde(default, skip_serializing_if = "ProviderOptions::is_empty")]
+        provider_options: ProviderOptions,
     },
     /// Tool/function call (for assistant messages in conversation history)
     ToolCall {
@@ -164,6 +198,12 @@ pub enum ContentPart {
         name: String,
         /// Arguments as JSON
         arguments: serde_json::Value,
+        /// Provider signature of the reasoning behind the call (Gemini), sent back unmodified
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        signature: Option<String>,
+        /// Provider-specific options
+        #[serde(default, skip_serializing_if = "ProviderOptions::is_empty")]
+        provider_options: ProviderOptions,
     },
     /// Tool/function call result
     ToolResult {
@@ -171,13 +211,39 @@ pub enum ContentPart {
         tool_call_id: String,
         /// Result content (can be text or JSON)
         content: serde_json::Value,
+        /// Whether the tool call failed and `content` describes the error
+        #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+        is_error: bool,
+        /// Provider-specific options
+        #[serde(default, skip_serializing_if = "ProviderOptions::is_empty")]
+        provider_options: ProviderOptions,
+    },
+    /// Model reasoning (for assistant messages in conversation history)
+    ///
+    /// Providers that sign reasoning (Anthropic, Gemini) require it to be sent
+    /// back unmodified, signature included.
+    Reasoning {
+        /// The reasoning text
+        text: String,
+        /// Provider signature verifying the reasoning
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        signature: Option<String>,
+        /// Opaque reasoning the provider redacted (Anthropic `redacted_thinking`)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        data: Option<String>,
+        /// Provider-specific options
+        #[serde(default, skip_serializing_if = "ProviderOptions::is_empty")]
+        provider_options: ProviderOptions,
     },
 }
 
 impl ContentPart {
     /// Create a text content part
     pub fn text(text: impl Into<String>) -> Self {
-        Self::Text { text: text.into() }
+        Self::Text {
+            text: text.into(),
+            provider_options: ProviderOptions::default(),
+        }
     }
 
     /// Create an image content part from URL
@@ -185,6 +251,7 @@ impl ContentPart {
         Self::Image {
             url: url.into(),
             detail: None,
+            provider_options: ProviderOptions::default(),
         }
     }
 
@@ -193,6 +260,7 @@ impl ContentPart {
         Self::Image {
             url: url.into(),
             detail: Some(detail),
+            provider_options: ProviderOptions::default(),
         }
     }
 
@@ -206,6 +274,8 @@ impl ContentPart {
             id: id.into(),
             name: name.into(),
             arguments,
+            signature: None,
+            provider_options: ProviderOptions::default(),
         }
     }
 
@@ -214,8 +284,89 @@ impl ContentPart {
         Self::ToolResult {
             tool_call_id: tool_call_id.into(),
             content,
+            is_error: false,
+            provider_options: ProviderOptions::default(),
+        }
+    }
+
+    /// Create a tool result content part for a failed tool call
+    pub fn tool_error(tool_call_id: impl Into<String>, content: serde_json::Value) -> Self {
+        Self::ToolResult {
+            tool_call_id: tool_call_id.into(),
+            content,
+            is_error: true,
+            provider_options: ProviderOptions::default(),
+        }
+    }
+
+    /// Create a reasoning content part
+    pub fn reasoning(text: impl Into<String>, signature: Option<String>) -> Self {
+        Self::Reasoning {
+            text: text.into(),
+            signature,
+            data: None,
+            provider_options: ProviderOptions::default(),
+        }
+    }
+
+    /// Create a redacted reasoning content part from its opaque data
+    pub fn redacted_reasoning(data: impl Into<String>) -> Self {
+        Self::Reasoning {
+            text: String::new(),
+            signature: None,
+            data: Some(data.into()),
+            provider_options: ProviderOptions::default(),
+        }
+    }
+
+    /// Provider-specific options of this part
+    pub fn provider_options(&self) -> &ProviderOptions {
+        match self {
+            Self::Text {
+                provider_options, ..
+            }
+            | Self::Image {
+                provider_options, ..
+            }
+            | Self::ToolCall {
+                provider_options, ..
+            }
+            | Self::ToolResult {
+                provider_options, ..
+            }
+            | Self::Reasoning {
+                provider_options, ..
+            } => provider_options,
         }
     }
+
+    /// Set provider-specific options, merged into any already set
+    pub fn with_provider_options(mut self, provider: impl Into<String>, options: Value) -> Self {
+        match &mut self {
+            Self::Text {
+                provider_options, ..
+            }
+            | Self::Image {
+                provider_options, ..
+            }
+            | Self::ToolCall {
+                provider_options, ..
+            }
+            | Self::ToolResult {
+                provider_options, ..
+            }
+            | Self::Reasoning {
+                provider_options, ..
+            } => provider_options.insert(provider, options),
+        }
+        self
+    }
+
+    /// Mark this part as the end of a prompt cache breakpoint
+    pub fn with_cache_control(self, cache_control: CacheControl) -> Self {
+        let (provider, options) = cache_control.into_provider_options();
+        self.with_provider_options(provider, options)
+    }
 }
 
 /// Image detail level for processing
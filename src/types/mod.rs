@@ -1,15 +1,31 @@
 //! Core types for the AI SDK
 
+mod cost;
+mod embed;
 mod headers;
 mod message;
+mod model;
 mod options;
+mod provider_options;
 mod request;
 mod response;
 mod stream;
 
+pub use cost::{Cost, CostTracker};
+pub use embed::{EmbedRequest, EmbedResponse};
 pub use headers::Headers;
 pub use message::{ContentPart, ImageDetail, Message, MessageContent, Role};
-pub use options::{GenerateOptions, Tool, ToolChoice, ToolFunction};
+pub use model::ModelInfo;
+pub use options::{
+    Document, GenerateOptions, ReasoningConfig, ReasoningEffort, ResponseFormat, Tool, ToolChoice,
+    ToolFunction,
+};
+pub(crate) use provider_options::merge_json;
+pub use provider_options::{CacheControl, ProviderOptions};
 pub use request::GenerateRequest;
-pub use response::{FinishReason, GenerateResponse, ResponseContent, ToolCall, Usage};
-pub use stream::{GenerateStream, StreamEvent};
+pub use response::{
+    Citation, ContentFilterResult, ContentFilterResults, FinishReason, GenerateResponse,
+    ResponseContent, ResponseMetadata, ToolCall, Usage,
+};
+pub(crate) use stream::ToolCallAccumulator;
+pub use stream::{GenerateStream, ResponseAccumulator, ResponseFuture, StreamEvent};
@@ -0,0 +1,48 @@
+//! Model metadata types
+
+use serde::{Deserialize, Serialize};
+
+/// Information about a model offered by a provider
+///
+/// Fields a provider doesn't report are left empty.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ModelInfo {
+    /// Model ID as used in requests (without provider prefix)
+    pub id: String,
+    /// ID of the provider serving the model (e.g., "openai")
+    pub provider: String,
+    /// Human-readable model name
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+    /// Maximum input tokens
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub context_window: Option<u32>,
+    /// Maximum output tokens
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_output_tokens: Option<u32>,
+    /// Supported input modalities (e.g., "text", "image")
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub modalities: Vec<String>,
+    /// Whether the model supports tool calling
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub supports_tools: Option<bool>,
+    /// Whether the model supports reasoning (extended thinking)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub supports_reasoning: Option<bool>,
+}
+
+impl ModelInfo {
+    /// Create model info with only provider and ID set
+    pub fn new(provider: impl Into<String>, id: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            provider: provider.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Model identifier with provider prefix (e.g., "openai:gpt-4o")
+    pub fn qualified_id(&self) -> String {
+        format!("{}:{}", self.provider, self.id)
+    }
+}
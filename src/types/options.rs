@@ -1,6 +1,6 @@
 //! Generation options and tool definitions
 
-use super::Headers;
+use super::{CacheControl, Headers, ProviderOptions};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
@@ -19,6 +19,10 @@ pub struct GenerateOptions {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub top_p: Option<f32>,
 
+    /// Sample only from the K most likely tokens (Anthropic, Gemini)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_k: Option<u32>,
+
     /// Sequences where generation should stop
     #[serde(skip_serializing_if = "Option::is_none")]
     pub stop_sequences: Option<Vec<String>>,
@@ -31,6 +35,11 @@ pub struct GenerateOptions {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tool_choice: Option<ToolChoice>,
 
+    /// Documents to ground the response in (Cohere); cited in
+    /// [`GenerateResponse::citations`](super::GenerateResponse::citations)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub documents: Option<Vec<Document>>,
+
     /// Frequency penalty (-2.0 to 2.0)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub frequency_penalty: Option<f32>,
@@ -39,9 +48,28 @@ pub struct GenerateOptions {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub presence_penalty: Option<f32>,
 
+    /// Reasoning (extended thinking) settings
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reasoning: Option<ReasoningConfig>,
+
+    /// Format of the response (text, JSON object or JSON schema)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response_format: Option<ResponseFormat>,
+
     /// Custom HTTP headers
     #[serde(skip_serializing_if = "Option::is_none")]
     pub headers: Option<Headers>,
+
+    /// Raw provider request fields, keyed by provider ID
+    ///
+    /// The entry for the provider handling the request is deep-merged into
+    /// the provider's request body, overriding fields set from the options above.
+    #[serde(default, skip_serializing_if = "ProviderOptions::is_empty")]
+    pub provider_options: ProviderOptions,
+
+    /// Keep the raw provider response body in [`ResponseMetadata::raw`](super::ResponseMetadata::raw)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub include_raw_response: bool,
 }
 
 impl GenerateOptions {
@@ -68,6 +96,24 @@ impl GenerateOptions {
         self
     }
 
+    /// Set top_k
+    pub fn top_k(mut self, top_k: u32) -> Self {
+        self.top_k = Some(top_k);
+        self
+    }
+
+    /// Set frequency penalty
+    pub fn frequency_penalty(mut self, penalty: f32) -> Self {
+        self.frequency_penalty = Some(penalty);
+        self
+    }
+
+    /// Set presence penalty
+    pub fn presence_penalty(mut self, penalty: f32) -> Self {
+        self.presence_penalty = Some(penalty);
+        self
+    }
+
     /// Add stop sequence
     pub fn add_stop_sequence(mut self, sequence: impl Into<String>) -> Self {
         self.stop_sequences
@@ -88,6 +134,34 @@ impl GenerateOptions {
         self
     }
 
+    /// Add document
+    pub fn add_document(mut self, document: Document) -> Self {
+        self.documents.get_or_insert_with(Vec::new).push(document);
+        self
+    }
+
+    /// Set reasoning settings
+    pub fn reasoning(mut self, reasoning: ReasoningConfig) -> Self {
+        self.reasoning = Some(reasoning);
+        self
+    }
+
+    /// Enable reasoning with an effort level
+    pub fn reasoning_effort(self, effort: ReasoningEffort) -> Self {
+        self.reasoning(ReasoningConfig::new().effort(effort))
+    }
+
+    /// Enable reasoning with a token budget
+    pub fn reasoning_budget(self, budget_tokens: u32) -> Self {
+        self.reasoning(ReasoningConfig::new().budget_tokens(budget_tokens))
+    }
+
+    /// Set response format
+    pub fn response_format(mut self, format: ResponseFormat) -> Self {
+        self.response_format = Some(format);
+        self
+    }
+
     /// Set custom headers
     pub fn headers(mut self, headers: Headers) -> Self {
         self.headers = Some(headers);
@@ -101,6 +175,184 @@ impl GenerateOptions {
             .insert(key, value);
         self
     }
+
+    /// Keep the raw provider response body in the response metadata
+    pub fn include_raw_response(mut self, include: bool) -> Self {
+        self.include_raw_response = include;
+        self
+    }
+
+    /// Set raw request fields for a provider, merged into any already set
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use stakai::types::GenerateOptions;
+    /// # use serde_json::json;
+    /// let options = GenerateOptions::new()
+    ///     .provider_options("openai", json!({"seed": 42, "logit_bias": {"50256": -100}}))
+    ///     .provider_options("google", json!({
+    ///         "safety_settings": [
+    ///             {"category": "HARM_CATEGORY_HARASSMENT", "threshold": "BLOCK_NONE"}
+    ///         ]
+    ///     }));
+    /// ```
+    pub fn provider_options(mut self, provider: impl Into<String>, options: Value) -> Self {
+        self.provider_options.insert(provider, options);
+        self
+    }
+}
+
+/// A document the model can ground its response in and cite
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Document {
+    /// ID referenced by citations (assigned by the provider if unset)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// Document title
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// Document text
+    pub text: String,
+}
+
+impl Document {
+    /// Create a document from its text
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            id: None,
+            title: None,
+            text: text.into(),
+        }
+    }
+
+    /// Set ID
+    pub fn with_id(mut self, id: impl Into<String>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// Set title
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+}
+
+/// Reasoning (extended thinking) settings
+///
+/// Providers take either an effort level (OpenAI) or a token budget
+/// (Anthropic, Gemini); whichever is missing is derived from the other.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReasoningConfig {
+    /// Effort level
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub effort: Option<ReasoningEffort>,
+    /// Maximum tokens to spend on reasoning
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub budget_tokens: Option<u32>,
+}
+
+impl ReasoningConfig {
+    /// Create reasoning settings with the default (medium) effort
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set effort level
+    pub fn effort(mut self, effort: ReasoningEffort) -> Self {
+        self.effort = Some(effort);
+        self
+    }
+
+    /// Set token budget
+    pub fn budget_tokens(mut self, budget_tokens: u32) -> Self {
+        self.budget_tokens = Some(budget_tokens);
+        self
+    }
+
+    /// Effort level, derived from the budget if unset
+    pub(crate) fn resolved_effort(&self) -> ReasoningEffort {
+        match (self.effort, self.budget_tokens) {
+            (Some(effort), _) => effort,
+            (None, Some(budget)) if budget < 4096 => ReasoningEffort::Low,
+            (None, Some(budget)) if budget >= 16384 => ReasoningEffort::High,
+            _ => ReasoningEffort::Medium,
+        }
+    }
+
+    /// Token budget, derived from the effort level if unset
+    pub(crate) fn resolved_budget(&self) -> u32 {
+        self.budget_tokens
+            .unwrap_or_else(|| match self.resolved_effort() {
+                ReasoningEffort::Low => 1024,
+                ReasoningEffort::Medium => 8192,
+                ReasoningEffort::High => 24576,
+            })
+    }
+}
+
+/// How much effort the model should spend reasoning
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReasoningEffort {
+    /// Minimal reasoning, fastest responses
+    Low,
+    /// Balanced reasoning
+    Medium,
+    /// Thorough reasoning
+    High,
+}
+
+impl ReasoningEffort {
+    /// Get the effort level as a string
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReasoningEffort::Low => "low",
+            ReasoningEffort::Medium => "medium",
+            ReasoningEffort::High => "high",
+        }
+    }
+}
+
+/// Format of the model's response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ResponseFormat {
+    /// Plain text (the default)
+    Text,
+    /// Any valid JSON object
+    JsonObject,
+    /// JSON matching a schema
+    JsonSchema {
+        /// Schema name (letters, digits, underscores and dashes)
+        name: String,
+        /// JSON Schema the response must match
+        schema: Value,
+        /// Require exact schema adherence (OpenAI strict mode)
+        #[serde(default)]
+        strict: bool,
+    },
+}
+
+impl ResponseFormat {
+    /// Create a JSON schema response format
+    pub fn json_schema(name: impl Into<String>, schema: Value) -> Self {
+        Self::JsonSchema {
+            name: name.into(),
+            schema,
+            strict: false,
+        }
+    }
+
+    /// Create a JSON schema response format with strict adherence
+    pub fn strict_json_schema(name: impl Into<String>, schema: Value) -> Self {
+        Self::JsonSchema {
+            name: name.into(),
+            schema,
+            strict: true,
+        }
+    }
 }
 
 /// A tool/function definition
@@ -111,6 +363,9 @@ pub struct Tool {
     pub tool_type: String,
     /// Function definition
     pub function: ToolFunction,
+    /// Provider-specific options (e.g., Anthropic `cache_control`)
+    #[serde(default, skip_serializing_if = "ProviderOptions::is_empty")]
+    pub provider_options: ProviderOptions,
 }
 
 impl Tool {
@@ -123,6 +378,7 @@ impl Tool {
                 description: description.into(),
                 parameters: Value::Object(Default::default()),
             },
+            provider_options: ProviderOptions::default(),
         }
     }
 
@@ -131,6 +387,18 @@ impl Tool {
         self.function.parameters = parameters;
         self
     }
+
+    /// Set provider-specific options, merged into any already set
+    pub fn with_provider_options(mut self, provider: impl Into<String>, options: Value) -> Self {
+        self.provider_options.insert(provider, options);
+        self
+    }
+
+    /// Mark the tool definitions up to this one as a prompt cache breakpoint
+    pub fn with_cache_control(self, cache_control: CacheControl) -> Self {
+        let (provider, options) = cache_control.into_provider_options();
+        self.with_provider_options(provider, options)
+    }
 }
 
 /// Function definition for a tool
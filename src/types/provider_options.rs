@@ -0,0 +1,141 @@
+//! Provider-specific options attached to requests, messages, content parts and tools
+
+use crate::error::Result;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+/// Provider-specific options, keyed by provider ID (e.g., "anthropic")
+///
+/// Each provider reads only its own entry and ignores the others, so options
+/// for several providers can be set on the same request.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ProviderOptions(HashMap<String, Value>);
+
+impl ProviderOptions {
+    /// Create empty provider options
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set options for a provider, deep-merging them into any already set
+    pub fn with(mut self, provider: impl Into<String>, options: Value) -> Self {
+        self.insert(provider, options);
+        self
+    }
+
+    /// Set options for a provider, deep-merging them into any already set
+    pub fn insert(&mut self, provider: impl Into<String>, options: Value) {
+        merge_json(
+            self.0.entry(provider.into()).or_insert(Value::Null),
+            options,
+        );
+    }
+
+    /// Options for a provider
+    pub fn get(&self, provider: &str) -> Option<&Value> {
+        self.0.get(provider)
+    }
+
+    /// Check if no options are set
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Serialize a provider request and deep-merge the provider's options into it
+    pub(crate) fn apply<T: Serialize>(&self, provider: &str, request: &T) -> Result<Value> {
+        let mut body = serde_json::to_value(request)?;
+        if let Some(options) = self.get(provider) {
+            merge_json(&mut body, options.clone());
+        }
+        Ok(body)
+    }
+}
+
+impl From<HashMap<String, Value>> for ProviderOptions {
+    fn from(options: HashMap<String, Value>) -> Self {
+        Self(options)
+    }
+}
+
+/// Prompt cache breakpoint (Anthropic `cache_control`)
+///
+/// Marks the end of a cacheable prompt prefix. Providers without explicit
+/// cache breakpoints ignore it.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CacheControl {
+    /// Cache lifetime (e.g., "5m", "1h"); provider default if unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ttl: Option<String>,
+}
+
+impl CacheControl {
+    /// Cache breakpoint with the provider's default lifetime
+    pub fn ephemeral() -> Self {
+        Self::default()
+    }
+
+    /// Set the cache lifetime
+    pub fn ttl(mut self, ttl: impl Into<String>) -> Self {
+        self.ttl = Some(ttl.into());
+        self
+    }
+
+    /// Provider options carrying this breakpoint
+    pub(crate) fn into_provider_options(self) -> (&'static str, Value) {
+        let mut cache_control = json!({"type": "ephemeral"});
+        if let Some(ttl) = self.ttl {
+            cache_control["ttl"] = Value::String(ttl);
+        }
+        ("anthropic", json!({ "cache_control": cache_control }))
+    }
+}
+
+/// Deep-merge `patch` into `target`
+///
+/// Objects are merged key by key; any other value in `patch` replaces the
+/// one in `target`.
+pub(crate) fn merge_json(target: &mut Value, patch: Value) {
+    match (target, patch) {
+        (Value::Object(target), Value::Object(patch)) => {
+            for (key, value) in patch {
+                merge_json(target.entry(key).or_insert(Value::Null), value);
+            }
+        }
+        (target, patch) => *target = patch,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_json() {
+        let mut target = json!({"a": {"b": 1, "c": [1, 2]}, "d": "x"});
+        merge_json(&mut target, json!({"a": {"c": [3], "e": true}, "f": null}));
+
+        assert_eq!(
+            target,
+            json!({"a": {"b": 1, "c": [3], "e": true}, "d": "x", "f": null})
+        );
+    }
+
+    #[test]
+    fn test_provider_options_merge_per_provider() {
+        let options = ProviderOptions::new()
+            .with("anthropic", json!({"cache_control": {"type": "ephemeral"}}))
+            .with("anthropic", json!({"cache_control": {"ttl": "1h"}}))
+            .with("openai", json!({"store": true}));
+
+        assert_eq!(
+            options.get("anthropic"),
+            Some(&json!({"cache_control": {"type": "ephemeral", "ttl": "1h"}}))
+        );
+        assert_eq!(
+            serde_json::to_value(&options).unwrap()["openai"],
+            json!({"store": true})
+        );
+    }
+}
@@ -1,10 +1,21 @@
 //! Response types from AI providers
 
+use super::{ContentPart, Cost, Headers, Message, ProviderOptions, Role};
+use crate::catalog;
+use crate::error::{Error, Result};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign};
+use std::time::{Duration, Instant};
 
 /// Response from a generation request
+///
+/// Fields may be added in minor releases, so responses built outside the
+/// crate (e.g. in tests) go through [`GenerateResponse::new`].
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[non_exhaustive]
 pub struct GenerateResponse {
     /// Generated content
     pub content: Vec<ResponseContent>,
@@ -12,12 +23,38 @@ pub struct GenerateResponse {
     pub usage: Usage,
     /// Why generation finished
     pub finish_reason: FinishReason,
-    /// Provider-specific metadata
+    /// Spans of the text backed by documents or tool results (Cohere)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub citations: Vec<Citation>,
+    /// Provider and HTTP metadata of the response
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub metadata: Option<Value>,
+    pub metadata: Option<ResponseMetadata>,
 }
 
 impl GenerateResponse {
+    /// Create a response without citations or metadata
+    pub fn new(content: Vec<ResponseContent>, usage: Usage, finish_reason: FinishReason) -> Self {
+        Self {
+            content,
+            usage,
+            finish_reason,
+            citations: Vec::new(),
+            metadata: None,
+        }
+    }
+
+    /// Set the citations backing the text
+    pub fn with_citations(mut self, citations: Vec<Citation>) -> Self {
+        self.citations = citations;
+        self
+    }
+
+    /// Set the response metadata
+    pub fn with_metadata(mut self, metadata: ResponseMetadata) -> Self {
+        self.metadata = Some(metadata);
+        self
+    }
+
     /// Get the text content from the response
     pub fn text(&self) -> String {
         self.content
@@ -30,6 +67,24 @@ impl GenerateResponse {
             .join("")
     }
 
+    /// Get the reasoning text from the response, if the model returned any
+    pub fn reasoning(&self) -> Option<String> {
+        let parts: Vec<&str> = self
+            .content
+            .iter()
+            .filter_map(|c| match c {
+                ResponseContent::Reasoning { text, .. } => Some(text.as_str()),
+                _ => None,
+            })
+            .collect();
+
+        if parts.is_empty() {
+            None
+        } else {
+            Some(parts.concat())
+        }
+    }
+
     /// Get all tool calls from the response
     pub fn tool_calls(&self) -> Vec<&ToolCall> {
         self.content
@@ -40,6 +95,187 @@ impl GenerateResponse {
             })
             .collect()
     }
+
+    /// Parse the text content as a JSON value of type `T`
+    ///
+    /// Markdown code fences around the JSON are ignored. Fails with
+    /// [`Error::SchemaMismatch`] if the text is not JSON or doesn't match `T`.
+    pub fn object<T: DeserializeOwned>(&self) -> Result<T> {
+        let output = self.text();
+        let json = strip_code_fence(&output);
+
+        let value: Value = serde_json::from_str(json).map_err(|e| Error::SchemaMismatch {
+            message: format!("output is not valid JSON: {}", e),
+            output: output.clone(),
+        })?;
+
+        serde_json::from_value(value).map_err(|e| Error::SchemaMismatch {
+            message: e.to_string(),
+            output,
+        })
+    }
+
+    /// Model that generated the response, as reported by the provider
+    pub fn model(&self) -> Option<&str> {
+        self.metadata.as_ref()?.model.as_deref()
+    }
+
+    /// Cost of the response, priced by the model it reports
+    ///
+    /// Returns `None` if the provider didn't report the model or the model has
+    /// no pricing in the [model catalog](crate::catalog); use [`Usage::cost`] then.
+    pub fn cost(&self) -> Option<Cost> {
+        self.usage.cost(self.model()?)
+    }
+
+    /// Fill the metadata from the HTTP response, keeping what the provider body reported
+    pub(crate) fn with_http_metadata(mut self, metadata: ResponseMetadata) -> Self {
+        self.metadata = Some(match self.metadata.take() {
+            Some(body) => metadata.or(body),
+            None => metadata,
+        });
+        self
+    }
+
+    /// Convert the response into an assistant message for the conversation history
+    ///
+    /// Reasoning is kept with its signature, so providers that require
+    /// reasoning blocks to be sent back unmodified accept the next turn.
+    pub fn to_message(&self) -> Message {
+        let parts = self
+            .content
+            .iter()
+            .map(|c| match c {
+                ResponseContent::Text { text } => ContentPart::text(text.clone()),
+                ResponseContent::Reasoning {
+                    text,
+                    signature,
+                    data,
+                } => ContentPart::Reasoning {
+                    text: text.clone(),
+                    signature: signature.clone(),
+                    data: data.clone(),
+                    provider_options: ProviderOptions::default(),
+                },
+                ResponseContent::ToolCall(call) => ContentPart::ToolCall {
+                    id: call.id.clone(),
+                    name: call.name.clone(),
+                    arguments: call.arguments.clone(),
+                    signature: call.signature.clone(),
+                    provider_options: ProviderOptions::default(),
+                },
+            })
+            .collect::<Vec<_>>();
+
+        Message::new(Role::Assistant, parts)
+    }
+}
+
+/// Metadata of a provider response
+///
+/// Body fields (`id`, `model`, `created`) come from the provider response;
+/// the rest from the HTTP exchange. Fields a provider doesn't report are left empty.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ResponseMetadata {
+    /// ID of the provider that served the request (e.g., "openai")
+    pub provider: String,
+    /// Response ID assigned by the provider
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// Model that served the request, as reported by the provider
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// Creation time in Unix seconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created: Option<u64>,
+    /// Provider request ID from the response headers
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+    /// HTTP status code
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<u16>,
+    /// Response headers with lowercase names, including rate limit headers
+    #[serde(default, skip_serializing_if = "Headers::is_empty")]
+    pub headers: Headers,
+    /// Time until the full response was received, or the response headers when streaming
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub latency: Option<Duration>,
+    /// Time until the first streamed content arrived
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time_to_first_token: Option<Duration>,
+    /// Fallback target (`provider:model`) that served the request, if routed through one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+    /// Content filter verdicts, from providers that report them (e.g., Azure OpenAI)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_filter: Option<ContentFilterResults>,
+    /// Raw provider response body (non-streaming only), if requested with
+    /// [`GenerateOptions::include_raw_response`](super::GenerateOptions::include_raw_response)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub raw: Option<Value>,
+}
+
+impl ResponseMetadata {
+    /// Get a response header by name
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .get(&name.to_ascii_lowercase())
+            .map(String::as_str)
+    }
+
+    /// Metadata of an HTTP response from `provider`, with latency measured from `started`
+    pub(crate) fn from_http(
+        provider: &str,
+        response: &reqwest::Response,
+        started: Instant,
+    ) -> Self {
+        Self {
+            provider: provider.to_string(),
+            request_id: crate::http::request_id(response.headers()),
+            status: Some(response.status().as_u16()),
+            headers: Headers::from(response.headers()),
+            latency: Some(started.elapsed()),
+            ..Default::default()
+        }
+    }
+
+    /// Fill fields left unset from `other`
+    pub(crate) fn or(self, other: ResponseMetadata) -> Self {
+        let headers = if self.headers.is_empty() {
+            other.headers
+        } else {
+            self.headers
+        };
+
+        Self {
+            provider: if self.provider.is_empty() {
+                other.provider
+            } else {
+                self.provider
+            },
+            id: self.id.or(other.id),
+            model: self.model.or(other.model),
+            created: self.created.or(other.created),
+            request_id: self.request_id.or(other.request_id),
+            status: self.status.or(other.status),
+            headers,
+            latency: self.latency.or(other.latency),
+            time_to_first_token: self.time_to_first_token.or(other.time_to_first_token),
+            target: self.target.or(other.target),
+            content_filter: self.content_filter.or(other.content_filter),
+            raw: self.raw.or(other.raw),
+        }
+    }
+}
+
+/// Strip a surrounding markdown code fence (```json ... ```), if any
+fn strip_code_fence(text: &str) -> &str {
+    let trimmed = text.trim();
+    trimmed
+        .strip_prefix("```")
+        .and_then(|rest| rest.strip_suffix("```"))
+        .map(|inner| inner.trim_start_matches("json").trim())
+        .unwrap_or(trimmed)
 }
 
 /// Content in a response
@@ -51,10 +287,68 @@ pub enum ResponseContent {
         /// The generated text
         text: String,
     },
+    /// Model reasoning (extended thinking)
+    Reasoning {
+        /// The reasoning text (may be a summary, depending on the provider)
+        text: String,
+        /// Provider signature, required to send the reasoning back
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        signature: Option<String>,
+        /// Opaque reasoning the provider redacted (Anthropic `redacted_thinking`)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        data: Option<String>,
+    },
     /// Tool/function call
     ToolCall(ToolCall),
 }
 
+/// A span of generated text backed by one or more sources
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Citation {
+    /// Start of the span in the response text (in characters)
+    pub start: usize,
+    /// End of the span in the response text (in characters, exclusive)
+    pub end: usize,
+    /// The cited text
+    pub text: String,
+    /// IDs of the cited documents or tool calls
+    pub sources: Vec<String>,
+}
+
+/// Content filter verdicts for a request's prompt and completion
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ContentFilterResults {
+    /// Verdicts for the prompt
+    pub prompt: Vec<ContentFilterResult>,
+    /// Verdicts for the completion
+    pub completion: Vec<ContentFilterResult>,
+}
+
+impl ContentFilterResults {
+    /// Whether any prompt or completion category was filtered
+    pub fn is_filtered(&self) -> bool {
+        self.prompt
+            .iter()
+            .chain(&self.completion)
+            .any(|r| r.filtered)
+    }
+}
+
+/// A content filter verdict for one category
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ContentFilterResult {
+    /// Category (e.g., "hate", "violence", "jailbreak")
+    pub category: String,
+    /// Whether content was blocked for this category
+    pub filtered: bool,
+    /// Severity (e.g., "safe", "low", "medium", "high")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub severity: Option<String>,
+    /// Whether the category was detected, for detection-only categories
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detected: Option<bool>,
+}
+
 /// A tool/function call in the response
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ToolCall {
@@ -64,10 +358,16 @@ pub struct ToolCall {
     pub name: String,
     /// Arguments as JSON
     pub arguments: Value,
+    /// Provider signature of the reasoning behind the call (Gemini)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
 }
 
 /// Token usage statistics
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+///
+/// `prompt_tokens` and `completion_tokens` are totals; the cache and
+/// reasoning counts are breakdowns included in them.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Usage {
     /// Tokens in the prompt
     pub prompt_tokens: u32,
@@ -75,6 +375,52 @@ pub struct Usage {
     pub completion_tokens: u32,
     /// Total tokens used
     pub total_tokens: u32,
+    /// Prompt tokens read from the provider's prompt cache
+    #[serde(default)]
+    pub cached_tokens: u32,
+    /// Prompt tokens written to the provider's prompt cache
+    #[serde(default)]
+    pub cache_creation_tokens: u32,
+    /// Completion tokens spent on reasoning
+    #[serde(default)]
+    pub reasoning_tokens: u32,
+}
+
+impl Usage {
+    /// Cost of this usage, priced with the catalog pricing of `model`
+    ///
+    /// Returns `None` if the model has no pricing in the [model catalog](crate::catalog).
+    pub fn cost(&self, model: &str) -> Option<Cost> {
+        catalog::lookup(model)?
+            .pricing
+            .map(|pricing| pricing.cost(self))
+    }
+}
+
+impl Add for Usage {
+    type Output = Usage;
+
+    fn add(mut self, other: Usage) -> Usage {
+        self += other;
+        self
+    }
+}
+
+impl AddAssign for Usage {
+    fn add_assign(&mut self, other: Usage) {
+        self.prompt_tokens += other.prompt_tokens;
+        self.completion_tokens += other.completion_tokens;
+        self.total_tokens += other.total_tokens;
+        self.cached_tokens += other.cached_tokens;
+        self.cache_creation_tokens += other.cache_creation_tokens;
+        self.reasoning_tokens += other.reasoning_tokens;
+    }
+}
+
+impl Sum for Usage {
+    fn sum<I: Iterator<Item = Usage>>(iter: I) -> Usage {
+        iter.fold(Usage::default(), Add::add)
+    }
 }
 
 /// Why generation finished
@@ -1,13 +1,20 @@
 //! Streaming types for AI generation
 
-use super::{FinishReason, Usage};
-use crate::error::Result;
-use futures::Stream;
+use super::{
+    Citation, ContentFilterResults, FinishReason, GenerateResponse, ResponseContent,
+    ResponseMetadata, ToolCall, Usage,
+};
+use crate::error::{Error, Result};
+use futures::{Stream, StreamExt};
 use pin_project::pin_project;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::future::Future;
 use std::pin::Pin;
 use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+use tokio::sync::oneshot;
 
 /// A stream of generation events
 #[pin_project]
@@ -21,6 +28,156 @@ impl GenerateStream {
     pub fn new(stream: Pin<Box<dyn Stream<Item = Result<StreamEvent>> + Send>>) -> Self {
         Self { inner: stream }
     }
+
+    /// Consume the stream and fold its events into a [`GenerateResponse`]
+    ///
+    /// The response has the same shape as the one returned by non-streaming
+    /// generation: text deltas are joined, tool calls carry their complete
+    /// arguments, and usage and finish reason come from the final event.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use stakai::{Inference, GenerateRequest, Message, Role};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Inference::new();
+    /// let request = GenerateRequest::new("openai:gpt-4", vec![Message::new(Role::User, "Hi")]);
+    ///
+    /// let response = client.stream(&request).await?.collect_response().await?;
+    /// println!("{}", response.text());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn collect_response(mut self) -> Result<GenerateResponse> {
+        let mut accumulator = ResponseAccumulator::new();
+        while let Some(event) = self.next().await {
+            accumulator.push(&event?)?;
+        }
+        accumulator.finish()
+    }
+
+    /// Split the stream into one that still yields every event and a future
+    /// resolving to the aggregated [`GenerateResponse`] once the stream ends
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use stakai::{Inference, GenerateRequest, Message, Role, StreamEvent};
+    /// # use futures::StreamExt;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Inference::new();
+    /// let request = GenerateRequest::new("openai:gpt-4", vec![Message::new(Role::User, "Hi")]);
+    ///
+    /// let (mut stream, response) = client.stream(&request).await?.tee_response();
+    /// while let Some(event) = stream.next().await {
+    ///     if let StreamEvent::TextDelta { delta, .. } = event? {
+    ///         print!("{}", delta);
+    ///     }
+    /// }
+    ///
+    /// let response = response.await?;
+    /// println!("\n{} tokens", response.usage.total_tokens);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn tee_response(mut self) -> (Self, ResponseFuture) {
+        let (sender, receiver) = oneshot::channel();
+
+        let stream = async_stream::stream! {
+            let mut accumulator = ResponseAccumulator::new();
+            let mut failure = None;
+
+            while let Some(event) = self.next().await {
+                if failure.is_none() {
+                    failure = match &event {
+                        Ok(event) => accumulator.push(event).err(),
+                        Err(e) => Some(Error::stream_error(e.to_string())),
+                    };
+                }
+                yield event;
+            }
+
+            let _ = sender.send(match failure {
+                Some(error) => Err(error),
+                None => accumulator.finish(),
+            });
+        };
+
+        (Self::new(Box::pin(stream)), ResponseFuture { receiver })
+    }
+
+    /// Emit a single `Start` event carrying `metadata` ahead of the first other event
+    ///
+    /// `Start` events from the provider are folded into it, and the time to
+    /// first token is measured from `started` when the first event is content.
+    pub(crate) fn with_metadata(mut self, metadata: ResponseMetadata, started: Instant) -> Self {
+        let stream = async_stream::stream! {
+            let mut pending = Some((String::new(), metadata));
+
+            while let Some(event) = self.next().await {
+                if let (Some((start_id, metadata)), Ok(StreamEvent::Start { id, metadata: body })) =
+                    (&mut pending, &event)
+                {
+                    if start_id.is_empty() {
+                        start_id.clone_from(id);
+                    }
+                    *metadata = std::mem::take(metadata).or((**body).clone());
+                    continue;
+                }
+
+                if let Some((id, mut metadata)) = pending.take() {
+                    if matches!(&event, Ok(event) if event.is_content()) {
+                        metadata.time_to_first_token = Some(started.elapsed());
+                    }
+                    yield Ok(StreamEvent::start_with_metadata(id, metadata));
+                }
+                yield event;
+            }
+
+            if let Some((id, metadata)) = pending {
+                yield Ok(StreamEvent::start_with_metadata(id, metadata));
+            }
+        };
+
+        Self::new(Box::pin(stream))
+    }
+
+    /// Wait for the first event other than `Start`, failing if it is an error
+    ///
+    /// The events read are kept, so the returned stream yields every event.
+    pub(crate) async fn ready(mut self) -> Result<Self> {
+        let mut head = Vec::new();
+        loop {
+            match self.next().await {
+                Some(Ok(event @ StreamEvent::Start { .. })) => head.push(Ok(event)),
+                Some(Err(error)) => return Err(error),
+                first => {
+                    head.extend(first);
+                    break;
+                }
+            }
+        }
+
+        Ok(Self::new(Box::pin(futures::stream::iter(head).chain(self))))
+    }
+
+    /// Fail with [`Error::Timeout`] when no event arrives within `timeout`
+    pub(crate) fn with_idle_timeout(mut self, timeout: Duration) -> Self {
+        let stream = async_stream::stream! {
+            loop {
+                match tokio::time::timeout(timeout, self.next()).await {
+                    Ok(Some(event)) => yield event,
+                    Ok(None) => break,
+                    Err(_) => {
+                        yield Err(Error::Timeout(timeout));
+                        break;
+                    }
+                }
+            }
+        };
+
+        Self::new(Box::pin(stream))
+    }
 }
 
 impl Stream for GenerateStream {
@@ -36,10 +193,13 @@ impl Stream for GenerateStream {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum StreamEvent {
-    /// Stream started
+    /// Stream started, emitted once ahead of all other events
     Start {
         /// Unique ID for this generation
         id: String,
+        /// Provider and HTTP metadata of the response
+        #[serde(default)]
+        metadata: Box<ResponseMetadata>,
     },
 
     /// Text content delta
@@ -50,6 +210,20 @@ pub enum StreamEvent {
         delta: String,
     },
 
+    /// Reasoning (extended thinking) delta
+    ReasoningDelta {
+        /// Generation ID
+        id: String,
+        /// Reasoning text delta to append
+        delta: String,
+        /// Signature completing the current reasoning block
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        signature: Option<String>,
+        /// Opaque reasoning the provider redacted, forming a block of its own
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        data: Option<String>,
+    },
+
     /// Tool call started
     ToolCallStart {
         /// Tool call ID
@@ -74,6 +248,25 @@ pub enum StreamEvent {
         name: String,
         /// Complete arguments as JSON
         arguments: Value,
+        /// Provider signature of the reasoning behind the call (Gemini)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        signature: Option<String>,
+    },
+
+    /// A span of the generated text was backed by sources
+    Citation {
+        /// Generation ID
+        id: String,
+        /// The citation
+        citation: Citation,
+    },
+
+    /// Content filter verdicts for the prompt and completion, ahead of `Finish`
+    ContentFilter {
+        /// Generation ID
+        id: String,
+        /// The verdicts, also reported in [`ResponseMetadata::content_filter`]
+        results: ContentFilterResults,
     },
 
     /// Generation finished
@@ -94,7 +287,27 @@ pub enum StreamEvent {
 impl StreamEvent {
     /// Create a start event
     pub fn start(id: impl Into<String>) -> Self {
-        Self::Start { id: id.into() }
+        Self::start_with_metadata(id, ResponseMetadata::default())
+    }
+
+    /// Create a start event carrying response metadata
+    pub fn start_with_metadata(id: impl Into<String>, metadata: ResponseMetadata) -> Self {
+        Self::Start {
+            id: id.into(),
+            metadata: Box::new(metadata),
+        }
+    }
+
+    /// Check if this event carries generated content (text, reasoning or a tool call)
+    pub fn is_content(&self) -> bool {
+        matches!(
+            self,
+            Self::TextDelta { .. }
+                | Self::ReasoningDelta { .. }
+                | Self::ToolCallStart { .. }
+                | Self::ToolCallDelta { .. }
+                | Self::ToolCallEnd { .. }
+        )
     }
 
     /// Create a text delta event
@@ -105,6 +318,36 @@ impl StreamEvent {
         }
     }
 
+    /// Create a reasoning delta event
+    pub fn reasoning_delta(id: impl Into<String>, delta: impl Into<String>) -> Self {
+        Self::ReasoningDelta {
+            id: id.into(),
+            delta: delta.into(),
+            signature: None,
+            data: None,
+        }
+    }
+
+    /// Create a reasoning event carrying the signature of the current reasoning block
+    pub fn reasoning_signature(id: impl Into<String>, signature: impl Into<String>) -> Self {
+        Self::ReasoningDelta {
+            id: id.into(),
+            delta: String::new(),
+            signature: Some(signature.into()),
+            data: None,
+        }
+    }
+
+    /// Create a redacted reasoning event from its opaque data
+    pub fn redacted_reasoning(id: impl Into<String>, data: impl Into<String>) -> Self {
+        Self::ReasoningDelta {
+            id: id.into(),
+            delta: String::new(),
+            signature: None,
+            data: Some(data.into()),
+        }
+    }
+
     /// Create a tool call start event
     pub fn tool_call_start(id: impl Into<String>, name: impl Into<String>) -> Self {
         Self::ToolCallStart {
@@ -127,6 +370,23 @@ impl StreamEvent {
             id: id.into(),
             name: name.into(),
             arguments,
+            signature: None,
+        }
+    }
+
+    /// Create a citation event
+    pub fn citation(id: impl Into<String>, citation: Citation) -> Self {
+        Self::Citation {
+            id: id.into(),
+            citation,
+        }
+    }
+
+    /// Create a content filter event
+    pub fn content_filter(id: impl Into<String>, results: ContentFilterResults) -> Self {
+        Self::ContentFilter {
+            id: id.into(),
+            results,
         }
     }
 
@@ -142,3 +402,515 @@ impl StreamEvent {
         }
     }
 }
+
+/// Future resolving to the response aggregated by [`GenerateStream::tee_response`]
+///
+/// Resolves once the tee'd stream has been consumed to the end, and fails if
+/// the stream is dropped before that.
+#[derive(Debug)]
+pub struct ResponseFuture {
+    receiver: oneshot::Receiver<Result<GenerateResponse>>,
+}
+
+impl Future for ResponseFuture {
+    type Output = Result<GenerateResponse>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.receiver).poll(cx).map(|result| {
+            result.unwrap_or_else(|_| {
+                Err(Error::stream_error(
+                    "Stream was dropped before it completed",
+                ))
+            })
+        })
+    }
+}
+
+/// Folds [`StreamEvent`]s into a [`GenerateResponse`]
+///
+/// Used by [`GenerateStream::collect_response`]; useful directly when events
+/// are consumed by a custom loop.
+#[derive(Debug, Default)]
+pub struct ResponseAccumulator {
+    metadata: Option<ResponseMetadata>,
+    content: Vec<ResponseContent>,
+    /// Tool calls without a `ToolCallEnd` yet: ID -> (content position, argument text)
+    pending: HashMap<String, (usize, String)>,
+    citations: Vec<Citation>,
+    usage: Usage,
+    finish_reason: Option<FinishReason>,
+}
+
+impl ResponseAccumulator {
+    /// Create an empty accumulator
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an event, failing on [`StreamEvent::Error`]
+    pub fn push(&mut self, event: &StreamEvent) -> Result<()> {
+        match event {
+            StreamEvent::Start { id, metadata } => {
+                self.metadata.get_or_insert_with(|| {
+                    let mut metadata = (**metadata).clone();
+                    if metadata.id.is_none() && !id.is_empty() {
+                        metadata.id = Some(id.clone());
+                    }
+                    metadata
+                });
+            }
+            StreamEvent::TextDelta { delta, .. } => match self.content.last_mut() {
+                Some(ResponseContent::Text { text }) => text.push_str(delta),
+                _ => self.content.push(ResponseContent::Text {
+                    text: delta.clone(),
+                }),
+            },
+            StreamEvent::ReasoningDelta {
+                delta,
+                signature,
+                data,
+                ..
+            } => match self.content.last_mut() {
+                // A signature closes a reasoning block, so later deltas start a new one
+                Some(ResponseContent::Reasoning {
+                    text,
+                    signature: current @ None,
+                    data: None,
+                }) if data.is_none() => {
+                    text.push_str(delta);
+                    current.clone_from(signature);
+                }
+                _ => self.content.push(ResponseContent::Reasoning {
+                    text: delta.clone(),
+                    signature: signature.clone(),
+                    data: data.clone(),
+                }),
+            },
+            StreamEvent::ToolCallStart { id, name } => {
+                self.pending
+                    .insert(id.clone(), (self.content.len(), String::new()));
+                self.content.push(ResponseContent::ToolCall(ToolCall {
+                    id: id.clone(),
+                    name: name.clone(),
+                    arguments: Value::Null,
+                    signature: None,
+                }));
+            }
+            StreamEvent::ToolCallDelta { id, delta } => {
+                if let Some((_, arguments)) = self.pending.get_mut(id) {
+                    arguments.push_str(delta);
+                }
+            }
+            StreamEvent::ToolCallEnd {
+                id,
+                name,
+                arguments,
+                signature,
+            } => {
+                let call = ToolCall {
+                    id: id.clone(),
+                    name: name.clone(),
+                    arguments: arguments.clone(),
+                    signature: signature.clone(),
+                };
+                match self.pending.remove(id) {
+                    Some((position, _)) => {
+                        self.content[position] = ResponseContent::ToolCall(call);
+                    }
+                    None => self.content.push(ResponseContent::ToolCall(call)),
+                }
+            }
+            StreamEvent::Citation { citation, .. } => self.citations.push(citation.clone()),
+            StreamEvent::ContentFilter { results, .. } => {
+                self.metadata
+                    .get_or_insert_with(Default::default)
+                    .content_filter = Some(results.clone());
+            }
+            StreamEvent::Finish { usage, reason } => {
+                self.usage = usage.clone();
+                self.finish_reason = Some(*reason);
+            }
+            StreamEvent::Error { message } => {
+                return Err(Error::stream_error(message.clone()));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Build the response from the events added so far
+    pub fn finish(mut self) -> Result<GenerateResponse> {
+        // Tool calls that never got a ToolCallEnd keep their streamed arguments
+        for (position, arguments) in self.pending.into_values() {
+            if let ResponseContent::ToolCall(call) = &mut self.content[position] {
+                call.arguments = parse_arguments(arguments);
+            }
+        }
+
+        let has_tool_calls = self
+            .content
+            .iter()
+            .any(|c| matches!(c, ResponseContent::ToolCall(_)));
+        let finish_reason = self.finish_reason.unwrap_or(if has_tool_calls {
+            FinishReason::ToolCalls
+        } else {
+            FinishReason::Other
+        });
+
+        Ok(GenerateResponse {
+            content: self.content,
+            usage: self.usage,
+            finish_reason,
+            citations: self.citations,
+            metadata: self.metadata,
+        })
+    }
+}
+
+/// Parse streamed tool call arguments, treating no arguments as an empty object
+fn parse_arguments(arguments: String) -> Value {
+    if arguments.trim().is_empty() {
+        Value::Object(Default::default())
+    } else {
+        serde_json::from_str(&arguments).unwrap_or(Value::String(arguments))
+    }
+}
+
+/// Assembles streamed tool calls into consistent tool call events
+///
+/// Providers stream tool calls as fragments addressed by position (OpenAI's
+/// `index`, Anthropic's content block index). The accumulator remembers the
+/// real ID and name for each position, so every `ToolCallStart`,
+/// `ToolCallDelta` and `ToolCallEnd` carries the tool call ID, and
+/// `ToolCallEnd` carries the complete parsed arguments.
+#[derive(Debug, Default)]
+pub(crate) struct ToolCallAccumulator {
+    calls: BTreeMap<u32, PendingToolCall>,
+}
+
+#[derive(Debug)]
+struct PendingToolCall {
+    id: String,
+    name: String,
+    arguments: String,
+}
+
+impl ToolCallAccumulator {
+    /// Create an empty accumulator
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Check if a tool call is in progress at `index`
+    pub(crate) fn contains(&self, index: u32) -> bool {
+        self.calls.contains_key(&index)
+    }
+
+    /// Start a tool call at `index`, generating an ID if the provider sent none
+    pub(crate) fn start(
+        &mut self,
+        index: u32,
+        id: impl Into<String>,
+        name: impl Into<String>,
+    ) -> StreamEvent {
+        let mut id = id.into();
+        if id.is_empty() {
+            id = format!("call_{}", uuid::Uuid::new_v4());
+        }
+        let name = name.into();
+
+        let event = StreamEvent::tool_call_start(&id, &name);
+        self.calls.insert(
+            index,
+            PendingToolCall {
+                id,
+                name,
+                arguments: String::new(),
+            },
+        );
+        event
+    }
+
+    /// Append an arguments fragment to the tool call at `index`
+    ///
+    /// Returns `None` if no tool call was started at `index` or the fragment is empty.
+    pub(crate) fn delta(&mut self, index: u32, fragment: &str) -> Option<StreamEvent> {
+        let call = self.calls.get_mut(&index)?;
+        if fragment.is_empty() {
+            return None;
+        }
+
+        call.arguments.push_str(fragment);
+        Some(StreamEvent::tool_call_delta(&call.id, fragment))
+    }
+
+    /// Complete the tool call at `index`
+    pub(crate) fn end(&mut self, index: u32) -> Option<StreamEvent> {
+        self.end_with_signature(index, None)
+    }
+
+    /// Complete the tool call at `index`, attaching the provider's signature
+    pub(crate) fn end_with_signature(
+        &mut self,
+        index: u32,
+        signature: Option<String>,
+    ) -> Option<StreamEvent> {
+        self.calls
+            .remove(&index)
+            .map(|call| call.into_event(signature))
+    }
+
+    /// Complete all pending tool calls in index order
+    pub(crate) fn end_all(&mut self) -> Vec<StreamEvent> {
+        std::mem::take(&mut self.calls)
+            .into_values()
+            .map(|call| call.into_event(None))
+            .collect()
+    }
+}
+
+impl PendingToolCall {
+    fn into_event(self, signature: Option<String>) -> StreamEvent {
+        StreamEvent::ToolCallEnd {
+            id: self.id,
+            name: self.name,
+            arguments: parse_arguments(self.arguments),
+            signature,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_response_accumulator() {
+        let mut acc = ResponseAccumulator::new();
+        let events = [
+            StreamEvent::start("msg_1"),
+            StreamEvent::text_delta("msg_1", "Let me "),
+            StreamEvent::text_delta("msg_1", "check."),
+            StreamEvent::tool_call_start("call_a", "get_weather"),
+            StreamEvent::tool_call_start("call_b", "get_time"),
+            StreamEvent::tool_call_delta("call_b", r#"{"city":"Rome"}"#),
+            StreamEvent::tool_call_end("call_a", "get_weather", json!({"city": "Paris"})),
+            StreamEvent::finish(
+                Usage {
+                    prompt_tokens: 10,
+                    completion_tokens: 5,
+                    total_tokens: 15,
+                    ..Default::default()
+                },
+                FinishReason::ToolCalls,
+            ),
+        ];
+        for event in &events {
+            acc.push(event).unwrap();
+        }
+
+        let response = acc.finish().unwrap();
+        assert_eq!(response.text(), "Let me check.");
+        assert_eq!(response.content.len(), 3);
+
+        let calls = response.tool_calls();
+        assert_eq!(calls[0].id, "call_a");
+        assert_eq!(calls[0].arguments, json!({"city": "Paris"}));
+        // Never ended, so built from its deltas
+        assert_eq!(calls[1].id, "call_b");
+        assert_eq!(calls[1].arguments, json!({"city": "Rome"}));
+
+        assert_eq!(response.finish_reason, FinishReason::ToolCalls);
+        assert_eq!(response.usage.total_tokens, 15);
+        assert_eq!(
+            response
+                .metadata
+                .and_then(|metadata| metadata.id)
+                .as_deref(),
+            Some("msg_1")
+        );
+    }
+
+    #[test]
+    fn test_response_accumulator_reasoning_blocks() {
+        let mut acc = ResponseAccumulator::new();
+        let events = [
+            StreamEvent::reasoning_delta("", "First, "),
+            StreamEvent::reasoning_delta("", "think."),
+            StreamEvent::reasoning_signature("", "sig_1"),
+            StreamEvent::reasoning_delta("", "Then think again."),
+            StreamEvent::reasoning_signature("", "sig_2"),
+            StreamEvent::text_delta("", "Done."),
+        ];
+        for event in &events {
+            acc.push(event).unwrap();
+        }
+
+        let response = acc.finish().unwrap();
+        assert_eq!(response.content.len(), 3);
+        assert!(matches!(
+            &response.content[0],
+            ResponseContent::Reasoning { text, signature: Some(sig), data: None }
+                if text == "First, think." && sig == "sig_1"
+        ));
+        assert!(matches!(
+            &response.content[1],
+            ResponseContent::Reasoning { text, signature: Some(sig), data: None }
+                if text == "Then think again." && sig == "sig_2"
+        ));
+        assert_eq!(
+            response.reasoning().as_deref(),
+            Some("First, think.Then think again.")
+        );
+        assert_eq!(response.text(), "Done.");
+    }
+
+    #[test]
+    fn test_response_accumulator_error_event() {
+        let mut acc = ResponseAccumulator::new();
+        assert!(acc.push(&StreamEvent::error("boom")).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_tee_response() {
+        let events = vec![
+            Ok(StreamEvent::text_delta("", "Hello")),
+            Ok(StreamEvent::text_delta("", " world")),
+            Ok(StreamEvent::finish(Usage::default(), FinishReason::Stop)),
+        ];
+        let stream = GenerateStream::new(Box::pin(futures::stream::iter(events)));
+
+        let (stream, response) = stream.tee_response();
+        let yielded: Vec<_> = stream.collect().await;
+        assert_eq!(yielded.len(), 3);
+
+        let response = response.await.unwrap();
+        assert_eq!(response.text(), "Hello world");
+        assert_eq!(response.finish_reason, FinishReason::Stop);
+    }
+
+    #[tokio::test]
+    async fn test_tee_response_dropped_stream() {
+        let events = vec![Ok(StreamEvent::text_delta("", "Hello"))];
+        let stream = GenerateStream::new(Box::pin(futures::stream::iter(events)));
+
+        let (stream, response) = stream.tee_response();
+        drop(stream);
+        assert!(response.await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_with_metadata_single_start() {
+        let events = vec![
+            Ok(StreamEvent::start_with_metadata(
+                "msg_1",
+                ResponseMetadata {
+                    model: Some("claude-sonnet-4-20250514".to_string()),
+                    ..Default::default()
+                },
+            )),
+            Ok(StreamEvent::text_delta("", "Hi")),
+            Ok(StreamEvent::finish(Usage::default(), FinishReason::Stop)),
+        ];
+        let http = ResponseMetadata {
+            provider: "anthropic".to_string(),
+            status: Some(200),
+            ..Default::default()
+        };
+        let stream = GenerateStream::new(Box::pin(futures::stream::iter(events)))
+            .with_metadata(http, Instant::now());
+
+        let events: Vec<_> = stream.map(Result::unwrap).collect().await;
+        assert_eq!(events.len(), 3);
+        match &events[0] {
+            StreamEvent::Start { id, metadata } => {
+                assert_eq!(id, "msg_1");
+                assert_eq!(metadata.provider, "anthropic");
+                assert_eq!(metadata.status, Some(200));
+                assert_eq!(metadata.model.as_deref(), Some("claude-sonnet-4-20250514"));
+                assert!(metadata.time_to_first_token.is_some());
+            }
+            other => panic!("Expected Start, got {:?}", other),
+        }
+        assert!(events[1].is_content());
+    }
+
+    #[tokio::test]
+    async fn test_with_metadata_empty_stream() {
+        let stream = GenerateStream::new(Box::pin(futures::stream::empty()))
+            .with_metadata(ResponseMetadata::default(), Instant::now());
+
+        let events: Vec<_> = stream.collect().await;
+        assert!(matches!(
+            events.as_slice(),
+            [Ok(StreamEvent::Start { metadata, .. })] if metadata.time_to_first_token.is_none()
+        ));
+    }
+
+    #[test]
+    fn test_accumulator_parallel_calls() {
+        let mut acc = ToolCallAccumulator::new();
+
+        acc.start(0, "call_a", "get_weather");
+        acc.start(1, "call_b", "get_time");
+        assert!(matches!(
+            acc.delta(1, r#"{"city":"#),
+            Some(StreamEvent::ToolCallDelta { id, .. }) if id == "call_b"
+        ));
+        acc.delta(0, r#"{"city":"Paris"}"#);
+        acc.delta(1, r#""Rome"}"#);
+
+        let events = acc.end_all();
+        assert_eq!(events.len(), 2);
+        match (&events[0], &events[1]) {
+            (
+                StreamEvent::ToolCallEnd {
+                    id: first_id,
+                    arguments: first_args,
+                    ..
+                },
+                StreamEvent::ToolCallEnd {
+                    id: second_id,
+                    name,
+                    arguments: second_args,
+                    signature: None,
+                },
+            ) => {
+                assert_eq!(first_id, "call_a");
+                assert_eq!(first_args, &json!({"city": "Paris"}));
+                assert_eq!(second_id, "call_b");
+                assert_eq!(name, "get_time");
+                assert_eq!(second_args, &json!({"city": "Rome"}));
+            }
+            other => panic!("Expected two ToolCallEnd events, got {:?}", other),
+        }
+        assert!(acc.end_all().is_empty());
+    }
+
+    #[test]
+    fn test_accumulator_empty_arguments_and_unknown_index() {
+        let mut acc = ToolCallAccumulator::new();
+
+        assert!(acc.delta(3, "{}").is_none());
+        assert!(acc.end(3).is_none());
+
+        let start = acc.start(0, "", "list_files");
+        let StreamEvent::ToolCallStart { id, .. } = start else {
+            panic!("Expected ToolCallStart");
+        };
+        assert!(id.starts_with("call_"));
+
+        match acc.end(0) {
+            Some(StreamEvent::ToolCallEnd {
+                id: end_id,
+                arguments,
+                ..
+            }) => {
+                assert_eq!(end_id, id);
+                assert_eq!(arguments, json!({}));
+            }
+            other => panic!("Expected ToolCallEnd, got {:?}", other),
+        }
+    }
+}
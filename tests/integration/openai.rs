@@ -12,11 +12,10 @@ async fn test_openai_generate() {
 
     let mut request = GenerateRequest::new(
         "gpt-3.5-turbo",
-        vec![Message {
-            role: Role::User,
-            content: "Say 'Hello, World!' and nothing else".into(),
-            name: None,
-        }],
+        vec![Message::new(
+            Role::User,
+            "Say 'Hello, World!' and nothing else",
+        )],
     );
     request.options.temperature = Some(0.0);
     request.options.max_tokens = Some(10);
@@ -37,11 +36,7 @@ async fn test_openai_streaming() {
 
     let mut request = GenerateRequest::new(
         "gpt-3.5-turbo",
-        vec![Message {
-            role: Role::User,
-            content: "Count from 1 to 3".into(),
-            name: None,
-        }],
+        vec![Message::new(Role::User, "Count from 1 to 3")],
     );
     request.options.temperature = Some(0.0);
     request.options.max_tokens = Some(20);
@@ -79,16 +74,11 @@ async fn test_openai_with_system_message() {
     let mut request = GenerateRequest::new(
         "gpt-3.5-turbo",
         vec![
-            Message {
-                role: Role::System,
-                content: "You are a helpful assistant that responds in one word".into(),
-                name: None,
-            },
-            Message {
-                role: Role::User,
-                content: "What color is the sky?".into(),
-                name: None,
-            },
+            Message::new(
+                Role::System,
+                "You are a helpful assistant that responds in one word",
+            ),
+            Message::new(Role::User, "What color is the sky?"),
         ],
     );
     request.options.temperature = Some(0.0);
@@ -109,11 +99,7 @@ async fn test_openai_explicit_provider() {
 
     let request = GenerateRequest::new(
         "openai:gpt-3.5-turbo",
-        vec![Message {
-            role: Role::User,
-            content: "Say hello".into(),
-            name: None,
-        }],
+        vec![Message::new(Role::User, "Say hello")],
     );
 
     // Test with explicit provider:model format
@@ -130,11 +116,7 @@ async fn test_openai_temperature_variation() {
     // Test with temperature 0 (deterministic)
     let mut request = GenerateRequest::new(
         "gpt-3.5-turbo",
-        vec![Message {
-            role: Role::User,
-            content: "Say exactly: 'Test'".into(),
-            name: None,
-        }],
+        vec![Message::new(Role::User, "Say exactly: 'Test'")],
     );
     request.options.temperature = Some(0.0);
     request.options.max_tokens = Some(5);
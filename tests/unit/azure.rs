@@ -0,0 +1,324 @@
+//! Azure OpenAI provider tests: deployment URLs, authentication and content filtering
+
+use super::common::request;
+use async_trait::async_trait;
+use mockito::Matcher;
+use serde_json::json;
+use stakai::providers::openai::{AzureOpenAIConfig, AzureOpenAIProvider, AzureTokenProvider};
+use stakai::types::ContentFilterResult;
+use stakai::{Error, FinishReason, Inference, RetryPolicy};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+const MODEL: &str = "azure:gpt-4o";
+const CHAT_PATH: &str = "/openai/deployments/prod-gpt-4o/chat/completions";
+
+fn config(server: &mockito::ServerGuard) -> AzureOpenAIConfig {
+    AzureOpenAIConfig::new(server.url(), "azure-key").with_deployment("gpt-4o", "prod-gpt-4o")
+}
+
+fn client(provider: AzureOpenAIProvider) -> Inference {
+    Inference::builder()
+        .register_provider("azure", provider)
+        .with_retry(RetryPolicy::none())
+        .build()
+        .unwrap()
+}
+
+fn safe(categories: &[&str]) -> serde_json::Value {
+    categories
+        .iter()
+        .map(|category| {
+            (
+                category.to_string(),
+                json!({"filtered": false, "severity": "safe"}),
+            )
+        })
+        .collect::<serde_json::Map<_, _>>()
+        .into()
+}
+
+fn sse(chunks: &[serde_json::Value]) -> String {
+    chunks
+        .iter()
+        .map(|chunk| format!("data: {}\n\n", chunk))
+        .chain(std::iter::once("data: [DONE]\n\n".to_string()))
+        .collect()
+}
+
+fn completion(finish_reason: &str, content_filter_results: serde_json::Value) -> String {
+    json!({
+        "id": "chatcmpl-azure",
+        "object": "chat.completion",
+        "created": 1_700_000_000,
+        "model": "gpt-4o-2024-08-06",
+        "prompt_filter_results": [{
+            "prompt_index": 0,
+            "content_filter_results": {
+                "hate": {"filtered": false, "severity": "safe"},
+                "jailbreak": {"filtered": false, "detected": false}
+            }
+        }],
+        "choices": [{
+            "index": 0,
+            "message": {"role": "assistant", "content": "Hello!"},
+            "finish_reason": finish_reason,
+            "content_filter_results": content_filter_results
+        }],
+        "usage": {"prompt_tokens": 5, "completion_tokens": 2, "total_tokens": 7}
+    })
+    .to_string()
+}
+
+#[tokio::test]
+async fn test_generate_uses_deployment_url_and_api_key() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", CHAT_PATH)
+        .match_query(Matcher::UrlEncoded(
+            "api-version".to_string(),
+            AzureOpenAIConfig::DEFAULT_API_VERSION.to_string(),
+        ))
+        .match_header("api-key", "azure-key")
+        .match_header("authorization", Matcher::Missing)
+        .match_body(Matcher::PartialJson(json!({"model": "gpt-4o"})))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(completion("stop", safe(&["hate", "violence"])))
+        .create_async()
+        .await;
+
+    let provider = AzureOpenAIProvider::new(config(&server)).unwrap();
+    let response = client(provider).generate(&request(MODEL)).await.unwrap();
+
+    mock.assert_async().await;
+    assert_eq!(response.text(), "Hello!");
+    assert_eq!(response.finish_reason, FinishReason::Stop);
+
+    let metadata = response.metadata.unwrap();
+    assert_eq!(metadata.provider, "azure");
+    let content_filter = metadata.content_filter.unwrap();
+    assert!(!content_filter.is_filtered());
+    assert_eq!(
+        content_filter.prompt,
+        vec![
+            ContentFilterResult {
+                category: "hate".to_string(),
+                filtered: false,
+                severity: Some("safe".to_string()),
+                detected: None,
+            },
+            ContentFilterResult {
+                category: "jailbreak".to_string(),
+                filtered: false,
+                severity: None,
+                detected: Some(false),
+            },
+        ]
+    );
+    assert_eq!(content_filter.completion.len(), 2);
+}
+
+#[tokio::test]
+async fn test_filtered_completion_maps_to_content_filter() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("POST", CHAT_PATH)
+        .match_query(Matcher::Any)
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(completion(
+            "content_filter",
+            json!({
+                "hate": {"filtered": false, "severity": "safe"},
+                "violence": {"filtered": true, "severity": "high"}
+            }),
+        ))
+        .create_async()
+        .await;
+
+    let provider = AzureOpenAIProvider::new(config(&server)).unwrap();
+    let response = client(provider).generate(&request(MODEL)).await.unwrap();
+
+    assert_eq!(response.finish_reason, FinishReason::ContentFilter);
+    let content_filter = response.metadata.unwrap().content_filter.unwrap();
+    assert!(content_filter.is_filtered());
+    assert!(content_filter
+        .completion
+        .iter()
+        .any(|r| r.category == "violence" && r.filtered));
+}
+
+#[tokio::test]
+async fn test_stream_filtered_finish() {
+    let chunks = [
+        // Azure sends the prompt verdicts first, in a chunk without choices
+        json!({"id": "", "object": "", "created": 0, "model": "", "choices": [],
+               "prompt_filter_results": [{"prompt_index": 0, "content_filter_results": {}}]}),
+        json!({"id": "chatcmpl-1", "object": "chat.completion.chunk", "created": 1, "model": "gpt-4o",
+               "choices": [{"index": 0, "delta": {"role": "assistant", "content": ""}, "finish_reason": null}]}),
+        json!({"id": "chatcmpl-1", "object": "chat.completion.chunk", "created": 1, "model": "gpt-4o",
+               "choices": [{"index": 0, "delta": {"content": "Once upon"}, "finish_reason": null,
+                            "content_filter_results": safe(&["violence"])}]}),
+        json!({"id": "chatcmpl-1", "object": "chat.completion.chunk", "created": 1, "model": "gpt-4o",
+               "choices": [{"index": 0, "delta": {}, "finish_reason": "stop",
+                            "content_filter_results": {"violence": {"filtered": true, "severity": "medium"}}}]}),
+    ];
+    let body = sse(&chunks);
+
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("POST", CHAT_PATH)
+        .match_query(Matcher::Any)
+        .with_status(200)
+        .with_header("content-type", "text/event-stream")
+        .with_body(body)
+        .create_async()
+        .await;
+
+    let provider = AzureOpenAIProvider::new(config(&server)).unwrap();
+    let response = client(provider)
+        .stream(&request(MODEL))
+        .await
+        .unwrap()
+        .collect_response()
+        .await
+        .unwrap();
+
+    assert_eq!(response.text(), "Once upon");
+    assert_eq!(response.finish_reason, FinishReason::ContentFilter);
+}
+
+#[tokio::test]
+async fn test_stream_reports_content_filter_metadata() {
+    let chunks = [
+        json!({"id": "", "object": "", "created": 0, "model": "", "choices": [],
+        "prompt_filter_results": [{"prompt_index": 0, "content_filter_results": {
+            "hate": {"filtered": false, "severity": "safe"},
+            "jailbreak": {"filtered": false, "detected": false}
+        }}]}),
+        json!({"id": "chatcmpl-1", "object": "chat.completion.chunk", "created": 1, "model": "gpt-4o",
+               "choices": [{"index": 0, "delta": {"role": "assistant", "content": ""}, "finish_reason": null}]}),
+        json!({"id": "chatcmpl-1", "object": "chat.completion.chunk", "created": 1, "model": "gpt-4o",
+               "choices": [{"index": 0, "delta": {"content": "Hello!"}, "finish_reason": null,
+                            "content_filter_results": safe(&["hate"])}]}),
+        json!({"id": "chatcmpl-1", "object": "chat.completion.chunk", "created": 1, "model": "gpt-4o",
+               "choices": [{"index": 0, "delta": {}, "finish_reason": "stop"}]}),
+        // Verdicts from the asynchronous filter trail the finish, without a delta
+        json!({"id": "chatcmpl-1", "object": "chat.completion.chunk", "created": 1, "model": "gpt-4o",
+               "choices": [{"index": 0, "finish_reason": null,
+                            "content_filter_results": safe(&["hate", "violence"])}]}),
+        json!({"id": "chatcmpl-1", "object": "chat.completion.chunk", "created": 1, "model": "gpt-4o",
+               "choices": [], "usage": {"prompt_tokens": 5, "completion_tokens": 2, "total_tokens": 7}}),
+    ];
+
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("POST", CHAT_PATH)
+        .match_query(Matcher::Any)
+        .with_status(200)
+        .with_header("content-type", "text/event-stream")
+        .with_body(sse(&chunks))
+        .create_async()
+        .await;
+
+    let provider = AzureOpenAIProvider::new(config(&server)).unwrap();
+    let response = client(provider)
+        .stream(&request(MODEL))
+        .await
+        .unwrap()
+        .collect_response()
+        .await
+        .unwrap();
+
+    assert_eq!(response.text(), "Hello!");
+    assert_eq!(response.finish_reason, FinishReason::Stop);
+    assert_eq!(response.usage.total_tokens, 7);
+
+    let content_filter = response.metadata.unwrap().content_filter.unwrap();
+    assert!(!content_filter.is_filtered());
+    assert_eq!(content_filter.prompt.len(), 2);
+    let mut categories: Vec<_> = content_filter
+        .completion
+        .iter()
+        .map(|r| r.category.as_str())
+        .collect();
+    categories.sort();
+    assert_eq!(categories, ["hate", "violence"]);
+}
+
+#[tokio::test]
+async fn test_filtered_prompt_is_content_policy_error() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("POST", CHAT_PATH)
+        .match_query(Matcher::Any)
+        .with_status(400)
+        .with_header("content-type", "application/json")
+        .with_body(
+            json!({"error": {
+                "message": "The response was filtered due to the prompt triggering content management policy.",
+                "type": null,
+                "param": "prompt",
+                "code": "content_filter",
+                "status": 400,
+                "innererror": {
+                    "code": "ResponsibleAIPolicyViolation",
+                    "content_filter_result": {"violence": {"filtered": true, "severity": "high"}}
+                }
+            }})
+            .to_string(),
+        )
+        .create_async()
+        .await;
+
+    let provider = AzureOpenAIProvider::new(config(&server)).unwrap();
+    let result = client(provider).generate(&request(MODEL)).await;
+
+    assert!(matches!(result, Err(Error::ContentPolicy(e)) if e.provider == "azure"));
+}
+
+struct CountingTokenProvider(Arc<AtomicUsize>);
+
+#[async_trait]
+impl AzureTokenProvider for CountingTokenProvider {
+    async fn token(&self) -> stakai::Result<String> {
+        let count = self.0.fetch_add(1, Ordering::SeqCst) + 1;
+        Ok(format!("entra-token-{}", count))
+    }
+}
+
+#[tokio::test]
+async fn test_entra_id_token_provider() {
+    let calls = Arc::new(AtomicUsize::new(0));
+
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", CHAT_PATH)
+        .match_query(Matcher::UrlEncoded(
+            "api-version".to_string(),
+            "2025-01-01-preview".to_string(),
+        ))
+        .match_header("authorization", "Bearer entra-token-1")
+        .match_header("api-key", Matcher::Missing)
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(completion("stop", safe(&["hate"])))
+        .create_async()
+        .await;
+
+    let config = config(&server)
+        .with_api_version("2025-01-01-preview")
+        .with_token_provider(CountingTokenProvider(calls.clone()));
+    let provider = AzureOpenAIProvider::new(config).unwrap();
+    client(provider).generate(&request(MODEL)).await.unwrap();
+
+    mock.assert_async().await;
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn test_missing_api_key() {
+    let result = AzureOpenAIProvider::new(AzureOpenAIConfig::from_resource("my-resource", ""));
+    assert!(matches!(result, Err(Error::MissingApiKey(id)) if id == "azure"));
+}
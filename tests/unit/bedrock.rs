@@ -0,0 +1,297 @@
+//! Bedrock provider tests: SigV4 signing, Converse, event stream decoding and errors
+
+use mockito::Matcher;
+use serde_json::json;
+use stakai::providers::bedrock::{AwsCredentials, BedrockConfig, BedrockProvider};
+use stakai::{Error, FinishReason, GenerateRequest, Inference, Message, RetryPolicy, Role};
+use std::time::{Duration, UNIX_EPOCH};
+
+const MODEL_PATH: &str = "/model/anthropic.claude-3-haiku-20240307-v1%3A0";
+
+/// Known credentials, signing at 2024-01-15T12:00:00Z
+fn client(server: &mockito::ServerGuard) -> Inference {
+    let config = BedrockConfig::new(
+        "us-west-2",
+        AwsCredentials::new("AKIDEXAMPLE", "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY")
+            .with_session_token("session-token"),
+    )
+    .with_base_url(server.url())
+    .with_signing_time(UNIX_EPOCH + Duration::from_secs(1_705_320_000));
+
+    Inference::builder()
+        .register_provider("bedrock", BedrockProvider::new(config).unwrap())
+        .with_retry(RetryPolicy::none())
+        .build()
+        .unwrap()
+}
+
+fn request() -> GenerateRequest {
+    let mut request = GenerateRequest::new(
+        "bedrock:anthropic.claude-3-haiku-20240307-v1:0",
+        vec![
+            Message::new(Role::System, "Be brief"),
+            Message::new(Role::User, "Hi"),
+        ],
+    );
+    request.options.max_tokens = Some(100);
+    request
+}
+
+/// Encode an event stream message with string headers
+fn encode_event(headers: &[(&str, &str)], payload: &serde_json::Value) -> Vec<u8> {
+    let payload = payload.to_string();
+    let mut header_block = Vec::new();
+    for (name, value) in headers {
+        header_block.push(name.len() as u8);
+        header_block.extend_from_slice(name.as_bytes());
+        header_block.push(7);
+        header_block.extend_from_slice(&(value.len() as u16).to_be_bytes());
+        header_block.extend_from_slice(value.as_bytes());
+    }
+
+    let total_len = (16 + header_block.len() + payload.len()) as u32;
+    let mut message = Vec::new();
+    message.extend_from_slice(&total_len.to_be_bytes());
+    message.extend_from_slice(&(header_block.len() as u32).to_be_bytes());
+    message.extend_from_slice(&crc32fast::hash(&message).to_be_bytes());
+    message.extend_from_slice(&header_block);
+    message.extend_from_slice(payload.as_bytes());
+    message.extend_from_slice(&crc32fast::hash(&message).to_be_bytes());
+    message
+}
+
+fn event(event_type: &str, payload: serde_json::Value) -> Vec<u8> {
+    encode_event(
+        &[
+            (":message-type", "event"),
+            (":event-type", event_type),
+            (":content-type", "application/json"),
+        ],
+        &payload,
+    )
+}
+
+#[tokio::test]
+async fn test_generate_signs_converse_request() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", format!("{}/converse", MODEL_PATH).as_str())
+        .match_header("x-amz-date", "20240115T120000Z")
+        .match_header("x-amz-security-token", "session-token")
+        .match_header(
+            "authorization",
+            Matcher::Regex(
+                "^AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20240115/us-west-2/bedrock/aws4_request, \
+                 SignedHeaders=content-type;host;x-amz-date;x-amz-security-token, \
+                 Signature=[0-9a-f]{64}$"
+                    .to_string(),
+            ),
+        )
+        .match_body(Matcher::Json(json!({
+            "messages": [{"role": "user", "content": [{"text": "Hi"}]}],
+            "system": [{"text": "Be brief"}],
+            "inferenceConfig": {"maxTokens": 100}
+        })))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_header("x-amzn-requestid", "req-123")
+        .with_body(
+            json!({
+                "output": {"message": {"role": "assistant", "content": [{"text": "Hello!"}]}},
+                "stopReason": "end_turn",
+                "usage": {"inputTokens": 9, "outputTokens": 3, "totalTokens": 12},
+                "metrics": {"latencyMs": 120}
+            })
+            .to_string(),
+        )
+        .create_async()
+        .await;
+
+    let response = client(&server).generate(&request()).await.unwrap();
+
+    mock.assert_async().await;
+    assert_eq!(response.text(), "Hello!");
+    assert_eq!(response.finish_reason, FinishReason::Stop);
+    assert_eq!(response.usage.total_tokens, 12);
+
+    let metadata = response.metadata.unwrap();
+    assert_eq!(metadata.provider, "bedrock");
+    assert_eq!(metadata.request_id.as_deref(), Some("req-123"));
+    assert_eq!(
+        metadata.model.as_deref(),
+        Some("anthropic.claude-3-haiku-20240307-v1:0")
+    );
+}
+
+#[tokio::test]
+async fn test_signature_is_deterministic_with_fixed_clock() {
+    let signatures = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    let seen = signatures.clone();
+
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("POST", format!("{}/converse", MODEL_PATH).as_str())
+        .match_request(move |request| {
+            let authorization = request.header("authorization")[0]
+                .to_str()
+                .unwrap()
+                .to_string();
+            seen.lock().unwrap().push(authorization);
+            true
+        })
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            json!({
+                "output": {"message": {"role": "assistant", "content": [{"text": "Hi"}]}},
+                "stopReason": "end_turn"
+            })
+            .to_string(),
+        )
+        .expect(2)
+        .create_async()
+        .await;
+
+    let client = client(&server);
+    client.generate(&request()).await.unwrap();
+    client.generate(&request()).await.unwrap();
+
+    let signatures = signatures.lock().unwrap();
+    assert_eq!(signatures.len(), 2);
+    assert_eq!(signatures[0], signatures[1]);
+}
+
+#[tokio::test]
+async fn test_stream_decodes_event_stream() {
+    let mut body = Vec::new();
+    body.extend(event("messageStart", json!({"role": "assistant"})));
+    body.extend(event(
+        "contentBlockDelta",
+        json!({"contentBlockIndex": 0, "delta": {"text": "Let me "}}),
+    ));
+    body.extend(event(
+        "contentBlockDelta",
+        json!({"contentBlockIndex": 0, "delta": {"text": "check."}}),
+    ));
+    body.extend(event("contentBlockStop", json!({"contentBlockIndex": 0})));
+    body.extend(event(
+        "contentBlockStart",
+        json!({"contentBlockIndex": 1, "start": {"toolUse": {"toolUseId": "tooluse_1", "name": "get_weather"}}}),
+    ));
+    body.extend(event(
+        "contentBlockDelta",
+        json!({"contentBlockIndex": 1, "delta": {"toolUse": {"input": "{\"city\":\"Paris\"}"}}}),
+    ));
+    body.extend(event("contentBlockStop", json!({"contentBlockIndex": 1})));
+    body.extend(event("messageStop", json!({"stopReason": "tool_use"})));
+    body.extend(event(
+        "metadata",
+        json!({"usage": {"inputTokens": 20, "outputTokens": 10, "totalTokens": 30}, "metrics": {"latencyMs": 300}}),
+    ));
+
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", format!("{}/converse-stream", MODEL_PATH).as_str())
+        .match_header(
+            "authorization",
+            Matcher::Regex("^AWS4-HMAC-SHA256 ".to_string()),
+        )
+        .with_status(200)
+        .with_header("content-type", "application/vnd.amazon.eventstream")
+        .with_body(body)
+        .create_async()
+        .await;
+
+    let response = client(&server)
+        .stream(&request())
+        .await
+        .unwrap()
+        .collect_response()
+        .await
+        .unwrap();
+
+    mock.assert_async().await;
+    assert_eq!(response.text(), "Let me check.");
+    assert_eq!(response.tool_calls()[0].name, "get_weather");
+    assert_eq!(response.tool_calls()[0].arguments, json!({"city": "Paris"}));
+    assert_eq!(response.finish_reason, FinishReason::ToolCalls);
+    assert_eq!(response.usage.total_tokens, 30);
+}
+
+#[tokio::test]
+async fn test_stream_exception_event() {
+    let mut body = event("messageStart", json!({"role": "assistant"}));
+    body.extend(encode_event(
+        &[
+            (":message-type", "exception"),
+            (":exception-type", "throttlingException"),
+        ],
+        &json!({"message": "Too many requests"}),
+    ));
+
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("POST", format!("{}/converse-stream", MODEL_PATH).as_str())
+        .with_status(200)
+        .with_header("content-type", "application/vnd.amazon.eventstream")
+        .with_body(body)
+        .create_async()
+        .await;
+
+    // Errors before any content surface when the stream is opened
+    let result = client(&server).stream(&request()).await;
+    assert!(matches!(result, Err(Error::RateLimitExceeded(e)) if e.message == "Too many requests"));
+}
+
+#[tokio::test]
+async fn test_validation_error_maps_to_context_length() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("POST", format!("{}/converse", MODEL_PATH).as_str())
+        .with_status(400)
+        .with_header("content-type", "application/json")
+        .with_header(
+            "x-amzn-errortype",
+            "ValidationException:http://internal.amazon.com/coral/com.amazon.bedrock/",
+        )
+        .with_body(r#"{"message":"Input is too long for requested model."}"#)
+        .create_async()
+        .await;
+
+    let result = client(&server).generate(&request()).await;
+
+    match result {
+        Err(Error::ContextLengthExceeded(e)) => {
+            assert_eq!(e.provider, "bedrock");
+            assert_eq!(e.error_type.as_deref(), Some("ValidationException"));
+            assert_eq!(e.message, "Input is too long for requested model.");
+        }
+        other => panic!("Expected ContextLengthExceeded, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_credentials_from_profile_file() {
+    let path = std::env::temp_dir().join(format!("stakai-credentials-{}", uuid::Uuid::new_v4()));
+    std::fs::write(
+        &path,
+        "[default]\naws_access_key_id = AKIDDEFAULT\naws_secret_access_key = secret\n\n\
+         [prod]\naws_access_key_id = AKIDPROD\naws_secret_access_key = prod-secret\naws_session_token = token\n",
+    )
+    .unwrap();
+
+    let credentials = AwsCredentials::from_profile_file(&path, "prod").unwrap();
+    let missing = AwsCredentials::from_profile_file(&path, "staging");
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(credentials.access_key_id, "AKIDPROD");
+    assert_eq!(credentials.secret_access_key, "prod-secret");
+    assert_eq!(credentials.session_token.as_deref(), Some("token"));
+    assert!(matches!(missing, Err(Error::ConfigError(_))));
+}
+
+#[test]
+fn test_missing_credentials() {
+    let result = BedrockProvider::new(BedrockConfig::new("us-east-1", AwsCredentials::new("", "")));
+    assert!(matches!(result, Err(Error::MissingApiKey(id)) if id == "bedrock"));
+}
@@ -0,0 +1,80 @@
+//! Model catalog tests: provider detection and registered models
+
+use super::common::{openai_completion, request};
+use stakai::catalog::{self, ModelEntry};
+use stakai::{Error, Inference};
+
+fn openai_client(server: &mockito::ServerGuard) -> Inference {
+    use stakai::providers::openai::{OpenAIConfig, OpenAIProvider};
+
+    let provider =
+        OpenAIProvider::new(OpenAIConfig::new("test-key").with_base_url(server.url())).unwrap();
+    Inference::builder()
+        .register_provider("openai", provider)
+        .build()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_unprefixed_models_resolve_through_catalog() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/chat/completions")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(openai_completion("Hi"))
+        .expect(2)
+        .create_async()
+        .await;
+
+    let client = openai_client(&server);
+    for model in ["o3-mini", "chatgpt-4o-latest"] {
+        let request = request(model);
+        client.generate(&request).await.unwrap();
+    }
+
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_registered_model_resolves() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/chat/completions")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(openai_completion("Hi"))
+        .create_async()
+        .await;
+
+    let client = openai_client(&server);
+    let request = request("acme-triage-v3");
+    assert!(matches!(
+        client.generate(&request).await,
+        Err(Error::UnknownProvider(_))
+    ));
+
+    catalog::register(ModelEntry::new("acme-triage-v3", "openai").max_output_tokens(2048));
+    client.generate(&request).await.unwrap();
+
+    mock.assert_async().await;
+    assert_eq!(
+        catalog::lookup("acme-triage-v3-2025-06-01")
+            .unwrap()
+            .max_output_tokens,
+        Some(2048)
+    );
+}
+
+#[test]
+fn test_load_json_overrides() {
+    catalog::load_json(
+        r#"{"models": [{"id": "acme-summarizer", "provider": "anthropic", "max_output_tokens": 1024}]}"#,
+    )
+    .unwrap();
+
+    let entry = catalog::lookup("acme-summarizer").unwrap();
+    assert_eq!(entry.provider, "anthropic");
+    assert_eq!(entry.max_output_tokens, Some(1024));
+    assert!(catalog::load_json("not json").is_err());
+}
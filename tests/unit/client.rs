@@ -1,8 +1,9 @@
 //! Unit tests for client
 
+use super::common::{openai_completion, request};
 use stakai::providers::openai::{OpenAIConfig, OpenAIProvider};
 use stakai::registry::ProviderRegistry;
-use stakai::{GenerateRequest, Inference, Message, Role};
+use stakai::{GenerateRequest, Inference, InferenceConfig, Message, ReasoningEffort, Role};
 
 #[test]
 fn test_client_creation() {
@@ -57,16 +58,8 @@ fn test_request_creation() {
     let mut request = GenerateRequest::new(
         "openai:gpt-4",
         vec![
-            Message {
-                role: Role::System,
-                content: "You are helpful".into(),
-                name: None,
-            },
-            Message {
-                role: Role::User,
-                content: "Hello".into(),
-                name: None,
-            },
+            Message::new(Role::System, "You are helpful"),
+            Message::new(Role::User, "Hello"),
         ],
     );
     request.options.temperature = Some(0.7);
@@ -79,14 +72,7 @@ fn test_request_creation() {
 
 #[test]
 fn test_request_with_model() {
-    let request = GenerateRequest::new(
-        "gpt-4",
-        vec![Message {
-            role: Role::User,
-            content: "Hello".into(),
-            name: None,
-        }],
-    );
+    let request = GenerateRequest::new("gpt-4", vec![Message::new(Role::User, "Hello")]);
 
     assert_eq!(request.model, "gpt-4");
     assert_eq!(request.messages.len(), 1);
@@ -95,16 +81,8 @@ fn test_request_with_model() {
 #[test]
 fn test_request_multiple_messages() {
     let messages = vec![
-        Message {
-            role: Role::System,
-            content: "You are helpful".into(),
-            name: None,
-        },
-        Message {
-            role: Role::User,
-            content: "Hello".into(),
-            name: None,
-        },
+        Message::new(Role::System, "You are helpful"),
+        Message::new(Role::User, "Hello"),
     ];
 
     let request = GenerateRequest::new("openai:gpt-4", messages);
@@ -133,3 +111,94 @@ fn test_provider_registry_manual_registration() {
     assert_eq!(registry.list_providers().len(), 1);
     assert!(registry.has_provider("openai"));
 }
+
+#[tokio::test]
+async fn test_client_defaults_fill_unset_options() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/chat/completions")
+        .match_body(mockito::Matcher::PartialJson(
+            serde_json::json!({"temperature": 0.5, "max_tokens": 42}),
+        ))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(openai_completion("Hi"))
+        .create_async()
+        .await;
+
+    let client = Inference::with_config(
+        InferenceConfig::new()
+            .openai("test-key", Some(server.url()))
+            .temperature(0.5)
+            .max_tokens(42),
+    )
+    .unwrap();
+
+    let request = request("openai:gpt-4");
+    client.generate(&request).await.unwrap();
+
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_request_options_override_client_defaults() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/chat/completions")
+        .match_body(mockito::Matcher::PartialJson(
+            serde_json::json!({"temperature": 0.0, "max_tokens": 42}),
+        ))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(openai_completion("Hi"))
+        .create_async()
+        .await;
+
+    let provider =
+        OpenAIProvider::new(OpenAIConfig::new("test-key").with_base_url(server.url())).unwrap();
+    let client = Inference::builder()
+        .register_provider("openai", provider)
+        .with_temperature(0.5)
+        .with_max_tokens(42)
+        .build()
+        .unwrap();
+
+    let mut request = request("openai:gpt-4");
+    request.options.temperature = Some(0.0);
+    client.generate(&request).await.unwrap();
+
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_reasoning_sends_max_completion_tokens() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/chat/completions")
+        .match_request(|request| {
+            let body: serde_json::Value = serde_json::from_slice(request.body().unwrap()).unwrap();
+            // Reasoning models reject max_tokens, including the client-wide default
+            body["max_completion_tokens"] == 42
+                && body.get("max_tokens").is_none()
+                && body["reasoning_effort"] == "high"
+        })
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(openai_completion("Hi"))
+        .create_async()
+        .await;
+
+    let provider =
+        OpenAIProvider::new(OpenAIConfig::new("test-key").with_base_url(server.url())).unwrap();
+    let client = Inference::builder()
+        .register_provider("openai", provider)
+        .with_max_tokens(42)
+        .build()
+        .unwrap();
+
+    let mut request = request("openai:o4-mini");
+    request.options = request.options.reasoning_effort(ReasoningEffort::High);
+    client.generate(&request).await.unwrap();
+
+    mock.assert_async().await;
+}
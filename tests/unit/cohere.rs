@@ -0,0 +1,254 @@
+//! Cohere provider tests: grounded generation, streaming, embeddings and errors
+
+use mockito::Matcher;
+use serde_json::json;
+use stakai::providers::cohere::{CohereConfig, CohereProvider};
+use stakai::types::GenerateOptions;
+use stakai::{
+    Citation, Document, EmbedRequest, Error, FinishReason, GenerateRequest, Inference, Message,
+    RetryPolicy, Role, StreamEvent,
+};
+
+fn client(server: &mockito::ServerGuard) -> Inference {
+    Inference::builder()
+        .register_provider(
+            "cohere",
+            CohereProvider::new(CohereConfig::new("co-test").with_base_url(server.url())).unwrap(),
+        )
+        .with_retry(RetryPolicy::none())
+        .build()
+        .unwrap()
+}
+
+fn grounded_request() -> GenerateRequest {
+    let mut request = GenerateRequest::new(
+        "cohere:command-r-plus",
+        vec![Message::new(Role::User, "Where is Paris?")],
+    );
+    request.options = GenerateOptions::new()
+        .add_document(Document::new("Paris is the capital of France").with_id("doc_1"));
+    request
+}
+
+#[tokio::test]
+async fn test_generate_with_documents_and_citations() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/v2/chat")
+        .match_header("authorization", "Bearer co-test")
+        .match_body(Matcher::PartialJson(json!({
+            "model": "command-r-plus",
+            "messages": [{"role": "user", "content": "Where is Paris?"}],
+            "documents": [{"id": "doc_1", "data": {"text": "Paris is the capital of France"}}]
+        })))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            json!({
+                "id": "resp_1",
+                "finish_reason": "COMPLETE",
+                "message": {
+                    "role": "assistant",
+                    "content": [{"type": "text", "text": "Paris is in France."}],
+                    "citations": [{
+                        "start": 12,
+                        "end": 18,
+                        "text": "France",
+                        "sources": [{"type": "document", "id": "doc_1", "document": {}}]
+                    }]
+                },
+                "usage": {"tokens": {"input_tokens": 40, "output_tokens": 6}}
+            })
+            .to_string(),
+        )
+        .create_async()
+        .await;
+
+    let response = client(&server).generate(&grounded_request()).await.unwrap();
+
+    mock.assert_async().await;
+    assert_eq!(response.text(), "Paris is in France.");
+    assert_eq!(response.finish_reason, FinishReason::Stop);
+    assert_eq!(
+        response.citations,
+        vec![Citation {
+            start: 12,
+            end: 18,
+            text: "France".to_string(),
+            sources: vec!["doc_1".to_string()],
+        }]
+    );
+    assert_eq!(response.usage.total_tokens, 46);
+
+    let metadata = response.metadata.unwrap();
+    assert_eq!(metadata.provider, "cohere");
+    assert_eq!(metadata.id.as_deref(), Some("resp_1"));
+}
+
+#[tokio::test]
+async fn test_stream_tool_calls_and_citations() {
+    let events = [
+        json!({"type": "message-start", "id": "resp_2", "delta": {"message": {"role": "assistant"}}}),
+        json!({"type": "tool-plan-delta", "delta": {"message": {"tool_plan": "Check the docs"}}}),
+        json!({"type": "content-delta", "index": 0, "delta": {"message": {"content": {"text": "Paris is in France."}}}}),
+        json!({"type": "citation-start", "index": 0, "delta": {"message": {"citations": {
+            "start": 12, "end": 18, "text": "France", "sources": [{"type": "document", "id": "doc_1"}]
+        }}}}),
+        json!({"type": "tool-call-start", "index": 0, "delta": {"message": {"tool_calls": {
+            "id": "call_1", "type": "function", "function": {"name": "lookup", "arguments": "{\"city\":"}
+        }}}}),
+        json!({"type": "tool-call-delta", "index": 0, "delta": {"message": {"tool_calls": {
+            "function": {"arguments": "\"Paris\"}"}
+        }}}}),
+        json!({"type": "tool-call-end", "index": 0}),
+        json!({"type": "message-end", "delta": {
+            "finish_reason": "TOOL_CALL",
+            "usage": {"billed_units": {"input_tokens": 10, "output_tokens": 5}}
+        }}),
+    ];
+    let body: String = events
+        .iter()
+        .map(|event| {
+            format!(
+                "event: {}\ndata: {}\n\n",
+                event["type"].as_str().unwrap(),
+                event
+            )
+        })
+        .collect();
+
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/v2/chat")
+        .match_body(Matcher::PartialJson(json!({"stream": true})))
+        .with_status(200)
+        .with_header("content-type", "text/event-stream")
+        .with_body(body)
+        .create_async()
+        .await;
+
+    let mut stream = client(&server).stream(&grounded_request()).await.unwrap();
+    let mut citations = 0;
+    let mut accumulator = stakai::types::ResponseAccumulator::new();
+    while let Some(event) = futures::StreamExt::next(&mut stream).await {
+        let event = event.unwrap();
+        if matches!(event, StreamEvent::Citation { .. }) {
+            citations += 1;
+        }
+        accumulator.push(&event).unwrap();
+    }
+    let response = accumulator.finish().unwrap();
+
+    mock.assert_async().await;
+    assert_eq!(citations, 1);
+    assert_eq!(response.reasoning().as_deref(), Some("Check the docs"));
+    assert_eq!(response.text(), "Paris is in France.");
+    assert_eq!(response.citations[0].sources, vec!["doc_1".to_string()]);
+    assert_eq!(response.tool_calls()[0].arguments, json!({"city": "Paris"}));
+    assert_eq!(response.finish_reason, FinishReason::ToolCalls);
+    assert_eq!(response.usage.total_tokens, 15);
+}
+
+#[tokio::test]
+async fn test_invalid_token_maps_to_authentication() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("POST", "/v2/chat")
+        .with_status(498)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"id":"req_1","message":"invalid api token"}"#)
+        .create_async()
+        .await;
+
+    let result = client(&server).generate(&grounded_request()).await;
+
+    match result {
+        Err(Error::Authentication(e)) => {
+            assert_eq!(e.provider, "cohere");
+            assert_eq!(e.message, "invalid api token");
+        }
+        other => panic!("Expected Authentication, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_embed_batches_inputs() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/v2/embed")
+        .match_body(Matcher::PartialJson(json!({
+            "model": "embed-v4.0",
+            "input_type": "search_query",
+            "embedding_types": ["float"]
+        })))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            json!({
+                "id": "emb_1",
+                "embeddings": {"float": [[0.5, 0.25]]},
+                "meta": {"billed_units": {"input_tokens": 3}}
+            })
+            .to_string(),
+        )
+        .expect(2)
+        .create_async()
+        .await;
+
+    let provider = CohereProvider::new(
+        CohereConfig::new("co-test")
+            .with_base_url(server.url())
+            .with_embed_input_type("search_query"),
+    )
+    .unwrap();
+    let client = Inference::builder()
+        .register_provider("cohere", provider)
+        .build()
+        .unwrap();
+
+    let inputs = vec!["text".to_string(); CohereProvider::MAX_EMBED_BATCH + 1];
+    let response = client
+        .embed(&EmbedRequest::new("cohere:embed-v4.0", inputs))
+        .await
+        .unwrap();
+
+    mock.assert_async().await;
+    assert_eq!(response.embeddings.len(), 2);
+    assert_eq!(response.usage.prompt_tokens, 6);
+}
+
+#[tokio::test]
+async fn test_list_models() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("GET", "/v1/models")
+        .match_query(Matcher::UrlEncoded("endpoint".into(), "chat".into()))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            json!({
+                "models": [{
+                    "name": "command-a-03-2025",
+                    "endpoints": ["chat"],
+                    "context_length": 256000,
+                    "features": ["tools", "strict_tools"]
+                }]
+            })
+            .to_string(),
+        )
+        .create_async()
+        .await;
+
+    let models = client(&server).list_models("cohere").await.unwrap();
+
+    mock.assert_async().await;
+    assert_eq!(models[0].qualified_id(), "cohere:command-a-03-2025");
+    assert_eq!(models[0].context_window, Some(256000));
+    assert_eq!(models[0].supports_tools, Some(true));
+}
+
+#[test]
+fn test_missing_api_key() {
+    let result = CohereProvider::new(CohereConfig::new(""));
+    assert!(matches!(result, Err(Error::MissingApiKey(id)) if id == "cohere"));
+}
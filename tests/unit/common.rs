@@ -0,0 +1,26 @@
+//! Fixtures shared by the unit tests
+
+use serde_json::json;
+use stakai::{GenerateRequest, Message, Role};
+
+/// Request for `model` with a single user message
+pub fn request(model: &str) -> GenerateRequest {
+    GenerateRequest::new(model, vec![Message::new(Role::User, "Hi")])
+}
+
+/// OpenAI chat completion body answering `content`
+pub fn openai_completion(content: &str) -> String {
+    json!({
+        "id": "chatcmpl-1",
+        "object": "chat.completion",
+        "created": 1,
+        "model": "gpt-4o",
+        "choices": [{
+            "index": 0,
+            "message": {"role": "assistant", "content": content},
+            "finish_reason": "stop"
+        }],
+        "usage": {"prompt_tokens": 1, "completion_tokens": 1, "total_tokens": 2}
+    })
+    .to_string()
+}
@@ -0,0 +1,218 @@
+//! Cross-provider tests replaying the same tool-use conversation against mock servers
+
+use mockito::Matcher;
+use serde_json::json;
+use stakai::providers::anthropic::{AnthropicConfig, AnthropicProvider};
+use stakai::providers::gemini::{GeminiConfig, GeminiProvider};
+use stakai::providers::openai::{OpenAIConfig, OpenAIProvider};
+use stakai::{ContentPart, GenerateRequest, Inference, Message, Role};
+
+/// A conversation where the assistant called two tools and one of them failed
+fn conversation() -> Vec<Message> {
+    vec![
+        Message::new(Role::System, "You are a weather assistant."),
+        Message::new(Role::User, "What's the weather in Paris and Rome?"),
+        Message::new(
+            Role::Assistant,
+            vec![
+                ContentPart::tool_call("call_1", "get_weather", json!({"city": "Paris"})),
+                ContentPart::tool_call("call_2", "get_weather", json!({"city": "Rome"})),
+            ],
+        ),
+        Message::new(
+            Role::Tool,
+            vec![ContentPart::tool_result("call_1", json!({"temp": 18}))],
+        ),
+        Message::new(
+            Role::Tool,
+            vec![ContentPart::tool_error(
+                "call_2",
+                json!("Weather service unavailable"),
+            )],
+        ),
+    ]
+}
+
+#[tokio::test]
+async fn test_conversation_openai() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/chat/completions")
+        .match_body(Matcher::PartialJson(json!({
+            "messages": [
+                {"role": "system", "content": "You are a weather assistant."},
+                {"role": "user", "content": "What's the weather in Paris and Rome?"},
+                {
+                    "role": "assistant",
+                    "tool_calls": [
+                        {"id": "call_1", "type": "function", "function": {"name": "get_weather", "arguments": "{\"city\":\"Paris\"}"}},
+                        {"id": "call_2", "type": "function", "function": {"name": "get_weather", "arguments": "{\"city\":\"Rome\"}"}}
+                    ]
+                },
+                {"role": "tool", "tool_call_id": "call_1", "content": "{\"temp\":18}"},
+                {"role": "tool", "tool_call_id": "call_2", "content": "Weather service unavailable"}
+            ]
+        })))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            json!({
+                "id": "chatcmpl-1",
+                "object": "chat.completion",
+                "created": 1,
+                "model": "gpt-4",
+                "choices": [{
+                    "index": 0,
+                    "message": {"role": "assistant", "content": "Paris is 18°C."},
+                    "finish_reason": "stop"
+                }],
+                "usage": {"prompt_tokens": 40, "completion_tokens": 5, "total_tokens": 45}
+            })
+            .to_string(),
+        )
+        .create_async()
+        .await;
+
+    let provider =
+        OpenAIProvider::new(OpenAIConfig::new("test-key").with_base_url(server.url())).unwrap();
+    let client = Inference::builder()
+        .register_provider("openai", provider)
+        .build()
+        .unwrap();
+
+    let response = client
+        .generate(&GenerateRequest::new("openai:gpt-4", conversation()))
+        .await
+        .unwrap();
+
+    mock.assert_async().await;
+    assert_eq!(response.text(), "Paris is 18°C.");
+}
+
+#[tokio::test]
+async fn test_conversation_anthropic() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/messages")
+        .match_body(Matcher::PartialJson(json!({
+            "system": "You are a weather assistant.",
+            "messages": [
+                {"role": "user", "content": "What's the weather in Paris and Rome?"},
+                {
+                    "role": "assistant",
+                    "content": [
+                        {"type": "tool_use", "id": "call_1", "name": "get_weather", "input": {"city": "Paris"}},
+                        {"type": "tool_use", "id": "call_2", "name": "get_weather", "input": {"city": "Rome"}}
+                    ]
+                },
+                {
+                    "role": "user",
+                    "content": [
+                        {"type": "tool_result", "tool_use_id": "call_1", "content": "{\"temp\":18}"},
+                        {"type": "tool_result", "tool_use_id": "call_2", "content": "Weather service unavailable", "is_error": true}
+                    ]
+                }
+            ]
+        })))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            json!({
+                "id": "msg_1",
+                "type": "message",
+                "role": "assistant",
+                "content": [{"type": "text", "text": "Paris is 18°C."}],
+                "model": "claude-3-5-sonnet-20241022",
+                "stop_reason": "end_turn",
+                "usage": {"input_tokens": 40, "output_tokens": 5}
+            })
+            .to_string(),
+        )
+        .create_async()
+        .await;
+
+    let provider =
+        AnthropicProvider::new(AnthropicConfig::new("test-key").with_base_url(server.url()))
+            .unwrap();
+    let client = Inference::builder()
+        .register_provider("anthropic", provider)
+        .build()
+        .unwrap();
+
+    let response = client
+        .generate(&GenerateRequest::new(
+            "anthropic:claude-3-5-sonnet-20241022",
+            conversation(),
+        ))
+        .await
+        .unwrap();
+
+    mock.assert_async().await;
+    assert_eq!(response.text(), "Paris is 18°C.");
+}
+
+#[tokio::test]
+async fn test_conversation_gemini() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/models/gemini-1.5-flash:generateContent")
+        .match_query(Matcher::Any)
+        .match_body(Matcher::PartialJson(json!({
+            "contents": [
+                {
+                    "role": "user",
+                    "parts": [
+                        {"text": "System instructions: You are a weather assistant.\n\n"},
+                        {"text": "What's the weather in Paris and Rome?"}
+                    ]
+                },
+                {
+                    "role": "model",
+                    "parts": [
+                        {"function_call": {"name": "get_weather", "args": {"city": "Paris"}}},
+                        {"function_call": {"name": "get_weather", "args": {"city": "Rome"}}}
+                    ]
+                },
+                {
+                    "role": "user",
+                    "parts": [
+                        {"function_response": {"name": "get_weather", "response": {"temp": 18}}},
+                        {"function_response": {"name": "get_weather", "response": {"error": "Weather service unavailable"}}}
+                    ]
+                }
+            ]
+        })))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            json!({
+                "candidates": [{
+                    "content": {"role": "model", "parts": [{"text": "Paris is 18°C."}]},
+                    "finishReason": "STOP"
+                }],
+                "usageMetadata": {"promptTokenCount": 40, "candidatesTokenCount": 5, "totalTokenCount": 45}
+            })
+            .to_string(),
+        )
+        .create_async()
+        .await;
+
+    let provider =
+        GeminiProvider::new(GeminiConfig::new("test-key").with_base_url(server.url())).unwrap();
+    let client = Inference::builder()
+        .register_provider("google", provider)
+        .build()
+        .unwrap();
+
+    let response = client
+        .generate(&GenerateRequest::new(
+            "google:gemini-1.5-flash",
+            conversation(),
+        ))
+        .await
+        .unwrap();
+
+    mock.assert_async().await;
+    assert_eq!(response.text(), "Paris is 18°C.");
+    assert_eq!(response.usage.total_tokens, 45);
+}
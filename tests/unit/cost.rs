@@ -0,0 +1,269 @@
+//! Cost accounting tests: usage breakdowns per provider, pricing and budgets
+
+use super::common::request;
+use serde_json::json;
+use stakai::catalog::ModelPricing;
+use stakai::providers::anthropic::{AnthropicConfig, AnthropicProvider};
+use stakai::providers::gemini::{GeminiConfig, GeminiProvider};
+use stakai::providers::openai::{OpenAIConfig, OpenAIProvider};
+use stakai::{CostTracker, Error, GenerateResponse, Inference, ResponseMetadata, Usage};
+
+fn assert_close(actual: f64, expected: f64) {
+    assert!(
+        (actual - expected).abs() < 1e-9,
+        "expected {}, got {}",
+        expected,
+        actual
+    );
+}
+
+#[tokio::test]
+async fn test_openai_usage_details() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("POST", "/chat/completions")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            json!({
+                "id": "chatcmpl-1",
+                "object": "chat.completion",
+                "created": 1,
+                "model": "o3-mini-2025-01-31",
+                "choices": [{
+                    "index": 0,
+                    "message": {"role": "assistant", "content": "Hi"},
+                    "finish_reason": "stop"
+                }],
+                "usage": {
+                    "prompt_tokens": 1000,
+                    "completion_tokens": 500,
+                    "total_tokens": 1500,
+                    "prompt_tokens_details": {"cached_tokens": 400},
+                    "completion_tokens_details": {"reasoning_tokens": 300}
+                }
+            })
+            .to_string(),
+        )
+        .create_async()
+        .await;
+
+    let provider =
+        OpenAIProvider::new(OpenAIConfig::new("test-key").with_base_url(server.url())).unwrap();
+    let client = Inference::builder()
+        .register_provider("openai", provider)
+        .build()
+        .unwrap();
+
+    let response = client.generate(&request("openai:o3-mini")).await.unwrap();
+    assert_eq!(response.usage.cached_tokens, 400);
+    assert_eq!(response.usage.reasoning_tokens, 300);
+
+    // o3-mini: $1.10 input, $0.55 cached input, $4.40 output per million tokens
+    let cost = response.cost().unwrap();
+    assert_close(cost.input, 600.0 * 1.10 / 1e6);
+    assert_close(cost.cached_input, 400.0 * 0.55 / 1e6);
+    assert_close(cost.output, 200.0 * 4.40 / 1e6);
+    assert_close(cost.reasoning, 300.0 * 4.40 / 1e6);
+    assert_close(
+        cost.total(),
+        (600.0 * 1.10 + 400.0 * 0.55 + 500.0 * 4.40) / 1e6,
+    );
+}
+
+#[tokio::test]
+async fn test_anthropic_cache_usage() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("POST", "/messages")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            json!({
+                "id": "msg_1",
+                "type": "message",
+                "role": "assistant",
+                "content": [{"type": "text", "text": "Hi"}],
+                "model": "claude-sonnet-4-20250514",
+                "stop_reason": "end_turn",
+                "usage": {
+                    "input_tokens": 100,
+                    "output_tokens": 50,
+                    "cache_creation_input_tokens": 2000,
+                    "cache_read_input_tokens": 3000
+                }
+            })
+            .to_string(),
+        )
+        .create_async()
+        .await;
+
+    let provider =
+        AnthropicProvider::new(AnthropicConfig::new("test-key").with_base_url(server.url()))
+            .unwrap();
+    let client = Inference::builder()
+        .register_provider("anthropic", provider)
+        .build()
+        .unwrap();
+
+    let response = client
+        .generate(&request("anthropic:claude-sonnet-4-20250514"))
+        .await
+        .unwrap();
+
+    assert_eq!(
+        response.usage,
+        Usage {
+            prompt_tokens: 5100,
+            completion_tokens: 50,
+            total_tokens: 5150,
+            cached_tokens: 3000,
+            cache_creation_tokens: 2000,
+            reasoning_tokens: 0,
+        }
+    );
+
+    // claude-sonnet-4: $3 input, $0.30 cache read, $3.75 cache write, $15 output
+    let cost = response.cost().unwrap();
+    assert_close(cost.input, 100.0 * 3.0 / 1e6);
+    assert_close(cost.cached_input, 3000.0 * 0.30 / 1e6);
+    assert_close(cost.cache_creation, 2000.0 * 3.75 / 1e6);
+    assert_close(cost.output, 50.0 * 15.0 / 1e6);
+}
+
+#[tokio::test]
+async fn test_gemini_thought_usage() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("POST", "/models/gemini-2.5-flash:generateContent")
+        .match_query(mockito::Matcher::Any)
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            json!({
+                "candidates": [{
+                    "content": {"role": "model", "parts": [{"text": "Hi"}]},
+                    "finishReason": "STOP"
+                }],
+                "usageMetadata": {
+                    "promptTokenCount": 100,
+                    "candidatesTokenCount": 20,
+                    "thoughtsTokenCount": 80,
+                    "cachedContentTokenCount": 40,
+                    "totalTokenCount": 200
+                },
+                "modelVersion": "gemini-2.5-flash",
+                "responseId": "resp-1"
+            })
+            .to_string(),
+        )
+        .create_async()
+        .await;
+
+    let provider =
+        GeminiProvider::new(GeminiConfig::new("test-key").with_base_url(server.url())).unwrap();
+    let client = Inference::builder()
+        .register_provider("google", provider)
+        .build()
+        .unwrap();
+
+    let response = client
+        .generate(&request("google:gemini-2.5-flash"))
+        .await
+        .unwrap();
+
+    assert_eq!(response.model(), Some("gemini-2.5-flash"));
+    assert_eq!(response.usage.completion_tokens, 100);
+    assert_eq!(response.usage.reasoning_tokens, 80);
+    assert_eq!(response.usage.cached_tokens, 40);
+    assert_eq!(response.usage.total_tokens, 200);
+    assert!(response.cost().unwrap().reasoning > 0.0);
+}
+
+#[test]
+fn test_usage_cost_unknown_model() {
+    let usage = Usage {
+        prompt_tokens: 10,
+        completion_tokens: 10,
+        total_tokens: 20,
+        ..Default::default()
+    };
+
+    assert!(usage.cost("acme-unpriced-model").is_none());
+    assert_close(
+        usage.cost("gpt-4o").unwrap().total(),
+        (10.0 * 2.5 + 10.0 * 10.0) / 1e6,
+    );
+}
+
+#[test]
+fn test_pricing_defaults_cache_prices_to_input() {
+    let usage = Usage {
+        prompt_tokens: 1_000_000,
+        cached_tokens: 250_000,
+        cache_creation_tokens: 250_000,
+        ..Default::default()
+    };
+
+    let cost = ModelPricing::new(2.0, 8.0).cost(&usage);
+    assert_close(cost.input, 1.0);
+    assert_close(cost.cached_input, 0.5);
+    assert_close(cost.cache_creation, 0.5);
+    assert_close(cost.total(), 2.0);
+}
+
+#[test]
+fn test_cost_tracker_aggregates_and_enforces_budget() {
+    let tracker = CostTracker::new().with_budget(0.01);
+    let usage = Usage {
+        prompt_tokens: 1000,
+        completion_tokens: 400,
+        total_tokens: 1400,
+        ..Default::default()
+    };
+
+    // gpt-4o: 1000 * $2.50 + 400 * $10 per million = $0.0065 per call
+    tracker.record("gpt-4o", &usage);
+    assert!(tracker.check_budget().is_ok());
+    assert_close(tracker.remaining().unwrap(), 0.0035);
+
+    tracker.record("gpt-4o", &usage);
+    tracker.record("acme-unpriced-model", &usage);
+    assert_eq!(tracker.usage().total_tokens, 4200);
+    assert_close(tracker.cost().total(), 0.013);
+    match tracker.check_budget() {
+        Err(Error::BudgetExceeded { spent, budget }) => {
+            assert_close(spent, 0.013);
+            assert_close(budget, 0.01);
+        }
+        other => panic!("expected BudgetExceeded, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_sum_usage_and_cost() {
+    let responses: Vec<GenerateResponse> = (0..3)
+        .map(|_| {
+            let usage = Usage {
+                prompt_tokens: 100,
+                completion_tokens: 10,
+                total_tokens: 110,
+                cached_tokens: 50,
+                ..Default::default()
+            };
+            GenerateResponse::new(vec![], usage, stakai::FinishReason::Stop).with_metadata(
+                ResponseMetadata {
+                    model: Some("gpt-4o-mini".to_string()),
+                    ..Default::default()
+                },
+            )
+        })
+        .collect();
+
+    let usage: Usage = responses.iter().map(|r| r.usage.clone()).sum();
+    assert_eq!(usage.total_tokens, 330);
+    assert_eq!(usage.cached_tokens, 150);
+
+    let cost: stakai::Cost = responses.iter().filter_map(GenerateResponse::cost).sum();
+    assert_close(cost.total(), usage.cost("gpt-4o-mini").unwrap().total());
+}
@@ -0,0 +1,306 @@
+//! Unit tests for the static provider dispatcher, run against local mock servers
+
+use super::common::request;
+use futures::StreamExt;
+use serde_json::json;
+use stakai::provider::{ProviderDispatcher, ProviderKind};
+use stakai::{Error, InferenceConfig, StreamEvent};
+
+#[tokio::test]
+async fn test_dispatch_generate_openai() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/chat/completions")
+        .match_header("authorization", "Bearer test-key")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            json!({
+                "id": "chatcmpl-1",
+                "object": "chat.completion",
+                "created": 1,
+                "model": "gpt-4",
+                "choices": [{
+                    "index": 0,
+                    "message": {"role": "assistant", "content": "Hi from OpenAI"},
+                    "finish_reason": "stop"
+                }],
+                "usage": {"prompt_tokens": 3, "completion_tokens": 4, "total_tokens": 7}
+            })
+            .to_string(),
+        )
+        .create_async()
+        .await;
+
+    let dispatcher =
+        ProviderDispatcher::new(InferenceConfig::new().openai("test-key", Some(server.url())))
+            .unwrap();
+
+    let response = dispatcher
+        .generate(ProviderKind::OpenAI, request("gpt-4"))
+        .await
+        .unwrap();
+
+    mock.assert_async().await;
+    assert_eq!(response.text(), "Hi from OpenAI");
+    assert_eq!(response.usage.total_tokens, 7);
+}
+
+#[tokio::test]
+async fn test_dispatch_generate_anthropic() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/messages")
+        .match_header("x-api-key", "test-key")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            json!({
+                "id": "msg_1",
+                "type": "message",
+                "role": "assistant",
+                "content": [{"type": "text", "text": "Hi from Claude"}],
+                "model": "claude-3-5-sonnet-20241022",
+                "stop_reason": "end_turn",
+                "usage": {"input_tokens": 3, "output_tokens": 4}
+            })
+            .to_string(),
+        )
+        .create_async()
+        .await;
+
+    let dispatcher =
+        ProviderDispatcher::new(InferenceConfig::new().anthropic("test-key", Some(server.url())))
+            .unwrap();
+
+    let response = dispatcher
+        .generate(
+            ProviderKind::Anthropic,
+            request("claude-3-5-sonnet-20241022"),
+        )
+        .await
+        .unwrap();
+
+    mock.assert_async().await;
+    assert_eq!(response.text(), "Hi from Claude");
+    assert_eq!(response.usage.total_tokens, 7);
+}
+
+#[tokio::test]
+async fn test_dispatch_generate_gemini() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/models/gemini-1.5-flash:generateContent")
+        .match_query(mockito::Matcher::UrlEncoded(
+            "key".into(),
+            "test-key".into(),
+        ))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            json!({
+                "candidates": [{
+                    "content": {"role": "model", "parts": [{"text": "Hi from Gemini"}]},
+                    "finish_reason": "STOP"
+                }],
+                "usage_metadata": {
+                    "prompt_token_count": 3,
+                    "candidates_token_count": 4,
+                    "total_token_count": 7
+                }
+            })
+            .to_string(),
+        )
+        .create_async()
+        .await;
+
+    let dispatcher =
+        ProviderDispatcher::new(InferenceConfig::new().gemini("test-key", Some(server.url())))
+            .unwrap();
+
+    let response = dispatcher
+        .generate(ProviderKind::Google, request("gemini-1.5-flash"))
+        .await
+        .unwrap();
+
+    mock.assert_async().await;
+    assert_eq!(response.text(), "Hi from Gemini");
+    assert_eq!(response.usage.total_tokens, 7);
+}
+
+#[tokio::test]
+async fn test_dispatch_stream_openai() {
+    let mut server = mockito::Server::new_async().await;
+    let chunk = |delta: serde_json::Value, finish: Option<&str>| {
+        format!(
+            "data: {}\n\n",
+            json!({
+                "id": "chatcmpl-1",
+                "object": "chat.completion.chunk",
+                "created": 1,
+                "model": "gpt-4",
+                "choices": [{"index": 0, "delta": delta, "finish_reason": finish}]
+            })
+        )
+    };
+    let body = [
+        chunk(json!({"content": "Hello"}), None),
+        chunk(json!({"content": " world"}), None),
+        chunk(json!({}), Some("stop")),
+        "data: [DONE]\n\n".to_string(),
+    ]
+    .concat();
+
+    server
+        .mock("POST", "/chat/completions")
+        .with_status(200)
+        .with_header("content-type", "text/event-stream")
+        .with_body(body)
+        .create_async()
+        .await;
+
+    let dispatcher =
+        ProviderDispatcher::new(InferenceConfig::new().openai("test-key", Some(server.url())))
+            .unwrap();
+
+    let mut stream = dispatcher
+        .stream(ProviderKind::OpenAI, request("gpt-4"))
+        .await
+        .unwrap();
+
+    let mut text = String::new();
+    let mut finished = false;
+    while let Some(event) = stream.next().await {
+        match event.unwrap() {
+            StreamEvent::TextDelta { delta, .. } => text.push_str(&delta),
+            StreamEvent::Finish { .. } => finished = true,
+            _ => {}
+        }
+    }
+
+    assert_eq!(text, "Hello world");
+    assert!(finished);
+}
+
+#[tokio::test]
+async fn test_dispatch_stream_anthropic() {
+    let mut server = mockito::Server::new_async().await;
+    let events = [
+        json!({
+            "type": "message_start",
+            "message": {
+                "id": "msg_1", "type": "message", "role": "assistant", "content": [],
+                "model": "claude-3-5-sonnet-20241022", "stop_reason": null,
+                "usage": {"input_tokens": 3, "output_tokens": 0}
+            }
+        }),
+        json!({"type": "content_block_start", "index": 0, "content_block": {"type": "text", "text": ""}}),
+        json!({"type": "content_block_delta", "index": 0, "delta": {"type": "text_delta", "text": "Hi"}}),
+        json!({"type": "content_block_stop", "index": 0}),
+        json!({"type": "message_delta", "delta": {"type": "message_delta"}, "usage": {"input_tokens": 0, "output_tokens": 4}}),
+        json!({"type": "message_stop"}),
+    ];
+    let body: String = events
+        .iter()
+        .map(|event| format!("event: {}\ndata: {}\n\n", event["type"], event))
+        .collect();
+
+    server
+        .mock("POST", "/messages")
+        .with_status(200)
+        .with_header("content-type", "text/event-stream")
+        .with_body(body)
+        .create_async()
+        .await;
+
+    let dispatcher =
+        ProviderDispatcher::new(InferenceConfig::new().anthropic("test-key", Some(server.url())))
+            .unwrap();
+
+    let mut stream = dispatcher
+        .stream(
+            ProviderKind::Anthropic,
+            request("claude-3-5-sonnet-20241022"),
+        )
+        .await
+        .unwrap();
+
+    let mut text = String::new();
+    let mut total_tokens = 0;
+    while let Some(event) = stream.next().await {
+        match event.unwrap() {
+            StreamEvent::TextDelta { delta, .. } => text.push_str(&delta),
+            StreamEvent::Finish { usage, .. } => total_tokens = usage.total_tokens,
+            _ => {}
+        }
+    }
+
+    assert_eq!(text, "Hi");
+    assert_eq!(total_tokens, 7);
+}
+
+#[tokio::test]
+async fn test_dispatch_stream_gemini() {
+    let mut server = mockito::Server::new_async().await;
+    let body = [
+        json!({"candidates": [{"content": {"role": "model", "parts": [{"text": "Hi"}]}}]}),
+        json!({
+            "candidates": [{"content": {"role": "model", "parts": [{"text": " there"}]}}],
+            "usage_metadata": {"prompt_token_count": 3, "candidates_token_count": 4, "total_token_count": 7}
+        }),
+    ]
+    .iter()
+    .map(|chunk| format!("data: {}\r\n\r\n", chunk))
+    .collect::<String>();
+
+    server
+        .mock("POST", "/models/gemini-1.5-flash:streamGenerateContent")
+        .match_query(mockito::Matcher::UrlEncoded("alt".into(), "sse".into()))
+        .with_status(200)
+        .with_header("content-type", "text/event-stream")
+        .with_body(body)
+        .create_async()
+        .await;
+
+    let dispatcher =
+        ProviderDispatcher::new(InferenceConfig::new().gemini("test-key", Some(server.url())))
+            .unwrap();
+
+    let mut stream = dispatcher
+        .stream(ProviderKind::Google, request("gemini-1.5-flash"))
+        .await
+        .unwrap();
+
+    let mut text = String::new();
+    while let Some(event) = stream.next().await {
+        if let StreamEvent::TextDelta { delta, .. } = event.unwrap() {
+            text.push_str(&delta);
+        }
+    }
+
+    assert_eq!(text, "Hi there");
+}
+
+#[tokio::test]
+async fn test_dispatch_unconfigured_provider() {
+    let dispatcher =
+        ProviderDispatcher::new(InferenceConfig::new().openai("test-key", None)).unwrap();
+
+    assert!(dispatcher.has_provider(ProviderKind::OpenAI));
+    assert!(!dispatcher.has_provider(ProviderKind::Anthropic));
+
+    let result = dispatcher
+        .generate(
+            ProviderKind::Anthropic,
+            request("claude-3-5-sonnet-20241022"),
+        )
+        .await;
+
+    assert!(matches!(result, Err(Error::ProviderNotFound(id)) if id == "anthropic"));
+}
+
+#[test]
+fn test_dispatch_invalid_config() {
+    let result = ProviderDispatcher::new(InferenceConfig::new().openai("", None));
+    assert!(matches!(result, Err(Error::MissingApiKey(_))));
+}
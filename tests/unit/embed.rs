@@ -0,0 +1,153 @@
+//! Embeddings API tests against mock provider servers
+
+use mockito::Matcher;
+use serde_json::json;
+use stakai::providers::anthropic::{AnthropicConfig, AnthropicProvider};
+use stakai::providers::gemini::{GeminiConfig, GeminiProvider};
+use stakai::providers::openai::{OpenAIConfig, OpenAIProvider};
+use stakai::{EmbedRequest, Error, Inference};
+
+#[tokio::test]
+async fn test_embed_openai() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/embeddings")
+        .match_body(Matcher::PartialJson(json!({
+            "model": "text-embedding-3-small",
+            "input": ["first", "second"],
+            "dimensions": 2
+        })))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            json!({
+                "object": "list",
+                "data": [
+                    {"object": "embedding", "index": 1, "embedding": [0.3, 0.4]},
+                    {"object": "embedding", "index": 0, "embedding": [0.1, 0.2]}
+                ],
+                "model": "text-embedding-3-small",
+                "usage": {"prompt_tokens": 4, "total_tokens": 4}
+            })
+            .to_string(),
+        )
+        .create_async()
+        .await;
+
+    let provider =
+        OpenAIProvider::new(OpenAIConfig::new("test-key").with_base_url(server.url())).unwrap();
+    let client = Inference::builder()
+        .register_provider("openai", provider)
+        .build()
+        .unwrap();
+
+    let request =
+        EmbedRequest::new("openai:text-embedding-3-small", ["first", "second"]).dimensions(2);
+    let response = client.embed(&request).await.unwrap();
+
+    mock.assert_async().await;
+    assert_eq!(response.embeddings, vec![vec![0.1, 0.2], vec![0.3, 0.4]]);
+    assert_eq!(response.usage.prompt_tokens, 4);
+    assert_eq!(response.usage.total_tokens, 4);
+}
+
+#[tokio::test]
+async fn test_embed_gemini_single() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/models/text-embedding-004:embedContent")
+        .match_query(Matcher::UrlEncoded("key".into(), "test-key".into()))
+        .match_body(Matcher::PartialJson(json!({
+            "content": {"parts": [{"text": "hello"}]},
+            "output_dimensionality": 3
+        })))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(json!({"embedding": {"values": [0.1, 0.2, 0.3]}}).to_string())
+        .create_async()
+        .await;
+
+    let provider =
+        GeminiProvider::new(GeminiConfig::new("test-key").with_base_url(server.url())).unwrap();
+    let client = Inference::builder()
+        .register_provider("google", provider)
+        .build()
+        .unwrap();
+
+    let request = EmbedRequest::new("google:text-embedding-004", ["hello"]).dimensions(3);
+    let response = client.embed(&request).await.unwrap();
+
+    mock.assert_async().await;
+    assert_eq!(response.embeddings, vec![vec![0.1, 0.2, 0.3]]);
+}
+
+#[tokio::test]
+async fn test_embed_gemini_batches_inputs() {
+    let input: Vec<String> = (0..150).map(|i| format!("text {}", i)).collect();
+
+    let mut server = mockito::Server::new_async().await;
+    let first = server
+        .mock("POST", "/models/text-embedding-004:batchEmbedContents")
+        .match_query(Matcher::Any)
+        .match_body(Matcher::PartialJson(json!({
+            "requests": [{
+                "model": "models/text-embedding-004",
+                "content": {"parts": [{"text": "text 0"}]}
+            }]
+        })))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            json!({"embeddings": vec![json!({"values": [0.0]}); GeminiProvider::MAX_EMBED_BATCH]})
+                .to_string(),
+        )
+        .create_async()
+        .await;
+    let second = server
+        .mock("POST", "/models/text-embedding-004:batchEmbedContents")
+        .match_query(Matcher::Any)
+        .match_body(Matcher::PartialJson(json!({
+            "requests": [{"content": {"parts": [{"text": "text 100"}]}}]
+        })))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(json!({"embeddings": vec![json!({"values": [1.0]}); 50]}).to_string())
+        .create_async()
+        .await;
+
+    let provider =
+        GeminiProvider::new(GeminiConfig::new("test-key").with_base_url(server.url())).unwrap();
+    let client = Inference::builder()
+        .register_provider("google", provider)
+        .build()
+        .unwrap();
+
+    let response = client
+        .embed(&EmbedRequest::new("google:text-embedding-004", input))
+        .await
+        .unwrap();
+
+    first.assert_async().await;
+    second.assert_async().await;
+    assert_eq!(response.embeddings.len(), 150);
+    assert_eq!(response.embeddings[99], vec![0.0]);
+    assert_eq!(response.embeddings[100], vec![1.0]);
+}
+
+#[tokio::test]
+async fn test_embed_unsupported_provider() {
+    let provider = AnthropicProvider::new(AnthropicConfig::new("test-key")).unwrap();
+    let client = Inference::builder()
+        .register_provider("anthropic", provider)
+        .build()
+        .unwrap();
+
+    let result = client
+        .embed(&EmbedRequest::new(
+            "anthropic:claude-3-5-sonnet-20241022",
+            ["hello"],
+        ))
+        .await;
+
+    assert!(matches!(result, Err(Error::ProviderError(_))));
+}
@@ -0,0 +1,394 @@
+//! Fallback chain tests: target order, error classification, streams and circuit breakers
+
+use super::common::{openai_completion, request};
+use futures::StreamExt;
+use mockito::Matcher;
+use serde_json::json;
+use stakai::provider::FallbackRoute;
+use stakai::providers::anthropic::{AnthropicConfig, AnthropicProvider};
+use stakai::providers::openai::{OpenAIConfig, OpenAIProvider};
+use stakai::{
+    ContentPart, Error, GenerateRequest, Inference, Message, RetryPolicy, Role, StreamEvent,
+};
+use std::time::Duration;
+
+fn anthropic_body() -> String {
+    json!({
+        "id": "msg_1",
+        "type": "message",
+        "role": "assistant",
+        "content": [{"type": "text", "text": "from anthropic"}],
+        "model": "claude-sonnet-4-20250514",
+        "stop_reason": "end_turn",
+        "usage": {"input_tokens": 1, "output_tokens": 2}
+    })
+    .to_string()
+}
+
+fn client(server: &mockito::ServerGuard, route: FallbackRoute) -> Inference {
+    let openai =
+        OpenAIProvider::new(OpenAIConfig::new("test-key").with_base_url(server.url())).unwrap();
+    let anthropic =
+        AnthropicProvider::new(AnthropicConfig::new("test-key").with_base_url(server.url()))
+            .unwrap();
+
+    Inference::builder()
+        .register_provider("openai", openai)
+        .register_provider("anthropic", anthropic)
+        .fallback("smart", route)
+        .with_retry(RetryPolicy::none())
+        .build()
+        .unwrap()
+}
+
+fn route() -> FallbackRoute {
+    FallbackRoute::new(["openai:gpt-4o", "anthropic:claude-sonnet-4-20250514"])
+}
+
+#[tokio::test]
+async fn test_falls_back_on_server_error() {
+    let mut server = mockito::Server::new_async().await;
+    let openai = server
+        .mock("POST", "/chat/completions")
+        .with_status(503)
+        .with_body(r#"{"error":{"message":"unavailable","type":"server_error"}}"#)
+        .expect(1)
+        .create_async()
+        .await;
+    let anthropic = server
+        .mock("POST", "/messages")
+        .match_body(Matcher::PartialJson(
+            json!({"model": "claude-sonnet-4-20250514"}),
+        ))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(anthropic_body())
+        .expect(1)
+        .create_async()
+        .await;
+
+    let response = client(&server, route())
+        .generate(&request("smart"))
+        .await
+        .unwrap();
+
+    openai.assert_async().await;
+    anthropic.assert_async().await;
+    assert_eq!(response.text(), "from anthropic");
+    assert_eq!(
+        response.metadata.unwrap().target.as_deref(),
+        Some("anthropic:claude-sonnet-4-20250514")
+    );
+}
+
+#[tokio::test]
+async fn test_client_errors_do_not_fall_back() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("POST", "/chat/completions")
+        .with_status(400)
+        .with_body(r#"{"error":{"message":"bad request","type":"invalid_request_error"}}"#)
+        .create_async()
+        .await;
+    let anthropic = server
+        .mock("POST", "/messages")
+        .expect(0)
+        .create_async()
+        .await;
+
+    let result = client(&server, route()).generate(&request("smart")).await;
+
+    assert!(matches!(result, Err(Error::InvalidRequest(_))));
+    anthropic.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_falls_back_on_context_length() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("POST", "/chat/completions")
+        .with_status(400)
+        .with_body(
+            r#"{"error":{"message":"This model's maximum context length is 128000 tokens.","type":"invalid_request_error","code":"context_length_exceeded"}}"#,
+        )
+        .create_async()
+        .await;
+    server
+        .mock("POST", "/messages")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(anthropic_body())
+        .create_async()
+        .await;
+
+    let response = client(&server, route())
+        .generate(&request("smart"))
+        .await
+        .unwrap();
+
+    assert_eq!(response.text(), "from anthropic");
+}
+
+/// Conversation whose assistant turn carries signed and redacted reasoning
+fn request_with_reasoning() -> GenerateRequest {
+    GenerateRequest::new(
+        "smart",
+        vec![
+            Message::new(Role::User, "Solve it"),
+            Message::new(
+                Role::Assistant,
+                vec![
+                    ContentPart::reasoning("Let me think.", Some("sig_1".to_string())),
+                    ContentPart::redacted_reasoning("opaque_data"),
+                    ContentPart::text("42"),
+                ],
+            ),
+            Message::new(Role::User, "Why?"),
+        ],
+    )
+}
+
+/// Body of a request received by a mock, as text
+fn body(request: &mockito::Request) -> String {
+    String::from_utf8_lossy(request.body().unwrap()).into_owned()
+}
+
+#[tokio::test]
+async fn test_other_provider_gets_no_signed_reasoning() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("POST", "/chat/completions")
+        .with_status(503)
+        .with_body(r#"{"error":{"message":"unavailable","type":"server_error"}}"#)
+        .create_async()
+        .await;
+    let anthropic = server
+        .mock("POST", "/messages")
+        .match_request(|request| {
+            let body = body(request);
+            body.contains("42") && !body.contains("sig_1") && !body.contains("opaque_data")
+        })
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(anthropic_body())
+        .create_async()
+        .await;
+
+    // The conversation's reasoning was signed for the first target's provider
+    let response = client(&server, route())
+        .generate(&request_with_reasoning())
+        .await
+        .unwrap();
+
+    anthropic.assert_async().await;
+    assert_eq!(response.text(), "from anthropic");
+}
+
+#[tokio::test]
+async fn test_same_provider_keeps_signed_reasoning() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("POST", "/messages")
+        .match_body(Matcher::PartialJson(json!({"model": "claude-opus-4-1"})))
+        .with_status(529)
+        .with_body(r#"{"type":"error","error":{"type":"overloaded_error","message":"Overloaded"}}"#)
+        .create_async()
+        .await;
+    let fallback = server
+        .mock("POST", "/messages")
+        .match_body(Matcher::PartialJson(
+            json!({"model": "claude-sonnet-4-20250514"}),
+        ))
+        .match_request(|request| {
+            let body = body(request);
+            body.contains("sig_1") && body.contains("opaque_data")
+        })
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(anthropic_body())
+        .create_async()
+        .await;
+
+    let route = FallbackRoute::new([
+        "anthropic:claude-opus-4-1",
+        "anthropic:claude-sonnet-4-20250514",
+    ]);
+    let response = client(&server, route)
+        .generate(&request_with_reasoning())
+        .await
+        .unwrap();
+
+    fallback.assert_async().await;
+    assert_eq!(response.text(), "from anthropic");
+}
+
+#[tokio::test]
+async fn test_first_target_serves_and_records_target() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("POST", "/chat/completions")
+        .match_body(Matcher::PartialJson(json!({"model": "gpt-4o"})))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(openai_completion("from openai"))
+        .create_async()
+        .await;
+
+    let response = client(&server, route())
+        .generate(&request("smart"))
+        .await
+        .unwrap();
+
+    assert_eq!(response.text(), "from openai");
+    assert_eq!(
+        response.metadata.unwrap().target.as_deref(),
+        Some("openai:gpt-4o")
+    );
+}
+
+#[tokio::test]
+async fn test_stream_falls_back_before_first_event() {
+    let body = [
+        json!({"type": "message_start", "message": {
+            "id": "msg_1", "type": "message", "role": "assistant", "content": [],
+            "model": "claude-sonnet-4-20250514", "stop_reason": null,
+            "usage": {"input_tokens": 1, "output_tokens": 1}
+        }}),
+        json!({"type": "content_block_start", "index": 0, "content_block": {"type": "text", "text": ""}}),
+        json!({"type": "content_block_delta", "index": 0, "delta": {"type": "text_delta", "text": "Hi"}}),
+        json!({"type": "content_block_stop", "index": 0}),
+        json!({"type": "message_delta", "delta": {"stop_reason": "end_turn"}, "usage": {"output_tokens": 2}}),
+        json!({"type": "message_stop"}),
+    ]
+    .iter()
+    .map(|event| format!("data: {}\n\n", event))
+    .collect::<String>();
+
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("POST", "/chat/completions")
+        .with_status(429)
+        .with_body(r#"{"error":{"message":"slow down","type":"rate_limit_error"}}"#)
+        .create_async()
+        .await;
+    server
+        .mock("POST", "/messages")
+        .with_status(200)
+        .with_header("content-type", "text/event-stream")
+        .with_body(body)
+        .create_async()
+        .await;
+
+    let events: Vec<_> = client(&server, route())
+        .stream(&request("smart"))
+        .await
+        .unwrap()
+        .collect()
+        .await;
+
+    match &events[0] {
+        Ok(StreamEvent::Start { metadata, .. }) => assert_eq!(
+            metadata.target.as_deref(),
+            Some("anthropic:claude-sonnet-4-20250514")
+        ),
+        other => panic!("expected start event, got {:?}", other),
+    }
+    assert!(events
+        .iter()
+        .any(|event| matches!(event, Ok(StreamEvent::TextDelta { delta, .. }) if delta == "Hi")));
+}
+
+#[tokio::test]
+async fn test_stream_does_not_fall_back_after_content() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("POST", "/chat/completions")
+        .with_status(200)
+        .with_header("content-type", "text/event-stream")
+        .with_body(concat!(
+            "data: {\"id\":\"1\",\"object\":\"chat.completion.chunk\",\"created\":1,\"model\":\"gpt-4o\",",
+            "\"choices\":[{\"index\":0,\"delta\":{\"content\":\"partial\"}}]}\n\n",
+            "data: {not json}\n\n",
+        ))
+        .create_async()
+        .await;
+    let anthropic = server
+        .mock("POST", "/messages")
+        .expect(0)
+        .create_async()
+        .await;
+
+    let events: Vec<_> = client(&server, route())
+        .stream(&request("smart"))
+        .await
+        .unwrap()
+        .collect()
+        .await;
+
+    assert!(events.iter().any(
+        |event| matches!(event, Ok(StreamEvent::TextDelta { delta, .. }) if delta == "partial")
+    ));
+    assert!(events.last().unwrap().is_err());
+    anthropic.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_circuit_breaker_skips_failing_target() {
+    let mut server = mockito::Server::new_async().await;
+    let openai = server
+        .mock("POST", "/chat/completions")
+        .with_status(500)
+        .with_body(r#"{"error":{"message":"boom","type":"server_error"}}"#)
+        .expect(1)
+        .create_async()
+        .await;
+    let anthropic = server
+        .mock("POST", "/messages")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(anthropic_body())
+        .expect(2)
+        .create_async()
+        .await;
+
+    let client = client(
+        &server,
+        route().with_circuit_breaker(1, Duration::from_secs(60)),
+    );
+    client.generate(&request("smart")).await.unwrap();
+    // Breaker on the first target is now open, so it is not called again
+    let response = client.generate(&request("smart")).await.unwrap();
+
+    openai.assert_async().await;
+    anthropic.assert_async().await;
+    assert_eq!(response.text(), "from anthropic");
+}
+
+#[tokio::test]
+async fn test_all_breakers_open() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("POST", "/chat/completions")
+        .with_status(503)
+        .create_async()
+        .await;
+
+    let client = client(
+        &server,
+        FallbackRoute::new(["openai:gpt-4o"]).with_circuit_breaker(1, Duration::from_secs(60)),
+    );
+    assert!(client.generate(&request("smart")).await.is_err());
+
+    let result = client.generate(&request("smart")).await;
+    assert!(matches!(result, Err(Error::CircuitOpen(target)) if target == "openai:gpt-4o"));
+}
+
+#[test]
+fn test_unknown_target_fails_build() {
+    let result = Inference::builder()
+        .with_registry(Default::default())
+        .fallback("smart", FallbackRoute::new(["missing:model"]))
+        .build();
+
+    assert!(result.is_err());
+}
@@ -0,0 +1,296 @@
+//! Provider layer tests: hooks, stacking order and built-in layers
+
+use super::common::{openai_completion, request};
+use futures::StreamExt;
+use mockito::Matcher;
+use serde_json::json;
+use stakai::provider::{DefaultHeadersLayer, LoggingLayer, ProviderLayer, RequestIdLayer};
+use stakai::providers::openai::{OpenAIConfig, OpenAIProvider};
+use stakai::{
+    Error, GenerateRequest, GenerateResponse, Inference, Message, ResponseContent, Result, Role,
+    StreamEvent,
+};
+use std::sync::{Arc, Mutex};
+
+fn provider(server: &mockito::ServerGuard) -> OpenAIProvider {
+    OpenAIProvider::new(OpenAIConfig::new("test-key").with_base_url(server.url())).unwrap()
+}
+
+/// Records hook calls and rewrites requests and responses
+struct Recorder {
+    name: &'static str,
+    calls: Arc<Mutex<Vec<String>>>,
+}
+
+#[async_trait::async_trait]
+impl ProviderLayer for Recorder {
+    async fn on_request(&self, provider: &str, request: &mut GenerateRequest) -> Result<()> {
+        self.calls
+            .lock()
+            .unwrap()
+            .push(format!("{} request {}", self.name, provider));
+        request.options.temperature = Some(0.5);
+        Ok(())
+    }
+
+    fn on_response(&self, _provider: &str, response: &mut GenerateResponse) {
+        self.calls
+            .lock()
+            .unwrap()
+            .push(format!("{} response", self.name));
+        for content in &mut response.content {
+            if let ResponseContent::Text { text } = content {
+                text.push_str(self.name);
+            }
+        }
+    }
+
+    fn on_stream_event(&self, _provider: &str, event: &mut StreamEvent) {
+        if let StreamEvent::TextDelta { delta, .. } = event {
+            *delta = delta.to_uppercase();
+        }
+    }
+
+    fn on_error(&self, _provider: &str, error: &Error) {
+        self.calls.lock().unwrap().push(format!("error {}", error));
+    }
+}
+
+#[tokio::test]
+async fn test_layers_stack_in_order() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/chat/completions")
+        .match_body(Matcher::PartialJson(json!({"temperature": 0.5})))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(openai_completion("hello"))
+        .create_async()
+        .await;
+
+    let calls = Arc::new(Mutex::new(Vec::new()));
+    let client = Inference::builder()
+        .register_provider("primary", provider(&server))
+        .layer(Recorder {
+            name: "outer",
+            calls: calls.clone(),
+        })
+        .layer(Recorder {
+            name: "inner",
+            calls: calls.clone(),
+        })
+        .build()
+        .unwrap();
+
+    let mut request = request("openai:gpt-4o");
+    request.model = "primary:gpt-4o".to_string();
+    let response = client.generate(&request).await.unwrap();
+
+    mock.assert_async().await;
+    assert_eq!(response.text(), "helloinnerouter");
+    assert_eq!(
+        *calls.lock().unwrap(),
+        vec![
+            "outer request primary",
+            "inner request primary",
+            "inner response",
+            "outer response",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_layer_sees_stream_events_and_errors() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("POST", "/chat/completions")
+        .with_status(200)
+        .with_header("content-type", "text/event-stream")
+        .with_body(concat!(
+            "data: {\"id\":\"1\",\"object\":\"chat.completion.chunk\",\"created\":1,\"model\":\"gpt-4o\",",
+            "\"choices\":[{\"index\":0,\"delta\":{\"content\":\"hello\"}}]}\n\n",
+            "data: {\"id\":\"1\",\"object\":\"chat.completion.chunk\",\"created\":1,\"model\":\"gpt-4o\",",
+            "\"choices\":[{\"index\":0,\"delta\":{},\"finish_reason\":\"stop\"}]}\n\n",
+            "data: [DONE]\n\n",
+        ))
+        .create_async()
+        .await;
+    server
+        .mock("POST", "/chat/completions")
+        .match_body(Matcher::PartialJson(json!({"model": "missing"})))
+        .with_status(404)
+        .with_body(r#"{"error":{"message":"model not found","type":"invalid_request_error"}}"#)
+        .create_async()
+        .await;
+
+    let calls = Arc::new(Mutex::new(Vec::new()));
+    let client = Inference::builder()
+        .register_provider("openai", provider(&server))
+        .layer(Recorder {
+            name: "layer",
+            calls: calls.clone(),
+        })
+        .build()
+        .unwrap();
+
+    let response = client
+        .stream(&request("openai:gpt-4o"))
+        .await
+        .unwrap()
+        .collect_response()
+        .await
+        .unwrap();
+    assert_eq!(response.text(), "HELLO");
+
+    let result = client
+        .generate(&GenerateRequest::new(
+            "openai:missing",
+            vec![Message::new(Role::User, "Hi")],
+        ))
+        .await;
+    assert!(matches!(result, Err(Error::NotFound(_))));
+    assert!(calls.lock().unwrap().last().unwrap().starts_with("error "));
+}
+
+#[tokio::test]
+async fn test_layer_rejects_request() {
+    struct Deny;
+
+    #[async_trait::async_trait]
+    impl ProviderLayer for Deny {
+        async fn on_request(&self, _provider: &str, _request: &mut GenerateRequest) -> Result<()> {
+            Err(Error::Other("denied by policy".to_string()))
+        }
+    }
+
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/chat/completions")
+        .expect(0)
+        .create_async()
+        .await;
+
+    let client = Inference::builder()
+        .register_provider("openai", provider(&server))
+        .layer(Deny)
+        .build()
+        .unwrap();
+
+    let result = client.stream(&request("openai:gpt-4o")).await;
+    assert!(matches!(result, Err(Error::Other(_))));
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_builtin_header_layers() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/chat/completions")
+        .match_header("x-team", "search")
+        .match_header("x-env", "request-value")
+        .match_header(
+            "x-request-id",
+            Matcher::Regex("^[0-9a-f]{8}-[0-9a-f]{4}-".to_string()),
+        )
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(openai_completion("hello"))
+        .expect(1)
+        .create_async()
+        .await;
+    let tagged = server
+        .mock("POST", "/chat/completions")
+        .match_header("x-request-id", "req-fixed")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(openai_completion("hello"))
+        .expect(1)
+        .create_async()
+        .await;
+
+    let client = Inference::builder()
+        .register_provider("openai", provider(&server))
+        .layer(LoggingLayer::new())
+        .layer(DefaultHeadersLayer::new([
+            ("x-team", "search"),
+            ("x-env", "default-value"),
+        ]))
+        .layer(RequestIdLayer::new())
+        .build()
+        .unwrap();
+
+    let mut first = request("openai:gpt-4o");
+    first.options = first.options.add_header("x-env", "request-value");
+    client.generate(&first).await.unwrap();
+
+    let mut second = request("openai:gpt-4o");
+    second.options = second.options.add_header("x-request-id", "req-fixed");
+    client.generate(&second).await.unwrap();
+
+    mock.assert_async().await;
+    tagged.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_header_layers_match_names_case_insensitively() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/chat/completions")
+        .match_header("x-env", "request-value")
+        .match_header("x-request-id", "req-fixed")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(openai_completion("hello"))
+        .expect(1)
+        .create_async()
+        .await;
+
+    let client = Inference::builder()
+        .register_provider("openai", provider(&server))
+        .layer(DefaultHeadersLayer::new([("x-env", "default-value")]))
+        .layer(RequestIdLayer::new())
+        .build()
+        .unwrap();
+
+    let mut request = request("openai:gpt-4o");
+    request.options = request
+        .options
+        .add_header("X-Env", "request-value")
+        .add_header("X-Request-ID", "req-fixed");
+    client.generate(&request).await.unwrap();
+
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_stream_events_pass_through_layer() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("POST", "/chat/completions")
+        .with_status(200)
+        .with_header("content-type", "text/event-stream")
+        .with_body(concat!(
+            "data: {\"id\":\"1\",\"object\":\"chat.completion.chunk\",\"created\":1,\"model\":\"gpt-4o\",",
+            "\"choices\":[{\"index\":0,\"delta\":{\"role\":\"assistant\"}}]}\n\n",
+            "data: [DONE]\n\n",
+        ))
+        .create_async()
+        .await;
+
+    let client = Inference::builder()
+        .register_provider("openai", provider(&server))
+        .layer(LoggingLayer::new())
+        .build()
+        .unwrap();
+
+    let events: Vec<_> = client
+        .stream(&request("openai:gpt-4o"))
+        .await
+        .unwrap()
+        .collect()
+        .await;
+    assert!(matches!(
+        events.as_slice(),
+        [Ok(StreamEvent::Start { id, .. })] if id == "1"
+    ));
+}
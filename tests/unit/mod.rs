@@ -1,5 +1,27 @@
 //! Unit tests
 
+mod azure;
+mod bedrock;
+mod catalog;
 mod client;
+mod cohere;
+mod common;
+mod conversation;
+mod cost;
+mod dispatcher;
+mod embed;
+mod fallback;
+mod layer;
+mod models;
+mod openai_compatible;
+mod pool;
+mod prompt_cache;
 mod provider;
+mod provider_options;
+mod response_metadata;
+mod retry;
+mod streaming;
+mod structured;
+mod timeout;
 mod types;
+mod vertex;
@@ -0,0 +1,340 @@
+//! Model listing tests against mock provider servers
+
+use mockito::Matcher;
+use serde_json::json;
+use stakai::providers::anthropic::{AnthropicConfig, AnthropicProvider};
+use stakai::providers::gemini::{GeminiConfig, GeminiProvider};
+use stakai::providers::openai::{OpenAIConfig, OpenAIProvider};
+use stakai::registry::ProviderRegistry;
+use stakai::{Error, Inference, ModelInfo};
+
+fn openai_provider(server: &mockito::ServerGuard) -> OpenAIProvider {
+    OpenAIProvider::new(OpenAIConfig::new("test-key").with_base_url(server.url())).unwrap()
+}
+
+async fn mock_openai_models(server: &mut mockito::ServerGuard) -> mockito::Mock {
+    server
+        .mock("GET", "/models")
+        .match_header("authorization", "Bearer test-key")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            json!({
+                "object": "list",
+                "data": [
+                    {"id": "gpt-4o", "object": "model", "created": 1, "owned_by": "system"},
+                    {"id": "gpt-4o-mini", "object": "model", "created": 1, "owned_by": "system"}
+                ]
+            })
+            .to_string(),
+        )
+        .create_async()
+        .await
+}
+
+#[tokio::test]
+async fn test_list_models_openai() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = mock_openai_models(&mut server).await;
+
+    let client = Inference::builder()
+        .register_provider("openai", openai_provider(&server))
+        .build()
+        .unwrap();
+
+    let models = client.list_models("openai").await.unwrap();
+
+    mock.assert_async().await;
+    assert_eq!(
+        models,
+        vec![
+            ModelInfo::new("openai", "gpt-4o"),
+            ModelInfo::new("openai", "gpt-4o-mini")
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_list_models_anthropic_paginates() {
+    let mut server = mockito::Server::new_async().await;
+    let first = server
+        .mock("GET", "/models")
+        .match_query(Matcher::UrlEncoded("limit".into(), "1000".into()))
+        .match_header("x-api-key", "test-key")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            json!({
+                "data": [{
+                    "type": "model",
+                    "id": "claude-sonnet-4-20250514",
+                    "display_name": "Claude Sonnet 4",
+                    "created_at": "2025-05-22T00:00:00Z",
+                    "max_input_tokens": 200000,
+                    "max_tokens": 64000
+                }],
+                "has_more": true,
+                "first_id": "claude-sonnet-4-20250514",
+                "last_id": "claude-sonnet-4-20250514"
+            })
+            .to_string(),
+        )
+        .create_async()
+        .await;
+    let second = server
+        .mock("GET", "/models")
+        .match_query(Matcher::AllOf(vec![
+            Matcher::UrlEncoded("limit".into(), "1000".into()),
+            Matcher::UrlEncoded("after_id".into(), "claude-sonnet-4-20250514".into()),
+        ]))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            json!({
+                "data": [{
+                    "type": "model",
+                    "id": "claude-3-5-haiku-20241022",
+                    "display_name": "Claude Haiku 3.5",
+                    "created_at": "2024-10-22T00:00:00Z"
+                }],
+                "has_more": false,
+                "first_id": "claude-3-5-haiku-20241022",
+                "last_id": "claude-3-5-haiku-20241022"
+            })
+            .to_string(),
+        )
+        .create_async()
+        .await;
+
+    let provider =
+        AnthropicProvider::new(AnthropicConfig::new("test-key").with_base_url(server.url()))
+            .unwrap();
+    let client = Inference::builder()
+        .register_provider("anthropic", provider)
+        .build()
+        .unwrap();
+
+    let models = client.list_models("anthropic").await.unwrap();
+
+    first.assert_async().await;
+    second.assert_async().await;
+    assert_eq!(models.len(), 2);
+    assert_eq!(models[0].id, "claude-sonnet-4-20250514");
+    assert_eq!(models[0].display_name.as_deref(), Some("Claude Sonnet 4"));
+    assert_eq!(models[0].context_window, Some(200000));
+    assert_eq!(models[0].max_output_tokens, Some(64000));
+    assert_eq!(models[1].id, "claude-3-5-haiku-20241022");
+    assert_eq!(models[1].context_window, None);
+}
+
+#[tokio::test]
+async fn test_list_models_gemini_paginates() {
+    let mut server = mockito::Server::new_async().await;
+    let first = server
+        .mock("GET", "/models")
+        .match_query(Matcher::AllOf(vec![
+            Matcher::UrlEncoded("key".into(), "test-key".into()),
+            Matcher::UrlEncoded("pageSize".into(), "1000".into()),
+        ]))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            json!({
+                "models": [{
+                    "name": "models/gemini-2.5-flash",
+                    "displayName": "Gemini 2.5 Flash",
+                    "inputTokenLimit": 1048576,
+                    "outputTokenLimit": 65536,
+                    "supportedGenerationMethods": ["generateContent", "countTokens"],
+                    "thinking": true
+                }],
+                "nextPageToken": "page-2"
+            })
+            .to_string(),
+        )
+        .create_async()
+        .await;
+    let second = server
+        .mock("GET", "/models")
+        .match_query(Matcher::UrlEncoded("pageToken".into(), "page-2".into()))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            json!({
+                "models": [{
+                    "name": "models/text-embedding-004",
+                    "displayName": "Text Embedding 004",
+                    "inputTokenLimit": 2048,
+                    "outputTokenLimit": 1,
+                    "supportedGenerationMethods": ["embedContent"]
+                }]
+            })
+            .to_string(),
+        )
+        .create_async()
+        .await;
+
+    let provider =
+        GeminiProvider::new(GeminiConfig::new("test-key").with_base_url(server.url())).unwrap();
+    let client = Inference::builder()
+        .register_provider("google", provider)
+        .build()
+        .unwrap();
+
+    let models = client.list_models("google").await.unwrap();
+
+    first.assert_async().await;
+    second.assert_async().await;
+    assert_eq!(
+        models[0],
+        ModelInfo {
+            display_name: Some("Gemini 2.5 Flash".to_string()),
+            context_window: Some(1048576),
+            max_output_tokens: Some(65536),
+            supports_reasoning: Some(true),
+            ..ModelInfo::new("google", "gemini-2.5-flash")
+        }
+    );
+    assert_eq!(models[1].id, "text-embedding-004");
+    assert_eq!(models[1].supports_reasoning, None);
+}
+
+#[tokio::test]
+async fn test_list_models_is_cached() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = mock_openai_models(&mut server).await.expect(1);
+
+    let client = Inference::builder()
+        .register_provider("openai", openai_provider(&server))
+        .build()
+        .unwrap();
+
+    let first = client.list_models("openai").await.unwrap();
+    let second = client.list_models("openai").await.unwrap();
+
+    mock.assert_async().await;
+    assert_eq!(first, second);
+}
+
+#[tokio::test]
+async fn test_list_models_cache_disabled() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = mock_openai_models(&mut server).await.expect(2);
+
+    let client = Inference::builder()
+        .register_provider("openai", openai_provider(&server))
+        .with_model_cache_ttl(0)
+        .build()
+        .unwrap();
+
+    client.list_models("openai").await.unwrap();
+    client.list_models("openai").await.unwrap();
+
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_list_all_models() {
+    let mut openai_server = mockito::Server::new_async().await;
+    mock_openai_models(&mut openai_server).await;
+
+    let mut gemini_server = mockito::Server::new_async().await;
+    gemini_server
+        .mock("GET", "/models")
+        .match_query(Matcher::Any)
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(json!({"models": [{"name": "models/gemini-2.5-pro"}]}).to_string())
+        .create_async()
+        .await;
+
+    // An explicit registry, so providers configured in the environment aren't listed
+    let registry = ProviderRegistry::new()
+        .register("openai", openai_provider(&openai_server))
+        .register(
+            "gemini-proxy",
+            GeminiProvider::new(GeminiConfig::new("test-key").with_base_url(gemini_server.url()))
+                .unwrap(),
+        );
+    let client = Inference::builder()
+        .with_registry(registry)
+        .build()
+        .unwrap();
+
+    let ids: Vec<String> = client
+        .list_all_models()
+        .await
+        .unwrap()
+        .iter()
+        .map(ModelInfo::qualified_id)
+        .collect();
+
+    assert_eq!(
+        ids,
+        vec![
+            "gemini-proxy:gemini-2.5-pro",
+            "openai:gpt-4o",
+            "openai:gpt-4o-mini"
+        ]
+    );
+}
+
+async fn mock_unauthorized(server: &mut mockito::ServerGuard) -> mockito::Mock {
+    server
+        .mock("GET", "/models")
+        .match_query(Matcher::Any)
+        .with_status(401)
+        .with_header("content-type", "application/json")
+        .with_body(
+            json!({"error": {"message": "Invalid API key", "type": "invalid_request_error"}})
+                .to_string(),
+        )
+        .create_async()
+        .await
+}
+
+#[tokio::test]
+async fn test_list_all_models_skips_failed_providers() {
+    let mut openai_server = mockito::Server::new_async().await;
+    mock_openai_models(&mut openai_server).await;
+
+    let mut broken_server = mockito::Server::new_async().await;
+    mock_unauthorized(&mut broken_server).await;
+
+    let registry = ProviderRegistry::new()
+        .register("openai", openai_provider(&openai_server))
+        .register("broken", openai_provider(&broken_server));
+    let client = Inference::builder()
+        .with_registry(registry)
+        .build()
+        .unwrap();
+
+    let ids: Vec<String> = client
+        .list_all_models()
+        .await
+        .unwrap()
+        .iter()
+        .map(ModelInfo::qualified_id)
+        .collect();
+
+    assert_eq!(ids, vec!["openai:gpt-4o", "openai:gpt-4o-mini"]);
+}
+
+#[tokio::test]
+async fn test_list_all_models_fails_when_every_provider_fails() {
+    let mut broken_server = mockito::Server::new_async().await;
+    mock_unauthorized(&mut broken_server).await;
+
+    let registry = ProviderRegistry::new().register("broken", openai_provider(&broken_server));
+    let client = Inference::builder()
+        .with_registry(registry)
+        .build()
+        .unwrap();
+
+    let result = client.list_all_models().await;
+    assert!(
+        matches!(result, Err(Error::Authentication(_))),
+        "{:?}",
+        result
+    );
+}
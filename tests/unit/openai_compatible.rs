@@ -0,0 +1,250 @@
+//! OpenAI-compatible provider tests: presets, quirks and authentication
+
+use mockito::Matcher;
+use serde_json::json;
+use stakai::providers::openai::{
+    OpenAICompatibleAuth, OpenAICompatibleConfig, OpenAICompatibleProvider, OpenAIConfig,
+    OpenAIProvider,
+};
+use stakai::{Error, GenerateRequest, Inference, ReasoningConfig, ResponseContent, StreamEvent};
+
+fn chat_body(message: serde_json::Value) -> String {
+    json!({
+        "id": "chatcmpl-1",
+        "object": "chat.completion",
+        "created": 1,
+        "model": "llama-3.3-70b",
+        "choices": [{"index": 0, "message": message, "finish_reason": "stop"}],
+        "usage": {"prompt_tokens": 3, "completion_tokens": 2, "total_tokens": 5}
+    })
+    .to_string()
+}
+
+fn client(id: &str, config: OpenAICompatibleConfig) -> Inference {
+    Inference::builder()
+        .register_provider(id, OpenAICompatibleProvider::new(config).unwrap())
+        .build()
+        .unwrap()
+}
+
+fn request(model: &str) -> GenerateRequest {
+    let mut request = super::common::request(model);
+    request.options.max_tokens = Some(100);
+    request
+}
+
+/// Body of the only request received, as JSON
+fn body(request: &mockito::Request) -> serde_json::Value {
+    serde_json::from_slice(request.body().unwrap()).unwrap()
+}
+
+#[tokio::test]
+async fn test_groq_preset_through_parse_model() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/chat/completions")
+        .match_header("authorization", "Bearer gsk-test")
+        .match_request(|request| {
+            let body = body(request);
+            body["model"] == "llama-3.3-70b"
+                && body["max_completion_tokens"] == 100
+                && body.get("max_tokens").is_none()
+        })
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(chat_body(json!({
+            "role": "assistant",
+            "content": "Hello",
+            "reasoning": "Greeting back"
+        })))
+        .create_async()
+        .await;
+
+    let client = client(
+        "groq",
+        OpenAICompatibleConfig::groq("gsk-test").with_base_url(server.url()),
+    );
+    let response = client
+        .generate(&request("groq:llama-3.3-70b"))
+        .await
+        .unwrap();
+
+    mock.assert_async().await;
+    assert_eq!(response.text(), "Hello");
+    assert!(matches!(
+        &response.content[0],
+        ResponseContent::Reasoning { text, .. } if text == "Greeting back"
+    ));
+    assert_eq!(response.metadata.unwrap().provider, "groq");
+}
+
+#[tokio::test]
+async fn test_deepseek_reads_reasoning_content() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("POST", "/chat/completions")
+        .match_request(|request| {
+            let body = body(request);
+            body["max_tokens"] == 100 && body.get("reasoning_effort").is_none()
+        })
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(chat_body(json!({
+            "role": "assistant",
+            "content": "42",
+            "reasoning_content": "Thinking it over",
+            "reasoning": "ignored"
+        })))
+        .create_async()
+        .await;
+
+    let client = client(
+        "deepseek",
+        OpenAICompatibleConfig::deepseek("sk-test").with_base_url(server.url()),
+    );
+    let mut request = request("deepseek:deepseek-reasoner");
+    request.options.reasoning = Some(ReasoningConfig::default());
+    let response = client.generate(&request).await.unwrap();
+
+    assert!(matches!(
+        &response.content[0],
+        ResponseContent::Reasoning { text, .. } if text == "Thinking it over"
+    ));
+}
+
+#[tokio::test]
+async fn test_stream_options_quirk() {
+    let stream_body = concat!(
+        "data: {\"id\":\"1\",\"object\":\"chat.completion.chunk\",\"created\":1,\"model\":\"m\",",
+        "\"choices\":[{\"index\":0,\"delta\":{\"content\":\"Hi\"},\"finish_reason\":null}]}\n\n",
+        "data: {\"id\":\"1\",\"object\":\"chat.completion.chunk\",\"created\":1,\"model\":\"m\",",
+        "\"choices\":[{\"index\":0,\"delta\":{},\"finish_reason\":\"stop\"}],",
+        "\"usage\":{\"prompt_tokens\":3,\"completion_tokens\":1,\"total_tokens\":4}}\n\n",
+        "data: [DONE]\n\n",
+    );
+
+    let mut server = mockito::Server::new_async().await;
+    let mistral = server
+        .mock("POST", "/mistral/chat/completions")
+        .match_request(|request| body(request).get("stream_options").is_none())
+        .with_status(200)
+        .with_header("content-type", "text/event-stream")
+        .with_body(stream_body)
+        .create_async()
+        .await;
+    let openai = server
+        .mock("POST", "/openai/chat/completions")
+        .match_body(Matcher::PartialJson(
+            json!({"stream_options": {"include_usage": true}}),
+        ))
+        .with_status(200)
+        .with_header("content-type", "text/event-stream")
+        .with_body(stream_body)
+        .create_async()
+        .await;
+
+    let client = Inference::builder()
+        .register_provider(
+            "mistral",
+            OpenAICompatibleProvider::new(
+                OpenAICompatibleConfig::mistral("test-key")
+                    .with_base_url(format!("{}/mistral", server.url())),
+            )
+            .unwrap(),
+        )
+        .register_provider(
+            "openai",
+            OpenAIProvider::new(
+                OpenAIConfig::new("test-key").with_base_url(format!("{}/openai", server.url())),
+            )
+            .unwrap(),
+        )
+        .build()
+        .unwrap();
+
+    for model in ["mistral:mistral-large-latest", "openai:gpt-4o"] {
+        let response = client
+            .stream(&request(model))
+            .await
+            .unwrap()
+            .collect_response()
+            .await
+            .unwrap();
+        assert_eq!(response.text(), "Hi");
+        assert_eq!(response.usage.total_tokens, 4);
+    }
+
+    mistral.assert_async().await;
+    openai.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_ollama_stream_reasoning_without_auth() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/chat/completions")
+        .match_header("authorization", Matcher::Missing)
+        .with_status(200)
+        .with_header("content-type", "text/event-stream")
+        .with_body(concat!(
+            "data: {\"id\":\"1\",\"object\":\"chat.completion.chunk\",\"created\":1,\"model\":\"qwen3\",",
+            "\"choices\":[{\"index\":0,\"delta\":{\"reasoning\":\"Hmm\"},\"finish_reason\":null}]}\n\n",
+            "data: {\"id\":\"1\",\"object\":\"chat.completion.chunk\",\"created\":1,\"model\":\"qwen3\",",
+            "\"choices\":[{\"index\":0,\"delta\":{\"content\":\"Hi\"},\"finish_reason\":\"stop\"}]}\n\n",
+            "data: [DONE]\n\n",
+        ))
+        .create_async()
+        .await;
+
+    let client = client(
+        "ollama",
+        OpenAICompatibleConfig::ollama().with_base_url(server.url()),
+    );
+    let mut stream = client.stream(&request("ollama:qwen3")).await.unwrap();
+
+    let mut reasoning = String::new();
+    while let Some(event) = futures::StreamExt::next(&mut stream).await {
+        if let StreamEvent::ReasoningDelta { delta, .. } = event.unwrap() {
+            reasoning.push_str(&delta);
+        }
+    }
+
+    mock.assert_async().await;
+    assert_eq!(reasoning, "Hmm");
+}
+
+#[tokio::test]
+async fn test_custom_auth_header_and_models() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("GET", "/models")
+        .match_header("api-key", "secret")
+        .match_header("x-tenant", "acme")
+        .match_header("authorization", Matcher::Missing)
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"data":[{"id":"llama-3.1-8b"}]}"#)
+        .create_async()
+        .await;
+
+    let client = client(
+        "vllm",
+        OpenAICompatibleConfig::vllm(server.url())
+            .with_auth(OpenAICompatibleAuth::Header("api-key".to_string()))
+            .with_api_key("secret")
+            .with_header("x-tenant", "acme"),
+    );
+    let models = client.list_models("vllm").await.unwrap();
+
+    mock.assert_async().await;
+    assert_eq!(models[0].id, "llama-3.1-8b");
+    assert_eq!(models[0].provider, "vllm");
+}
+
+#[test]
+fn test_missing_api_key() {
+    let result = OpenAICompatibleProvider::new(OpenAICompatibleConfig::xai(""));
+    assert!(matches!(result, Err(Error::MissingApiKey(id)) if id == "xai"));
+
+    assert!(OpenAICompatibleProvider::new(OpenAICompatibleConfig::ollama()).is_ok());
+}
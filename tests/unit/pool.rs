@@ -0,0 +1,211 @@
+//! Provider pool tests: strategies, ejection on 429/401 and registry integration
+
+use super::common::{openai_completion, request};
+use serde_json::json;
+use stakai::provider::{PoolStrategy, PooledProvider, Provider};
+use stakai::providers::openai::{OpenAIConfig, OpenAIProvider};
+use stakai::{Error, Inference, RetryPolicy};
+
+fn member(server: &mockito::ServerGuard, key: &str) -> OpenAIProvider {
+    OpenAIProvider::new(OpenAIConfig::new(key).with_base_url(server.url())).unwrap()
+}
+
+async fn mock_key(
+    server: &mut mockito::ServerGuard,
+    key: &str,
+    status: usize,
+    expect: usize,
+) -> mockito::Mock {
+    let body = if status == 200 {
+        openai_completion(key)
+    } else {
+        r#"{"error":{"message":"nope","type":"error"}}"#.to_string()
+    };
+    server
+        .mock("POST", "/chat/completions")
+        .match_header("authorization", format!("Bearer {}", key).as_str())
+        .with_status(status)
+        .with_header("content-type", "application/json")
+        .with_body(body)
+        .expect(expect)
+        .create_async()
+        .await
+}
+
+#[tokio::test]
+async fn test_round_robin_rotates_members() {
+    let mut server = mockito::Server::new_async().await;
+    let a = mock_key(&mut server, "key-a", 200, 2).await;
+    let b = mock_key(&mut server, "key-b", 200, 2).await;
+
+    let pool = PooledProvider::new(PoolStrategy::RoundRobin)
+        .with_member(member(&server, "key-a"))
+        .with_member(member(&server, "key-b"));
+
+    let mut served = Vec::new();
+    for _ in 0..4 {
+        served.push(pool.generate(request("gpt-4o")).await.unwrap().text());
+    }
+
+    assert_eq!(served, vec!["key-a", "key-b", "key-a", "key-b"]);
+    a.assert_async().await;
+    b.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_weighted_follows_weights() {
+    let mut server = mockito::Server::new_async().await;
+    let a = mock_key(&mut server, "key-a", 200, 3).await;
+    let b = mock_key(&mut server, "key-b", 200, 1).await;
+
+    let pool = PooledProvider::new(PoolStrategy::Weighted)
+        .with_weighted_member(member(&server, "key-a"), 3)
+        .with_weighted_member(member(&server, "key-b"), 1);
+
+    for _ in 0..4 {
+        pool.generate(request("gpt-4o")).await.unwrap();
+    }
+
+    a.assert_async().await;
+    b.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_least_in_flight_avoids_busy_member() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("POST", "/chat/completions")
+        .match_header("authorization", "Bearer key-a")
+        .with_status(200)
+        .with_header("content-type", "text/event-stream")
+        .with_body("data: {\"id\":\"1\",\"object\":\"chat.completion.chunk\",\"created\":1,\"model\":\"gpt-4o\",\"choices\":[]}\n\n")
+        .create_async()
+        .await;
+    let b = mock_key(&mut server, "key-b", 200, 2).await;
+
+    let pool = PooledProvider::new(PoolStrategy::LeastInFlight)
+        .with_member(member(&server, "key-a"))
+        .with_member(member(&server, "key-b"));
+
+    // An open stream keeps its member busy until dropped
+    let stream = pool.stream(request("gpt-4o")).await.unwrap();
+    pool.generate(request("gpt-4o")).await.unwrap();
+    pool.generate(request("gpt-4o")).await.unwrap();
+    drop(stream);
+
+    b.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_rate_limited_member_is_ejected() {
+    let mut server = mockito::Server::new_async().await;
+    let a = mock_key(&mut server, "key-a", 429, 1).await;
+    let b = mock_key(&mut server, "key-b", 200, 3).await;
+
+    let pool = PooledProvider::new(PoolStrategy::RoundRobin)
+        .with_member(member(&server, "key-a"))
+        .with_member(member(&server, "key-b"));
+
+    // The 429 is absorbed by moving on to the next member
+    for _ in 0..3 {
+        assert_eq!(
+            pool.generate(request("gpt-4o")).await.unwrap().text(),
+            "key-b"
+        );
+    }
+
+    a.assert_async().await;
+    b.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_member_out_of_quota_is_ejected() {
+    let mut server = mockito::Server::new_async().await;
+    let a = server
+        .mock("POST", "/chat/completions")
+        .match_header("authorization", "Bearer key-a")
+        .with_status(429)
+        .with_header("content-type", "application/json")
+        .with_body(
+            json!({"error": {
+                "message": "You exceeded your current quota, please check your plan and billing details.",
+                "type": "insufficient_quota",
+                "param": null,
+                "code": "insufficient_quota"
+            }})
+            .to_string(),
+        )
+        .expect(1)
+        .create_async()
+        .await;
+    let b = mock_key(&mut server, "key-b", 200, 2).await;
+
+    let pool = PooledProvider::new(PoolStrategy::RoundRobin)
+        .with_member(member(&server, "key-a"))
+        .with_member(member(&server, "key-b"));
+
+    // Not retryable, but the 429 still moves the request to the next key
+    for _ in 0..2 {
+        assert_eq!(
+            pool.generate(request("gpt-4o")).await.unwrap().text(),
+            "key-b"
+        );
+    }
+
+    a.assert_async().await;
+    b.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_all_members_ejected() {
+    let mut server = mockito::Server::new_async().await;
+    mock_key(&mut server, "key-a", 401, 1).await;
+    mock_key(&mut server, "key-b", 401, 1).await;
+
+    let pool = PooledProvider::new(PoolStrategy::RoundRobin)
+        .with_member(member(&server, "key-a"))
+        .with_member(member(&server, "key-b"));
+
+    let result = pool.generate(request("gpt-4o")).await;
+    assert!(matches!(result, Err(Error::Authentication(_))));
+
+    let result = pool.generate(request("gpt-4o")).await;
+    assert!(matches!(result, Err(Error::CircuitOpen(_))));
+}
+
+#[tokio::test]
+async fn test_other_errors_are_returned() {
+    let mut server = mockito::Server::new_async().await;
+    mock_key(&mut server, "key-a", 400, 1).await;
+    let b = mock_key(&mut server, "key-b", 200, 0).await;
+
+    let pool = PooledProvider::new(PoolStrategy::RoundRobin)
+        .with_member(member(&server, "key-a"))
+        .with_member(member(&server, "key-b"));
+
+    let result = pool.generate(request("gpt-4o")).await;
+    assert!(matches!(result, Err(Error::InvalidRequest(_))));
+    b.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_pool_registers_under_single_id() {
+    let mut server = mockito::Server::new_async().await;
+    mock_key(&mut server, "key-a", 200, 1).await;
+    mock_key(&mut server, "key-b", 200, 1).await;
+
+    let pool = PooledProvider::new(PoolStrategy::RoundRobin)
+        .with_member(member(&server, "key-a"))
+        .with_member(member(&server, "key-b"));
+    assert_eq!(pool.provider_id(), "openai");
+
+    let client = Inference::builder()
+        .register_provider("openai", pool)
+        .with_retry(RetryPolicy::none())
+        .build()
+        .unwrap();
+
+    let request = request("openai:gpt-4o");
+    assert_eq!(client.generate(&request).await.unwrap().text(), "key-a");
+    assert_eq!(client.generate(&request).await.unwrap().text(), "key-b");
+}
@@ -0,0 +1,86 @@
+//! Anthropic prompt caching tests: cache breakpoints and cache usage
+
+use mockito::Matcher;
+use serde_json::json;
+use stakai::providers::anthropic::{AnthropicConfig, AnthropicProvider};
+use stakai::{CacheControl, GenerateRequest, Inference, Message, Role};
+
+fn cached_request() -> GenerateRequest {
+    GenerateRequest::new(
+        "anthropic:claude-sonnet-4-20250514",
+        vec![
+            Message::new(Role::System, "Large shared prefix")
+                .with_cache_control(CacheControl::ephemeral()),
+            Message::new(Role::User, "Hi"),
+        ],
+    )
+}
+
+#[tokio::test]
+async fn test_stream_reports_cache_usage() {
+    let body = [
+        r#"{"type":"message_start","message":{"id":"msg_1","type":"message","role":"assistant","content":[],"model":"claude-sonnet-4-20250514","stop_reason":null,"usage":{"input_tokens":12,"cache_creation_input_tokens":0,"cache_read_input_tokens":50000,"output_tokens":1}}}"#,
+        r#"{"type":"content_block_start","index":0,"content_block":{"type":"text","text":""}}"#,
+        r#"{"type":"content_block_delta","index":0,"delta":{"type":"text_delta","text":"Hello"}}"#,
+        r#"{"type":"content_block_stop","index":0}"#,
+        r#"{"type":"message_delta","delta":{"stop_reason":"end_turn"},"usage":{"output_tokens":5}}"#,
+        r#"{"type":"message_stop"}"#,
+    ]
+    .iter()
+    .map(|data| format!("data: {}\n\n", data))
+    .collect::<String>();
+
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/messages")
+        .match_body(Matcher::PartialJson(json!({
+            "system": [{
+                "type": "text",
+                "text": "Large shared prefix",
+                "cache_control": {"type": "ephemeral"}
+            }]
+        })))
+        .with_status(200)
+        .with_header("content-type", "text/event-stream")
+        .with_body(body)
+        .create_async()
+        .await;
+
+    let provider =
+        AnthropicProvider::new(AnthropicConfig::new("test-key").with_base_url(server.url()))
+            .unwrap();
+    let client = Inference::builder()
+        .register_provider("anthropic", provider)
+        .build()
+        .unwrap();
+
+    let response = client
+        .stream(&cached_request())
+        .await
+        .unwrap()
+        .collect_response()
+        .await
+        .unwrap();
+
+    mock.assert_async().await;
+    assert_eq!(response.text(), "Hello");
+    assert_eq!(response.usage.cached_tokens, 50000);
+    assert_eq!(response.usage.cache_creation_tokens, 0);
+    assert_eq!(response.usage.prompt_tokens, 50012);
+    assert_eq!(response.usage.completion_tokens, 5);
+    assert_eq!(response.usage.total_tokens, 50017);
+}
+
+#[test]
+fn test_cache_control_serializes_as_provider_options() {
+    let message = Message::new(Role::User, "Hi").with_cache_control(CacheControl::ephemeral());
+
+    assert_eq!(
+        serde_json::to_value(&message).unwrap(),
+        json!({
+            "role": "user",
+            "content": "Hi",
+            "provider_options": {"anthropic": {"cache_control": {"type": "ephemeral"}}}
+        })
+    );
+}
@@ -0,0 +1,180 @@
+//! Provider options tests: unified option mapping and raw request fields
+
+use mockito::Matcher;
+use serde_json::json;
+use stakai::providers::anthropic::{AnthropicConfig, AnthropicProvider};
+use stakai::providers::gemini::{GeminiConfig, GeminiProvider};
+use stakai::providers::openai::{OpenAIConfig, OpenAIProvider};
+use stakai::types::GenerateOptions;
+use stakai::{GenerateRequest, Inference};
+
+fn request(model: &str, options: GenerateOptions) -> GenerateRequest {
+    let mut request = super::common::request(model);
+    request.options = options;
+    request
+}
+
+#[tokio::test]
+async fn test_openai_provider_options() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/chat/completions")
+        .match_body(Matcher::PartialJson(json!({
+            "model": "gpt-4o",
+            "temperature": 0.5,
+            "frequency_penalty": 0.25,
+            "presence_penalty": 0.5,
+            "seed": 42,
+            "logit_bias": {"50256": -100}
+        })))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            json!({
+                "id": "chatcmpl-1",
+                "object": "chat.completion",
+                "created": 1,
+                "model": "gpt-4o",
+                "choices": [{
+                    "index": 0,
+                    "message": {"role": "assistant", "content": "Hi"},
+                    "finish_reason": "stop"
+                }],
+                "usage": {"prompt_tokens": 1, "completion_tokens": 1, "total_tokens": 2}
+            })
+            .to_string(),
+        )
+        .create_async()
+        .await;
+
+    let provider =
+        OpenAIProvider::new(OpenAIConfig::new("test-key").with_base_url(server.url())).unwrap();
+    let client = Inference::builder()
+        .register_provider("openai", provider)
+        .build()
+        .unwrap();
+
+    let options = GenerateOptions::new()
+        .temperature(0.9)
+        .frequency_penalty(0.25)
+        .presence_penalty(0.5)
+        .provider_options("openai", json!({"seed": 42, "temperature": 0.5}))
+        .provider_options("openai", json!({"logit_bias": {"50256": -100}}))
+        .provider_options("anthropic", json!({"top_k": 3}));
+    client
+        .generate(&request("openai:gpt-4o", options))
+        .await
+        .unwrap();
+
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_anthropic_provider_options() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/messages")
+        .match_body(Matcher::PartialJson(json!({
+            "top_k": 40,
+            "metadata": {"user_id": "user-1"},
+            "thinking": {"type": "enabled", "budget_tokens": 2048}
+        })))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            json!({
+                "id": "msg_1",
+                "type": "message",
+                "role": "assistant",
+                "content": [{"type": "text", "text": "Hi"}],
+                "model": "claude-sonnet-4-20250514",
+                "stop_reason": "end_turn",
+                "usage": {"input_tokens": 1, "output_tokens": 1}
+            })
+            .to_string(),
+        )
+        .create_async()
+        .await;
+
+    let provider =
+        AnthropicProvider::new(AnthropicConfig::new("test-key").with_base_url(server.url()))
+            .unwrap();
+    let client = Inference::builder()
+        .register_provider("anthropic", provider)
+        .build()
+        .unwrap();
+
+    let options = GenerateOptions::new().top_k(40).provider_options(
+        "anthropic",
+        json!({
+            "metadata": {"user_id": "user-1"},
+            "thinking": {"type": "enabled", "budget_tokens": 2048}
+        }),
+    );
+    client
+        .generate(&request("anthropic:claude-sonnet-4-20250514", options))
+        .await
+        .unwrap();
+
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_gemini_provider_options_deep_merge() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/models/gemini-2.5-flash:generateContent")
+        .match_query(Matcher::Any)
+        .match_body(Matcher::PartialJson(json!({
+            "generation_config": {
+                "temperature": 0.5,
+                "top_k": 16,
+                "presence_penalty": 0.5,
+                "thinking_config": {"thinking_budget": 0}
+            },
+            "safety_settings": [
+                {"category": "HARM_CATEGORY_HARASSMENT", "threshold": "BLOCK_NONE"}
+            ]
+        })))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            json!({
+                "candidates": [{
+                    "content": {"role": "model", "parts": [{"text": "Hi"}]},
+                    "finishReason": "STOP"
+                }]
+            })
+            .to_string(),
+        )
+        .create_async()
+        .await;
+
+    let provider =
+        GeminiProvider::new(GeminiConfig::new("test-key").with_base_url(server.url())).unwrap();
+    let client = Inference::builder()
+        .register_provider("google", provider)
+        .build()
+        .unwrap();
+
+    let options = GenerateOptions::new()
+        .temperature(0.5)
+        .top_k(16)
+        .presence_penalty(0.5)
+        .provider_options(
+            "google",
+            json!({
+                "generation_config": {"thinking_config": {"thinking_budget": 0}},
+                "safety_settings": [
+                    {"category": "HARM_CATEGORY_HARASSMENT", "threshold": "BLOCK_NONE"}
+                ]
+            }),
+        );
+    let response = client
+        .generate(&request("google:gemini-2.5-flash", options))
+        .await
+        .unwrap();
+
+    mock.assert_async().await;
+    assert_eq!(response.text(), "Hi");
+}
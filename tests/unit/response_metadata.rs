@@ -0,0 +1,203 @@
+//! Response metadata tests: HTTP details, raw bodies and stream start events
+
+use super::common::request;
+use futures::StreamExt;
+use mockito::Matcher;
+use serde_json::json;
+use stakai::providers::anthropic::{AnthropicConfig, AnthropicProvider};
+use stakai::providers::gemini::{GeminiConfig, GeminiProvider};
+use stakai::providers::openai::{OpenAIConfig, OpenAIProvider};
+use stakai::types::GenerateOptions;
+use stakai::{Inference, StreamEvent};
+
+fn openai_body() -> serde_json::Value {
+    json!({
+        "id": "chatcmpl-1",
+        "object": "chat.completion",
+        "created": 1700000000,
+        "model": "gpt-4o-2024-08-06",
+        "choices": [{
+            "index": 0,
+            "message": {"role": "assistant", "content": "Hi"},
+            "finish_reason": "stop"
+        }],
+        "usage": {"prompt_tokens": 1, "completion_tokens": 1, "total_tokens": 2},
+        "system_fingerprint": "fp_1"
+    })
+}
+
+async fn openai_client(server: &mockito::ServerGuard) -> Inference {
+    let provider =
+        OpenAIProvider::new(OpenAIConfig::new("test-key").with_base_url(server.url())).unwrap();
+    Inference::builder()
+        .register_provider("openai", provider)
+        .build()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_generate_metadata_from_http_response() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("POST", "/chat/completions")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_header("x-request-id", "req_123")
+        .with_header("x-ratelimit-remaining-requests", "99")
+        .with_body(openai_body().to_string())
+        .create_async()
+        .await;
+
+    let response = openai_client(&server)
+        .await
+        .generate(&request("openai:gpt-4o"))
+        .await
+        .unwrap();
+
+    let metadata = response.metadata.as_ref().unwrap();
+    assert_eq!(metadata.provider, "openai");
+    assert_eq!(metadata.id.as_deref(), Some("chatcmpl-1"));
+    assert_eq!(metadata.model.as_deref(), Some("gpt-4o-2024-08-06"));
+    assert_eq!(metadata.created, Some(1700000000));
+    assert_eq!(metadata.request_id.as_deref(), Some("req_123"));
+    assert_eq!(metadata.status, Some(200));
+    assert_eq!(
+        metadata.header("X-RateLimit-Remaining-Requests"),
+        Some("99")
+    );
+    assert!(metadata.latency.is_some());
+    assert!(metadata.time_to_first_token.is_none());
+    assert!(metadata.raw.is_none());
+}
+
+#[tokio::test]
+async fn test_generate_raw_response_opt_in() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("POST", "/chat/completions")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(openai_body().to_string())
+        .create_async()
+        .await;
+
+    let mut request = request("openai:gpt-4o");
+    request.options = GenerateOptions::new().include_raw_response(true);
+    let response = openai_client(&server)
+        .await
+        .generate(&request)
+        .await
+        .unwrap();
+
+    let raw = response.metadata.unwrap().raw.unwrap();
+    assert_eq!(raw, openai_body());
+    assert_eq!(raw["system_fingerprint"], "fp_1");
+}
+
+#[tokio::test]
+async fn test_gemini_generate_metadata() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("POST", "/models/gemini-2.5-flash:generateContent")
+        .match_query(Matcher::Any)
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            json!({
+                "candidates": [{
+                    "content": {"role": "model", "parts": [{"text": "Hi"}]},
+                    "finishReason": "STOP"
+                }],
+                "modelVersion": "gemini-2.5-flash-preview",
+                "responseId": "resp-1"
+            })
+            .to_string(),
+        )
+        .create_async()
+        .await;
+
+    let provider =
+        GeminiProvider::new(GeminiConfig::new("test-key").with_base_url(server.url())).unwrap();
+    let client = Inference::builder()
+        .register_provider("google", provider)
+        .build()
+        .unwrap();
+
+    let response = client
+        .generate(&request("google:gemini-2.5-flash"))
+        .await
+        .unwrap();
+
+    let metadata = response.metadata.unwrap();
+    assert_eq!(metadata.provider, "google");
+    assert_eq!(metadata.id.as_deref(), Some("resp-1"));
+    assert_eq!(metadata.model.as_deref(), Some("gemini-2.5-flash-preview"));
+    assert_eq!(metadata.status, Some(200));
+}
+
+#[tokio::test]
+async fn test_anthropic_stream_start_carries_metadata() {
+    let body = [
+        json!({"type": "message_start", "message": {
+            "id": "msg_1", "type": "message", "role": "assistant", "content": [],
+            "model": "claude-sonnet-4-20250514", "stop_reason": null,
+            "usage": {"input_tokens": 10, "output_tokens": 1}
+        }}),
+        json!({"type": "content_block_start", "index": 0, "content_block": {"type": "text", "text": ""}}),
+        json!({"type": "content_block_delta", "index": 0, "delta": {"type": "text_delta", "text": "Hi"}}),
+        json!({"type": "content_block_stop", "index": 0}),
+        json!({"type": "message_delta", "delta": {"stop_reason": "end_turn"}, "usage": {"output_tokens": 2}}),
+        json!({"type": "message_stop"}),
+    ]
+    .iter()
+    .map(|event| format!("data: {}\n\n", event))
+    .collect::<String>();
+
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("POST", "/messages")
+        .with_status(200)
+        .with_header("content-type", "text/event-stream")
+        .with_header("request-id", "req_abc")
+        .with_header("anthropic-ratelimit-tokens-remaining", "5000")
+        .with_body(body)
+        .create_async()
+        .await;
+
+    let provider =
+        AnthropicProvider::new(AnthropicConfig::new("test-key").with_base_url(server.url()))
+            .unwrap();
+    let client = Inference::builder()
+        .register_provider("anthropic", provider)
+        .build()
+        .unwrap();
+
+    let (mut stream, response) = client
+        .stream(&request("anthropic:claude-sonnet-4-20250514"))
+        .await
+        .unwrap()
+        .tee_response();
+
+    let mut starts = 0;
+    while let Some(event) = stream.next().await {
+        if let StreamEvent::Start { id, metadata } = event.unwrap() {
+            starts += 1;
+            assert_eq!(id, "msg_1");
+            assert_eq!(metadata.provider, "anthropic");
+            assert_eq!(metadata.model.as_deref(), Some("claude-sonnet-4-20250514"));
+            assert_eq!(metadata.request_id.as_deref(), Some("req_abc"));
+            assert_eq!(
+                metadata.header("anthropic-ratelimit-tokens-remaining"),
+                Some("5000")
+            );
+            assert!(metadata.latency.is_some());
+            assert!(metadata.time_to_first_token.is_some());
+        }
+    }
+    assert_eq!(starts, 1);
+
+    let response = response.await.unwrap();
+    assert_eq!(response.text(), "Hi");
+    assert_eq!(response.model(), Some("claude-sonnet-4-20250514"));
+    assert_eq!(response.metadata.unwrap().id.as_deref(), Some("msg_1"));
+}
@@ -0,0 +1,308 @@
+//! Unit tests for retry handling, run against local mock servers
+
+use super::common::request;
+use futures::StreamExt;
+use serde_json::json;
+use stakai::providers::anthropic::{AnthropicConfig, AnthropicProvider};
+use stakai::providers::openai::{OpenAIConfig, OpenAIProvider};
+use stakai::{Error, Inference, RetryPolicy, StreamEvent};
+use std::time::Duration;
+
+fn fast_policy() -> RetryPolicy {
+    RetryPolicy::new()
+        .with_max_attempts(3)
+        .with_base_delay(Duration::from_millis(1))
+        .with_jitter(false)
+}
+
+fn anthropic_client(server: &mockito::Server, policy: RetryPolicy) -> Inference {
+    let provider =
+        AnthropicProvider::new(AnthropicConfig::new("test-key").with_base_url(server.url()))
+            .unwrap();
+    Inference::builder()
+        .register_provider("anthropic", provider)
+        .with_retry(policy)
+        .build()
+        .unwrap()
+}
+
+const MODEL: &str = "anthropic:claude-3-5-sonnet-20241022";
+
+fn anthropic_message() -> String {
+    json!({
+        "id": "msg_1",
+        "type": "message",
+        "role": "assistant",
+        "content": [{"type": "text", "text": "Hi"}],
+        "model": "claude-3-5-sonnet-20241022",
+        "stop_reason": "end_turn",
+        "usage": {"input_tokens": 3, "output_tokens": 1}
+    })
+    .to_string()
+}
+
+fn anthropic_sse(events: &[serde_json::Value]) -> String {
+    events
+        .iter()
+        .map(|event| format!("event: {}\ndata: {}\n\n", event["type"], event))
+        .collect()
+}
+
+fn anthropic_text_events() -> String {
+    anthropic_sse(&[
+        json!({"type": "content_block_delta", "index": 0, "delta": {"type": "text_delta", "text": "Hi"}}),
+        json!({"type": "message_stop"}),
+    ])
+}
+
+#[tokio::test]
+async fn test_retry_overloaded_then_success() {
+    let mut server = mockito::Server::new_async().await;
+    let overloaded = server
+        .mock("POST", "/messages")
+        .with_status(529)
+        .with_body(r#"{"type":"error","error":{"type":"overloaded_error","message":"Overloaded"}}"#)
+        .expect(2)
+        .create_async()
+        .await;
+    let success = server
+        .mock("POST", "/messages")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(anthropic_message())
+        .expect(1)
+        .create_async()
+        .await;
+
+    let client = anthropic_client(&server, fast_policy());
+    let response = client.generate(&request(MODEL)).await.unwrap();
+
+    overloaded.assert_async().await;
+    success.assert_async().await;
+    assert_eq!(response.text(), "Hi");
+}
+
+#[tokio::test]
+async fn test_retry_gives_up_after_max_attempts() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/messages")
+        .with_status(503)
+        .with_body("unavailable")
+        .expect(3)
+        .create_async()
+        .await;
+
+    let client = anthropic_client(&server, fast_policy());
+    let result = client.generate(&request(MODEL)).await;
+
+    mock.assert_async().await;
+    assert_eq!(result.unwrap_err().status(), Some(503));
+}
+
+#[tokio::test]
+async fn test_no_retry_on_client_error() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/messages")
+        .with_status(400)
+        .with_body("bad request")
+        .expect(1)
+        .create_async()
+        .await;
+
+    let client = anthropic_client(&server, fast_policy());
+    let result = client.generate(&request(MODEL)).await;
+
+    mock.assert_async().await;
+    assert!(matches!(result, Err(Error::InvalidRequest(e)) if e.status == 400));
+}
+
+#[tokio::test]
+async fn test_retry_gives_up_when_retry_after_exceeds_max_delay() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/messages")
+        .with_status(429)
+        .with_header("retry-after", "120")
+        .with_body("rate limited")
+        .expect(1)
+        .create_async()
+        .await;
+
+    let client = anthropic_client(&server, fast_policy());
+    let result = tokio::time::timeout(Duration::from_secs(5), client.generate(&request(MODEL)))
+        .await
+        .expect("waited for a Retry-After beyond the maximum delay");
+
+    mock.assert_async().await;
+    assert!(matches!(result, Err(Error::RateLimitExceeded(e)) if e.status == 429));
+}
+
+#[tokio::test]
+async fn test_no_retry_by_default() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/messages")
+        .with_status(503)
+        .expect(1)
+        .create_async()
+        .await;
+
+    let provider =
+        AnthropicProvider::new(AnthropicConfig::new("test-key").with_base_url(server.url()))
+            .unwrap();
+    let client = Inference::builder()
+        .register_provider("anthropic", provider)
+        .build()
+        .unwrap();
+    assert!(client.generate(&request(MODEL)).await.is_err());
+
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_retry_disabled() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/messages")
+        .with_status(500)
+        .expect(1)
+        .create_async()
+        .await;
+
+    let client = anthropic_client(&server, RetryPolicy::none());
+    assert!(client.generate(&request(MODEL)).await.is_err());
+
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_retry_honors_retry_after_header() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("POST", "/chat/completions")
+        .with_status(429)
+        .with_header("retry-after-ms", "20")
+        .expect(1)
+        .create_async()
+        .await;
+    server
+        .mock("POST", "/chat/completions")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            json!({
+                "id": "chatcmpl-1",
+                "object": "chat.completion",
+                "created": 1,
+                "model": "gpt-4",
+                "choices": [{
+                    "index": 0,
+                    "message": {"role": "assistant", "content": "Hi"},
+                    "finish_reason": "stop"
+                }],
+                "usage": {"prompt_tokens": 1, "completion_tokens": 1, "total_tokens": 2}
+            })
+            .to_string(),
+        )
+        .create_async()
+        .await;
+
+    // Backoff alone would wait far longer than the test allows
+    let policy = fast_policy().with_base_delay(Duration::from_secs(60));
+    let provider =
+        OpenAIProvider::new(OpenAIConfig::new("test-key").with_base_url(server.url())).unwrap();
+    let client = Inference::builder()
+        .register_provider("openai", provider)
+        .with_retry(policy)
+        .build()
+        .unwrap();
+
+    let request = request("openai:gpt-4");
+    let response = tokio::time::timeout(Duration::from_secs(5), client.generate(&request))
+        .await
+        .expect("Retry-After was not honored")
+        .unwrap();
+
+    assert_eq!(response.text(), "Hi");
+}
+
+#[tokio::test]
+async fn test_stream_retries_before_first_event() {
+    let mut server = mockito::Server::new_async().await;
+    let unavailable = server
+        .mock("POST", "/messages")
+        .with_status(503)
+        .expect(1)
+        .create_async()
+        .await;
+    let overloaded = server
+        .mock("POST", "/messages")
+        .with_status(200)
+        .with_header("content-type", "text/event-stream")
+        .with_body(anthropic_sse(&[json!({
+            "type": "error",
+            "error": {"type": "overloaded_error", "message": "Overloaded"}
+        })]))
+        .expect(1)
+        .create_async()
+        .await;
+    let success = server
+        .mock("POST", "/messages")
+        .with_status(200)
+        .with_header("content-type", "text/event-stream")
+        .with_body(anthropic_text_events())
+        .expect(1)
+        .create_async()
+        .await;
+
+    let client = anthropic_client(&server, fast_policy());
+    let mut stream = client.stream(&request(MODEL)).await.unwrap();
+
+    let mut text = String::new();
+    while let Some(event) = stream.next().await {
+        if let StreamEvent::TextDelta { delta, .. } = event.unwrap() {
+            text.push_str(&delta);
+        }
+    }
+
+    unavailable.assert_async().await;
+    overloaded.assert_async().await;
+    success.assert_async().await;
+    assert_eq!(text, "Hi");
+}
+
+#[tokio::test]
+async fn test_stream_not_retried_after_first_event() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/messages")
+        .with_status(200)
+        .with_header("content-type", "text/event-stream")
+        .with_body(anthropic_sse(&[
+            json!({"type": "content_block_delta", "index": 0, "delta": {"type": "text_delta", "text": "Hi"}}),
+            json!({"type": "error", "error": {"type": "overloaded_error", "message": "Overloaded"}}),
+        ]))
+        .expect(1)
+        .create_async()
+        .await;
+
+    let client = anthropic_client(&server, fast_policy());
+    let mut stream = client.stream(&request(MODEL)).await.unwrap();
+
+    assert!(matches!(
+        stream.next().await,
+        Some(Ok(StreamEvent::Start { .. }))
+    ));
+    assert!(matches!(
+        stream.next().await,
+        Some(Ok(StreamEvent::TextDelta { .. }))
+    ));
+    assert!(matches!(
+        stream.next().await,
+        Some(Err(Error::Overloaded(_)))
+    ));
+
+    mock.assert_async().await;
+}
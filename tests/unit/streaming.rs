@@ -0,0 +1,251 @@
+//! Streaming tool-call assembly and aggregation tests, replaying recorded SSE fixtures
+
+use futures::StreamExt;
+use serde_json::{json, Value};
+use stakai::providers::anthropic::{AnthropicConfig, AnthropicProvider};
+use stakai::providers::gemini::{GeminiConfig, GeminiProvider};
+use stakai::providers::openai::{OpenAIConfig, OpenAIProvider};
+use stakai::{
+    FinishReason, GenerateRequest, Inference, Message, ResponseContent, Role, StreamEvent,
+};
+use std::collections::HashMap;
+
+const OPENAI_TOOL_CALLS: &str = include_str!("../fixtures/openai_tool_calls.sse");
+const ANTHROPIC_TOOL_CALLS: &str = include_str!("../fixtures/anthropic_tool_calls.sse");
+const GEMINI_TOOL_CALLS: &str = include_str!("../fixtures/gemini_tool_calls.sse");
+
+/// A tool call assembled from stream events
+#[derive(Debug, Default)]
+struct StreamedCall {
+    name: String,
+    deltas: String,
+    arguments: Option<Value>,
+}
+
+/// Collect a stream, checking that every tool call event refers to a started call
+async fn collect(client: &Inference, model: &str) -> (Vec<(String, StreamedCall)>, FinishReason) {
+    let request = GenerateRequest::new(model, vec![Message::new(Role::User, "Weather?")]);
+    let mut stream = client.stream(&request).await.unwrap();
+
+    let mut order = Vec::new();
+    let mut calls: HashMap<String, StreamedCall> = HashMap::new();
+    let mut finish = None;
+
+    while let Some(event) = stream.next().await {
+        match event.unwrap() {
+            StreamEvent::ToolCallStart { id, name } => {
+                assert!(!id.is_empty(), "tool call started without an id");
+                order.push(id.clone());
+                calls.insert(
+                    id,
+                    StreamedCall {
+                        name,
+                        ..Default::default()
+                    },
+                );
+            }
+            StreamEvent::ToolCallDelta { id, delta } => {
+                let call = calls.get_mut(&id).expect("delta for unknown tool call");
+                assert!(call.arguments.is_none(), "delta after tool call ended");
+                call.deltas.push_str(&delta);
+            }
+            StreamEvent::ToolCallEnd {
+                id,
+                name,
+                arguments,
+                ..
+            } => {
+                let call = calls.get_mut(&id).expect("end for unknown tool call");
+                assert_eq!(call.name, name);
+                call.arguments = Some(arguments);
+            }
+            StreamEvent::Finish { reason, .. } => {
+                assert!(finish.is_none(), "more than one finish event");
+                finish = Some(reason);
+            }
+            _ => {}
+        }
+    }
+
+    let calls = order
+        .into_iter()
+        .map(|id| {
+            let call = calls.remove(&id).unwrap();
+            (id, call)
+        })
+        .collect();
+    (calls, finish.expect("stream did not finish"))
+}
+
+fn assert_weather_calls(calls: &[(String, StreamedCall)]) {
+    assert_eq!(calls.len(), 2);
+    for ((_, call), city) in calls.iter().zip(["Paris", "Rome"]) {
+        assert_eq!(call.name, "get_weather");
+        assert_eq!(call.arguments, Some(json!({"city": city})));
+        assert_eq!(
+            serde_json::from_str::<Value>(&call.deltas).unwrap(),
+            json!({"city": city})
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_stream_tool_calls_openai() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("POST", "/chat/completions")
+        .with_status(200)
+        .with_header("content-type", "text/event-stream")
+        .with_body(OPENAI_TOOL_CALLS)
+        .create_async()
+        .await;
+
+    let provider =
+        OpenAIProvider::new(OpenAIConfig::new("test-key").with_base_url(server.url())).unwrap();
+    let client = Inference::builder()
+        .register_provider("openai", provider)
+        .build()
+        .unwrap();
+
+    let (calls, finish) = collect(&client, "openai:gpt-4o").await;
+
+    assert_weather_calls(&calls);
+    assert_eq!(calls[0].0, "call_paris");
+    assert_eq!(calls[1].0, "call_rome");
+    assert_eq!(finish, FinishReason::ToolCalls);
+}
+
+#[tokio::test]
+async fn test_stream_usage_openai() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("POST", "/chat/completions")
+        .with_status(200)
+        .with_header("content-type", "text/event-stream")
+        .with_body(OPENAI_TOOL_CALLS)
+        .create_async()
+        .await;
+
+    let provider =
+        OpenAIProvider::new(OpenAIConfig::new("test-key").with_base_url(server.url())).unwrap();
+    let client = Inference::builder()
+        .register_provider("openai", provider)
+        .build()
+        .unwrap();
+
+    // Usage arrives in a chunk without choices, after the finish_reason chunk
+    let request = GenerateRequest::new("openai:gpt-4o", vec![Message::new(Role::User, "Weather?")]);
+    let mut stream = client.stream(&request).await.unwrap();
+    let mut finishes = Vec::new();
+    while let Some(event) = stream.next().await {
+        if let StreamEvent::Finish { usage, reason } = event.unwrap() {
+            finishes.push((usage, reason));
+        }
+    }
+
+    assert_eq!(finishes.len(), 1);
+    let (usage, reason) = &finishes[0];
+    assert_eq!(*reason, FinishReason::ToolCalls);
+    assert_eq!(usage.prompt_tokens, 50);
+    assert_eq!(usage.completion_tokens, 30);
+    assert_eq!(usage.total_tokens, 80);
+}
+
+#[tokio::test]
+async fn test_stream_tool_calls_anthropic() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("POST", "/messages")
+        .with_status(200)
+        .with_header("content-type", "text/event-stream")
+        .with_body(ANTHROPIC_TOOL_CALLS)
+        .create_async()
+        .await;
+
+    let provider =
+        AnthropicProvider::new(AnthropicConfig::new("test-key").with_base_url(server.url()))
+            .unwrap();
+    let client = Inference::builder()
+        .register_provider("anthropic", provider)
+        .build()
+        .unwrap();
+
+    let (calls, finish) = collect(&client, "anthropic:claude-3-5-sonnet-20241022").await;
+
+    assert_weather_calls(&calls);
+    assert_eq!(calls[0].0, "toolu_paris");
+    assert_eq!(calls[1].0, "toolu_rome");
+    assert_eq!(finish, FinishReason::ToolCalls);
+}
+
+#[tokio::test]
+async fn test_stream_tool_calls_gemini() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("POST", "/models/gemini-1.5-flash:streamGenerateContent")
+        .match_query(mockito::Matcher::UrlEncoded("alt".into(), "sse".into()))
+        .with_status(200)
+        .with_header("content-type", "text/event-stream")
+        .with_body(GEMINI_TOOL_CALLS)
+        .create_async()
+        .await;
+
+    let provider =
+        GeminiProvider::new(GeminiConfig::new("test-key").with_base_url(server.url())).unwrap();
+    let client = Inference::builder()
+        .register_provider("google", provider)
+        .build()
+        .unwrap();
+
+    let (calls, finish) = collect(&client, "google:gemini-1.5-flash").await;
+
+    assert_weather_calls(&calls);
+    assert_ne!(calls[0].0, calls[1].0);
+    assert_eq!(finish, FinishReason::ToolCalls);
+}
+
+#[tokio::test]
+async fn test_collect_response_matches_generate_shape() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("POST", "/messages")
+        .with_status(200)
+        .with_header("content-type", "text/event-stream")
+        .with_body(ANTHROPIC_TOOL_CALLS)
+        .create_async()
+        .await;
+
+    let provider =
+        AnthropicProvider::new(AnthropicConfig::new("test-key").with_base_url(server.url()))
+            .unwrap();
+    let client = Inference::builder()
+        .register_provider("anthropic", provider)
+        .build()
+        .unwrap();
+
+    let request = GenerateRequest::new(
+        "anthropic:claude-3-5-sonnet-20241022",
+        vec![Message::new(Role::User, "Weather?")],
+    );
+    let response = client
+        .stream(&request)
+        .await
+        .unwrap()
+        .collect_response()
+        .await
+        .unwrap();
+
+    assert!(matches!(
+        &response.content[0],
+        ResponseContent::Text { text } if text == "Checking both cities."
+    ));
+    let calls = response.tool_calls();
+    assert_eq!(calls.len(), 2);
+    assert_eq!(calls[0].id, "toolu_paris");
+    assert_eq!(calls[0].arguments, json!({"city": "Paris"}));
+    assert_eq!(calls[1].id, "toolu_rome");
+    assert_eq!(calls[1].arguments, json!({"city": "Rome"}));
+    assert_eq!(response.finish_reason, FinishReason::ToolCalls);
+    assert_eq!(response.usage.prompt_tokens, 50);
+    assert_eq!(response.usage.completion_tokens, 30);
+}
@@ -0,0 +1,298 @@
+//! Structured output tests: response formats per provider and typed parsing
+
+use super::common::openai_completion;
+use mockito::Matcher;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use stakai::providers::anthropic::{AnthropicConfig, AnthropicProvider};
+use stakai::providers::gemini::{GeminiConfig, GeminiProvider};
+use stakai::providers::openai::{OpenAIConfig, OpenAIProvider};
+use stakai::{
+    Error, FinishReason, GenerateRequest, GenerateResponse, Inference, Message, ResponseContent,
+    ResponseFormat, Role, Usage,
+};
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Person {
+    name: String,
+    age: u32,
+}
+
+fn person_schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "name": {"type": "string"},
+            "age": {"type": "integer"}
+        },
+        "required": ["name", "age"],
+        "additionalProperties": false
+    })
+}
+
+fn person_request(model: &str) -> GenerateRequest {
+    let mut request = GenerateRequest::new(
+        model,
+        vec![Message::new(Role::User, "Alice is 30. Extract the person.")],
+    );
+    request.options = request
+        .options
+        .response_format(ResponseFormat::strict_json_schema(
+            "person",
+            person_schema(),
+        ));
+    request
+}
+
+async fn openai_client(server: &mockito::ServerGuard) -> Inference {
+    let provider =
+        OpenAIProvider::new(OpenAIConfig::new("test-key").with_base_url(server.url())).unwrap();
+    Inference::builder()
+        .register_provider("openai", provider)
+        .build()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_generate_object_openai() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/chat/completions")
+        .match_body(Matcher::PartialJson(json!({
+            "response_format": {
+                "type": "json_schema",
+                "json_schema": {"name": "person", "schema": person_schema(), "strict": true}
+            }
+        })))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(openai_completion(r#"{"name":"Alice","age":30}"#))
+        .create_async()
+        .await;
+
+    let client = openai_client(&server).await;
+    let person: Person = client
+        .generate_object(&person_request("openai:gpt-4o"))
+        .await
+        .unwrap();
+
+    mock.assert_async().await;
+    assert_eq!(
+        person,
+        Person {
+            name: "Alice".to_string(),
+            age: 30
+        }
+    );
+}
+
+#[tokio::test]
+async fn test_generate_object_defaults_to_json_object() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/chat/completions")
+        .match_body(Matcher::PartialJson(json!({
+            "response_format": {"type": "json_object"}
+        })))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(openai_completion(r#"{"name":"Alice","age":30}"#))
+        .create_async()
+        .await;
+
+    let client = openai_client(&server).await;
+    let request = GenerateRequest::new(
+        "openai:gpt-4o",
+        vec![Message::new(Role::User, "Alice is 30. Reply in JSON.")],
+    );
+    let person: Person = client.generate_object(&request).await.unwrap();
+
+    mock.assert_async().await;
+    assert_eq!(person.age, 30);
+}
+
+#[tokio::test]
+async fn test_generate_object_schema_mismatch() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("POST", "/chat/completions")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(openai_completion(r#"{"name":"Alice"}"#))
+        .create_async()
+        .await;
+
+    let client = openai_client(&server).await;
+    let result = client
+        .generate_object::<Person>(&person_request("openai:gpt-4o"))
+        .await;
+
+    match result {
+        Err(Error::SchemaMismatch { message, output }) => {
+            assert!(message.contains("age"), "unexpected message: {}", message);
+            assert_eq!(output, r#"{"name":"Alice"}"#);
+        }
+        other => panic!("expected SchemaMismatch, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_generate_object_anthropic_forced_tool() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/messages")
+        .match_body(Matcher::PartialJson(json!({
+            "tools": [{"name": "person", "input_schema": person_schema()}],
+            "tool_choice": {"type": "tool", "name": "person"}
+        })))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            json!({
+                "id": "msg_1",
+                "type": "message",
+                "role": "assistant",
+                "content": [{
+                    "type": "tool_use",
+                    "id": "toolu_1",
+                    "name": "person",
+                    "input": {"name": "Alice", "age": 30}
+                }],
+                "model": "claude-3-5-sonnet-20241022",
+                "stop_reason": "tool_use",
+                "usage": {"input_tokens": 20, "output_tokens": 10}
+            })
+            .to_string(),
+        )
+        .create_async()
+        .await;
+
+    let provider =
+        AnthropicProvider::new(AnthropicConfig::new("test-key").with_base_url(server.url()))
+            .unwrap();
+    let client = Inference::builder()
+        .register_provider("anthropic", provider)
+        .build()
+        .unwrap();
+
+    let request = person_request("anthropic:claude-3-5-sonnet-20241022");
+    let response = client.generate(&request).await.unwrap();
+    mock.assert_async().await;
+
+    // The forced tool call comes back as JSON text, not as a tool call
+    assert!(response.tool_calls().is_empty());
+    assert_eq!(response.finish_reason, FinishReason::Stop);
+    assert_eq!(
+        response.object::<Person>().unwrap(),
+        Person {
+            name: "Alice".to_string(),
+            age: 30
+        }
+    );
+}
+
+#[tokio::test]
+async fn test_stream_structured_output_anthropic() {
+    let body = [
+        r#"{"type":"message_start","message":{"id":"msg_1","type":"message","role":"assistant","content":[],"model":"claude-3-5-sonnet-20241022","stop_reason":null,"usage":{"input_tokens":20,"output_tokens":1}}}"#,
+        r#"{"type":"content_block_start","index":0,"content_block":{"type":"tool_use","id":"toolu_1","name":"person","input":{}}}"#,
+        r#"{"type":"content_block_delta","index":0,"delta":{"type":"input_json_delta","partial_json":"{\"name\":\"Alice\","}}"#,
+        r#"{"type":"content_block_delta","index":0,"delta":{"type":"input_json_delta","partial_json":"\"age\":30}"}}"#,
+        r#"{"type":"content_block_stop","index":0}"#,
+        r#"{"type":"message_delta","delta":{"stop_reason":"tool_use"},"usage":{"output_tokens":10}}"#,
+        r#"{"type":"message_stop"}"#,
+    ]
+    .iter()
+    .map(|data| format!("data: {}\n\n", data))
+    .collect::<String>();
+
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("POST", "/messages")
+        .with_status(200)
+        .with_header("content-type", "text/event-stream")
+        .with_body(body)
+        .create_async()
+        .await;
+
+    let provider =
+        AnthropicProvider::new(AnthropicConfig::new("test-key").with_base_url(server.url()))
+            .unwrap();
+    let client = Inference::builder()
+        .register_provider("anthropic", provider)
+        .build()
+        .unwrap();
+
+    let response = client
+        .stream(&person_request("anthropic:claude-3-5-sonnet-20241022"))
+        .await
+        .unwrap()
+        .collect_response()
+        .await
+        .unwrap();
+
+    assert!(response.tool_calls().is_empty());
+    assert_eq!(response.finish_reason, FinishReason::Stop);
+    assert_eq!(response.object::<Person>().unwrap().name, "Alice");
+}
+
+#[tokio::test]
+async fn test_generate_object_gemini_response_schema() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/models/gemini-1.5-flash:generateContent")
+        .match_query(Matcher::Any)
+        .match_body(Matcher::PartialJson(json!({
+            "generation_config": {
+                "response_mime_type": "application/json",
+                "response_schema": {
+                    "type": "object",
+                    "properties": {
+                        "name": {"type": "string"},
+                        "age": {"type": "integer"}
+                    },
+                    "required": ["name", "age"]
+                }
+            }
+        })))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            json!({
+                "candidates": [{
+                    "content": {"role": "model", "parts": [{"text": "{\"name\": \"Alice\", \"age\": 30}"}]},
+                    "finishReason": "STOP"
+                }]
+            })
+            .to_string(),
+        )
+        .create_async()
+        .await;
+
+    let provider =
+        GeminiProvider::new(GeminiConfig::new("test-key").with_base_url(server.url())).unwrap();
+    let client = Inference::builder()
+        .register_provider("google", provider)
+        .build()
+        .unwrap();
+
+    let request = person_request("google:gemini-1.5-flash");
+    let response = client.generate(&request).await.unwrap();
+    mock.assert_async().await;
+
+    let person: Person = response.object().unwrap();
+    assert_eq!(person.name, "Alice");
+}
+
+#[test]
+fn test_object_ignores_code_fence() {
+    let response = GenerateResponse::new(
+        vec![ResponseContent::Text {
+            text: "```json\n{\"name\": \"Alice\", \"age\": 30}\n```".to_string(),
+        }],
+        Usage::default(),
+        FinishReason::Stop,
+    );
+
+    assert_eq!(response.object::<Person>().unwrap().age, 30);
+}
@@ -0,0 +1,79 @@
+//! Unit tests for client timeouts, run against local servers that stall
+
+use super::common::request;
+use futures::StreamExt;
+use stakai::providers::openai::{OpenAIConfig, OpenAIProvider};
+use stakai::{Error, Inference, RetryPolicy, StreamEvent};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+/// Accept one connection, write `response` and then keep the connection open
+async fn stalling_server(response: &'static str) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+
+    tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut buf = [0u8; 4096];
+        let _ = socket.read(&mut buf).await;
+        socket.write_all(response.as_bytes()).await.unwrap();
+        tokio::time::sleep(Duration::from_secs(30)).await;
+    });
+
+    url
+}
+
+fn client(url: String) -> Inference {
+    let provider = OpenAIProvider::new(OpenAIConfig::new("test-key").with_base_url(url)).unwrap();
+    Inference::builder()
+        .register_provider("openai", provider)
+        .with_timeout(1)
+        .with_retry(RetryPolicy::none())
+        .build()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_generate_request_timeout() {
+    let url = stalling_server("").await;
+
+    let result = tokio::time::timeout(
+        Duration::from_secs(5),
+        client(url).generate(&request("openai:gpt-4")),
+    )
+    .await
+    .expect("request timeout was not applied");
+
+    assert!(matches!(result, Err(Error::Timeout(d)) if d == Duration::from_secs(1)));
+}
+
+#[tokio::test]
+async fn test_stream_idle_timeout() {
+    let url = stalling_server(concat!(
+        "HTTP/1.1 200 OK\r\n",
+        "content-type: text/event-stream\r\n",
+        "transfer-encoding: chunked\r\n\r\n",
+        "80\r\n",
+        "data: {\"id\":\"1\",\"object\":\"chat.completion.chunk\",\"created\":1,\"model\":\"gpt-4\",",
+        "\"choices\":[{\"index\":0,\"delta\":{\"content\":\"Hi\"}}]}\n\n\r\n",
+    ))
+    .await;
+
+    let mut stream = client(url).stream(&request("openai:gpt-4")).await.unwrap();
+
+    assert!(matches!(
+        stream.next().await,
+        Some(Ok(StreamEvent::Start { .. }))
+    ));
+    assert!(matches!(
+        stream.next().await,
+        Some(Ok(StreamEvent::TextDelta { .. }))
+    ));
+
+    let next = tokio::time::timeout(Duration::from_secs(5), stream.next())
+        .await
+        .expect("idle timeout was not applied");
+    assert!(matches!(next, Some(Err(Error::Timeout(_)))));
+    assert!(stream.next().await.is_none());
+}
@@ -4,44 +4,28 @@ use stakai::types::*;
 
 #[test]
 fn test_message_creation() {
-    let msg = Message {
-        role: Role::User,
-        content: "Hello".into(),
-        name: None,
-    };
+    let msg = Message::new(Role::User, "Hello");
     assert_eq!(msg.role, Role::User);
     assert_eq!(msg.text(), Some("Hello".to_string()));
 }
 
 #[test]
 fn test_message_system() {
-    let msg = Message {
-        role: Role::System,
-        content: "You are helpful".into(),
-        name: None,
-    };
+    let msg = Message::new(Role::System, "You are helpful");
     assert_eq!(msg.role, Role::System);
     assert_eq!(msg.text(), Some("You are helpful".to_string()));
 }
 
 #[test]
 fn test_message_assistant() {
-    let msg = Message {
-        role: Role::Assistant,
-        content: "I can help".into(),
-        name: None,
-    };
+    let msg = Message::new(Role::Assistant, "I can help");
     assert_eq!(msg.role, Role::Assistant);
     assert_eq!(msg.text(), Some("I can help".to_string()));
 }
 
 #[test]
 fn test_message_with_name() {
-    let msg = Message {
-        role: Role::User,
-        content: "Hello".into(),
-        name: Some("Alice".to_string()),
-    };
+    let msg = Message::new(Role::User, "Hello").with_name("Alice");
     assert_eq!(msg.name, Some("Alice".to_string()));
 }
 
@@ -49,7 +33,7 @@ fn test_message_with_name() {
 fn test_content_part_text() {
     let part = ContentPart::text("Hello");
     match part {
-        ContentPart::Text { text } => assert_eq!(text, "Hello"),
+        ContentPart::Text { text, .. } => assert_eq!(text, "Hello"),
         _ => panic!("Expected text content"),
     }
 }
@@ -58,7 +42,7 @@ fn test_content_part_text() {
 fn test_content_part_image() {
     let part = ContentPart::image("https://example.com/image.jpg");
     match part {
-        ContentPart::Image { url, detail } => {
+        ContentPart::Image { url, detail, .. } => {
             assert_eq!(url, "https://example.com/image.jpg");
             assert_eq!(detail, None);
         }
@@ -70,7 +54,7 @@ fn test_content_part_image() {
 fn test_content_part_image_with_detail() {
     let part = ContentPart::image_with_detail("https://example.com/image.jpg", ImageDetail::High);
     match part {
-        ContentPart::Image { url, detail } => {
+        ContentPart::Image { url, detail, .. } => {
             assert_eq!(url, "https://example.com/image.jpg");
             assert_eq!(detail, Some(ImageDetail::High));
         }
@@ -80,14 +64,7 @@ fn test_content_part_image_with_detail() {
 
 #[test]
 fn test_generate_request_creation() {
-    let mut request = GenerateRequest::new(
-        "openai:gpt-4",
-        vec![Message {
-            role: Role::User,
-            content: "Hello".into(),
-            name: None,
-        }],
-    );
+    let mut request = GenerateRequest::new("openai:gpt-4", vec![Message::new(Role::User, "Hello")]);
     request.options.temperature = Some(0.7);
     request.options.max_tokens = Some(100);
 
@@ -98,14 +75,7 @@ fn test_generate_request_creation() {
 
 #[test]
 fn test_generate_request_simple() {
-    let request = GenerateRequest::new(
-        "openai:gpt-4",
-        vec![Message {
-            role: Role::User,
-            content: "Hello".into(),
-            name: None,
-        }],
-    );
+    let request = GenerateRequest::new("openai:gpt-4", vec![Message::new(Role::User, "Hello")]);
     assert_eq!(request.messages.len(), 1);
     assert_eq!(request.messages[0].role, Role::User);
 }
@@ -155,8 +125,8 @@ fn test_finish_reason_serialization() {
 
 #[test]
 fn test_response_text_extraction() {
-    let response = GenerateResponse {
-        content: vec![
+    let response = GenerateResponse::new(
+        vec![
             ResponseContent::Text {
                 text: "Hello ".to_string(),
             },
@@ -164,10 +134,9 @@ fn test_response_text_extraction() {
                 text: "World".to_string(),
             },
         ],
-        usage: Usage::default(),
-        finish_reason: FinishReason::Stop,
-        metadata: None,
-    };
+        Usage::default(),
+        FinishReason::Stop,
+    );
 
     assert_eq!(response.text(), "Hello World");
 }
@@ -176,7 +145,7 @@ fn test_response_text_extraction() {
 fn test_stream_event_creation() {
     let event = StreamEvent::start("test-id");
     match event {
-        StreamEvent::Start { id } => assert_eq!(id, "test-id"),
+        StreamEvent::Start { id, .. } => assert_eq!(id, "test-id"),
         _ => panic!("Expected Start event"),
     }
 
@@ -0,0 +1,258 @@
+//! Vertex AI mode tests: service account tokens and publisher model URLs
+
+use mockito::Matcher;
+use serde_json::json;
+use stakai::providers::anthropic::{AnthropicConfig, AnthropicProvider};
+use stakai::providers::gemini::{GeminiConfig, GeminiProvider};
+use stakai::providers::vertex::{ServiceAccountKey, VertexConfig};
+use stakai::{Error, GenerateRequest, Inference, Message, RetryPolicy, Role};
+
+const KEY: &str = include_str!("../fixtures/vertex_service_account.json");
+
+const MODELS_PATH: &str = "/projects/test-project/locations/us-central1/publishers";
+
+fn vertex(server: &mockito::ServerGuard) -> VertexConfig {
+    VertexConfig::new(
+        "test-project",
+        "us-central1",
+        ServiceAccountKey::from_json(KEY).unwrap(),
+    )
+    .with_base_url(server.url())
+    .with_token_url(format!("{}/token", server.url()))
+}
+
+fn gemini_client(server: &mockito::ServerGuard) -> Inference {
+    let provider = GeminiProvider::new(GeminiConfig::vertex(vertex(server))).unwrap();
+    Inference::builder()
+        .register_provider("google", provider)
+        .with_retry(RetryPolicy::none())
+        .build()
+        .unwrap()
+}
+
+fn anthropic_client(server: &mockito::ServerGuard) -> Inference {
+    let provider = AnthropicProvider::new(AnthropicConfig::vertex(vertex(server))).unwrap();
+    Inference::builder()
+        .register_provider("anthropic", provider)
+        .with_retry(RetryPolicy::none())
+        .build()
+        .unwrap()
+}
+
+async fn token_mock(server: &mut mockito::ServerGuard, expires_in: u64) -> mockito::Mock {
+    server
+        .mock("POST", "/token")
+        .match_body(Matcher::AllOf(vec![
+            Matcher::UrlEncoded(
+                "grant_type".to_string(),
+                "urn:ietf:params:oauth:grant-type:jwt-bearer".to_string(),
+            ),
+            Matcher::Regex("assertion=[\\w-]+\\.[\\w-]+\\.[\\w-]+".to_string()),
+        ]))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            json!({"access_token": "ya29.test-token", "expires_in": expires_in, "token_type": "Bearer"})
+                .to_string(),
+        )
+}
+
+fn gemini_response() -> String {
+    json!({
+        "candidates": [{
+            "content": {"role": "model", "parts": [{"text": "Hello!"}]},
+            "finishReason": "STOP"
+        }],
+        "usageMetadata": {"promptTokenCount": 3, "candidatesTokenCount": 2, "totalTokenCount": 5}
+    })
+    .to_string()
+}
+
+fn user_message() -> Vec<Message> {
+    vec![Message::new(Role::User, "Hi")]
+}
+
+#[tokio::test]
+async fn test_gemini_vertex_caches_token() {
+    let mut server = mockito::Server::new_async().await;
+    let token = token_mock(&mut server, 3600)
+        .await
+        .expect(1)
+        .create_async()
+        .await;
+    let generate = server
+        .mock(
+            "POST",
+            format!(
+                "{}/google/models/gemini-2.5-flash:generateContent",
+                MODELS_PATH
+            )
+            .as_str(),
+        )
+        .match_header("authorization", "Bearer ya29.test-token")
+        .match_query(Matcher::Missing)
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(gemini_response())
+        .expect(2)
+        .create_async()
+        .await;
+
+    let client = gemini_client(&server);
+    let request = GenerateRequest::new("google:gemini-2.5-flash", user_message());
+    let first = client.generate(&request).await.unwrap();
+    client.generate(&request).await.unwrap();
+
+    token.assert_async().await;
+    generate.assert_async().await;
+    assert_eq!(first.text(), "Hello!");
+    assert_eq!(first.usage.total_tokens, 5);
+}
+
+#[tokio::test]
+async fn test_gemini_vertex_stream() {
+    let mut server = mockito::Server::new_async().await;
+    token_mock(&mut server, 3600).await.create_async().await;
+    let stream = server
+        .mock(
+            "POST",
+            format!(
+                "{}/google/models/gemini-2.5-flash:streamGenerateContent",
+                MODELS_PATH
+            )
+            .as_str(),
+        )
+        .match_query(Matcher::UrlEncoded("alt".to_string(), "sse".to_string()))
+        .match_header("authorization", "Bearer ya29.test-token")
+        .with_status(200)
+        .with_header("content-type", "text/event-stream")
+        .with_body(format!("data: {}\n\n", gemini_response()))
+        .create_async()
+        .await;
+
+    let response = gemini_client(&server)
+        .stream(&GenerateRequest::new(
+            "google:gemini-2.5-flash",
+            user_message(),
+        ))
+        .await
+        .unwrap()
+        .collect_response()
+        .await
+        .unwrap();
+
+    stream.assert_async().await;
+    assert_eq!(response.text(), "Hello!");
+}
+
+#[tokio::test]
+async fn test_token_refreshed_before_expiry() {
+    let mut server = mockito::Server::new_async().await;
+    // Tokens expiring within the refresh margin are fetched again
+    let token = token_mock(&mut server, 60)
+        .await
+        .expect(2)
+        .create_async()
+        .await;
+    server
+        .mock(
+            "POST",
+            format!(
+                "{}/google/models/gemini-2.5-flash:generateContent",
+                MODELS_PATH
+            )
+            .as_str(),
+        )
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(gemini_response())
+        .expect(2)
+        .create_async()
+        .await;
+
+    let client = gemini_client(&server);
+    let request = GenerateRequest::new("google:gemini-2.5-flash", user_message());
+    client.generate(&request).await.unwrap();
+    client.generate(&request).await.unwrap();
+
+    token.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_anthropic_vertex_raw_predict() {
+    let mut server = mockito::Server::new_async().await;
+    token_mock(&mut server, 3600).await.create_async().await;
+    let predict = server
+        .mock(
+            "POST",
+            format!(
+                "{}/anthropic/models/claude-sonnet-4-5@20250929:rawPredict",
+                MODELS_PATH
+            )
+            .as_str(),
+        )
+        .match_header("authorization", "Bearer ya29.test-token")
+        .match_header("x-api-key", Matcher::Missing)
+        .match_body(Matcher::PartialJson(json!({
+            "anthropic_version": "vertex-2023-10-16"
+        })))
+        .match_request(|request| {
+            let body: serde_json::Value = serde_json::from_slice(request.body().unwrap()).unwrap();
+            body.get("model").is_none()
+        })
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            json!({
+                "id": "msg_vrtx_01",
+                "type": "message",
+                "role": "assistant",
+                "model": "claude-sonnet-4-5-20250929",
+                "content": [{"type": "text", "text": "Hello!"}],
+                "stop_reason": "end_turn",
+                "usage": {"input_tokens": 3, "output_tokens": 2}
+            })
+            .to_string(),
+        )
+        .create_async()
+        .await;
+
+    let response = anthropic_client(&server)
+        .generate(&GenerateRequest::new(
+            "anthropic:claude-sonnet-4-5@20250929",
+            user_message(),
+        ))
+        .await
+        .unwrap();
+
+    predict.assert_async().await;
+    assert_eq!(response.text(), "Hello!");
+}
+
+#[tokio::test]
+async fn test_rejected_assertion_is_authentication_error() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("POST", "/token")
+        .with_status(400)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"error":"invalid_grant","error_description":"Invalid JWT Signature."}"#)
+        .create_async()
+        .await;
+
+    let result = gemini_client(&server)
+        .generate(&GenerateRequest::new(
+            "google:gemini-2.5-flash",
+            user_message(),
+        ))
+        .await;
+
+    match result {
+        Err(Error::Authentication(e)) => {
+            assert_eq!(e.provider, "google");
+            assert_eq!(e.error_type.as_deref(), Some("invalid_grant"));
+            assert_eq!(e.message, "Invalid JWT Signature.");
+        }
+        other => panic!("Expected Authentication error, got {:?}", other),
+    }
+}